@@ -1,7 +1,7 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use qcomnetsim::quantum::TwoQubitState;
 use rayon::prelude::*;
 use std::hint::black_box;
-use QComNetSim::quantum::TwoQubitState;
 
 fn benchmark_parallel_fidelity(c: &mut Criterion) {
     let mut group = c.benchmark_group("Parallel Operations");
@@ -1,7 +1,9 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use qcomnetsim::quantum::gates::{hadamard, pauli_x};
+use qcomnetsim::quantum::{measure_z_with_rng, sample_z, Circuit, Qubit, TwoQubitState};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::hint::black_box;
-use QComNetSim::quantum::gates::{hadamard, pauli_x, pauli_y, pauli_z};
-use QComNetSim::quantum::{Qubit, TwoQubitState};
 
 fn benchmark_single_qubit_gates(c: &mut Criterion) {
     let mut group = c.benchmark_group("Single Qubit Gates");
@@ -66,9 +68,69 @@ fn benchmark_state_creation(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_circuit_compilation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Circuit Application");
+    let qubits = 1_000;
+
+    for gates in [10, 50].iter() {
+        let circuit = (0..*gates).fold(Circuit::new(), |circuit, i| match i % 3 {
+            0 => circuit.h(),
+            1 => circuit.t(),
+            _ => circuit.rx(0.1 * i as f64),
+        });
+        let compiled = circuit.compile();
+
+        group.bench_with_input(BenchmarkId::new("Sequential", gates), gates, |b, _| {
+            b.iter(|| {
+                for _ in 0..qubits {
+                    let mut qubit = Qubit::new_zero();
+                    circuit.apply(&mut qubit);
+                    black_box(qubit);
+                }
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("Compiled", gates), gates, |b, _| {
+            b.iter(|| {
+                for _ in 0..qubits {
+                    let mut qubit = Qubit::new_zero();
+                    compiled.apply(&mut qubit);
+                    black_box(qubit);
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_measurement_sampling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Measurement Sampling");
+    let shots = 100_000;
+    let mut rng = StdRng::seed_from_u64(42);
+
+    group.bench_function("Loop of measure_z", |b| {
+        b.iter(|| {
+            let ones = (0..shots)
+                .filter(|_| measure_z_with_rng(&mut Qubit::new_plus(), &mut rng))
+                .count();
+            black_box(ones)
+        });
+    });
+
+    group.bench_function("sample_z", |b| {
+        let plus = Qubit::new_plus();
+        b.iter(|| black_box(sample_z(&plus, shots, &mut rng)));
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
+    benchmark_measurement_sampling,
     benchmark_single_qubit_gates,
+    benchmark_circuit_compilation,
     benchmark_fidelity_calculation,
     benchmark_state_creation
 );
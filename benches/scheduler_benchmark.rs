@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use qcomnetsim::simulation::{Event, EventScheduler, EventType};
 use std::hint::black_box;
-use QComNetSim::simulation::{Event, EventScheduler, EventType};
 
 fn benchmark_event_scheduling(c: &mut Criterion) {
     let mut group = c.benchmark_group("Event Scheduling");
@@ -0,0 +1,6 @@
+//! `libqcomnetsim_capi`: the C API of `qcomnetsim::ffi` as a shared library
+//!
+//! The functions live in the main crate behind its `capi` feature; this
+//! crate only links them into a cdylib. See `examples/c/run.sh`.
+
+pub use qcomnetsim::ffi::*;
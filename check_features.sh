@@ -0,0 +1,17 @@
+#!/bin/sh
+# Build and test every supported feature combination
+set -e
+cd "$(dirname "$0")"
+
+for features in "" serde parallel telemetry capi "serde,parallel"; do
+    echo "=== features: ${features:-<core>} ==="
+    cargo check --all-targets --no-default-features --features "$features"
+done
+
+echo "=== C API shared library ==="
+cargo check -p qcomnetsim-capi
+
+echo "=== core tests ==="
+cargo test --no-default-features
+echo "=== all-features tests ==="
+cargo test --all-features
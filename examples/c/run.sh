@@ -0,0 +1,11 @@
+#!/bin/sh
+# Build the C API and run examples/c/embed.c against it
+set -e
+cd "$(dirname "$0")/../.."
+
+cargo build --release -p qcomnetsim-capi
+mkdir -p target/c-example
+cc -Wall -Wextra -Werror -Iinclude examples/c/embed.c \
+    -Ltarget/release -lqcomnetsim_capi -Wl,-rpath,"$PWD/target/release" \
+    -o target/c-example/embed
+./target/c-example/embed
@@ -1,5 +1,8 @@
-use qcomnetsim::quantum::measurement::{measure_z, measure_z_with_noise, MeasurementConfig};
+use qcomnetsim::quantum::measurement::{
+    measure_z_with_config, outcome_probabilities, sample, sample_z, Basis, MeasurementConfig,
+};
 use qcomnetsim::quantum::state::Qubit;
+use rand::SeedableRng;
 
 fn main() {
     println!("QComNetSim - Measurement Operations Demo\n");
@@ -13,12 +16,7 @@ fn main() {
 
     for _ in 0..trials {
         let mut qubit = Qubit::new_zero();
-        let result = measure_z_with_noise(
-            &mut qubit,
-            config.detector_efficiency,
-            config.dark_count_rate,
-            config.measurement_error_rate,
-        );
+        let result = measure_z_with_config(&mut qubit, &config);
         if !result {
             correct += 1;
         }
@@ -37,19 +35,14 @@ fn main() {
     println!("Detector efficiency: {}", config.detector_efficiency);
     println!("Dark count rate: {}", config.dark_count_rate);
     println!(
-        "Measurement error rate: {}\n",
-        config.measurement_error_rate
+        "Readout error: P(1|0) = {}, P(0|1) = {}\n",
+        config.confusion.p_report1_given0, config.confusion.p_report0_given1
     );
 
     correct = 0;
     for _ in 0..trials {
         let mut qubit = Qubit::new_zero();
-        let result = measure_z_with_noise(
-            &mut qubit,
-            config.detector_efficiency,
-            config.dark_count_rate,
-            config.measurement_error_rate,
-        );
+        let result = measure_z_with_config(&mut qubit, &config);
         if !result {
             correct += 1;
         }
@@ -68,23 +61,33 @@ fn main() {
 
     // Superposition measurement
     println!("=== Superposition Measurement ===");
-    let mut ones = 0;
-    for _ in 0..trials {
-        let mut qubit = Qubit::new_plus();
-        if measure_z(&mut qubit) {
-            ones += 1;
-        }
-    }
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let plus = Qubit::new_plus();
+    let (zeros, ones) = sample_z(&plus, trials, &mut rng);
 
     println!("Measuring |+⟩ state {} times", trials);
     println!(
         "|0⟩: {} ({:.1}%)",
-        trials - ones,
-        100.0 * (trials - ones) as f64 / trials as f64
+        zeros,
+        100.0 * zeros as f64 / trials as f64
     );
     println!(
-        "|1⟩: {} ({:.1}%)",
+        "|1⟩: {} ({:.1}%)\n",
         ones,
         100.0 * ones as f64 / trials as f64
     );
+
+    // Analytic vs sampled distribution in each basis
+    println!("=== Born Probabilities of |+⟩ ===");
+    for (name, basis) in [("Z", Basis::Z), ("X", Basis::X), ("Y", Basis::Y)] {
+        let (p0, p1) = outcome_probabilities(&plus, basis);
+        let (_, sampled_ones) = sample(&plus, basis, trials, &mut rng);
+        println!(
+            "{}: P(0) = {:.3}, P(1) = {:.3}, sampled P(1) = {:.3}",
+            name,
+            p0,
+            p1,
+            sampled_ones as f64 / trials as f64
+        );
+    }
 }
@@ -1,4 +1,6 @@
-use qcomnetsim::quantum::{hadamard, pauli_x, pauli_y, pauli_z, Qubit};
+use qcomnetsim::quantum::{hadamard, pauli_x, pauli_y, pauli_z, Circuit, Qubit};
+use qcomnetsim::telemetry::BlochTrajectory;
+use std::fs;
 
 fn main() {
     println!("QComNetSim - Pauli Gates Demo\n");
@@ -32,7 +34,7 @@ fn main() {
         q.prob_zero(),
         q.prob_one()
     );
-    println!("State: {:.3} + {:.3}i", q.state[1].re, q.state[1].im);
+    println!("State: {}", q);
     println!("(Should be i|1⟩)\n");
 
     // Pauli-Z
@@ -79,4 +81,13 @@ fn main() {
     println!("After H:  |1⟩");
     println!("Final: P(0)={:.2}, P(1)={:.2}", q.prob_zero(), q.prob_one());
     println!("(HZH|0⟩ = |1⟩)");
+
+    // Bloch trajectory of the same composition
+    let circuit = Circuit::new().h().z().h();
+    let mut q = Qubit::new_zero();
+    let trajectory = BlochTrajectory::record_circuit(&circuit, &mut q);
+
+    fs::create_dir_all("data").unwrap();
+    trajectory.write_csv("data/bloch_trajectory.csv").unwrap();
+    println!("\nBloch trajectory saved to data/bloch_trajectory.csv");
 }
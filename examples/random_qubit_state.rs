@@ -3,6 +3,7 @@ use qcomnetsim::quantum::*;
 fn main() {
     println!("QComNetSim - Random Qubit States\n");
     let mut q = Qubit::new_random();
+    println!("Random state: {}", q);
     println!(
         "Starting state: P(0)={:.3}, P(1)={:.3}",
         q.prob_zero(),
@@ -26,5 +27,6 @@ fn main() {
         q.prob_zero(),
         q.prob_one()
     );
-    print!(" -> Probabilities unchanged but phase flipped!\n");
+    println!(" -> Probabilities unchanged but phase flipped!");
+    println!("Final state: {}", q);
 }
@@ -0,0 +1,49 @@
+use qcomnetsim::experiment::{
+    crossover_distance, rate_curve, write_rate_curve_csv, RateCurveConfig,
+};
+use qcomnetsim::format::{format_rate, format_time};
+use std::fs;
+
+fn main() {
+    println!("QComNetSim - Direct vs One-Repeater Rate Curve\n");
+
+    let config = RateCurveConfig::realistic();
+
+    println!("=== Configuration ===");
+    println!("Attenuation: {} dB/km", config.chain.attenuation_db_per_km);
+    println!("Swap success: {}", config.chain.swap_success_probability);
+    match config.chain.cutoff_ms {
+        Some(cutoff_ms) => println!("Cutoff: {}", format_time(cutoff_ms)),
+        None => println!("Cutoff: none"),
+    }
+    println!(
+        "Attempt interval: {}",
+        format_time(config.chain.attempt_interval_ms)
+    );
+    println!();
+
+    let points = rate_curve(&config);
+
+    println!(
+        "{:>12} {:>14} {:>14} {:>14}",
+        "distance_km", "direct", "repeater", "analytic"
+    );
+    for point in &points {
+        println!(
+            "{:>12.1} {:>14} {:>14} {:>14}",
+            point.distance_km,
+            format_rate(point.direct_rate_hz),
+            format_rate(point.repeater_rate_hz),
+            format_rate(point.analytic_repeater_rate_hz)
+        );
+    }
+
+    match crossover_distance(&points) {
+        Some(distance) => println!("\nCrossover distance: {:.1} km", distance),
+        None => println!("\nNo crossover in the swept range"),
+    }
+
+    fs::create_dir_all("data").unwrap();
+    write_rate_curve_csv(&points, "data/repeater_rate_curve.csv").unwrap();
+    println!("Results saved to data/repeater_rate_curve.csv");
+}
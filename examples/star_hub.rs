@@ -0,0 +1,31 @@
+use qcomnetsim::experiment::{round_robin_requests, run_star, StarConfig};
+use qcomnetsim::format::format_rate;
+
+fn main() {
+    println!("QComNetSim - Star Hub Entanglement Distribution\n");
+
+    let config = StarConfig {
+        num_leaves: 5,
+        slots_per_run: 50_000,
+        ..StarConfig::default()
+    };
+    let requests = round_robin_requests(config.num_leaves, 10_000);
+
+    let stats = run_star(&config, &requests).unwrap();
+
+    println!("{:>6} {:>12} {:>14}", "leaf", "delivered", "rate");
+    for (leaf_id, rate) in (1..).zip(stats.leaf_rates_hz()) {
+        println!(
+            "{:>6} {:>12} {:>14}",
+            leaf_id,
+            stats.delivered_per_leaf[leaf_id - 1],
+            format_rate(rate)
+        );
+    }
+
+    println!();
+    println!("Jain's fairness index: {:.4}", stats.fairness());
+    println!("Slots blocked on hub memory: {}", stats.hub_blocked_slots);
+    println!();
+    println!("{}", stats.report(&config).summary());
+}
@@ -0,0 +1,27 @@
+use qcomnetsim::experiment::{compare_with_theory, ChainConfig};
+use std::fs;
+
+fn main() {
+    println!("QComNetSim - Simulation vs Analytic Predictions\n");
+
+    // No cutoff: the analytic model assumes links wait indefinitely
+    let config = ChainConfig {
+        coherence_time_ms: 50.0,
+        slots_per_run: 50_000,
+        replications: 3,
+        swap_success_probability: 0.5,
+        ..ChainConfig::default()
+    };
+    let link_distances_km = [5.0, 10.0, 20.0, 30.0, 40.0, 50.0];
+
+    fs::create_dir_all("data").unwrap();
+    for num_nodes in [2, 3] {
+        let comparison = compare_with_theory(num_nodes, &link_distances_km, &config).unwrap();
+        println!("{}", comparison.summary_table());
+
+        let path = format!("data/theory_comparison_{}_nodes", num_nodes);
+        comparison.write_csv(&format!("{}.csv", path)).unwrap();
+        fs::write(format!("{}.json", path), comparison.to_json()).unwrap();
+        println!("Results saved to {}.csv and {}.json\n", path, path);
+    }
+}
@@ -8,9 +8,10 @@ fn main() {
     let linear = NetworkTopology::new_linear(2, 10, 10.0, 0.2);
     println!("Nodes: {}", linear.num_nodes());
     println!("Channels: {}", linear.num_channels());
-    if let Some(ch) = linear.find_channel(0, 1) {
+    if let Some((id, ch)) = linear.find_channel(0, 1) {
         println!(
-            "Channel 0-1: {} km, success p={:.3}\n",
+            "Channel {} (0-1): {} km, success p={:.3}\n",
+            id,
             ch.distance_km,
             ch.success_probability()
         );
@@ -1,9 +1,10 @@
-use qcomnetsim::network::{QuantumChannel, QuantumNode};
+use qcomnetsim::network::{ChannelId, QuantumChannel, QuantumNode};
 use qcomnetsim::protocols::barrett_kok::BarrettKokProtocol;
-use qcomnetsim::simulation::{Event, EventScheduler, EventType};
+use qcomnetsim::simulation::{Event, EventScheduler, EventType, RunReport};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::fs::{self, File};
 use std::io::Write;
-use std::time::Instant;
 
 fn main() {
     println!("QComNetSim - Barrett-Kok Protocol Comparison\n");
@@ -14,15 +15,6 @@ fn main() {
     let coherence_time_ms = 100.0;
     let memory_size = 200; // SeQUeNCe uses 1 qubit/node
     let simulation_time_sec = 10.0;
-    let generation_frequency_khz = 2.0; // 2 kHz from SeQUeNCe
-
-    println!("=== Configuration ===");
-    println!("Attenuation: {} dB/km", attenuation_db_per_km);
-    println!("Coherence time: {} ms", coherence_time_ms);
-    println!("Memory size: {} qubit/node", memory_size);
-    println!("Generation frequency: {} kHz", generation_frequency_khz);
-    println!("Simulation time: {} seconds", simulation_time_sec);
-    println!();
 
     // Create CSV file
     fs::create_dir_all("data").unwrap();
@@ -38,23 +30,24 @@ fn main() {
     for &distance_km in &distances {
         println!("Running simulation for {} km...", distance_km);
 
-        let (successes, attempts, avg_fidelity) = run_simulation(
+        let report = run_simulation(
             distance_km as f64,
             attenuation_db_per_km,
             coherence_time_ms,
             memory_size,
             simulation_time_sec,
-            generation_frequency_khz,
             &protocol,
         );
 
-        let success_rate = if attempts > 0 {
-            successes as f64 / attempts as f64
+        let success_rate = report.generation.success_rate();
+        let throughput = report.generation.successes as f64 / simulation_time_sec;
+        let memory_used = report.generation.successes;
+        let fidelities = report.histogram("fidelity");
+        let avg_fidelity = if !fidelities.is_empty() {
+            fidelities.iter().sum::<f64>() / fidelities.len() as f64
         } else {
             0.0
         };
-        let throughput = successes as f64 / simulation_time_sec;
-        let memory_used = successes;
         writeln!(
             csv,
             "{},{:.4},{:.4},{},{:.2}",
@@ -62,13 +55,7 @@ fn main() {
         )
         .unwrap();
 
-        println!("  Distance: {} km", distance_km);
-        println!("  Attempts: {}", attempts);
-        println!("  Successes: {}", successes);
-        println!("  Success rate: {:.4}%", success_rate * 100.0);
-        println!("  Throughput: {:.4} pair/sec", throughput);
-        println!("  Avg Fidelity: {:.4}", avg_fidelity);
-        println!();
+        println!("{}", report.summary());
     }
 
     println!("Results saved to qcomnetsim_results.csv");
@@ -80,60 +67,71 @@ fn run_simulation(
     coherence_time_ms: f64,
     memory_size: usize,
     simulation_time_sec: f64,
-    _generation_frequency_khz: f64, // Ignore this
     protocol: &BarrettKokProtocol,
-) -> (usize, usize, f64) {
+) -> RunReport {
     let mut node_a = QuantumNode::new(0, memory_size);
     let mut node_b = QuantumNode::new(1, memory_size);
     let channel = QuantumChannel::new(0, 1, distance_km, attenuation_db_per_km);
 
     let mut scheduler = EventScheduler::new();
+    // Fixed seed: reruns give the same success/failure sequence
+    let mut rng = StdRng::seed_from_u64(42);
 
     // Match SeQUeNCe: 100 attempts per distance
     let num_attempts = 100;
     let attempt_interval_ms = (simulation_time_sec * 1000.0) / num_attempts as f64;
 
-    for i in 0..num_attempts {
-        let time = i as f64 * attempt_interval_ms;
-        scheduler.schedule(Event::new(time, EventType::EntanglementGeneration, 0));
-    }
-
-    // Run simulation
-    let mut successes = 0;
+    // Each attempt is scheduled once the previous one has heralded back
+    scheduler.schedule(Event::new(0.0, EventType::EntanglementGeneration, 0));
     let mut attempts = 0;
-    let mut fidelities: Vec<f64> = Vec::new(); // ADD THIS
 
+    let mut report = RunReport {
+        replications: 1,
+        ..RunReport::default()
+    };
+    for (name, value) in [
+        ("distance_km", distance_km),
+        ("attenuation_db_per_km", attenuation_db_per_km),
+        ("coherence_time_ms", coherence_time_ms),
+        ("memory_size", memory_size as f64),
+        ("simulation_time_sec", simulation_time_sec),
+    ] {
+        report.config.insert(name.to_string(), value.to_string());
+    }
+
+    // Run simulation; memory full keeps trying (SeQUeNCe behavior)
+    let mut fidelities: Vec<f64> = Vec::new();
     while let Some(event) = scheduler.next_event() {
         if event.event_type == EventType::EntanglementGeneration {
-            attempts += 1;
-
-            match protocol.attempt_generation(
+            let attempt = protocol.attempt_generation_with_rng(
                 &mut node_a,
                 &mut node_b,
                 &channel,
                 event.time,
                 coherence_time_ms,
-            ) {
-                Ok(true) => {
-                    successes += 1;
-                    if let Some(pair) = node_a.stored_pairs.last() {
-                        fidelities.push(pair.fidelity);
-                    }
-                }
-                Ok(false) => {
-                    // Channel or protocol failure
-                }
-                Err(_) => {
-                    // Memory full - continue trying (SeQUeNCe behavior)
+                &mut rng,
+            );
+            attempts += 1;
+            if attempts < num_attempts {
+                let wait_ms = attempt_interval_ms.max(protocol.attempt_duration_ms(&channel));
+                scheduler.schedule(Event::new(
+                    event.time + wait_ms,
+                    EventType::EntanglementGeneration,
+                    0,
+                ));
+            }
+            let result = attempt.map(|attempt| attempt.success);
+            report.generation.record(&result);
+            report.link_mut(ChannelId(0), 0, 1).record(&result);
+            if let Ok(true) = result {
+                if let Some(pair) = node_a.stored_pairs().last() {
+                    fidelities.push(pair.fidelity);
                 }
             }
+            report.events_processed += 1;
+            report.final_time = event.time;
         }
     }
-    let avg_fidelity = if !fidelities.is_empty() {
-        fidelities.iter().sum::<f64>() / fidelities.len() as f64
-    } else {
-        0.0
-    };
-
-    (successes, attempts, avg_fidelity) // RETURN IT
+    report.record_samples("fidelity", &fidelities);
+    report
 }
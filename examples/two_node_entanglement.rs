@@ -1,7 +1,8 @@
+use qcomnetsim::format::{format_probability, format_time};
 use qcomnetsim::network::{
-    attempt_entanglement_generation, GenerationStats, QuantumChannel, QuantumNode,
+    attempt_entanglement_generation, ChannelId, GenerationStats, QuantumChannel, QuantumNode,
 };
-use qcomnetsim::simulation::{Event, EventScheduler, EventType};
+use qcomnetsim::simulation::{Event, EventScheduler, EventType, NodeStats, RunReport};
 
 fn main() {
     println!("QComNetSim - 2-Node Entanglement Generation Demo\n");
@@ -13,13 +14,6 @@ fn main() {
     let num_attempts = 100;
     let attempt_interval_ms = 1.0; // Try every 1ms
 
-    println!("=== Configuration ===");
-    println!("Distance: {} km", distance_km);
-    println!("Attenuation: {} dB/km", attenuation_db_per_km);
-    println!("Coherence time: {} ms", coherence_time_ms);
-    println!("Attempts: {}", num_attempts);
-    println!();
-
     // Create nodes
     let mut node_a = QuantumNode::new(0, 50);
     let mut node_b = QuantumNode::new(1, 50);
@@ -28,8 +22,8 @@ fn main() {
     let channel = QuantumChannel::new(0, 1, distance_km, attenuation_db_per_km);
 
     println!(
-        "Channel success probability: {:.1}%",
-        channel.success_probability() * 100.0
+        "Channel success probability: {}",
+        format_probability(channel.success_probability())
     );
     println!();
 
@@ -48,77 +42,77 @@ fn main() {
 
     // Run simulation
     let mut stats = GenerationStats::new();
+    let mut last_time = 0.0;
 
     println!("=== Running Simulation ===");
     while let Some(event) = scheduler.next_event() {
         if event.event_type == EventType::EntanglementGeneration {
-            stats.attempts += 1;
-
-            match attempt_entanglement_generation(
+            last_time = event.time;
+            let result = attempt_entanglement_generation(
                 &mut node_a,
                 &mut node_b,
                 &channel,
                 event.time,
                 coherence_time_ms,
-            ) {
-                Ok(true) => {
-                    stats.successes += 1;
-                    println!(
-                        "[{:.1}ms] ✓ Entanglement generated (attempt #{})",
-                        event.time, stats.attempts
-                    );
-                }
-                Ok(false) => {
-                    stats.channel_failures += 1;
-                    println!(
-                        "[{:.1}ms] ✗ Channel failure (attempt #{})",
-                        event.time, stats.attempts
-                    );
-                }
-                Err(e) => {
-                    stats.memory_full_errors += 1;
-                    println!(
-                        "[{:.1}ms] ⚠ Memory full: {} (attempt #{})",
-                        event.time, e, stats.attempts
-                    );
-                }
+            );
+            stats.record(&result);
+
+            match result {
+                Ok(true) => println!(
+                    "[{}] ✓ Entanglement generated (attempt #{})",
+                    format_time(event.time),
+                    stats.attempts
+                ),
+                Ok(false) => println!(
+                    "[{}] ✗ Channel failure (attempt #{})",
+                    format_time(event.time),
+                    stats.attempts
+                ),
+                Err(e) => println!(
+                    "[{}] ⚠ Memory full: {} (attempt #{})",
+                    format_time(event.time),
+                    e,
+                    stats.attempts
+                ),
             }
         }
     }
 
     // Print results
-    stats.print_summary();
-
-    println!("=== Final State ===");
-    println!(
-        "Node A memory: {}/{} used",
-        node_a.num_stored_pairs(),
-        node_a.memory_capacity
-    );
-    println!(
-        "Node B memory: {}/{} used",
-        node_b.num_stored_pairs(),
-        node_b.memory_capacity
-    );
-
-    // Check fidelity of stored pairs
-    if node_a.num_stored_pairs() > 0 {
-        println!("\n=== Checking Fidelity After Storage ===");
-        let final_time = num_attempts as f64 * attempt_interval_ms;
-
-        // Get average fidelity
-        let mut total_fidelity = 0.0;
-        for pair in &node_a.stored_pairs {
-            let mut pair_copy = pair.clone();
-            pair_copy.update_fidelity(final_time);
-            total_fidelity += pair_copy.fidelity;
-        }
-
-        let avg_fidelity = total_fidelity / node_a.num_stored_pairs() as f64;
-        println!("Average fidelity: {:.4}", avg_fidelity);
-
-        if avg_fidelity < 0.9 {
-            println!("⚠ Warning: Average fidelity below threshold!");
-        }
+    let final_time = num_attempts as f64 * attempt_interval_ms;
+    let mut report = RunReport {
+        replications: 1,
+        events_processed: stats.attempts,
+        final_time: last_time,
+        ..RunReport::default()
+    };
+    for (name, value) in [
+        ("distance_km", distance_km),
+        ("attenuation_db_per_km", attenuation_db_per_km),
+        ("coherence_time_ms", coherence_time_ms),
+        ("attempt_interval_ms", attempt_interval_ms),
+    ] {
+        report.config.insert(name.to_string(), value.to_string());
+    }
+    *report.link_mut(ChannelId(0), 0, 1) = stats.clone();
+    report.generation = stats;
+    // Pairs are never consumed, so the final occupancy is the peak
+    for node in [&node_a, &node_b] {
+        report.nodes.insert(
+            node.id,
+            NodeStats {
+                memory_capacity: node.memory_capacity,
+                peak_occupancy: node.num_stored_pairs(),
+                max_pair_age_ms: node.oldest_pair_age(final_time).unwrap_or(0.0),
+            },
+        );
     }
+    let fidelities: Vec<f64> = node_a
+        .stored_pairs()
+        .iter()
+        .map(|pair| pair.fidelity_at(final_time))
+        .collect();
+    report.record_samples("fidelity_at_end", &fidelities);
+
+    println!("{}", report.summary());
 }
@@ -0,0 +1,103 @@
+#ifndef QCOMNETSIM_H
+#define QCOMNETSIM_H
+
+/* C API of src/ffi.rs; regenerate with `cbindgen --config cbindgen.toml --output include/qcomnetsim.h` */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define QCS_OK 0
+
+/**
+ * A required pointer argument was null
+ */
+#define QCS_ERR_NULL_POINTER -1
+
+/**
+ * The config string is not valid UTF-8 or not a valid config
+ */
+#define QCS_ERR_INVALID_CONFIG -2
+
+/**
+ * The request has no route or identical ends
+ */
+#define QCS_ERR_INVALID_REQUEST -3
+
+/**
+ * `qcs_run_until` was asked to go back in time
+ */
+#define QCS_ERR_INVALID_TIME -4
+
+/**
+ * The simulator panicked; the handle should be destroyed
+ */
+#define QCS_ERR_PANIC -5
+
+/**
+ * Opaque simulation handle
+ */
+typedef struct QcsSimulation QcsSimulation;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a simulation from a JSON config
+ *
+ * On success `*out` holds a handle to free with `qcs_destroy`.
+ *
+ * # Safety
+ * `config_json` must be null or a NUL-terminated string, and `out` must be
+ * null or valid for writing a pointer.
+ */
+int qcs_create(const char *config_json, QcsSimulation **out);
+
+/**
+ * Free a handle created by `qcs_create`; null is ignored
+ *
+ * # Safety
+ * `handle` must be null or a handle from `qcs_create` not yet destroyed.
+ */
+void qcs_destroy(QcsSimulation *handle);
+
+/**
+ * Process every event up to and including `time` (ms)
+ *
+ * # Safety
+ * `handle` must be null or a live handle from `qcs_create`.
+ */
+int qcs_run_until(QcsSimulation *handle, double time);
+
+/**
+ * Queue `count` end-to-end pairs between `source` and `destination`
+ * Service starts at the current simulation time
+ *
+ * # Safety
+ * `handle` must be null or a live handle from `qcs_create`.
+ */
+int qcs_submit_request(QcsSimulation *handle, uint32_t source, uint32_t destination, uint32_t count);
+
+/**
+ * Current statistics as a JSON object
+ *
+ * The string is owned by the handle and valid until the next call on it;
+ * returns null on a null handle or a panic.
+ *
+ * # Safety
+ * `handle` must be null or a live handle from `qcs_create`.
+ */
+const char *qcs_get_stats_json(QcsSimulation *handle);
+
+/**
+ * Static description of a status code
+ */
+const char *qcs_status_message(int status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* QCOMNETSIM_H */
@@ -0,0 +1,255 @@
+use crate::experiment::ChainConfig;
+use crate::network::QuantumChannel;
+use crate::protocols::swapping::swapped_fidelity;
+use crate::quantum::fidelity_after_decoherence;
+
+/// Parameters of a two-link (single repeater) chain for cutoff analysis
+#[derive(Debug, Clone, Copy)]
+pub struct CutoffLinkParams {
+    /// Per-slot success probability of each elementary link
+    pub link_success_probability: f64,
+    /// Fidelity of a freshly heralded link pair
+    pub initial_fidelity: f64,
+    /// Memory coherence time (ms)
+    pub coherence_time_ms: f64,
+    /// Time between attempts on each link (ms)
+    pub attempt_interval_ms: f64,
+    /// Probability that the swap succeeds
+    pub swap_success_probability: f64,
+    /// Largest cutoff considered by the search (ms)
+    pub max_cutoff_ms: f64,
+}
+
+impl CutoffLinkParams {
+    /// Derive parameters from a chain configuration and elementary link length
+    pub fn from_chain(
+        config: &ChainConfig,
+        link_distance_km: f64,
+        initial_fidelity: f64,
+        max_cutoff_ms: f64,
+    ) -> Self {
+        let channel = QuantumChannel::new(0, 1, link_distance_km, config.attenuation_db_per_km);
+        CutoffLinkParams {
+            link_success_probability: channel.success_probability(),
+            initial_fidelity,
+            coherence_time_ms: config.coherence_time_ms,
+            attempt_interval_ms: config.attempt_interval_ms,
+            swap_success_probability: config.swap_success_probability,
+            max_cutoff_ms,
+        }
+    }
+}
+
+/// Objective for the cutoff search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptimizeFor {
+    /// Maximize delivered pairs per second
+    Throughput,
+    /// Maximize throughput while keeping mean delivered fidelity >= the floor
+    FidelityFloor(f64),
+    /// Maximize rate × (1 - 2h(QBER)) with QBER = 2(1-F)/3 (Werner pairs)
+    SecretKeyRate,
+}
+
+/// Predicted end-to-end metrics for one cutoff value
+#[derive(Debug, Clone, Copy)]
+pub struct CutoffPrediction {
+    pub cutoff_ms: f64,
+    /// Delivered pairs per second
+    pub rate_hz: f64,
+    /// Mean fidelity of delivered pairs
+    pub mean_fidelity: f64,
+    /// Secret-key-rate-like figure (bits/s, 0 when QBER is too high)
+    pub secret_key_rate: f64,
+}
+
+/// Recommended cutoff with the metrics predicted at that point
+#[derive(Debug, Clone)]
+pub struct CutoffRecommendation {
+    pub objective: OptimizeFor,
+    pub best: CutoffPrediction,
+    /// Every evaluated cutoff, in increasing order
+    pub sweep: Vec<CutoffPrediction>,
+}
+
+/// Binary Shannon entropy h(x) in bits
+pub fn binary_entropy(x: f64) -> f64 {
+    if x <= 0.0 || x >= 1.0 {
+        0.0
+    } else {
+        -x * x.log2() - (1.0 - x) * (1.0 - x).log2()
+    }
+}
+
+/// Expected number of slots until every link has succeeded at least once,
+/// links attempting independently each slot with the given probabilities
+/// None if some link can never succeed
+pub fn expected_max_geometric(probabilities: &[f64]) -> Option<f64> {
+    if probabilities.iter().any(|&p| p <= 0.0) {
+        return None;
+    }
+    // E[max] = Σ_{k≥0} P(max > k) = Σ_k (1 - Π_i (1 - (1 - p_i)^k))
+    let mut expected = 0.0;
+    for k in 0.. {
+        let all_done: f64 = probabilities
+            .iter()
+            .map(|&p| 1.0 - (1.0 - p.min(1.0)).powi(k))
+            .product();
+        let term = 1.0 - all_done;
+        expected += term;
+        if term < 1e-12 {
+            break;
+        }
+    }
+    Some(expected)
+}
+
+/// Analytic metrics of a single-repeater chain with a cutoff of `max_age_slots`
+///
+/// Renewal argument from the empty state: both links attempt; if one succeeds
+/// its pair waits up to `max_age_slots` slots for the other link, otherwise it
+/// is discarded and the cycle restarts.
+pub fn predict_with_cutoff(params: &CutoffLinkParams, max_age_slots: usize) -> CutoffPrediction {
+    let p = params.link_success_probability;
+    let q = 1.0 - p;
+    let dt = params.attempt_interval_ms;
+    let f0 = params.initial_fidelity;
+
+    // Σ_{k=1..τ} k q^(k-1) p and fidelity weights of a k-slot wait
+    let mut weighted_wait = 0.0;
+    let mut fidelity_sum = p * p * swapped_fidelity(f0, f0);
+    let mut weight_sum = p * p;
+    for k in 1..=max_age_slots {
+        let prob_k = q.powi(k as i32 - 1) * p;
+        weighted_wait += k as f64 * prob_k;
+
+        let held = fidelity_after_decoherence(f0, k as f64 * dt, params.coherence_time_ms);
+        fidelity_sum += 2.0 * p * q * prob_k * swapped_fidelity(held, f0);
+        weight_sum += 2.0 * p * q * prob_k;
+    }
+    let p_discard = q.powi(max_age_slots as i32);
+
+    // E(1 - q² - 2pq q^τ) = 1 + 2pq (Σ k q^(k-1) p + τ q^τ)
+    let expected_slots = (1.0 + 2.0 * p * q * (weighted_wait + max_age_slots as f64 * p_discard))
+        / (1.0 - q * q - 2.0 * p * q * p_discard);
+
+    let rate_hz = params.swap_success_probability / (expected_slots * dt / 1000.0);
+    let mean_fidelity = fidelity_sum / weight_sum;
+    let qber = 2.0 * (1.0 - mean_fidelity) / 3.0;
+    let key_fraction = (1.0 - 2.0 * binary_entropy(qber)).max(0.0);
+
+    CutoffPrediction {
+        cutoff_ms: max_age_slots as f64 * dt,
+        rate_hz,
+        mean_fidelity,
+        secret_key_rate: rate_hz * key_fraction,
+    }
+}
+
+/// Find the cutoff (a whole number of attempt slots) that best serves `target`
+pub fn optimal_cutoff(params: &CutoffLinkParams, target: OptimizeFor) -> CutoffRecommendation {
+    let max_slots = (params.max_cutoff_ms / params.attempt_interval_ms).floor() as usize;
+    let sweep: Vec<CutoffPrediction> = (0..=max_slots)
+        .map(|slots| predict_with_cutoff(params, slots))
+        .collect();
+
+    let score = |prediction: &CutoffPrediction| match target {
+        OptimizeFor::Throughput => prediction.rate_hz,
+        OptimizeFor::FidelityFloor(floor) => {
+            if prediction.mean_fidelity >= floor {
+                prediction.rate_hz
+            } else {
+                f64::NEG_INFINITY
+            }
+        }
+        OptimizeFor::SecretKeyRate => prediction.secret_key_rate,
+    };
+
+    // Strictly-greater keeps the shortest cutoff among equal scores
+    let mut best = sweep[0];
+    for prediction in &sweep {
+        if score(prediction) > score(&best) {
+            best = *prediction;
+        }
+    }
+
+    CutoffRecommendation {
+        objective: target,
+        best,
+        sweep,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding_params() -> CutoffLinkParams {
+        CutoffLinkParams {
+            link_success_probability: 0.1,
+            initial_fidelity: 0.98,
+            coherence_time_ms: 50.0,
+            attempt_interval_ms: 1.0,
+            swap_success_probability: 1.0,
+            max_cutoff_ms: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_no_cutoff_limit_matches_expected_max() {
+        // Very long cutoff: E[max(G1, G2)] = 2/p - 1/(p(2-p))
+        let params = CutoffLinkParams {
+            max_cutoff_ms: 10_000.0,
+            ..binding_params()
+        };
+        let prediction = predict_with_cutoff(&params, 10_000);
+        let p = params.link_success_probability;
+        let expected_slots = 2.0 / p - 1.0 / (p * (2.0 - p));
+        assert!((prediction.rate_hz - 1000.0 / expected_slots).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_prediction_matches_chain_simulation() {
+        let config = ChainConfig {
+            slots_per_run: 20_000,
+            cutoff_ms: Some(3.0),
+            ..ChainConfig::default()
+        };
+        let params = CutoffLinkParams::from_chain(&config, 25.0, 1.0, 3.0);
+
+        let predicted = predict_with_cutoff(&params, 3).rate_hz;
+        let simulated = crate::experiment::run_chain(3, 25.0, &config).rate_hz();
+        assert!((simulated - predicted).abs() / predicted < 0.1);
+    }
+
+    #[test]
+    fn test_secret_key_cutoff_is_interior() {
+        let params = binding_params();
+        let recommendation = optimal_cutoff(&params, OptimizeFor::SecretKeyRate);
+
+        assert!(recommendation.best.cutoff_ms > 0.0);
+        assert!(recommendation.best.cutoff_ms < params.max_cutoff_ms);
+        assert!(recommendation.best.secret_key_rate > 0.0);
+    }
+
+    #[test]
+    fn test_throughput_prefers_longer_cutoff_than_fidelity_floor() {
+        let params = binding_params();
+        let throughput = optimal_cutoff(&params, OptimizeFor::Throughput);
+        let floor = optimal_cutoff(&params, OptimizeFor::FidelityFloor(0.9));
+
+        assert!(throughput.best.cutoff_ms > floor.best.cutoff_ms);
+        assert!(floor.best.mean_fidelity >= 0.9);
+    }
+
+    #[test]
+    fn test_expected_max_geometric() {
+        assert_eq!(expected_max_geometric(&[1.0, 1.0]), Some(1.0));
+        assert!((expected_max_geometric(&[0.25]).unwrap() - 4.0).abs() < 1e-9);
+        // E[max(G1, G2)] = 2/p - 1/(p(2-p))
+        let p: f64 = 0.3;
+        let expected = 2.0 / p - 1.0 / (p * (2.0 - p));
+        assert!((expected_max_geometric(&[p, p]).unwrap() - expected).abs() < 1e-9);
+        assert_eq!(expected_max_geometric(&[0.5, 0.0]), None);
+    }
+}
@@ -0,0 +1,281 @@
+use crate::network::{NetworkTopology, RoutePath};
+use crate::protocols::barrett_kok::BarrettKokProtocol;
+use crate::quantum::{fidelity_after_decoherence, ConfusionMatrix};
+use std::fs::File;
+use std::io::Write;
+
+/// Operating assumptions the budget cannot read from the topology
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetPolicies {
+    /// Memory coherence time of stored pairs (ms)
+    pub coherence_time_ms: f64,
+    /// Expected time a link pair waits in memory before it is swapped (ms)
+    pub expected_wait_ms: f64,
+    /// Readout confusion at each end node
+    pub readout: ConfusionMatrix,
+}
+
+/// One loss mechanism at one place on the path
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetEntry {
+    /// e.g. "heralded infidelity", "storage decoherence"
+    pub mechanism: &'static str,
+    /// e.g. "link 0-1", "node 1"
+    pub location: String,
+    /// Multiplicative factor on the Werner parameter w = (4F - 1)/3
+    pub factor: f64,
+    /// Fidelity lost to this entry when applied in order (linear terms sum
+    /// to 1 - total fidelity)
+    pub fidelity_loss: f64,
+    /// -10 log10(factor); dB terms sum to the total dB
+    pub db: f64,
+}
+
+/// Static attribution of end-to-end infidelity to each mechanism
+///
+/// Every mechanism acts on Werner pairs as a factor on w: swapping multiplies
+/// the w of its inputs, so the end-to-end w is the product of all factors,
+/// much like a link budget multiplies gains
+#[derive(Debug, Clone, PartialEq)]
+pub struct FidelityBudget {
+    pub entries: Vec<BudgetEntry>,
+    /// Expected end-to-end fidelity (as seen through the end measurements)
+    pub total_fidelity: f64,
+}
+
+/// Werner parameter of a pair with fidelity F
+fn werner_parameter(fidelity: f64) -> f64 {
+    (4.0 * fidelity - 1.0) / 3.0
+}
+
+/// Build the fidelity budget of `path`
+///
+/// Per link: heralded infidelity of `protocol` over the hop's channel and
+/// storage decoherence over `expected_wait_ms`. Per repeater: swap gate error.
+/// Per end node: readout error.
+pub fn fidelity_budget(
+    topology: &NetworkTopology,
+    path: &RoutePath,
+    protocol: &BarrettKokProtocol,
+    policies: &BudgetPolicies,
+) -> Result<FidelityBudget, String> {
+    let mut factors: Vec<(&'static str, String, f64)> = Vec::new();
+
+    for hop in &path.hops {
+        let channel = topology
+            .channel(hop.channel_id)
+            .ok_or(format!("Channel {} does not exist", hop.channel_id))?;
+        let location = format!("link {}-{}", hop.from, hop.to);
+
+        let heralded = protocol.heralded_fidelity(channel);
+        factors.push((
+            "heralded infidelity",
+            location.clone(),
+            werner_parameter(heralded),
+        ));
+
+        let stored = fidelity_after_decoherence(
+            heralded,
+            policies.expected_wait_ms,
+            policies.coherence_time_ms,
+        );
+        factors.push((
+            "storage decoherence",
+            location,
+            werner_parameter(stored) / werner_parameter(heralded),
+        ));
+    }
+
+    let repeaters = path.nodes.len().saturating_sub(2);
+    for &node_id in path.nodes.iter().skip(1).take(repeaters) {
+        let node = topology
+            .get_node(node_id)
+            .ok_or(format!("Node {} does not exist", node_id))?;
+        // Two-qubit depolarizing: F → (1-p)F + p(F/5 + 4(1-F)/15)
+        factors.push((
+            "swap gate error",
+            format!("node {}", node_id),
+            1.0 - 16.0 * node.gate_error / 15.0,
+        ));
+    }
+
+    if let (Some(&first), Some(&last)) = (path.nodes.first(), path.nodes.last()) {
+        // Bell-pair outcomes are uniform, so a readout flip happens with the
+        // mean rate e = (p10 + p01)/2 and scales the correlation 1 - 2·QBER by 1 - 2e
+        let readout = &policies.readout;
+        for node_id in [first, last] {
+            factors.push((
+                "measurement error",
+                format!("node {}", node_id),
+                1.0 - readout.p_report1_given0 - readout.p_report0_given1,
+            ));
+        }
+    }
+
+    let mut werner = 1.0;
+    let entries = factors
+        .into_iter()
+        .map(|(mechanism, location, factor)| {
+            let fidelity_loss = 0.75 * werner * (1.0 - factor);
+            werner *= factor;
+            BudgetEntry {
+                mechanism,
+                location,
+                factor,
+                fidelity_loss,
+                db: -10.0 * factor.log10(),
+            }
+        })
+        .collect();
+
+    Ok(FidelityBudget {
+        entries,
+        total_fidelity: (3.0 * werner + 1.0) / 4.0,
+    })
+}
+
+impl FidelityBudget {
+    /// Total loss in dB-like units
+    pub fn total_db(&self) -> f64 {
+        self.entries.iter().map(|entry| entry.db).sum()
+    }
+
+    /// Total fidelity loss from a perfect pair
+    pub fn total_loss(&self) -> f64 {
+        self.entries.iter().map(|entry| entry.fidelity_loss).sum()
+    }
+
+    /// Budget table in the style of `ScalingReport::summary_table`
+    pub fn to_text(&self) -> String {
+        let mut table = String::from("=== Fidelity Budget ===\n");
+        table.push_str(&format!(
+            "{:<22} {:>12} {:>10} {:>10} {:>8}\n",
+            "Mechanism", "Location", "Factor", "Loss", "dB"
+        ));
+        for entry in &self.entries {
+            table.push_str(&format!(
+                "{:<22} {:>12} {:>10.5} {:>10.5} {:>8.3}\n",
+                entry.mechanism, entry.location, entry.factor, entry.fidelity_loss, entry.db
+            ));
+        }
+        table.push_str(&format!(
+            "{:<22} {:>12} {:>10} {:>10.5} {:>8.3}\n",
+            "Total",
+            "",
+            "",
+            self.total_loss(),
+            self.total_db()
+        ));
+        table.push_str(&format!(
+            "End-to-end fidelity: {:.5}\n",
+            self.total_fidelity
+        ));
+        table
+    }
+
+    /// One line per entry
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("mechanism,location,factor,fidelity_loss,db\n");
+        for entry in &self.entries {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                entry.mechanism, entry.location, entry.factor, entry.fidelity_loss, entry.db
+            ));
+        }
+        csv
+    }
+
+    /// Write the CSV rendering to a file
+    pub fn write_csv(&self, path: &str) -> std::io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(self.to_csv().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::ChannelSelector;
+    use crate::protocols::qkd::{sample_outcomes, Basis};
+    use crate::protocols::swapping::perform_entanglement_swap;
+    use crate::quantum::PauliErrorRates;
+    use rand::SeedableRng;
+
+    const POLICIES: BudgetPolicies = BudgetPolicies {
+        coherence_time_ms: 100.0,
+        expected_wait_ms: 5.0,
+        readout: ConfusionMatrix {
+            p_report1_given0: 0.005,
+            p_report0_given1: 0.015,
+        },
+    };
+
+    fn noisy_chain() -> NetworkTopology {
+        let mut topology = NetworkTopology::new_linear(3, 2, 0.0, 0.2);
+        topology.get_node_mut(1).unwrap().gate_error = 0.02;
+        topology
+    }
+
+    #[test]
+    fn test_contributions_sum_to_total() {
+        let topology = noisy_chain();
+        let path = topology
+            .find_path(0, 2, ChannelSelector::BestSuccessProbability)
+            .unwrap();
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let budget = fidelity_budget(&topology, &path, &protocol, &POLICIES).unwrap();
+
+        // 2 links × 2 mechanisms + 1 repeater + 2 end nodes
+        assert_eq!(budget.entries.len(), 7);
+        assert!((budget.total_loss() - (1.0 - budget.total_fidelity)).abs() < 1e-12);
+
+        let product: f64 = budget.entries.iter().map(|entry| entry.factor).product();
+        assert!((budget.total_db() + 10.0 * product.log10()).abs() < 1e-9);
+        assert_eq!(budget.to_csv().lines().count(), 8);
+    }
+
+    #[test]
+    fn test_budget_matches_monte_carlo() {
+        let mut topology = noisy_chain();
+        let path = topology
+            .find_path(0, 2, ChannelSelector::BestSuccessProbability)
+            .unwrap();
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let budget = fidelity_budget(&topology, &path, &protocol, &POLICIES).unwrap();
+
+        // Generate both links at t = 0, swap after the expected wait
+        for (a, b) in [(0, 1), (1, 2)] {
+            let channel = topology.find_channel(a, b).unwrap().1.clone();
+            let (node_a, node_b) = topology.get_node_pair_mut(a, b).unwrap();
+            while !protocol
+                .attempt_generation(node_a, node_b, &channel, 0.0, POLICIES.coherence_time_ms)
+                .unwrap()
+                .success
+            {}
+        }
+        let swapped =
+            perform_entanglement_swap(&mut topology, 1, 0, 2, POLICIES.expected_wait_ms).unwrap();
+
+        // Estimate fidelity from measured QBER, including readout flips
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let errors = PauliErrorRates::werner(swapped);
+        let rounds = 40000;
+        let mut mismatches = 0;
+        for round in 0..rounds {
+            let basis = if round % 2 == 0 { Basis::Z } else { Basis::X };
+            let (a, b) = sample_outcomes(&errors, basis, basis, &mut rng);
+            let a = POLICIES.readout.apply(a, &mut rng);
+            let b = POLICIES.readout.apply(b, &mut rng);
+            mismatches += (a != b) as usize;
+        }
+        let qber = mismatches as f64 / rounds as f64;
+        let measured_fidelity = 1.0 - 1.5 * qber;
+
+        assert!(
+            (measured_fidelity - budget.total_fidelity).abs() < 0.01,
+            "Monte Carlo {} vs budget {}",
+            measured_fidelity,
+            budget.total_fidelity
+        );
+    }
+}
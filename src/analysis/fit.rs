@@ -0,0 +1,553 @@
+//! Least-squares fit of link model parameters to measured link data
+
+use std::fmt;
+
+/// One measured elementary link
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkObservation {
+    /// Link length (km)
+    pub distance_km: f64,
+    /// Measured heralding probability per attempt
+    pub success_rate: f64,
+    /// Measured fidelity of heralded pairs
+    pub fidelity: f64,
+}
+
+/// Model parameter that can be left free in `fit_link_model`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParamName {
+    AttenuationDbPerKm,
+    DetectorEfficiency,
+    MemoryEfficiency,
+    HomVisibility,
+}
+
+impl ParamName {
+    pub const ALL: [ParamName; 4] = [
+        ParamName::AttenuationDbPerKm,
+        ParamName::DetectorEfficiency,
+        ParamName::MemoryEfficiency,
+        ParamName::HomVisibility,
+    ];
+
+    /// Physical (lower, upper) bounds enforced during the fit
+    pub fn bounds(self) -> (f64, f64) {
+        match self {
+            ParamName::AttenuationDbPerKm => (0.0, 2.0),
+            ParamName::DetectorEfficiency => (1e-6, 1.0),
+            ParamName::MemoryEfficiency => (1e-6, 1.0),
+            ParamName::HomVisibility => (0.0, 1.0),
+        }
+    }
+}
+
+impl fmt::Display for ParamName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ParamName::AttenuationDbPerKm => "attenuation_db_per_km",
+            ParamName::DetectorEfficiency => "detector_efficiency",
+            ParamName::MemoryEfficiency => "memory_efficiency",
+            ParamName::HomVisibility => "hom_visibility",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Barrett-Kok style link model used for fitting
+///
+/// success = bsm × (memory × detector × transmission)², fidelity = (1 + V) / 2
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkModel {
+    pub attenuation_db_per_km: f64,
+    pub detector_efficiency: f64,
+    /// Probability a memory emits a photon into the fiber
+    pub memory_efficiency: f64,
+    /// Hong-Ou-Mandel visibility of the interfering photons
+    pub hom_visibility: f64,
+    /// Fixed BSM success probability (not fitted)
+    pub bsm_efficiency: f64,
+}
+
+impl Default for LinkModel {
+    fn default() -> Self {
+        LinkModel {
+            attenuation_db_per_km: 0.2,
+            detector_efficiency: 0.9,
+            memory_efficiency: 0.9,
+            hom_visibility: 0.9,
+            bsm_efficiency: 0.5,
+        }
+    }
+}
+
+impl LinkModel {
+    pub fn get(&self, param: ParamName) -> f64 {
+        match param {
+            ParamName::AttenuationDbPerKm => self.attenuation_db_per_km,
+            ParamName::DetectorEfficiency => self.detector_efficiency,
+            ParamName::MemoryEfficiency => self.memory_efficiency,
+            ParamName::HomVisibility => self.hom_visibility,
+        }
+    }
+
+    pub fn set(&mut self, param: ParamName, value: f64) {
+        match param {
+            ParamName::AttenuationDbPerKm => self.attenuation_db_per_km = value,
+            ParamName::DetectorEfficiency => self.detector_efficiency = value,
+            ParamName::MemoryEfficiency => self.memory_efficiency = value,
+            ParamName::HomVisibility => self.hom_visibility = value,
+        }
+    }
+
+    /// Predicted heralding probability at `distance_km`
+    pub fn success_rate(&self, distance_km: f64) -> f64 {
+        let alpha = (10.0_f64.ln() / 10.0) * self.attenuation_db_per_km;
+        let per_photon =
+            self.memory_efficiency * self.detector_efficiency * (-alpha * distance_km).exp();
+        self.bsm_efficiency * per_photon * per_photon
+    }
+
+    /// Predicted heralded fidelity
+    pub fn fidelity(&self) -> f64 {
+        (1.0 + self.hom_visibility) / 2.0
+    }
+
+    /// Residuals of one observation: (ln(predicted / measured) rate, fidelity difference)
+    pub fn residual(&self, observation: &LinkObservation) -> LinkResidual {
+        LinkResidual {
+            distance_km: observation.distance_km,
+            log_success_rate: (self.success_rate(observation.distance_km)
+                / observation.success_rate)
+                .ln(),
+            fidelity: self.fidelity() - observation.fidelity,
+        }
+    }
+}
+
+/// Residual of one observation under the fitted model
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkResidual {
+    pub distance_km: f64,
+    /// ln(predicted / measured) success rate
+    pub log_success_rate: f64,
+    /// Predicted minus measured fidelity
+    pub fidelity: f64,
+}
+
+/// Outcome of `fit_link_model`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FitResult {
+    /// Model with the free parameters at their fitted values
+    pub model: LinkModel,
+    /// Fitted value of each free parameter, in the order requested
+    pub fitted: Vec<(ParamName, f64)>,
+    pub residuals: Vec<LinkResidual>,
+    /// Sum of squared residuals (both components)
+    pub sum_squared_residuals: f64,
+    /// Root-mean-square residual per observation component
+    pub rms_residual: f64,
+    pub iterations: usize,
+    /// Degeneracies, parameters pinned at a bound and similar caveats
+    pub warnings: Vec<String>,
+}
+
+impl FitResult {
+    /// Fitted value of `param`, if it was free
+    pub fn value(&self, param: ParamName) -> Option<f64> {
+        self.fitted
+            .iter()
+            .find(|(name, _)| *name == param)
+            .map(|(_, value)| *value)
+    }
+}
+
+const MAX_ITERATIONS: usize = 5_000;
+const TOLERANCE: f64 = 1e-14;
+/// Jacobian columns more aligned than this are reported as degenerate
+const DEGENERACY_COSINE: f64 = 0.9999;
+
+/// Fit `free_params` of the default `LinkModel` to `observations`
+pub fn fit_link_model(observations: &[LinkObservation], free_params: &[ParamName]) -> FitResult {
+    fit_link_model_from(&LinkModel::default(), observations, free_params)
+}
+
+/// Fit `free_params` starting from `initial`; other parameters keep their values
+///
+/// Minimizes squared log-rate and fidelity residuals with a bounded Nelder-Mead search
+pub fn fit_link_model_from(
+    initial: &LinkModel,
+    observations: &[LinkObservation],
+    free_params: &[ParamName],
+) -> FitResult {
+    let mut free: Vec<ParamName> = Vec::new();
+    for param in free_params {
+        if !free.contains(param) {
+            free.push(*param);
+        }
+    }
+
+    let mut warnings = Vec::new();
+    let usable: Vec<LinkObservation> = observations
+        .iter()
+        .copied()
+        .filter(|obs| obs.success_rate > 0.0 && obs.distance_km >= 0.0)
+        .collect();
+    if usable.len() < observations.len() {
+        warnings.push(format!(
+            "Ignored {} observation(s) with non-positive success rate or negative distance",
+            observations.len() - usable.len()
+        ));
+    }
+    if usable.is_empty() {
+        warnings.push("No usable observations; returning the initial model".to_string());
+    }
+    if 2 * usable.len() < free.len() {
+        warnings.push(format!(
+            "{} free parameter(s) but only {} residual(s)",
+            free.len(),
+            2 * usable.len()
+        ));
+    }
+
+    let model_at = |point: &[f64]| {
+        let mut model = *initial;
+        for (param, value) in free.iter().zip(point) {
+            model.set(*param, *value);
+        }
+        model
+    };
+    let cost = |point: &[f64]| sum_squares(&model_at(point), &usable);
+
+    let start: Vec<f64> = free
+        .iter()
+        .map(|param| clamp(*param, initial.get(*param)))
+        .collect();
+    let (best, iterations) = if free.is_empty() || usable.is_empty() {
+        (start, 0)
+    } else {
+        // Restart once from the first optimum to escape a collapsed simplex
+        let (first, first_iterations) = nelder_mead(&free, &start, &cost);
+        let (second, second_iterations) = nelder_mead(&free, &first, &cost);
+        (second, first_iterations + second_iterations)
+    };
+
+    let model = model_at(&best);
+    for (param, value) in free.iter().zip(&best) {
+        let (low, high) = param.bounds();
+        if (value - low).abs() < 1e-9 || (value - high).abs() < 1e-9 {
+            warnings.push(format!("{} is pinned at its bound {}", param, value));
+        }
+    }
+    warnings.extend(degeneracy_warnings(&free, &best, &model_at, &usable));
+
+    let residuals: Vec<LinkResidual> = usable.iter().map(|obs| model.residual(obs)).collect();
+    let sum_squared_residuals = sum_squares(&model, &usable);
+    let rms_residual = if usable.is_empty() {
+        0.0
+    } else {
+        (sum_squared_residuals / (2 * usable.len()) as f64).sqrt()
+    };
+
+    FitResult {
+        model,
+        fitted: free.iter().copied().zip(best).collect(),
+        residuals,
+        sum_squared_residuals,
+        rms_residual,
+        iterations,
+        warnings,
+    }
+}
+
+fn clamp(param: ParamName, value: f64) -> f64 {
+    let (low, high) = param.bounds();
+    value.clamp(low, high)
+}
+
+fn sum_squares(model: &LinkModel, observations: &[LinkObservation]) -> f64 {
+    observations
+        .iter()
+        .map(|obs| {
+            let r = model.residual(obs);
+            r.log_success_rate * r.log_success_rate + r.fidelity * r.fidelity
+        })
+        .sum()
+}
+
+/// Nelder-Mead minimization with every vertex clamped to the parameter bounds
+fn nelder_mead(
+    free: &[ParamName],
+    start: &[f64],
+    cost: &impl Fn(&[f64]) -> f64,
+) -> (Vec<f64>, usize) {
+    let n = free.len();
+    let project = |point: Vec<f64>| -> Vec<f64> {
+        point
+            .iter()
+            .zip(free)
+            .map(|(value, param)| clamp(*param, *value))
+            .collect()
+    };
+
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(n + 1);
+    simplex.push((start.to_vec(), cost(start)));
+    for (i, param) in free.iter().enumerate() {
+        let (low, high) = param.bounds();
+        let step = 0.1 * (high - low);
+        let mut vertex = start.to_vec();
+        // Step away from the nearer bound so the vertex stays distinct after clamping
+        vertex[i] += if start[i] + step <= high { step } else { -step };
+        let vertex = project(vertex);
+        let value = cost(&vertex);
+        simplex.push((vertex, value));
+    }
+
+    let mut iterations = 0;
+    while iterations < MAX_ITERATIONS {
+        iterations += 1;
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if simplex[n].1 - simplex[0].1 <= TOLERANCE {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|i| simplex[..n].iter().map(|(v, _)| v[i]).sum::<f64>() / n as f64)
+            .collect();
+        let along = |t: f64| -> Vec<f64> {
+            project(
+                centroid
+                    .iter()
+                    .zip(&simplex[n].0)
+                    .map(|(c, w)| c + t * (c - w))
+                    .collect(),
+            )
+        };
+
+        let reflected = along(1.0);
+        let reflected_cost = cost(&reflected);
+        if reflected_cost < simplex[0].1 {
+            let expanded = along(2.0);
+            let expanded_cost = cost(&expanded);
+            simplex[n] = if expanded_cost < reflected_cost {
+                (expanded, expanded_cost)
+            } else {
+                (reflected, reflected_cost)
+            };
+        } else if reflected_cost < simplex[n - 1].1 {
+            simplex[n] = (reflected, reflected_cost);
+        } else {
+            let contracted = if reflected_cost < simplex[n].1 {
+                along(0.5)
+            } else {
+                along(-0.5)
+            };
+            let contracted_cost = cost(&contracted);
+            if contracted_cost < simplex[n].1.min(reflected_cost) {
+                simplex[n] = (contracted, contracted_cost);
+            } else {
+                // Shrink towards the best vertex
+                let best = simplex[0].0.clone();
+                for vertex in simplex.iter_mut().skip(1) {
+                    let shrunk: Vec<f64> = best
+                        .iter()
+                        .zip(&vertex.0)
+                        .map(|(b, v)| b + 0.5 * (v - b))
+                        .collect();
+                    let value = cost(&shrunk);
+                    *vertex = (shrunk, value);
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    (simplex.swap_remove(0).0, iterations)
+}
+
+/// Warn about free parameters whose residual Jacobian columns are (anti)parallel
+/// or zero: the data only constrains a combination of them
+fn degeneracy_warnings(
+    free: &[ParamName],
+    point: &[f64],
+    model_at: &impl Fn(&[f64]) -> LinkModel,
+    observations: &[LinkObservation],
+) -> Vec<String> {
+    if observations.is_empty() {
+        return Vec::new();
+    }
+    let residual_vector = |p: &[f64]| -> Vec<f64> {
+        let model = model_at(p);
+        observations
+            .iter()
+            .flat_map(|obs| {
+                let r = model.residual(obs);
+                [r.log_success_rate, r.fidelity]
+            })
+            .collect()
+    };
+
+    let columns: Vec<Vec<f64>> = free
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let (low, high) = param.bounds();
+            let h = 1e-6 * (high - low);
+            let mut up = point.to_vec();
+            let mut down = point.to_vec();
+            up[i] = (point[i] + h).min(high);
+            down[i] = (point[i] - h).max(low);
+            let width = up[i] - down[i];
+            residual_vector(&up)
+                .iter()
+                .zip(residual_vector(&down))
+                .map(|(u, d)| (u - d) / width)
+                .collect()
+        })
+        .collect();
+
+    let norm = |c: &[f64]| c.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let mut warnings = Vec::new();
+    for (i, column) in columns.iter().enumerate() {
+        if norm(column) < 1e-12 {
+            warnings.push(format!(
+                "Degenerate fit: the observations do not constrain {}",
+                free[i]
+            ));
+        }
+    }
+    for i in 0..columns.len() {
+        for j in (i + 1)..columns.len() {
+            let (ni, nj) = (norm(&columns[i]), norm(&columns[j]));
+            if ni < 1e-12 || nj < 1e-12 {
+                continue;
+            }
+            let dot: f64 = columns[i].iter().zip(&columns[j]).map(|(a, b)| a * b).sum();
+            if (dot / (ni * nj)).abs() > DEGENERACY_COSINE {
+                warnings.push(format!(
+                    "Degenerate fit: {} and {} are not separately identifiable from these observations",
+                    free[i], free[j]
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn truth() -> LinkModel {
+        LinkModel {
+            attenuation_db_per_km: 0.25,
+            detector_efficiency: 0.8,
+            memory_efficiency: 0.6,
+            hom_visibility: 0.92,
+            bsm_efficiency: 0.5,
+        }
+    }
+
+    /// Observations from `model` with ±2% rate and ±0.005 fidelity noise
+    fn synthetic(model: &LinkModel, seed: u64) -> Vec<LinkObservation> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (1..=10)
+            .map(|i| {
+                let distance_km = 5.0 * i as f64;
+                LinkObservation {
+                    distance_km,
+                    success_rate: model.success_rate(distance_km)
+                        * (1.0 + rng.random_range(-0.02..0.02)),
+                    fidelity: model.fidelity() + rng.random_range(-0.005..0.005),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recovers_known_parameters() {
+        let truth = truth();
+        let observations = synthetic(&truth, 7);
+        let initial = LinkModel {
+            detector_efficiency: truth.detector_efficiency,
+            ..LinkModel::default()
+        };
+        let result = fit_link_model_from(
+            &initial,
+            &observations,
+            &[
+                ParamName::AttenuationDbPerKm,
+                ParamName::MemoryEfficiency,
+                ParamName::HomVisibility,
+            ],
+        );
+
+        let attenuation = result.value(ParamName::AttenuationDbPerKm).unwrap();
+        let memory = result.value(ParamName::MemoryEfficiency).unwrap();
+        let visibility = result.value(ParamName::HomVisibility).unwrap();
+        assert!(
+            (attenuation - 0.25).abs() < 0.005,
+            "attenuation {}",
+            attenuation
+        );
+        assert!((memory - 0.6).abs() < 0.02, "memory {}", memory);
+        assert!(
+            (visibility - 0.92).abs() < 0.01,
+            "visibility {}",
+            visibility
+        );
+        assert!(result.rms_residual < 0.02);
+        assert_eq!(result.residuals.len(), observations.len());
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn test_over_parameterized_fit_warns_about_degeneracy() {
+        let observations = synthetic(&truth(), 11);
+        let result = fit_link_model(&observations, &ParamName::ALL);
+
+        assert!(result.warnings.iter().any(|w| w.contains("Degenerate")
+            && w.contains("detector_efficiency")
+            && w.contains("memory_efficiency")));
+        // The identifiable product is still fitted well
+        let product = result.model.detector_efficiency * result.model.memory_efficiency;
+        assert!((product - 0.48).abs() < 0.02, "product {}", product);
+    }
+
+    #[test]
+    fn test_single_distance_cannot_separate_attenuation() {
+        let truth = truth();
+        let observations: Vec<LinkObservation> = (0..5)
+            .map(|_| LinkObservation {
+                distance_km: 20.0,
+                success_rate: truth.success_rate(20.0),
+                fidelity: truth.fidelity(),
+            })
+            .collect();
+        let result = fit_link_model(
+            &observations,
+            &[ParamName::AttenuationDbPerKm, ParamName::MemoryEfficiency],
+        );
+        assert!(result.warnings.iter().any(|w| w.contains("Degenerate")));
+    }
+
+    #[test]
+    fn test_fit_respects_bounds() {
+        // Rates above what a lossless perfect link allows push efficiencies to 1
+        let observations = vec![LinkObservation {
+            distance_km: 0.0,
+            success_rate: 0.9,
+            fidelity: 1.0,
+        }];
+        let result = fit_link_model(&observations, &[ParamName::MemoryEfficiency]);
+        let memory = result.value(ParamName::MemoryEfficiency).unwrap();
+        assert!(memory <= 1.0);
+        assert!(result.warnings.iter().any(|w| w.contains("pinned")));
+    }
+}
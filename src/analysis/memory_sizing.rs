@@ -0,0 +1,269 @@
+use super::cutoff::expected_max_geometric;
+use crate::network::{ChannelSelector, NetworkTopology};
+use std::collections::BTreeMap;
+
+/// Sustained end-to-end pair demand between two nodes
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrafficDemand {
+    pub source: usize,
+    pub destination: usize,
+    /// Delivered pairs per second
+    pub rate_hz: f64,
+}
+
+/// Protocol parameters that set how long a memory qubit stays occupied
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SizingParams {
+    /// Time between generation attempts on each link (ms)
+    pub attempt_interval_ms: f64,
+    /// Per-attempt success on top of channel transmission (emission, BSM, detectors)
+    pub link_efficiency: f64,
+    pub swap_success_probability: f64,
+    /// Link pairs waiting longer than this for the rest of the path are discarded
+    pub cutoff_ms: Option<f64>,
+    /// Time an application keeps a delivered pair before consuming it (ms)
+    pub consumption_time_ms: f64,
+}
+
+impl Default for SizingParams {
+    fn default() -> Self {
+        SizingParams {
+            attempt_interval_ms: 1.0,
+            link_efficiency: 1.0,
+            swap_success_probability: 1.0,
+            cutoff_ms: None,
+            consumption_time_ms: 0.0,
+        }
+    }
+}
+
+/// Recommended memory per node with the model inputs behind it
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryRecommendation {
+    /// (node id, recommended capacity) for every node, by id
+    pub capacities: Vec<(usize, usize)>,
+    /// Mean number of occupied qubits per node (Erlangs)
+    pub offered_load: BTreeMap<usize, f64>,
+    pub target_blocking: f64,
+    /// Modelling assumptions, one sentence each
+    pub assumptions: Vec<String>,
+}
+
+impl MemoryRecommendation {
+    /// Recommended capacity of `node_id` (0 if the node is unknown)
+    pub fn capacity(&self, node_id: usize) -> usize {
+        self.capacities
+            .iter()
+            .find(|(id, _)| *id == node_id)
+            .map_or(0, |(_, capacity)| *capacity)
+    }
+}
+
+/// Erlang B blocking probability of `servers` servers under `offered_load` Erlangs
+pub fn erlang_b(servers: usize, offered_load: f64) -> f64 {
+    // B(0) = 1, B(c) = a B(c-1) / (c + a B(c-1))
+    let mut blocking = 1.0;
+    for c in 1..=servers {
+        blocking = offered_load * blocking / (c as f64 + offered_load * blocking);
+    }
+    blocking
+}
+
+/// Smallest capacity whose Erlang B blocking is at most `target_blocking`
+fn erlang_capacity(offered_load: f64, target_blocking: f64) -> usize {
+    if offered_load <= 0.0 {
+        return 0;
+    }
+    let mut blocking = 1.0;
+    let mut servers = 0;
+    while blocking > target_blocking {
+        servers += 1;
+        blocking = offered_load * blocking / (servers as f64 + offered_load * blocking);
+    }
+    servers
+}
+
+/// Recommend per-node memory capacities keeping the blocking probability
+/// of a new pair under `target_blocking`
+///
+/// Each demand is routed with `find_path`; every hop occupies one qubit at
+/// both ends while its link pair waits for the rest of the path, and the
+/// end nodes hold the delivered pair until it is consumed. The summed
+/// occupancy per node is sized as an Erlang loss system.
+pub fn recommend_memory(
+    topology: &NetworkTopology,
+    traffic: &[TrafficDemand],
+    params: &SizingParams,
+    target_blocking: f64,
+) -> Result<MemoryRecommendation, String> {
+    if !(target_blocking > 0.0 && target_blocking < 1.0) {
+        return Err(format!(
+            "Target blocking must be in (0, 1), got {}",
+            target_blocking
+        ));
+    }
+
+    let mut offered_load: BTreeMap<usize, f64> =
+        topology.nodes().iter().map(|node| (node.id, 0.0)).collect();
+    let interval = params.attempt_interval_ms;
+    for demand in traffic {
+        let path = topology
+            .find_path(
+                demand.source,
+                demand.destination,
+                ChannelSelector::BestSuccessProbability,
+            )
+            .ok_or_else(|| format!("No route from {} to {}", demand.source, demand.destination))?;
+        let probabilities: Vec<f64> = path
+            .hops
+            .iter()
+            .map(|hop| {
+                let channel = topology.channel(hop.channel_id).unwrap();
+                channel.success_probability() * params.link_efficiency
+            })
+            .collect();
+        let slots_for_all = expected_max_geometric(&probabilities).ok_or_else(|| {
+            format!(
+                "A link from {} to {} can never succeed",
+                demand.source, demand.destination
+            )
+        })?;
+
+        // Every failed swap round consumes a full set of link pairs
+        let repeaters = path.nodes.len() - 2;
+        let rounds_per_pair = 1.0 / params.swap_success_probability.powi(repeaters as i32);
+        let pairs_per_ms = demand.rate_hz / 1000.0;
+
+        for (hop, p) in path.hops.iter().zip(&probabilities) {
+            // A link pair waits from its own success until the slowest hop succeeds
+            let mut hold_ms = (slots_for_all - 1.0 / p + 1.0) * interval;
+            if let Some(cutoff_ms) = params.cutoff_ms {
+                hold_ms = hold_ms.min(cutoff_ms);
+            }
+            let load = pairs_per_ms * rounds_per_pair * hold_ms;
+            *offered_load.entry(hop.from).or_insert(0.0) += load;
+            *offered_load.entry(hop.to).or_insert(0.0) += load;
+        }
+        for end in [demand.source, demand.destination] {
+            *offered_load.entry(end).or_insert(0.0) += pairs_per_ms * params.consumption_time_ms;
+        }
+    }
+
+    let capacities = offered_load
+        .iter()
+        .map(|(&node_id, &load)| (node_id, erlang_capacity(load, target_blocking)))
+        .collect();
+
+    let mut assumptions = vec![
+        "Pair requests arrive as a Poisson process and a blocked request is lost (Erlang B)"
+            .to_string(),
+        "Each demand uses the single best-success-probability route".to_string(),
+        "Link pairs are held for the expected wait until every hop of the path has a pair"
+            .to_string(),
+        "Links attempt independently; pairs are never discarded for low fidelity".to_string(),
+    ];
+    if let Some(cutoff_ms) = params.cutoff_ms {
+        assumptions.push(format!(
+            "Holding times are capped by the {} ms cutoff",
+            cutoff_ms
+        ));
+    }
+    if params.consumption_time_ms > 0.0 {
+        assumptions.push(format!(
+            "End nodes hold each delivered pair for {} ms before consuming it",
+            params.consumption_time_ms
+        ));
+    }
+
+    Ok(MemoryRecommendation {
+        capacities,
+        offered_load,
+        target_blocking,
+        assumptions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Star with hub 0; every leaf sends `rate_hz` pairs per second to the next
+    /// Each leaf ends two two-hop demands, the hub relays all four
+    fn star_recommendation(target_blocking: f64, rate_hz: f64) -> MemoryRecommendation {
+        let topology = NetworkTopology::new_star(5, 1, 10.0, 0.2);
+        let traffic: Vec<TrafficDemand> = (1..=4)
+            .map(|leaf| TrafficDemand {
+                source: leaf,
+                destination: leaf % 4 + 1,
+                rate_hz,
+            })
+            .collect();
+        recommend_memory(
+            &topology,
+            &traffic,
+            &SizingParams::default(),
+            target_blocking,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_erlang_b_known_values() {
+        assert_eq!(erlang_b(0, 3.0), 1.0);
+        assert!((erlang_b(1, 1.0) - 0.5).abs() < 1e-12);
+        // B(2, 1) = 1/5
+        assert!((erlang_b(2, 1.0) - 0.2).abs() < 1e-12);
+        assert_eq!(erlang_capacity(1.0, 0.2), 2);
+        assert_eq!(erlang_capacity(0.0, 0.01), 0);
+    }
+
+    #[test]
+    fn test_star_hub_needs_about_four_times_a_leaf() {
+        let recommendation = star_recommendation(0.05, 20_000.0);
+        let hub = recommendation.capacity(0) as f64;
+        for leaf in 1..=4 {
+            let ratio = hub / recommendation.capacity(leaf) as f64;
+            assert!((ratio - 4.0).abs() < 0.6, "hub/leaf ratio {}", ratio);
+        }
+        assert!(!recommendation.assumptions.is_empty());
+    }
+
+    #[test]
+    fn test_higher_target_blocking_never_needs_more_memory() {
+        let targets = [0.001, 0.01, 0.05, 0.2];
+        let recommendations: Vec<MemoryRecommendation> = targets
+            .iter()
+            .map(|&target| star_recommendation(target, 2_000.0))
+            .collect();
+        for pair in recommendations.windows(2) {
+            for (&(id, looser), &(_, stricter)) in
+                pair[1].capacities.iter().zip(&pair[0].capacities)
+            {
+                assert!(looser <= stricter, "node {}: {} > {}", id, looser, stricter);
+            }
+        }
+        let total = |r: &MemoryRecommendation| r.capacities.iter().map(|(_, c)| c).sum::<usize>();
+        assert!(total(&recommendations[3]) < total(&recommendations[0]));
+    }
+
+    #[test]
+    fn test_rejects_unroutable_demand() {
+        let mut topology = NetworkTopology::new_custom();
+        topology
+            .add_node(crate::network::QuantumNode::new(0, 1))
+            .unwrap();
+        topology
+            .add_node(crate::network::QuantumNode::new(1, 1))
+            .unwrap();
+        let traffic = [TrafficDemand {
+            source: 0,
+            destination: 1,
+            rate_hz: 1.0,
+        }];
+        assert!(recommend_memory(&topology, &traffic, &SizingParams::default(), 0.01).is_err());
+        assert!(recommend_memory(&topology, &[], &SizingParams::default(), 1.5).is_err());
+    }
+}
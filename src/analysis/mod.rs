@@ -0,0 +1,17 @@
+pub mod cutoff;
+pub mod fidelity_budget;
+pub mod fit;
+pub mod memory_sizing;
+
+pub use cutoff::{
+    binary_entropy, expected_max_geometric, optimal_cutoff, predict_with_cutoff, CutoffLinkParams,
+    CutoffPrediction, CutoffRecommendation, OptimizeFor,
+};
+pub use fidelity_budget::{fidelity_budget, BudgetEntry, BudgetPolicies, FidelityBudget};
+pub use fit::{
+    fit_link_model, fit_link_model_from, FitResult, LinkModel, LinkObservation, LinkResidual,
+    ParamName,
+};
+pub use memory_sizing::{
+    erlang_b, recommend_memory, MemoryRecommendation, SizingParams, TrafficDemand,
+};
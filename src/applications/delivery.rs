@@ -0,0 +1,166 @@
+use crate::network::StoredPair;
+use crate::quantum::PauliFrame;
+use std::collections::VecDeque;
+
+/// An end-to-end pair handed to an application but not yet used
+#[derive(Clone)]
+pub struct DeliveredPair {
+    /// Source node's half (it keeps decohering while queued)
+    pub pair: StoredPair,
+    pub delivered_at: f64,
+    /// Fidelity when the pair left node memory
+    pub fidelity_at_delivery: f64,
+}
+
+/// A delivered pair the application has consumed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConsumedPair {
+    pub delivered_at: f64,
+    pub consumed_at: f64,
+    pub fidelity_at_delivery: f64,
+    pub fidelity_at_consumption: f64,
+    /// Pauli correction still owed to the pair; measurement outcomes are
+    /// read through `PauliFrame::reinterpret_outcome`
+    pub pauli_frame: PauliFrame,
+}
+
+impl ConsumedPair {
+    /// Time spent waiting in the delivery queue (ms)
+    pub fn storage_duration_ms(&self) -> f64 {
+        self.consumed_at - self.delivered_at
+    }
+}
+
+/// Counters of one delivery queue
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeliveryStats {
+    pub delivered: usize,
+    pub consumed: usize,
+    /// Pairs dropped by the cutoff before being consumed
+    pub expired: usize,
+    /// Fidelity of each consumed pair at consumption time
+    pub consumed_fidelities: Vec<f64>,
+    /// Queueing time of each consumed pair (ms)
+    pub storage_times_ms: Vec<f64>,
+}
+
+/// Pairs delivered for one (source, destination) request, awaiting the application
+///
+/// Delivered pairs leave node memory, so they never block generation;
+/// unconsumed pairs are dropped once older than `cutoff_ms`
+pub struct DeliveryQueue {
+    pub source: usize,
+    pub destination: usize,
+    /// Drop pairs queued longer than this (ms); None keeps them forever
+    pub cutoff_ms: Option<f64>,
+    pending: VecDeque<DeliveredPair>,
+    stats: DeliveryStats,
+}
+
+impl DeliveryQueue {
+    pub fn new(source: usize, destination: usize, cutoff_ms: Option<f64>) -> Self {
+        DeliveryQueue {
+            source,
+            destination,
+            cutoff_ms,
+            pending: VecDeque::new(),
+            stats: DeliveryStats::default(),
+        }
+    }
+
+    /// Hand a pair (already removed from both end nodes) to the application
+    pub fn deliver(&mut self, pair: StoredPair, current_time: f64) {
+        let fidelity_at_delivery = pair.fidelity_at(current_time);
+        self.pending.push_back(DeliveredPair {
+            pair,
+            delivered_at: current_time,
+            fidelity_at_delivery,
+        });
+        self.stats.delivered += 1;
+    }
+
+    /// Drop pairs past the cutoff; returns how many were dropped
+    pub fn expire(&mut self, current_time: f64) -> usize {
+        let Some(cutoff_ms) = self.cutoff_ms else {
+            return 0;
+        };
+        let before = self.pending.len();
+        self.pending
+            .retain(|delivered| current_time - delivered.delivered_at <= cutoff_ms);
+        let expired = before - self.pending.len();
+        self.stats.expired += expired;
+        expired
+    }
+
+    /// Consume the oldest unexpired pair, finalizing its stats
+    pub fn take_delivered(&mut self, current_time: f64) -> Option<ConsumedPair> {
+        self.expire(current_time);
+        let delivered = self.pending.pop_front()?;
+        let consumed = ConsumedPair {
+            delivered_at: delivered.delivered_at,
+            consumed_at: current_time,
+            fidelity_at_delivery: delivered.fidelity_at_delivery,
+            fidelity_at_consumption: delivered.pair.fidelity_at(current_time),
+            pauli_frame: delivered.pair.pauli_frame,
+        };
+        self.stats.consumed += 1;
+        self.stats
+            .consumed_fidelities
+            .push(consumed.fidelity_at_consumption);
+        self.stats
+            .storage_times_ms
+            .push(consumed.storage_duration_ms());
+        Some(consumed)
+    }
+
+    /// Pairs waiting to be consumed (expired ones included until the next `expire`)
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn stats(&self) -> &DeliveryStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantum::{fidelity_after_decoherence, TwoQubitState};
+
+    fn pair_created_at(time: f64) -> StoredPair {
+        StoredPair::new(1, TwoQubitState::new_bell_phi_plus(), time, 50.0)
+    }
+
+    #[test]
+    fn test_consumption_fidelity_includes_queueing_decoherence() {
+        let mut queue = DeliveryQueue::new(0, 1, None);
+        queue.deliver(pair_created_at(0.0), 2.0);
+
+        let consumed = queue.take_delivered(12.0).unwrap();
+        assert_eq!(consumed.storage_duration_ms(), 10.0);
+        let expected = fidelity_after_decoherence(consumed.fidelity_at_delivery, 10.0, 50.0);
+        assert!((consumed.fidelity_at_consumption - expected).abs() < 1e-12);
+        assert!(consumed.fidelity_at_consumption < consumed.fidelity_at_delivery);
+        assert_eq!(queue.stats().storage_times_ms, vec![10.0]);
+    }
+
+    #[test]
+    fn test_unconsumed_pairs_expire() {
+        let mut queue = DeliveryQueue::new(0, 1, Some(5.0));
+        queue.deliver(pair_created_at(0.0), 0.0);
+        queue.deliver(pair_created_at(3.0), 3.0);
+
+        // At t = 6 the first pair is past the cutoff, the second is not
+        let consumed = queue.take_delivered(6.0).unwrap();
+        assert_eq!(consumed.delivered_at, 3.0);
+        assert_eq!(queue.stats().expired, 1);
+        assert!(queue.take_delivered(6.0).is_none());
+        assert_eq!(queue.stats().consumed, 1);
+    }
+}
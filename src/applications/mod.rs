@@ -0,0 +1,5 @@
+pub mod delivery;
+pub mod ping_pong;
+
+pub use delivery::{ConsumedPair, DeliveredPair, DeliveryQueue, DeliveryStats};
+pub use ping_pong::{Hit, PingPong};
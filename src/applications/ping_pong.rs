@@ -0,0 +1,134 @@
+use crate::experiment::RequestService;
+use crate::network::NetworkTopology;
+use crate::simulation::{Event, EventHandler, EventScheduler, RunReport};
+
+/// One pass of the ball
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    pub from: usize,
+    pub to: usize,
+    pub time: f64,
+    /// Fidelity of the pair used, at consumption time
+    pub fidelity: f64,
+}
+
+/// Two nodes pass a ball back and forth, teleporting it over one
+/// end-to-end pair per hit
+///
+/// Wraps a `RequestService` as the event handler and consumes pairs from
+/// its delivery queue as soon as they are delivered.
+pub struct PingPong {
+    pub service: RequestService,
+    pub player_a: usize,
+    pub player_b: usize,
+    ball_at: usize,
+    hits: Vec<Hit>,
+}
+
+impl PingPong {
+    /// `player_a` starts with the ball
+    pub fn new(service: RequestService, player_a: usize, player_b: usize) -> Self {
+        PingPong {
+            service,
+            player_a,
+            player_b,
+            ball_at: player_a,
+            hits: Vec::new(),
+        }
+    }
+
+    /// Request pairs for `hits` more passes
+    pub fn play(
+        &mut self,
+        hits: usize,
+        scheduler: &mut EventScheduler,
+        current_time: f64,
+    ) -> Result<(), String> {
+        self.service
+            .submit(self.player_a, self.player_b, hits, scheduler, current_time)
+    }
+
+    /// Node holding the ball
+    pub fn ball_at(&self) -> usize {
+        self.ball_at
+    }
+
+    pub fn hits(&self) -> &[Hit] {
+        &self.hits
+    }
+}
+
+impl EventHandler for PingPong {
+    fn check_invariants(&self) -> Result<(), String> {
+        self.service.check_invariants()
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.service.set_seed(seed);
+    }
+
+    fn topology(&self) -> Option<&NetworkTopology> {
+        Some(&self.service.topology)
+    }
+
+    fn handle(&mut self, event: &Event, scheduler: &mut EventScheduler) {
+        self.service.handle(event, scheduler);
+
+        while let Some(consumed) =
+            self.service
+                .take_delivered(self.player_a, self.player_b, event.time)
+        {
+            let to = if self.ball_at == self.player_a {
+                self.player_b
+            } else {
+                self.player_a
+            };
+            self.hits.push(Hit {
+                from: self.ball_at,
+                to,
+                time: event.time,
+                fidelity: consumed.fidelity_at_consumption,
+            });
+            self.ball_at = to;
+        }
+    }
+
+    fn report(&self, report: &mut RunReport) {
+        self.service.report(report);
+        let fidelities: Vec<f64> = self.hits.iter().map(|hit| hit.fidelity).collect();
+        report.record_samples("hit_fidelity", &fidelities);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::experiment::ServiceConfig;
+    use crate::simulation::SimulationEngine;
+
+    #[test]
+    fn test_rally_consumes_every_delivery() {
+        let mut topology = NetworkTopology::new_linear(3, 2, 0.0, 0.2);
+        topology.get_node_mut(0).unwrap().memory_capacity = 1;
+        topology.get_node_mut(2).unwrap().memory_capacity = 1;
+        let mut game = PingPong::new(
+            RequestService::new(topology, ServiceConfig::default()),
+            0,
+            2,
+        );
+        let mut engine = SimulationEngine::new();
+
+        game.play(4, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut game);
+
+        let passes: Vec<(usize, usize)> =
+            game.hits().iter().map(|hit| (hit.from, hit.to)).collect();
+        assert_eq!(passes, vec![(0, 2), (2, 0), (0, 2), (2, 0)]);
+        assert_eq!(game.ball_at(), 0);
+        assert!(game.service.delivery_queue(0, 2).unwrap().is_empty());
+        assert_eq!(
+            game.service.delivery_queue(0, 2).unwrap().stats().consumed,
+            4
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use crate::network::{attempt_entanglement_generation_with_rng, NetworkTopology, QuantumChannel};
+use crate::protocols::swapping::perform_entanglement_swap_with_rng;
+use crate::simulation::unseeded_rng;
+use rand::Rng;
+
+/// Parameters for a slotted repeater-chain run
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainConfig {
+    /// Fiber attenuation (dB/km)
+    pub attenuation_db_per_km: f64,
+    /// Memory slots per node (repeaters need at least 2)
+    pub memory_per_node: usize,
+    /// Memory coherence time in milliseconds
+    pub coherence_time_ms: f64,
+    /// Time between generation attempts on each link (ms)
+    /// Raised to the links' attempt duration when that is longer
+    pub attempt_interval_ms: f64,
+    /// Number of attempt slots per replication
+    pub slots_per_run: usize,
+    /// Number of independent replications
+    pub replications: usize,
+    /// Probability that a swap succeeds (0.5 for a linear-optics BSM)
+    pub swap_success_probability: f64,
+    /// Discard link pairs stored longer than this (ms); None keeps them forever
+    pub cutoff_ms: Option<f64>,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        ChainConfig {
+            attenuation_db_per_km: 0.2,
+            memory_per_node: 2,
+            coherence_time_ms: 100.0,
+            attempt_interval_ms: 1.0,
+            slots_per_run: 10_000,
+            replications: 5,
+            swap_success_probability: 1.0,
+            cutoff_ms: None,
+        }
+    }
+}
+
+/// Results of one chain replication
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainRunStats {
+    /// End-to-end pairs delivered
+    pub deliveries: usize,
+    /// Simulated time covered by the run (ms)
+    pub total_time_ms: f64,
+    /// Fidelity of each delivered pair at delivery time
+    pub fidelities: Vec<f64>,
+    /// Time from the start of each delivery cycle to delivery (ms)
+    pub latencies_ms: Vec<f64>,
+    /// Elementary-link generation attempts
+    pub link_attempts: usize,
+    /// Elementary-link generation attempts that heralded a pair
+    pub link_successes: usize,
+}
+
+impl ChainRunStats {
+    /// Delivered pairs per second
+    pub fn rate_hz(&self) -> f64 {
+        if self.total_time_ms == 0.0 {
+            0.0
+        } else {
+            self.deliveries as f64 / (self.total_time_ms / 1000.0)
+        }
+    }
+
+    /// Fraction of link attempts that succeeded
+    pub fn link_success_rate(&self) -> f64 {
+        if self.link_attempts == 0 {
+            0.0
+        } else {
+            self.link_successes as f64 / self.link_attempts as f64
+        }
+    }
+}
+
+/// Run one replication of a linear repeater chain
+///
+/// Every slot, each link without a stored pair attempts generation. Once all
+/// links hold a pair, swaps are performed left to right and the end-to-end pair
+/// is delivered (removed from the end nodes). A failed swap discards every
+/// pair in the chain; pairs older than the cutoff are discarded before each slot.
+pub fn run_chain(num_nodes: usize, link_distance_km: f64, config: &ChainConfig) -> ChainRunStats {
+    run_chain_with_rng(
+        num_nodes,
+        link_distance_km,
+        config,
+        &mut unseeded_rng("run_chain"),
+    )
+}
+
+/// `run_chain` drawing every generation, swap and BSM outcome from the caller's RNG
+pub fn run_chain_with_rng(
+    num_nodes: usize,
+    link_distance_km: f64,
+    config: &ChainConfig,
+    rng: &mut impl Rng,
+) -> ChainRunStats {
+    let mut network = NetworkTopology::new_linear(
+        num_nodes,
+        config.memory_per_node,
+        link_distance_km,
+        config.attenuation_db_per_km,
+    );
+    let last = num_nodes - 1;
+    // No link may attempt again before its previous attempt has heralded
+    let slot_ms = network
+        .channels()
+        .iter()
+        .map(QuantumChannel::attempt_duration_ms)
+        .fold(config.attempt_interval_ms, f64::max);
+
+    let mut stats = ChainRunStats::default();
+    let mut cycle_start = 0.0;
+
+    for slot in 0..config.slots_per_run {
+        let time = slot as f64 * slot_ms;
+
+        if let Some(cutoff_ms) = config.cutoff_ms {
+            for i in 0..last {
+                let node = network.get_node(i).unwrap();
+                let expired = node
+                    .find_pair_with(i + 1)
+                    .and_then(|handle| node.pair(handle))
+                    .is_some_and(|pair| time - pair.creation_time > cutoff_ms);
+                if expired {
+                    let (node_a, node_b) = network.get_node_pair_mut(i, i + 1).unwrap();
+                    if let Some(pair) = node_a.remove_pair_with(i + 1, time) {
+                        node_b.remove_pair_by_id(pair.pair_id, time);
+                    }
+                }
+            }
+        }
+
+        // Attempt generation on every idle link
+        for i in 0..last {
+            if network.get_node(i).unwrap().find_pair_with(i + 1).is_some() {
+                continue;
+            }
+            let channel = network.find_channel(i, i + 1).unwrap().1.clone();
+            let (node_a, node_b) = network.get_node_pair_mut(i, i + 1).unwrap();
+            stats.link_attempts += 1;
+            if let Ok(true) = attempt_entanglement_generation_with_rng(
+                node_a,
+                node_b,
+                &channel,
+                time,
+                config.coherence_time_ms,
+                rng,
+            ) {
+                stats.link_successes += 1;
+            }
+        }
+
+        let all_links_ready =
+            (0..last).all(|i| network.get_node(i).unwrap().find_pair_with(i + 1).is_some());
+        if !all_links_ready {
+            continue;
+        }
+
+        // Swap at each repeater: (0-1, 1-2) -> 0-2, then (0-2, 2-3) -> 0-3 ...
+        let all_swaps_succeeded =
+            (1..last).all(|_| rng.random::<f64>() < config.swap_success_probability);
+        if !all_swaps_succeeded {
+            for node in 0..num_nodes {
+                network.get_node_mut(node).unwrap().clear_memory(time);
+            }
+            continue;
+        }
+        for repeater in 1..last {
+            perform_entanglement_swap_with_rng(&mut network, repeater, 0, repeater + 1, time, rng)
+                .expect("all links hold a pair");
+        }
+
+        let pair = network
+            .get_node_mut(0)
+            .unwrap()
+            .remove_pair_with(last, time)
+            .unwrap();
+        network
+            .get_node_mut(last)
+            .unwrap()
+            .remove_pair_by_id(pair.pair_id, time);
+
+        stats.deliveries += 1;
+        stats.fidelities.push(pair.fidelity_at(time));
+        stats.latencies_ms.push(time + slot_ms - cycle_start);
+        cycle_start = time + slot_ms;
+    }
+
+    stats.total_time_ms = config.slots_per_run as f64 * slot_ms;
+    stats
+}
+
+/// Expected delivery rate (pairs/s) of a single-repeater chain without cutoff
+///
+/// Both links retry independently until each holds a pair, so the expected
+/// number of slots per attempt cycle is E[max(G1, G2)] = 2/p - 1/(p(2-p)).
+pub fn analytic_one_repeater_rate(
+    link_success_probability: f64,
+    swap_success_probability: f64,
+    attempt_interval_ms: f64,
+) -> f64 {
+    let p = link_success_probability;
+    if p <= 0.0 {
+        return 0.0;
+    }
+    let expected_slots = 2.0 / p - 1.0 / (p * (2.0 - p));
+    swap_success_probability / (expected_slots * attempt_interval_ms / 1000.0)
+}
+
+/// Expected delivered fidelity of a single-repeater chain without cutoff
+///
+/// Links start at fidelity 1, so the swapped pair carries the decay of the
+/// older link over the gap D = |G1 - G2| slots between the two successes:
+/// E[e^(-D dt/T)] = p/(2-p) * (1 + 2qx/(1-qx)) with q = 1-p, x = e^(-dt/T).
+pub fn analytic_one_repeater_fidelity(
+    link_success_probability: f64,
+    attempt_interval_ms: f64,
+    coherence_time_ms: f64,
+) -> f64 {
+    let p = link_success_probability;
+    if p <= 0.0 {
+        return 0.0;
+    }
+    let qx = (1.0 - p) * (-attempt_interval_ms / coherence_time_ms).exp();
+    p / (2.0 - p) * (1.0 + 2.0 * qx / (1.0 - qx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::QuantumChannel;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_lossless_chain_delivers_every_slot() {
+        let config = ChainConfig {
+            slots_per_run: 50,
+            ..ChainConfig::default()
+        };
+        let stats = run_chain(4, 0.0, &config);
+
+        assert_eq!(stats.deliveries, 50);
+        assert!(stats.latencies_ms.iter().all(|&l| (l - 1.0).abs() < 1e-10));
+        assert!(stats.fidelities.iter().all(|&f| (f - 1.0).abs() < 1e-10));
+    }
+
+    #[test]
+    fn test_long_links_stretch_the_slot() {
+        // 500 km lossless links: one attempt takes ≈ 2.45 ms, longer than the interval
+        let config = ChainConfig {
+            attenuation_db_per_km: 0.0,
+            slots_per_run: 10,
+            ..ChainConfig::default()
+        };
+        let stats = run_chain(3, 500.0, &config);
+        let duration = QuantumChannel::new(0, 1, 500.0, 0.0).attempt_duration_ms();
+
+        assert_eq!(stats.deliveries, 10);
+        assert!((stats.total_time_ms - 10.0 * duration).abs() < 1e-9);
+        assert!(stats
+            .latencies_ms
+            .iter()
+            .all(|&l| (l - duration).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_seeded_runs_repeat_exactly() {
+        let config = ChainConfig {
+            slots_per_run: 200,
+            swap_success_probability: 0.5,
+            ..ChainConfig::default()
+        };
+        let run = |seed| run_chain_with_rng(3, 25.0, &config, &mut StdRng::seed_from_u64(seed));
+
+        let (first, second) = (run(3), run(3));
+        assert_eq!(first.deliveries, second.deliveries);
+        assert_eq!(first.latencies_ms, second.latencies_ms);
+        assert_eq!(first.link_attempts, second.link_attempts);
+        assert_ne!(first.latencies_ms, run(4).latencies_ms);
+    }
+
+    #[test]
+    fn test_cutoff_discards_old_link_pairs() {
+        // Cutoff shorter than one slot: both links must succeed in the same slot
+        let config = ChainConfig {
+            slots_per_run: 20_000,
+            cutoff_ms: Some(0.5),
+            ..ChainConfig::default()
+        };
+        let link_p =
+            QuantumChannel::new(0, 1, 25.0, config.attenuation_db_per_km).success_probability();
+
+        let stats = run_chain(3, 25.0, &config);
+        let per_slot = stats.deliveries as f64 / config.slots_per_run as f64;
+        assert!((per_slot - link_p * link_p).abs() / (link_p * link_p) < 0.15);
+    }
+
+    #[test]
+    fn test_one_repeater_matches_analytic_rate() {
+        let config = ChainConfig {
+            slots_per_run: 20_000,
+            swap_success_probability: 0.5,
+            ..ChainConfig::default()
+        };
+        let link_p =
+            QuantumChannel::new(0, 1, 25.0, config.attenuation_db_per_km).success_probability();
+
+        let simulated = run_chain(3, 25.0, &config).rate_hz();
+        let analytic = analytic_one_repeater_rate(
+            link_p,
+            config.swap_success_probability,
+            config.attempt_interval_ms,
+        );
+
+        // Several hundred deliveries: allow 20% statistical slack
+        assert!((simulated - analytic).abs() / analytic < 0.2);
+    }
+}
@@ -0,0 +1,351 @@
+use super::chain::{
+    analytic_one_repeater_fidelity, analytic_one_repeater_rate, run_chain, ChainConfig,
+};
+use crate::format::format_rate;
+use crate::network::QuantumChannel;
+use std::fs::File;
+use std::io::Write;
+
+/// CSV header of `TheoryComparison::to_csv`; also the JSON keys of each point
+pub const COMPARISON_COLUMNS: [&str; 12] = [
+    "link_distance_km",
+    "deliveries",
+    "link_success_rate",
+    "link_success_rate_theory",
+    "link_success_rate_rel_error",
+    "rate_hz",
+    "rate_hz_theory",
+    "rate_hz_rel_error",
+    "mean_fidelity",
+    "mean_fidelity_theory",
+    "mean_fidelity_rel_error",
+    "deliveries_expected",
+];
+
+/// Empirical and analytic results at one link distance
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComparisonPoint {
+    pub link_distance_km: f64,
+    /// End-to-end pairs delivered over all replications
+    pub deliveries: usize,
+    /// Fraction of link attempts that succeeded
+    pub link_success_rate: f64,
+    /// Channel success probability
+    pub link_success_rate_theory: f64,
+    /// Mean delivery rate across replications (pairs/s)
+    pub rate_hz: f64,
+    /// Expected rate at the configured attempt cadence (pairs/s)
+    pub rate_hz_theory: f64,
+    /// Mean fidelity of delivered pairs (0.0 if none delivered)
+    pub mean_fidelity: f64,
+    /// Expected delivered fidelity from the decoherence model
+    pub mean_fidelity_theory: f64,
+    /// Deliveries predicted over the simulated time of all replications
+    pub deliveries_expected: f64,
+}
+
+impl ComparisonPoint {
+    pub fn link_success_rate_rel_error(&self) -> f64 {
+        relative_error(self.link_success_rate, self.link_success_rate_theory)
+    }
+
+    pub fn rate_hz_rel_error(&self) -> f64 {
+        relative_error(self.rate_hz, self.rate_hz_theory)
+    }
+
+    pub fn mean_fidelity_rel_error(&self) -> f64 {
+        relative_error(self.mean_fidelity, self.mean_fidelity_theory)
+    }
+
+    fn values(&self) -> [f64; 12] {
+        [
+            self.link_distance_km,
+            self.deliveries as f64,
+            self.link_success_rate,
+            self.link_success_rate_theory,
+            self.link_success_rate_rel_error(),
+            self.rate_hz,
+            self.rate_hz_theory,
+            self.rate_hz_rel_error(),
+            self.mean_fidelity,
+            self.mean_fidelity_theory,
+            self.mean_fidelity_rel_error(),
+            self.deliveries_expected,
+        ]
+    }
+}
+
+/// (empirical - theory) / theory; 0.0 when both are zero
+pub fn relative_error(empirical: f64, theory: f64) -> f64 {
+    if theory == 0.0 {
+        if empirical == 0.0 {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        (empirical - theory) / theory
+    }
+}
+
+/// Simulated vs analytic results across a link-distance sweep
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TheoryComparison {
+    /// 2 (direct link) or 3 (one repeater)
+    pub num_nodes: usize,
+    pub points: Vec<ComparisonPoint>,
+}
+
+impl TheoryComparison {
+    /// Pearson chi-square of delivery counts: sum of (observed - expected)^2 / expected
+    pub fn chi_square(&self) -> f64 {
+        self.points
+            .iter()
+            .filter(|point| point.deliveries_expected > 0.0)
+            .map(|point| {
+                let residual = point.deliveries as f64 - point.deliveries_expected;
+                residual * residual / point.deliveries_expected
+            })
+            .sum()
+    }
+
+    /// `chi_square` divided by the number of points; close to 1 when the model fits
+    pub fn chi_square_per_point(&self) -> f64 {
+        if self.points.is_empty() {
+            0.0
+        } else {
+            self.chi_square() / self.points.len() as f64
+        }
+    }
+
+    /// Render as CSV with the `COMPARISON_COLUMNS` header
+    pub fn to_csv(&self) -> String {
+        let mut csv = COMPARISON_COLUMNS.join(",");
+        csv.push('\n');
+        for point in &self.points {
+            let values: Vec<String> = point.values().iter().map(f64::to_string).collect();
+            csv.push_str(&values.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Write the CSV rendering to a file
+    pub fn write_csv(&self, path: &str) -> std::io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(self.to_csv().as_bytes())
+    }
+
+    /// JSON object with the goodness-of-fit summary and one object per point
+    pub fn to_json(&self) -> String {
+        let points: Vec<String> = self
+            .points
+            .iter()
+            .map(|point| {
+                let fields: Vec<String> = COMPARISON_COLUMNS
+                    .iter()
+                    .zip(point.values())
+                    .map(|(name, value)| format!("\"{}\":{}", name, json_number(value)))
+                    .collect();
+                format!("{{{}}}", fields.join(","))
+            })
+            .collect();
+        format!(
+            "{{\"num_nodes\":{},\"chi_square\":{},\"chi_square_per_point\":{},\"points\":[{}]}}",
+            self.num_nodes,
+            json_number(self.chi_square()),
+            json_number(self.chi_square_per_point()),
+            points.join(",")
+        )
+    }
+
+    /// Compact human-readable table
+    pub fn summary_table(&self) -> String {
+        let mut table = format!("=== Theory vs Simulation ({} nodes) ===\n", self.num_nodes);
+        table.push_str(&format!(
+            "{:>8} {:>9} {:>9} {:>7} {:>13} {:>13} {:>7} {:>8} {:>8} {:>7}\n",
+            "link_km",
+            "p_link",
+            "p_theory",
+            "err",
+            "rate",
+            "theory",
+            "err",
+            "fidelity",
+            "theory",
+            "err"
+        ));
+        for point in &self.points {
+            table.push_str(&format!(
+                "{:>8.1} {:>9.5} {:>9.5} {:>6.1}% {:>13} {:>13} {:>6.1}% {:>8.4} {:>8.4} {:>6.1}%\n",
+                point.link_distance_km,
+                point.link_success_rate,
+                point.link_success_rate_theory,
+                100.0 * point.link_success_rate_rel_error(),
+                format_rate(point.rate_hz),
+                format_rate(point.rate_hz_theory),
+                100.0 * point.rate_hz_rel_error(),
+                point.mean_fidelity,
+                point.mean_fidelity_theory,
+                100.0 * point.mean_fidelity_rel_error()
+            ));
+        }
+        table.push_str(&format!(
+            "chi-square: {:.2} over {} points ({:.2} per point)\n",
+            self.chi_square(),
+            self.points.len(),
+            self.chi_square_per_point()
+        ));
+        table
+    }
+}
+
+/// JSON has no representation for infinities or NaN
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Simulate a direct link (2 nodes) or one-repeater chain (3 nodes) at each
+/// link distance and pair the results with their analytic predictions
+///
+/// The predictions ignore `cutoff_ms`, so set it to None for a fair comparison.
+pub fn compare_with_theory(
+    num_nodes: usize,
+    link_distances_km: &[f64],
+    config: &ChainConfig,
+) -> Result<TheoryComparison, String> {
+    if num_nodes != 2 && num_nodes != 3 {
+        return Err(format!(
+            "Analytic predictions exist for 2 or 3 nodes, got {}",
+            num_nodes
+        ));
+    }
+    let attempt_interval_s = config.attempt_interval_ms / 1000.0;
+
+    let points = link_distances_km
+        .iter()
+        .map(|&link_distance_km| {
+            let p = QuantumChannel::new(0, 1, link_distance_km, config.attenuation_db_per_km)
+                .success_probability();
+            let (rate_hz_theory, mean_fidelity_theory) = if num_nodes == 2 {
+                // Delivered in the slot it is generated, so no decay
+                (p / attempt_interval_s, 1.0)
+            } else {
+                (
+                    analytic_one_repeater_rate(
+                        p,
+                        config.swap_success_probability,
+                        config.attempt_interval_ms,
+                    ),
+                    analytic_one_repeater_fidelity(
+                        p,
+                        config.attempt_interval_ms,
+                        config.coherence_time_ms,
+                    ),
+                )
+            };
+
+            let mut deliveries = 0;
+            let mut total_time_ms = 0.0;
+            let mut link_attempts = 0;
+            let mut link_successes = 0;
+            let mut fidelities = Vec::new();
+            for _ in 0..config.replications {
+                let stats = run_chain(num_nodes, link_distance_km, config);
+                deliveries += stats.deliveries;
+                total_time_ms += stats.total_time_ms;
+                link_attempts += stats.link_attempts;
+                link_successes += stats.link_successes;
+                fidelities.extend(stats.fidelities);
+            }
+
+            ComparisonPoint {
+                link_distance_km,
+                deliveries,
+                link_success_rate: ratio(link_successes as f64, link_attempts as f64),
+                link_success_rate_theory: p,
+                rate_hz: ratio(deliveries as f64, total_time_ms / 1000.0),
+                rate_hz_theory,
+                mean_fidelity: ratio(fidelities.iter().sum(), fidelities.len() as f64),
+                mean_fidelity_theory,
+                deliveries_expected: rate_hz_theory * total_time_ms / 1000.0,
+            }
+        })
+        .collect();
+
+    Ok(TheoryComparison { num_nodes, points })
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        0.0
+    } else {
+        numerator / denominator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_config() -> ChainConfig {
+        ChainConfig {
+            coherence_time_ms: 20.0,
+            slots_per_run: 20_000,
+            replications: 2,
+            ..ChainConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_converged_sweep_matches_theory() {
+        let comparison = compare_with_theory(3, &[5.0, 15.0, 25.0], &fast_config()).unwrap();
+
+        for point in &comparison.points {
+            // Four standard errors of a Poisson count
+            let tolerance = 4.0 / (point.deliveries as f64).sqrt();
+            assert!(point.rate_hz_rel_error().abs() < tolerance, "{:?}", point);
+            assert!(point.link_success_rate_rel_error().abs() < tolerance);
+            assert!(point.mean_fidelity_rel_error().abs() < tolerance);
+        }
+        // Expected value is 1 per point; 5 is far in the tail for 3 points
+        assert!(comparison.chi_square_per_point() < 5.0);
+    }
+
+    #[test]
+    fn test_direct_link_fidelity_is_exact() {
+        let comparison = compare_with_theory(2, &[10.0], &fast_config()).unwrap();
+        assert!(comparison.points[0].mean_fidelity_rel_error().abs() < 1e-10);
+        assert!(compare_with_theory(4, &[10.0], &fast_config()).is_err());
+    }
+
+    #[test]
+    fn test_exports_use_documented_columns() {
+        let comparison = compare_with_theory(2, &[0.0, 10.0], &fast_config()).unwrap();
+
+        let csv = comparison.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), COMPARISON_COLUMNS.join(","));
+        assert_eq!(lines.count(), 2);
+
+        let json = comparison.to_json();
+        assert!(json.starts_with("{\"num_nodes\":2,\"chi_square\":"));
+        for column in COMPARISON_COLUMNS {
+            assert!(json.contains(&format!("\"{}\":", column)), "{}", column);
+        }
+        assert!(comparison.summary_table().contains("chi-square"));
+    }
+
+    #[test]
+    fn test_relative_error() {
+        assert!((relative_error(1.1, 1.0) - 0.1).abs() < 1e-12);
+        assert_eq!(relative_error(0.0, 0.0), 0.0);
+        assert!(relative_error(1.0, 0.0).is_infinite());
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+
+/// Settings of the replication convergence check
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConvergenceConfig {
+    /// Number of most recent replications compared against the overall mean
+    pub window: usize,
+    /// Largest accepted |window mean - overall mean| / |overall mean|
+    pub relative_shift: f64,
+    /// Replications run before the first check
+    pub min_replications: usize,
+    /// Stop adding replications at this count even if metrics are flagged
+    pub max_replications: usize,
+}
+
+impl Default for ConvergenceConfig {
+    fn default() -> Self {
+        ConvergenceConfig {
+            window: 10,
+            relative_shift: 0.05,
+            min_replications: 20,
+            max_replications: 200,
+        }
+    }
+}
+
+/// Convergence diagnostics of one metric across replications
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricConvergence {
+    pub name: String,
+    /// Value of the metric in each replication
+    pub samples: Vec<f64>,
+    /// Mean of the first n samples, for n = 1..=samples.len()
+    pub running_mean: Vec<f64>,
+    /// Standard error of the mean after n samples (0.0 below 2 samples)
+    pub standard_error: Vec<f64>,
+    /// Mean of the last `window` samples
+    pub window_mean: f64,
+    /// Mean of all samples
+    pub overall_mean: f64,
+    /// Too few samples, or the window mean drifted beyond the threshold
+    pub flagged: bool,
+}
+
+impl MetricConvergence {
+    /// Geweke-like check: compare the mean of the last `window` samples to the
+    /// overall mean; fewer than 2 * `window` samples is always flagged
+    pub fn analyze(name: &str, samples: &[f64], config: &ConvergenceConfig) -> Self {
+        let mut running_mean = Vec::with_capacity(samples.len());
+        let mut standard_error = Vec::with_capacity(samples.len());
+        let (mut sum, mut sum_sq) = (0.0, 0.0);
+        for (i, &value) in samples.iter().enumerate() {
+            let n = (i + 1) as f64;
+            sum += value;
+            sum_sq += value * value;
+            let mean = sum / n;
+            running_mean.push(mean);
+            standard_error.push(if i == 0 {
+                0.0
+            } else {
+                // Sample variance with Bessel's correction
+                let variance = ((sum_sq - n * mean * mean) / (n - 1.0)).max(0.0);
+                (variance / n).sqrt()
+            });
+        }
+
+        let window = config.window.max(1).min(samples.len());
+        let window_mean = mean(&samples[samples.len() - window..]);
+        let overall_mean = mean(samples);
+        let mut diagnostic = MetricConvergence {
+            name: name.to_string(),
+            samples: samples.to_vec(),
+            running_mean,
+            standard_error,
+            window_mean,
+            overall_mean,
+            flagged: false,
+        };
+        diagnostic.flagged = samples.len() < 2 * config.window.max(1)
+            || diagnostic.relative_shift() > config.relative_shift;
+        diagnostic
+    }
+
+    /// |window mean - overall mean| / |overall mean|; 0.0 if both means are zero
+    pub fn relative_shift(&self) -> f64 {
+        let shift = (self.window_mean - self.overall_mean).abs();
+        if shift == 0.0 {
+            0.0
+        } else {
+            shift / self.overall_mean.abs()
+        }
+    }
+
+    /// Standard error of the mean over all samples
+    pub fn final_standard_error(&self) -> f64 {
+        self.standard_error.last().copied().unwrap_or(0.0)
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Convergence diagnostics of every metric of a set of replications
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConvergenceReport {
+    pub replications: usize,
+    /// One entry per metric, sorted by name
+    pub metrics: Vec<MetricConvergence>,
+    /// Replications stopped at `max_replications` with metrics still flagged
+    pub hit_cap: bool,
+}
+
+impl ConvergenceReport {
+    /// Diagnose each named metric series
+    pub fn analyze(samples: &BTreeMap<String, Vec<f64>>, config: &ConvergenceConfig) -> Self {
+        let metrics: Vec<MetricConvergence> = samples
+            .iter()
+            .map(|(name, values)| MetricConvergence::analyze(name, values, config))
+            .collect();
+        ConvergenceReport {
+            replications: samples.values().map(Vec::len).max().unwrap_or(0),
+            metrics,
+            hit_cap: false,
+        }
+    }
+
+    pub fn all_converged(&self) -> bool {
+        self.metrics.iter().all(|metric| !metric.flagged)
+    }
+
+    /// Names of the metrics that have not converged
+    pub fn flagged(&self) -> Vec<&str> {
+        self.metrics
+            .iter()
+            .filter(|metric| metric.flagged)
+            .map(|metric| metric.name.as_str())
+            .collect()
+    }
+
+    pub fn metric(&self, name: &str) -> Option<&MetricConvergence> {
+        self.metrics.iter().find(|metric| metric.name == name)
+    }
+}
+
+/// Run replications until every metric converges or `max_replications` is hit
+///
+/// `run` receives the replication index and returns (metric name, value)
+/// pairs. After `min_replications`, one replication is added at a time.
+pub fn run_until_converged<F>(config: &ConvergenceConfig, mut run: F) -> ConvergenceReport
+where
+    F: FnMut(usize) -> Vec<(&'static str, f64)>,
+{
+    let mut samples: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut replications = 0;
+    loop {
+        for (name, value) in run(replications) {
+            samples.entry(name.to_string()).or_default().push(value);
+        }
+        replications += 1;
+        if replications < config.min_replications {
+            continue;
+        }
+
+        let mut report = ConvergenceReport::analyze(&samples, config);
+        if report.all_converged() || replications >= config.max_replications {
+            report.hit_cap = !report.all_converged();
+            return report;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_drifting_metric_is_flagged() {
+        let config = ConvergenceConfig::default();
+        // Mean creeps up 1% per replication
+        let drifting: Vec<f64> = (0..100).map(|i| 10.0 * (1.0 + 0.01 * i as f64)).collect();
+        let mut rng = StdRng::seed_from_u64(3);
+        let iid: Vec<f64> = (0..100).map(|_| 10.0 + rng.random::<f64>() - 0.5).collect();
+
+        let mut samples = BTreeMap::new();
+        samples.insert("drifting".to_string(), drifting);
+        samples.insert("iid".to_string(), iid);
+        let report = ConvergenceReport::analyze(&samples, &config);
+
+        assert_eq!(report.replications, 100);
+        assert_eq!(report.flagged(), vec!["drifting"]);
+        let iid = report.metric("iid").unwrap();
+        assert!(iid.relative_shift() < config.relative_shift);
+        assert_eq!(iid.running_mean.len(), 100);
+        // Uniform noise of width 1: sd = 1/sqrt(12), so SE ≈ 0.029 after 100 samples
+        assert!((iid.final_standard_error() - 0.029).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_too_few_samples_flagged() {
+        let report = MetricConvergence::analyze("x", &[1.0; 5], &ConvergenceConfig::default());
+        assert!(report.flagged);
+        assert_eq!(report.standard_error, vec![0.0; 5]);
+    }
+
+    #[test]
+    fn test_auto_extension_stops_when_converged_or_at_cap() {
+        let config = ConvergenceConfig::default();
+        let mut rng = StdRng::seed_from_u64(9);
+        let report = run_until_converged(&config, |_| vec![("iid", 5.0 + rng.random::<f64>())]);
+        assert!(report.all_converged());
+        assert!(!report.hit_cap);
+        assert_eq!(report.replications, config.min_replications);
+
+        let report = run_until_converged(&config, |index| {
+            vec![("constant", 1.0), ("drifting", index as f64)]
+        });
+        assert!(report.hit_cap);
+        assert_eq!(report.replications, config.max_replications);
+        assert_eq!(report.flagged(), vec!["drifting"]);
+    }
+}
@@ -0,0 +1,33 @@
+pub mod chain;
+pub mod comparison;
+pub mod convergence;
+pub mod provenance;
+pub mod rate_curve;
+pub mod runner;
+pub mod scaling;
+pub mod service;
+pub mod star;
+
+pub use chain::{
+    analytic_one_repeater_fidelity, analytic_one_repeater_rate, run_chain, run_chain_with_rng,
+    ChainConfig, ChainRunStats,
+};
+pub use comparison::{
+    compare_with_theory, relative_error, ComparisonPoint, TheoryComparison, COMPARISON_COLUMNS,
+};
+pub use convergence::{
+    run_until_converged, ConvergenceConfig, ConvergenceReport, MetricConvergence,
+};
+pub use provenance::{LatencyBreakdown, LinkProvenance, PairProvenance, LATENCY_STATES};
+pub use rate_curve::{
+    crossover_distance, rate_curve, write_rate_curve_csv, RateCurveConfig, RateCurvePoint,
+};
+#[cfg(feature = "parallel")]
+pub use runner::run_replications_parallel;
+pub use runner::{run_replications, ReplicationResults};
+pub use scaling::{direct_transmission_rate, scaling_study, ScalingReport, ScalingRow};
+pub use service::{RequestService, ServiceConfig, ServiceStats};
+pub use star::{
+    jain_fairness_index, round_robin_requests, run_star, run_star_with_rng, StarConfig,
+    StarRunStats,
+};
@@ -0,0 +1,259 @@
+use crate::format::format_time;
+use crate::network::ChannelId;
+use std::collections::BTreeMap;
+
+/// Timestamps of the elementary link pair a delivery was swapped from
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkProvenance {
+    pub channel_id: ChannelId,
+    pub from: usize,
+    pub to: usize,
+    /// Successful photon attempt
+    pub generated_at: f64,
+    /// Heralding message received at both ends
+    pub heralded_at: f64,
+}
+
+/// Timestamps of one delivered end-to-end pair
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairProvenance {
+    pub source: usize,
+    pub destination: usize,
+    pub submitted_at: f64,
+    /// The request reached the head of the queue
+    pub service_started_at: f64,
+    /// One entry per hop of the path, source to destination
+    pub links: Vec<LinkProvenance>,
+    pub swap_started_at: f64,
+    pub swap_completed_at: f64,
+    /// Classical corrections applied; the pair is handed to the application
+    pub delivered_at: f64,
+}
+
+impl PairProvenance {
+    pub fn latency_ms(&self) -> f64 {
+        self.delivered_at - self.submitted_at
+    }
+
+    /// Where the delivery latency went, following the last link to be heralded
+    pub fn end_to_end_breakdown(&self) -> LatencyBreakdown {
+        let critical = self
+            .links
+            .iter()
+            .max_by(|a, b| a.heralded_at.total_cmp(&b.heralded_at))
+            .expect("a delivered pair has at least one link");
+        self.breakdown_through(critical)
+    }
+
+    /// Breakdown following each link of the path, keyed by (channel id, from, to)
+    pub fn link_breakdowns(&self) -> Vec<((ChannelId, usize, usize), LatencyBreakdown)> {
+        self.links
+            .iter()
+            .map(|link| {
+                (
+                    (link.channel_id, link.from, link.to),
+                    self.breakdown_through(link),
+                )
+            })
+            .collect()
+    }
+
+    fn breakdown_through(&self, link: &LinkProvenance) -> LatencyBreakdown {
+        LatencyBreakdown {
+            queued_ms: self.service_started_at - self.submitted_at,
+            photon_attempts_ms: link.generated_at - self.service_started_at,
+            heralding_ms: link.heralded_at - link.generated_at,
+            idle_in_memory_ms: self.swap_started_at - link.heralded_at,
+            swap_processing_ms: self.swap_completed_at - self.swap_started_at,
+            classical_corrections_ms: self.delivered_at - self.swap_completed_at,
+        }
+    }
+}
+
+/// Time a delivery spent in each state; the parts sum to the delivery latency
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatencyBreakdown {
+    /// Behind earlier requests in the queue
+    pub queued_ms: f64,
+    /// Until the photon attempt that produced the link pair
+    pub photon_attempts_ms: f64,
+    /// Until the heralding message arrived
+    pub heralding_ms: f64,
+    /// Heralded and stored, waiting for the rest of the path
+    pub idle_in_memory_ms: f64,
+    pub swap_processing_ms: f64,
+    /// Until the Pauli corrections from the swaps arrived
+    pub classical_corrections_ms: f64,
+}
+
+/// Labels of the `LatencyBreakdown` states, in `parts` order
+pub const LATENCY_STATES: [&str; 6] = [
+    "queued",
+    "photon_attempts",
+    "heralding",
+    "idle_in_memory",
+    "swap_processing",
+    "classical_corrections",
+];
+
+impl LatencyBreakdown {
+    /// Times in `LATENCY_STATES` order
+    pub fn parts(&self) -> [f64; 6] {
+        [
+            self.queued_ms,
+            self.photon_attempts_ms,
+            self.heralding_ms,
+            self.idle_in_memory_ms,
+            self.swap_processing_ms,
+            self.classical_corrections_ms,
+        ]
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.parts().iter().sum()
+    }
+
+    /// Percentage of the total in each state (all zero for a zero total)
+    pub fn percentages(&self) -> [f64; 6] {
+        let total = self.total_ms();
+        self.parts().map(|part| {
+            if total == 0.0 {
+                0.0
+            } else {
+                100.0 * part / total
+            }
+        })
+    }
+
+    /// Waiting on classical messages: heralding plus corrections
+    pub fn classical_wait_ms(&self) -> f64 {
+        self.heralding_ms + self.classical_corrections_ms
+    }
+
+    /// Component-wise mean
+    pub fn mean(breakdowns: &[LatencyBreakdown]) -> LatencyBreakdown {
+        if breakdowns.is_empty() {
+            return LatencyBreakdown::default();
+        }
+        let n = breakdowns.len() as f64;
+        let mut sums = [0.0; 6];
+        for breakdown in breakdowns {
+            for (sum, part) in sums.iter_mut().zip(breakdown.parts()) {
+                *sum += part;
+            }
+        }
+        LatencyBreakdown {
+            queued_ms: sums[0] / n,
+            photon_attempts_ms: sums[1] / n,
+            heralding_ms: sums[2] / n,
+            idle_in_memory_ms: sums[3] / n,
+            swap_processing_ms: sums[4] / n,
+            classical_corrections_ms: sums[5] / n,
+        }
+    }
+}
+
+/// Mean breakdown per link, keyed by (channel id, from, to), over all deliveries
+pub fn mean_link_breakdowns(
+    provenance: &[PairProvenance],
+) -> BTreeMap<(ChannelId, usize, usize), LatencyBreakdown> {
+    let mut by_link: BTreeMap<(ChannelId, usize, usize), Vec<LatencyBreakdown>> = BTreeMap::new();
+    for pair in provenance {
+        for (link, breakdown) in pair.link_breakdowns() {
+            by_link.entry(link).or_default().push(breakdown);
+        }
+    }
+    by_link
+        .into_iter()
+        .map(|(link, breakdowns)| (link, LatencyBreakdown::mean(&breakdowns)))
+        .collect()
+}
+
+/// Mean breakdowns, end-to-end then per link, as absolute times and percentages
+pub fn latency_breakdown_table(provenance: &[PairProvenance]) -> String {
+    let mut table = format!(
+        "=== Delivery Latency Breakdown ({} pairs, mean time / %) ===\n{:>10}",
+        provenance.len(),
+        "scope"
+    );
+    for state in LATENCY_STATES {
+        table.push_str(&format!(" {:>22}", state));
+    }
+    table.push_str(&format!(" {:>10}\n", "total"));
+
+    let end_to_end: Vec<LatencyBreakdown> = provenance
+        .iter()
+        .map(PairProvenance::end_to_end_breakdown)
+        .collect();
+    let mut rows = vec![(
+        "end-to-end".to_string(),
+        LatencyBreakdown::mean(&end_to_end),
+    )];
+    rows.extend(mean_link_breakdowns(provenance).into_iter().map(
+        |((channel_id, from, to), breakdown)| {
+            (format!("{}-{} #{}", from, to, channel_id), breakdown)
+        },
+    ));
+    for (scope, breakdown) in rows {
+        table.push_str(&format!("{:>10}", scope));
+        for (part, percentage) in breakdown.parts().iter().zip(breakdown.percentages()) {
+            table.push_str(&format!(
+                " {:>13} ({:>5.1}%)",
+                format_time(*part),
+                percentage
+            ));
+        }
+        table.push_str(&format!(" {:>10}\n", format_time(breakdown.total_ms())));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_follows_last_heralded_link() {
+        let pair = PairProvenance {
+            source: 0,
+            destination: 2,
+            submitted_at: 0.0,
+            service_started_at: 2.0,
+            links: vec![
+                LinkProvenance {
+                    channel_id: ChannelId(0),
+                    from: 0,
+                    to: 1,
+                    generated_at: 3.0,
+                    heralded_at: 4.0,
+                },
+                LinkProvenance {
+                    channel_id: ChannelId(1),
+                    from: 1,
+                    to: 2,
+                    generated_at: 6.0,
+                    heralded_at: 7.0,
+                },
+            ],
+            swap_started_at: 8.0,
+            swap_completed_at: 8.5,
+            delivered_at: 10.0,
+        };
+
+        let breakdown = pair.end_to_end_breakdown();
+        assert_eq!(breakdown.parts(), [2.0, 4.0, 1.0, 1.0, 0.5, 1.5]);
+        assert_eq!(breakdown.total_ms(), pair.latency_ms());
+
+        let links = pair.link_breakdowns();
+        assert_eq!(links[0].0, (ChannelId(0), 0, 1));
+        assert_eq!(links[0].1.idle_in_memory_ms, 4.0);
+        assert!(links.iter().all(|(_, b)| b.total_ms() == pair.latency_ms()));
+
+        let table = latency_breakdown_table(&[pair]);
+        assert_eq!(table.lines().count(), 5);
+        assert!(table.contains("classical_corrections"));
+    }
+}
@@ -0,0 +1,141 @@
+use super::chain::{analytic_one_repeater_rate, run_chain, ChainConfig};
+use super::scaling::direct_transmission_rate;
+use crate::network::QuantumChannel;
+use std::fs::File;
+use std::io::Write;
+
+/// Configuration of a direct-link vs single-repeater distance sweep
+#[derive(Debug, Clone)]
+pub struct RateCurveConfig {
+    /// Total end-to-end distances to evaluate (km)
+    pub distances_km: Vec<f64>,
+    /// Chain parameters used for the one-repeater runs
+    pub chain: ChainConfig,
+}
+
+impl RateCurveConfig {
+    /// Telecom fiber, linear-optics swap and a memory cutoff
+    /// Sweeps 10-300 km in 10 km steps
+    pub fn realistic() -> Self {
+        RateCurveConfig {
+            distances_km: (1..=30).map(|i| i as f64 * 10.0).collect(),
+            chain: ChainConfig {
+                attenuation_db_per_km: 0.2,
+                memory_per_node: 2,
+                coherence_time_ms: 100.0,
+                attempt_interval_ms: 1.0,
+                slots_per_run: 100_000,
+                replications: 3,
+                swap_success_probability: 0.5,
+                cutoff_ms: Some(50.0),
+            },
+        }
+    }
+}
+
+/// Rates at one total distance
+#[derive(Debug, Clone)]
+pub struct RateCurvePoint {
+    pub distance_km: f64,
+    /// Analytic direct-transmission rate (pairs/s)
+    pub direct_rate_hz: f64,
+    /// Simulated one-repeater rate with a midpoint swap (pairs/s)
+    pub repeater_rate_hz: f64,
+    /// Analytic one-repeater rate ignoring the cutoff (pairs/s)
+    pub analytic_repeater_rate_hz: f64,
+}
+
+/// Sweep the configured distances, simulating a 3-node chain at each one
+pub fn rate_curve(config: &RateCurveConfig) -> Vec<RateCurvePoint> {
+    let chain = &config.chain;
+    config
+        .distances_km
+        .iter()
+        .map(|&distance_km| {
+            let link_km = distance_km / 2.0;
+            let repeater_rate_hz = (0..chain.replications)
+                .map(|_| run_chain(3, link_km, chain).rate_hz())
+                .sum::<f64>()
+                / chain.replications.max(1) as f64;
+            let link_p = QuantumChannel::new(0, 1, link_km, chain.attenuation_db_per_km)
+                .success_probability();
+
+            RateCurvePoint {
+                distance_km,
+                direct_rate_hz: direct_transmission_rate(distance_km, chain),
+                repeater_rate_hz,
+                analytic_repeater_rate_hz: analytic_one_repeater_rate(
+                    link_p,
+                    chain.swap_success_probability,
+                    chain.attempt_interval_ms,
+                ),
+            }
+        })
+        .collect()
+}
+
+/// First distance where the repeater overtakes direct transmission
+/// Linearly interpolated between the two bracketing sweep points
+pub fn crossover_distance(points: &[RateCurvePoint]) -> Option<f64> {
+    points.windows(2).find_map(|w| {
+        let before = w[0].repeater_rate_hz - w[0].direct_rate_hz;
+        let after = w[1].repeater_rate_hz - w[1].direct_rate_hz;
+        if before <= 0.0 && after > 0.0 {
+            let t = -before / (after - before);
+            Some(w[0].distance_km + t * (w[1].distance_km - w[0].distance_km))
+        } else {
+            None
+        }
+    })
+}
+
+/// Write the curve as CSV
+pub fn write_rate_curve_csv(points: &[RateCurvePoint], path: &str) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    writeln!(
+        file,
+        "distance_km,direct_rate_hz,repeater_rate_hz,analytic_repeater_rate_hz"
+    )?;
+    for point in points {
+        writeln!(
+            file,
+            "{},{},{},{}",
+            point.distance_km,
+            point.direct_rate_hz,
+            point.repeater_rate_hz,
+            point.analytic_repeater_rate_hz
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(distance_km: f64, direct: f64, repeater: f64) -> RateCurvePoint {
+        RateCurvePoint {
+            distance_km,
+            direct_rate_hz: direct,
+            repeater_rate_hz: repeater,
+            analytic_repeater_rate_hz: repeater,
+        }
+    }
+
+    #[test]
+    fn test_crossover_interpolation() {
+        let points = vec![
+            point(10.0, 600.0, 300.0),
+            point(20.0, 400.0, 300.0),
+            point(30.0, 250.0, 350.0),
+        ];
+        // Difference goes -100 -> +100 between 20 and 30 km
+        assert!((crossover_distance(&points).unwrap() - 25.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_no_crossover() {
+        let points = vec![point(10.0, 600.0, 300.0), point(20.0, 400.0, 300.0)];
+        assert!(crossover_distance(&points).is_none());
+    }
+}
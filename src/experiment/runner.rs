@@ -0,0 +1,170 @@
+use super::convergence::{ConvergenceConfig, ConvergenceReport};
+use crate::simulation::{RunOutcome, RunReport, TimeoutReason};
+use std::collections::BTreeMap;
+
+/// Replication results, keeping timed-out and paused runs apart from completed ones
+#[derive(Debug, Clone, Default)]
+pub struct ReplicationResults {
+    pub completed: Vec<RunReport>,
+    pub timed_out: Vec<(TimeoutReason, RunReport)>,
+    /// Runs stopped by `pause_at`, with the pause time; replications are not resumed
+    pub paused: Vec<(f64, RunReport)>,
+}
+
+impl ReplicationResults {
+    /// Mean events processed over completed replications only
+    pub fn mean_events_processed(&self) -> f64 {
+        if self.completed.is_empty() {
+            0.0
+        } else {
+            self.completed
+                .iter()
+                .map(|report| report.events_processed as f64)
+                .sum::<f64>()
+                / self.completed.len() as f64
+        }
+    }
+
+    /// Completed replications merged into one report
+    pub fn merged(&self) -> RunReport {
+        RunReport::merge_all(&self.completed)
+    }
+
+    /// Total number of replications run
+    pub fn total(&self) -> usize {
+        self.completed.len() + self.timed_out.len() + self.paused.len()
+    }
+
+    /// Convergence of `events_processed` and `final_time` over completed replications
+    pub fn convergence(&self, config: &ConvergenceConfig) -> ConvergenceReport {
+        let mut samples = BTreeMap::new();
+        samples.insert(
+            "events_processed".to_string(),
+            self.completed
+                .iter()
+                .map(|report| report.events_processed as f64)
+                .collect(),
+        );
+        samples.insert(
+            "final_time".to_string(),
+            self.completed
+                .iter()
+                .map(|report| report.final_time)
+                .collect(),
+        );
+        ConvergenceReport::analyze(&samples, config)
+    }
+}
+
+/// Run `replications` independent runs; `run` receives the replication index
+pub fn run_replications<F>(replications: usize, mut run: F) -> ReplicationResults
+where
+    F: FnMut(usize) -> RunOutcome,
+{
+    let mut results = ReplicationResults::default();
+    for index in 0..replications {
+        match run(index) {
+            RunOutcome::Completed(report) => results.completed.push(report),
+            RunOutcome::TimedOut { reason, report } => results.timed_out.push((reason, report)),
+            RunOutcome::Paused { at, report } => results.paused.push((at, report)),
+        }
+    }
+    results
+}
+
+/// `run_replications` on the rayon thread pool
+/// Results keep replication order, so they match a sequential run
+#[cfg(feature = "parallel")]
+pub fn run_replications_parallel<F>(replications: usize, run: F) -> ReplicationResults
+where
+    F: Fn(usize) -> RunOutcome + Sync,
+{
+    use rayon::prelude::*;
+
+    let outcomes: Vec<RunOutcome> = (0..replications).into_par_iter().map(&run).collect();
+    let mut outcomes = outcomes.into_iter();
+    run_replications(replications, |_| outcomes.next().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timed_out_runs_excluded_from_aggregates() {
+        let results = run_replications(4, |index| {
+            let report = RunReport {
+                events_processed: if index == 2 { 1_000_000 } else { 10 },
+                ..RunReport::default()
+            };
+            if index == 2 {
+                RunOutcome::TimedOut {
+                    reason: TimeoutReason::WallClock,
+                    report,
+                }
+            } else {
+                RunOutcome::Completed(report)
+            }
+        });
+
+        assert_eq!(results.total(), 4);
+        assert_eq!(results.timed_out.len(), 1);
+        assert!((results.mean_events_processed() - 10.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_paused_runs_kept_apart_from_timeouts() {
+        let results = run_replications(3, |index| {
+            let report = RunReport {
+                events_processed: 10,
+                ..RunReport::default()
+            };
+            match index {
+                0 => RunOutcome::Paused { at: 5.0, report },
+                1 => RunOutcome::TimedOut {
+                    reason: TimeoutReason::SimTime,
+                    report,
+                },
+                _ => RunOutcome::Completed(report),
+            }
+        });
+
+        assert_eq!(results.total(), 3);
+        assert_eq!(results.completed.len(), 1);
+        assert_eq!(results.timed_out.len(), 1);
+        assert_eq!(results.paused.len(), 1);
+        assert_eq!(results.paused[0].0, 5.0);
+    }
+
+    #[test]
+    fn test_convergence_of_run_reports() {
+        // Run length grows with the replication index: final_time never settles
+        let results = run_replications(40, |index| {
+            RunOutcome::Completed(RunReport {
+                events_processed: 100,
+                final_time: index as f64,
+                ..RunReport::default()
+            })
+        });
+        let report = results.convergence(&ConvergenceConfig::default());
+        assert_eq!(report.replications, 40);
+        assert_eq!(report.flagged(), vec!["final_time"]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_replications_keep_order() {
+        let results = run_replications_parallel(16, |index| {
+            RunOutcome::Completed(RunReport {
+                events_processed: index,
+                ..RunReport::default()
+            })
+        });
+        let processed: Vec<usize> = results
+            .completed
+            .iter()
+            .map(|report| report.events_processed)
+            .collect();
+        assert_eq!(processed, (0..16).collect::<Vec<_>>());
+    }
+}
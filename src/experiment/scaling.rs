@@ -0,0 +1,180 @@
+use super::chain::{run_chain, ChainConfig};
+use crate::format::{format_rate, format_time};
+use crate::network::QuantumChannel;
+use std::fs::File;
+use std::io::Write;
+
+/// One row of a scaling study: metrics for a chain with `num_nodes` nodes
+#[derive(Debug, Clone)]
+pub struct ScalingRow {
+    /// Number of nodes in the chain (2 = direct link, no repeater)
+    pub num_nodes: usize,
+    /// Length of each elementary link (km)
+    pub link_distance_km: f64,
+    /// Mean end-to-end delivery rate across replications (pairs/s)
+    pub rate_hz: f64,
+    /// Mean fidelity of delivered pairs (0.0 if none delivered)
+    pub mean_fidelity: f64,
+    /// Mean delivery latency (ms, 0.0 if none delivered)
+    pub mean_latency_ms: f64,
+    /// Analytic direct-transmission rate over the total distance (pairs/s)
+    pub direct_rate_hz: f64,
+}
+
+/// End-to-end rate, fidelity and latency vs chain length at fixed total distance
+#[derive(Debug, Clone)]
+pub struct ScalingReport {
+    pub total_distance_km: f64,
+    pub rows: Vec<ScalingRow>,
+}
+
+impl ScalingReport {
+    /// Render the report as CSV (header + one line per chain length)
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "num_nodes,link_distance_km,rate_hz,mean_fidelity,mean_latency_ms,direct_rate_hz\n",
+        );
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                row.num_nodes,
+                row.link_distance_km,
+                row.rate_hz,
+                row.mean_fidelity,
+                row.mean_latency_ms,
+                row.direct_rate_hz
+            ));
+        }
+        csv
+    }
+
+    /// Write the CSV rendering to a file
+    pub fn write_csv(&self, path: &str) -> std::io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(self.to_csv().as_bytes())
+    }
+
+    /// Human-readable summary table
+    pub fn summary_table(&self) -> String {
+        let mut table = format!(
+            "=== Scaling Study ({} km total) ===\n",
+            self.total_distance_km
+        );
+        table.push_str(&format!(
+            "{:>5} {:>10} {:>14} {:>9} {:>10} {:>14}\n",
+            "nodes", "link_km", "rate", "fidelity", "latency", "direct"
+        ));
+        for row in &self.rows {
+            table.push_str(&format!(
+                "{:>5} {:>10.2} {:>14} {:>9.4} {:>10} {:>14}\n",
+                row.num_nodes,
+                row.link_distance_km,
+                format_rate(row.rate_hz),
+                row.mean_fidelity,
+                format_time(row.mean_latency_ms),
+                format_rate(row.direct_rate_hz)
+            ));
+        }
+        table
+    }
+}
+
+/// Analytic direct-transmission rate (pairs/s) over a single fiber link
+pub fn direct_transmission_rate(distance_km: f64, config: &ChainConfig) -> f64 {
+    let channel = QuantumChannel::new(0, 1, distance_km, config.attenuation_db_per_km);
+    channel.success_probability() / (config.attempt_interval_ms / 1000.0)
+}
+
+/// Run a chain for every n in `n_values`, splitting `total_distance_km` evenly
+pub fn scaling_study(
+    total_distance_km: f64,
+    n_values: &[usize],
+    base_config: &ChainConfig,
+) -> ScalingReport {
+    let direct_rate_hz = direct_transmission_rate(total_distance_km, base_config);
+
+    let rows = n_values
+        .iter()
+        .map(|&num_nodes| {
+            assert!(num_nodes >= 2, "Chain requires at least 2 nodes");
+            let link_distance_km = total_distance_km / (num_nodes - 1) as f64;
+
+            let mut rate_sum = 0.0;
+            let mut fidelities = Vec::new();
+            let mut latencies = Vec::new();
+            for _ in 0..base_config.replications {
+                let stats = run_chain(num_nodes, link_distance_km, base_config);
+                rate_sum += stats.rate_hz();
+                fidelities.extend(stats.fidelities);
+                latencies.extend(stats.latencies_ms);
+            }
+
+            ScalingRow {
+                num_nodes,
+                link_distance_km,
+                rate_hz: rate_sum / base_config.replications.max(1) as f64,
+                mean_fidelity: mean(&fidelities),
+                mean_latency_ms: mean(&latencies),
+                direct_rate_hz,
+            }
+        })
+        .collect();
+
+    ScalingReport {
+        total_distance_km,
+        rows,
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiny_config() -> ChainConfig {
+        ChainConfig {
+            coherence_time_ms: 1000.0,
+            slots_per_run: 2000,
+            replications: 3,
+            ..ChainConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_one_row_per_n() {
+        let report = scaling_study(20.0, &[2, 3, 5], &tiny_config());
+        assert_eq!(report.rows.len(), 3);
+        assert_eq!(report.rows[2].num_nodes, 5);
+        assert!((report.rows[2].link_distance_km - 5.0).abs() < 1e-10);
+
+        // Header plus one line per row
+        assert_eq!(report.to_csv().lines().count(), 4);
+    }
+
+    #[test]
+    fn test_baseline_matches_success_probability() {
+        let config = tiny_config();
+        let report = scaling_study(50.0, &[2], &config);
+
+        let p = QuantumChannel::new(0, 1, 50.0, config.attenuation_db_per_km).success_probability();
+        let expected = p / (config.attempt_interval_ms / 1000.0);
+        assert!((report.rows[0].direct_rate_hz - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_repeater_advantage_at_long_distance() {
+        // 200 km: direct p ≈ 1e-4, each 100 km link p ≈ 1e-2
+        let report = scaling_study(200.0, &[3], &tiny_config());
+        let row = &report.rows[0];
+
+        // Expected ~6.7 pairs/s vs 0.1 pairs/s direct; allow a very wide margin
+        assert!(row.rate_hz > 10.0 * row.direct_rate_hz);
+    }
+}
@@ -0,0 +1,1398 @@
+use super::provenance::{
+    latency_breakdown_table, mean_link_breakdowns, LatencyBreakdown, LinkProvenance, PairProvenance,
+};
+use crate::analysis::expected_max_geometric;
+use crate::applications::{ConsumedPair, DeliveryQueue};
+use crate::network::{
+    attempt_entanglement_generation_with_hints, expected_wait_from_path_position, ChannelId,
+    ChannelSelector, GenerationStats, LinkRetryState, NetworkTopology, PairHandle, QuantumChannel,
+    RoutePath,
+};
+use crate::protocols::swapping::perform_entanglement_swap_with_hints;
+use crate::quantum::{apply_pauli_twirl, PauliFrame, TwoQubitState};
+use crate::simulation::{
+    DetailLevel, Event, EventHandler, EventScheduler, EventType, HandlerRng, ParamOverride,
+    RandomnessDecl, RandomnessSource, RunReport,
+};
+use rand::Rng;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Parameters of the request service
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ServiceConfig {
+    /// Memory coherence time in milliseconds
+    pub coherence_time_ms: f64,
+    /// Time between generation attempts on each link (ms)
+    /// Raised to the longest attempt duration on the head request's route
+    pub attempt_interval_ms: f64,
+    /// Probability that each swap succeeds
+    pub swap_success_probability: f64,
+    /// Drop delivered pairs the application has not consumed after this long (ms)
+    pub delivery_cutoff_ms: Option<f64>,
+    /// Classical heralding latency after a successful photon attempt (ms)
+    pub heralding_delay_ms: f64,
+    /// Time for the repeaters' swaps, performed in parallel (ms)
+    pub swap_processing_ms: f64,
+    /// Classical latency of the swap outcomes to the end nodes (ms)
+    pub classical_correction_delay_ms: f64,
+    /// Skip the slots in which every hop still lacking a pair backs off
+    /// The run is otherwise identical to the full one, with fewer events
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compress_idle_time: bool,
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        ServiceConfig {
+            coherence_time_ms: 100.0,
+            attempt_interval_ms: 1.0,
+            swap_success_probability: 1.0,
+            delivery_cutoff_ms: None,
+            heralding_delay_ms: 0.0,
+            swap_processing_ms: 0.0,
+            classical_correction_delay_ms: 0.0,
+            compress_idle_time: false,
+        }
+    }
+}
+
+/// Delivery statistics of a request service
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ServiceStats {
+    /// End-to-end pairs requested so far
+    pub submitted: usize,
+    /// End-to-end pairs delivered so far
+    pub delivered: usize,
+    /// Swap rounds that failed and discarded their link pairs
+    pub failed_swaps: usize,
+    /// Fidelity of each delivered pair at delivery time
+    pub fidelities: Vec<f64>,
+    /// Fidelity of each delivered state vector to |Φ+⟩ (`StateVector` detail only)
+    pub state_fidelities: Vec<f64>,
+    /// Time from submission to delivery of each pair (ms)
+    pub latencies_ms: Vec<f64>,
+    /// Timestamps of each delivered pair
+    pub provenance: Vec<PairProvenance>,
+    /// Generation attempts over all links
+    pub generation: GenerationStats,
+}
+
+impl ServiceStats {
+    /// Pairs requested but not yet delivered
+    pub fn pending(&self) -> usize {
+        self.submitted - self.delivered
+    }
+
+    pub fn mean_fidelity(&self) -> f64 {
+        mean(&self.fidelities)
+    }
+
+    pub fn mean_latency_ms(&self) -> f64 {
+        mean(&self.latencies_ms)
+    }
+
+    /// Mean end-to-end latency breakdown over delivered pairs
+    pub fn mean_latency_breakdown(&self) -> LatencyBreakdown {
+        let breakdowns: Vec<LatencyBreakdown> = self
+            .provenance
+            .iter()
+            .map(PairProvenance::end_to_end_breakdown)
+            .collect();
+        LatencyBreakdown::mean(&breakdowns)
+    }
+
+    /// Mean latency breakdown per link
+    pub fn mean_link_breakdowns(&self) -> BTreeMap<(ChannelId, usize, usize), LatencyBreakdown> {
+        mean_link_breakdowns(&self.provenance)
+    }
+
+    /// Latency breakdown table, end-to-end and per link
+    pub fn latency_breakdown_table(&self) -> String {
+        latency_breakdown_table(&self.provenance)
+    }
+
+    /// JSON object with the counters and means
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"submitted\":{},\"delivered\":{},\"pending\":{},\"failed_swaps\":{},\"mean_fidelity\":{},\"mean_latency_ms\":{}}}",
+            self.submitted,
+            self.delivered,
+            self.pending(),
+            self.failed_swaps,
+            self.mean_fidelity(),
+            self.mean_latency_ms()
+        )
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// One end-to-end pair waiting for delivery
+#[derive(Debug, Clone, Copy)]
+struct PendingPair {
+    source: usize,
+    destination: usize,
+    submitted_at: f64,
+}
+
+/// Swapped pair of the head request waiting for classical corrections
+struct Completion {
+    request: PendingPair,
+    /// The swapped pair, the same handle at both end nodes
+    pair: PairHandle,
+    provenance: PairProvenance,
+    /// Accumulated BSM outcomes, sent to the end nodes as the correction message
+    correction: PauliFrame,
+}
+
+/// Expected delivery of the head request at `Analytic` detail
+#[derive(Debug, Clone, Copy)]
+struct AnalyticDelivery {
+    delivered_at: f64,
+    /// Classical delays ended the request, so the next one starts at once
+    /// rather than in the following attempt slot
+    next_starts_at_delivery: bool,
+}
+
+/// Serves end-to-end pair requests over any topology, first come first served
+///
+/// The head request is routed with `find_path`. Each attempt slot every hop
+/// without a pair attempts generation, unless its channel's `retry_policy`
+/// is backing it off after repeated failures; once all hops hold a pair the path is
+/// swapped left to right and the pair is delivered: both halves leave node
+/// memory for the request's `DeliveryQueue`, where the application takes it
+/// with `take_delivered`. A failed swap discards every link pair on the path.
+///
+/// With classical latencies configured, a link pair is only usable once
+/// heralded, and the swapped pair is delivered after swap processing and
+/// the correction messages; the head request waits meanwhile.
+///
+/// At `Analytic` detail no pairs are generated: each request is delivered
+/// at its expected completion time, the expected maximum of the hops'
+/// geometric generation times over the probability that every swap
+/// succeeds, plus the classical delays (not rounded to attempt slots).
+/// At `StateVector` detail each delivered state vector, which follows the
+/// channel noise and swaps of its pair, picks up one trajectory of memory
+/// Pauli errors and is then compared with |Φ+⟩.
+pub struct RequestService {
+    pub topology: NetworkTopology,
+    pub config: ServiceConfig,
+    queue: VecDeque<PendingPair>,
+    /// Delivered pairs per (source, destination)
+    deliveries: BTreeMap<(usize, usize), DeliveryQueue>,
+    stats: ServiceStats,
+    /// A service event is in flight
+    active: bool,
+    /// When the head request reached the head of the queue
+    head_started_at: Option<f64>,
+    /// Success time of each link pair held for the head request
+    generated_at: HashMap<(usize, usize), f64>,
+    completion: Option<Completion>,
+    /// Generation attempts per link, by channel id and hop endpoints
+    link_generation: BTreeMap<(ChannelId, usize, usize), GenerationStats>,
+    /// Failure streak per channel and the earliest time of its next attempt
+    link_retry: BTreeMap<ChannelId, (LinkRetryState, f64)>,
+    detail_level: DetailLevel,
+    analytic_delivery: Option<AnalyticDelivery>,
+    /// Draws generation and swap outcomes; seeded by the engine
+    rng: HandlerRng,
+}
+
+impl RequestService {
+    pub fn new(topology: NetworkTopology, config: ServiceConfig) -> Self {
+        RequestService {
+            topology,
+            config,
+            queue: VecDeque::new(),
+            deliveries: BTreeMap::new(),
+            stats: ServiceStats::default(),
+            active: false,
+            head_started_at: None,
+            generated_at: HashMap::new(),
+            completion: None,
+            link_generation: BTreeMap::new(),
+            link_retry: BTreeMap::new(),
+            detail_level: DetailLevel::default(),
+            analytic_delivery: None,
+            rng: HandlerRng::unseeded("RequestService"),
+        }
+    }
+
+    /// Queue `count` end-to-end pairs from `source` to `destination`
+    /// Starts serving at `current_time` if the service was idle
+    pub fn submit(
+        &mut self,
+        source: usize,
+        destination: usize,
+        count: usize,
+        scheduler: &mut EventScheduler,
+        current_time: f64,
+    ) -> Result<(), String> {
+        if source == destination {
+            return Err(format!(
+                "Request {}-{} has identical ends",
+                source, destination
+            ));
+        }
+        if self
+            .topology
+            .find_path(source, destination, ChannelSelector::BestSuccessProbability)
+            .is_none()
+        {
+            return Err(format!("No route from {} to {}", source, destination));
+        }
+
+        for _ in 0..count {
+            self.queue.push_back(PendingPair {
+                source,
+                destination,
+                submitted_at: current_time,
+            });
+        }
+        self.stats.submitted += count;
+
+        if !self.active && !self.queue.is_empty() {
+            self.active = true;
+            scheduler.try_schedule(Event::new(
+                current_time,
+                EventType::EntanglementGeneration,
+                source,
+            ))?;
+        }
+        Ok(())
+    }
+
+    pub fn stats(&self) -> &ServiceStats {
+        &self.stats
+    }
+
+    /// Pairs delivered from `source` to `destination` and not yet consumed
+    pub fn delivery_queue(&self, source: usize, destination: usize) -> Option<&DeliveryQueue> {
+        self.deliveries.get(&(source, destination))
+    }
+
+    /// Consume the oldest delivered pair from `source` to `destination`
+    pub fn take_delivered(
+        &mut self,
+        source: usize,
+        destination: usize,
+        current_time: f64,
+    ) -> Option<ConsumedPair> {
+        self.deliveries
+            .get_mut(&(source, destination))?
+            .take_delivered(current_time)
+    }
+
+    /// Attempt slot length on `path`: the attempt interval, raised so that no
+    /// hop attempts again before its previous attempt has heralded
+    fn slot_ms(&self, path: &RoutePath) -> f64 {
+        path.hops
+            .iter()
+            .filter_map(|hop| self.topology.channel(hop.channel_id))
+            .map(QuantumChannel::attempt_duration_ms)
+            .fold(self.config.attempt_interval_ms, f64::max)
+    }
+
+    /// `slot_ms` on the head request's route
+    fn head_slot_ms(&self) -> f64 {
+        self.queue
+            .front()
+            .and_then(|request| {
+                self.topology.find_path(
+                    request.source,
+                    request.destination,
+                    ChannelSelector::BestSuccessProbability,
+                )
+            })
+            .map_or(self.config.attempt_interval_ms, |path| self.slot_ms(&path))
+    }
+
+    /// Time of the next slot in which the head request's route can change
+    ///
+    /// Slots in which every hop without a pair backs off are skipped when
+    /// `compress_idle_time` is set. Stepping slot by slot keeps the times
+    /// bit-identical to the full run.
+    fn next_slot_time(&self, time: f64) -> f64 {
+        let slot_ms = self.head_slot_ms();
+        if !self.config.compress_idle_time {
+            return time + slot_ms;
+        }
+        let Some(path) = self.queue.front().and_then(|request| {
+            self.topology.find_path(
+                request.source,
+                request.destination,
+                ChannelSelector::BestSuccessProbability,
+            )
+        }) else {
+            return time + slot_ms;
+        };
+        let mut resume_at = None;
+        for hop in &path.hops {
+            let has_pair = self
+                .topology
+                .get_node(hop.from)
+                .unwrap()
+                .find_pair_with(hop.to)
+                .is_some();
+            if has_pair {
+                continue;
+            }
+            match self.link_retry.get(&hop.channel_id) {
+                Some(&(_, ready_at)) => {
+                    resume_at =
+                        Some(resume_at.map_or(ready_at, |earliest: f64| earliest.min(ready_at)));
+                }
+                // A hop ready to attempt needs the next slot
+                None => return time + slot_ms,
+            }
+        }
+        // Every hop holds a pair: the swap waits on heralding, not backoff
+        let Some(resume_at) = resume_at else {
+            return time + slot_ms;
+        };
+        let mut next = time + slot_ms;
+        while next < resume_at {
+            next += slot_ms;
+        }
+        next
+    }
+
+    /// One attempt slot for the head request
+    fn serve(&mut self, time: f64) {
+        if let Some(completion) = &self.completion {
+            if time < completion.provenance.delivered_at {
+                return;
+            }
+            let completion = self.completion.take().unwrap();
+            self.deliver(completion);
+        }
+        let Some(request) = self.queue.front().copied() else {
+            return;
+        };
+        let Some(path) = self.topology.find_path(
+            request.source,
+            request.destination,
+            ChannelSelector::BestSuccessProbability,
+        ) else {
+            return;
+        };
+        let started_at = *self.head_started_at.get_or_insert(time);
+        let slot_ms = self.slot_ms(&path);
+        // Halves nearer the middle of the path wait less for the swaps
+        let expected_wait = |position| {
+            Some(expected_wait_from_path_position(
+                position,
+                path.nodes.len(),
+                slot_ms,
+            ))
+        };
+
+        for (position, hop) in path.hops.iter().enumerate() {
+            if self
+                .topology
+                .get_node(hop.from)
+                .unwrap()
+                .find_pair_with(hop.to)
+                .is_some()
+            {
+                continue;
+            }
+            let backing_off = self
+                .link_retry
+                .get(&hop.channel_id)
+                .is_some_and(|&(_, ready_at)| time < ready_at);
+            if backing_off {
+                continue;
+            }
+            let channel = self.topology.channel(hop.channel_id).unwrap().clone();
+            self.topology.record_channel_use(hop.channel_id);
+            let (node_a, node_b) = self.topology.get_node_pair_mut(hop.from, hop.to).unwrap();
+            let result = attempt_entanglement_generation_with_hints(
+                node_a,
+                node_b,
+                &channel,
+                time,
+                self.config.coherence_time_ms,
+                [expected_wait(position), expected_wait(position + 1)],
+                &mut self.rng,
+            );
+            let link_stats = self
+                .link_generation
+                .entry((hop.channel_id, hop.from, hop.to))
+                .or_default();
+            link_stats.record(&result);
+            self.stats.generation.record(&result);
+            // A full memory says nothing about the link, so only photon
+            // outcomes count towards the failure streak
+            if let Ok(success) = result {
+                let (retry, ready_at) = self
+                    .link_retry
+                    .entry(hop.channel_id)
+                    .or_insert_with(|| (LinkRetryState::new(), time));
+                let backoff_ms = retry.record_attempt(success, &channel.retry_policy);
+                *ready_at = time + slot_ms + backoff_ms;
+                link_stats.backoff_idle_time += backoff_ms;
+                self.stats.generation.backoff_idle_time += backoff_ms;
+            }
+            if let Ok(true) = result {
+                self.generated_at.insert((hop.from, hop.to), time);
+            }
+        }
+
+        let heralding_delay_ms = self.config.heralding_delay_ms;
+        let links: Option<Vec<LinkProvenance>> = path
+            .hops
+            .iter()
+            .map(|hop| {
+                self.topology
+                    .get_node(hop.from)
+                    .unwrap()
+                    .find_pair_with(hop.to)?;
+                // Pairs left over from before this request count as heralded at its start
+                let generated_at = self
+                    .generated_at
+                    .get(&(hop.from, hop.to))
+                    .copied()
+                    .unwrap_or(started_at);
+                let heralded_at = generated_at + heralding_delay_ms;
+                (heralded_at <= time).then_some(LinkProvenance {
+                    channel_id: hop.channel_id,
+                    from: hop.from,
+                    to: hop.to,
+                    generated_at,
+                    heralded_at,
+                })
+            })
+            .collect();
+        let Some(links) = links else {
+            return;
+        };
+
+        let repeaters = path.nodes.len() - 2;
+        let all_swaps_succeeded =
+            (0..repeaters).all(|_| self.rng.random::<f64>() < self.config.swap_success_probability);
+        if !all_swaps_succeeded {
+            for hop in &path.hops {
+                self.topology.evict_link_pair(hop.from, hop.to, time);
+                self.generated_at.remove(&(hop.from, hop.to));
+            }
+            self.stats.failed_swaps += 1;
+            return;
+        }
+        // A direct link delivers its own pair; otherwise the last swap's
+        let mut handle = self
+            .topology
+            .get_node(request.source)
+            .unwrap()
+            .find_pair_with(request.destination);
+        for i in 1..=repeaters {
+            let swapped = perform_entanglement_swap_with_hints(
+                &mut self.topology,
+                path.nodes[i],
+                request.source,
+                path.nodes[i + 1],
+                time,
+                [expected_wait(0), expected_wait(i + 1)],
+                &mut self.rng,
+            )
+            .expect("all hops hold a pair");
+            handle = Some(swapped);
+        }
+        let handle = handle.expect("swapped pair spans the path");
+        for hop in &path.hops {
+            self.generated_at.remove(&(hop.from, hop.to));
+        }
+
+        // A direct link needs no swap and no corrections
+        let (swap_ms, correction_ms) = if repeaters == 0 {
+            (0.0, 0.0)
+        } else {
+            (
+                self.config.swap_processing_ms,
+                self.config.classical_correction_delay_ms,
+            )
+        };
+        let correction = self
+            .topology
+            .get_node(request.source)
+            .unwrap()
+            .pair(handle)
+            .expect("swapped pair spans the path")
+            .pauli_frame;
+        let completion = Completion {
+            request,
+            pair: handle,
+            correction,
+            provenance: PairProvenance {
+                source: request.source,
+                destination: request.destination,
+                submitted_at: request.submitted_at,
+                service_started_at: started_at,
+                links,
+                swap_started_at: time,
+                swap_completed_at: time + swap_ms,
+                delivered_at: time + swap_ms + correction_ms,
+            },
+        };
+        if completion.provenance.delivered_at <= time {
+            self.deliver(completion);
+        } else {
+            self.completion = Some(completion);
+        }
+    }
+
+    /// `Analytic` counterpart of `serve`; returns when the next event is due
+    fn serve_analytic(&mut self, time: f64) -> Option<f64> {
+        if let Some(delivery) = self.analytic_delivery {
+            if time < delivery.delivered_at {
+                return Some(delivery.delivered_at);
+            }
+            self.analytic_delivery = None;
+            let slot_ms = self.head_slot_ms();
+            let request = self.queue.pop_front()?;
+            self.stats.delivered += 1;
+            self.stats
+                .latencies_ms
+                .push(delivery.delivered_at - request.submitted_at);
+            if !delivery.next_starts_at_delivery {
+                return (!self.queue.is_empty()).then_some(time + slot_ms);
+            }
+        }
+
+        let request = self.queue.front()?;
+        let path = self.topology.find_path(
+            request.source,
+            request.destination,
+            ChannelSelector::BestSuccessProbability,
+        )?;
+        let probabilities: Vec<f64> = path
+            .hops
+            .iter()
+            .map(|hop| {
+                self.topology
+                    .channel(hop.channel_id)
+                    .unwrap()
+                    .success_probability()
+            })
+            .collect();
+        let repeaters = path.nodes.len() - 2;
+        let all_swaps = self.config.swap_success_probability.powi(repeaters as i32);
+        let slots = expected_max_geometric(&probabilities)? / all_swaps;
+        if !slots.is_finite() {
+            return None;
+        }
+
+        let classical_ms = self.config.heralding_delay_ms
+            + if repeaters == 0 {
+                0.0
+            } else {
+                self.config.swap_processing_ms + self.config.classical_correction_delay_ms
+            };
+        // The first attempt happens in this slot
+        let delivered_at = time + (slots - 1.0) * self.slot_ms(&path) + classical_ms;
+        self.analytic_delivery = Some(AnalyticDelivery {
+            delivered_at,
+            next_starts_at_delivery: classical_ms > 0.0,
+        });
+        Some(delivered_at)
+    }
+
+    /// Hand the head request's swapped pair to its delivery queue
+    fn deliver(&mut self, completion: Completion) {
+        let Completion {
+            request,
+            pair: handle,
+            provenance,
+            correction,
+        } = completion;
+        let time = provenance.delivered_at;
+        let (source, destination) = self
+            .topology
+            .get_node_pair_mut(request.source, request.destination)
+            .unwrap();
+        let mut pair = source
+            .remove_pair_at(handle, time)
+            .expect("swapped pair spans the path");
+        destination.remove_pair_at(handle, time);
+        // The end nodes learn the frame from the correction message
+        pair.pauli_frame = correction;
+        self.queue.pop_front();
+        self.head_started_at = None;
+        self.stats.delivered += 1;
+        self.stats.fidelities.push(pair.fidelity_at(time));
+        if self.detail_level == DetailLevel::StateVector {
+            // Both halves share the pair's creation time and memory times
+            let errors = pair.memory_errors_at(time);
+            apply_pauli_twirl(
+                &mut pair.state,
+                errors.p_x,
+                errors.p_y,
+                errors.p_z,
+                &mut self.rng,
+            );
+            self.stats
+                .state_fidelities
+                .push(pair.state.fidelity(&TwoQubitState::new_bell_phi_plus()));
+        }
+        self.stats.latencies_ms.push(time - request.submitted_at);
+        self.stats.provenance.push(provenance);
+        let cutoff_ms = self.config.delivery_cutoff_ms;
+        self.deliveries
+            .entry((request.source, request.destination))
+            .or_insert_with(|| DeliveryQueue::new(request.source, request.destination, cutoff_ms))
+            .deliver(pair, time);
+    }
+}
+
+impl RandomnessSource for RequestService {
+    fn randomness(&self) -> Vec<RandomnessDecl> {
+        let mut decls: Vec<RandomnessDecl> = self
+            .topology
+            .channels()
+            .iter()
+            .flat_map(RandomnessSource::randomness)
+            .collect();
+        decls.push(RandomnessDecl::bernoulli(
+            "RequestService",
+            "swap_success",
+            self.config.swap_success_probability,
+        ));
+        decls.push(RandomnessDecl::uniform(
+            "perform_entanglement_swap",
+            "bsm_outcome",
+            4,
+        ));
+        // Drawn at `StateVector` detail only
+        decls.push(RandomnessDecl::new(
+            "RequestService",
+            "memory_pauli_error",
+            "Pauli",
+            &[("coherence_time_ms", self.config.coherence_time_ms)],
+        ));
+        decls
+    }
+}
+
+impl EventHandler for RequestService {
+    fn check_invariants(&self) -> Result<(), String> {
+        self.topology.verify_memory_consistency()
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng.seed(seed);
+    }
+
+    fn topology(&self) -> Option<&NetworkTopology> {
+        // No pairs are stored at analytic detail, so there is no memory to watch
+        (self.detail_level != DetailLevel::Analytic).then_some(&self.topology)
+    }
+
+    fn set_detail_level(&mut self, level: DetailLevel) -> DetailLevel {
+        self.detail_level = level;
+        level
+    }
+
+    fn apply_override(
+        &mut self,
+        change: &ParamOverride,
+        _scheduler: &mut EventScheduler,
+        _current_time: f64,
+    ) -> Result<(), String> {
+        if change.apply_to_topology(&mut self.topology)? {
+            return Ok(());
+        }
+        match *change {
+            ParamOverride::DeliveryCutoff(cutoff_ms) => {
+                self.config.delivery_cutoff_ms = cutoff_ms;
+                for deliveries in self.deliveries.values_mut() {
+                    deliveries.cutoff_ms = cutoff_ms;
+                }
+            }
+            ParamOverride::AttemptInterval(interval_ms) => {
+                self.config.attempt_interval_ms = interval_ms;
+            }
+            ParamOverride::SwapSuccessProbability(probability) => {
+                self.config.swap_success_probability = probability;
+            }
+            _ => {
+                return Err(format!(
+                    "Request service has no parameter for override: {}",
+                    change
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, event: &Event, scheduler: &mut EventScheduler) {
+        if event.event_type != EventType::EntanglementGeneration {
+            return;
+        }
+        if self.detail_level == DetailLevel::Analytic {
+            match (self.serve_analytic(event.time), self.queue.front()) {
+                (Some(next_time), Some(next)) => {
+                    scheduler.schedule(Event::new(
+                        next_time,
+                        EventType::EntanglementGeneration,
+                        next.source,
+                    ));
+                }
+                _ => self.active = false,
+            }
+            return;
+        }
+        self.serve(event.time);
+        for deliveries in self.deliveries.values_mut() {
+            deliveries.expire(event.time);
+        }
+
+        match self.queue.front() {
+            Some(next) => {
+                let next_time = match &self.completion {
+                    Some(completion) => completion.provenance.delivered_at,
+                    None => self.next_slot_time(event.time),
+                };
+                scheduler.schedule(Event::new(
+                    next_time,
+                    EventType::EntanglementGeneration,
+                    next.source,
+                ));
+            }
+            None => self.active = false,
+        }
+    }
+
+    fn report(&self, report: &mut RunReport) {
+        let config = &self.config;
+        for (name, value) in [
+            ("coherence_time_ms", config.coherence_time_ms.to_string()),
+            (
+                "attempt_interval_ms",
+                config.attempt_interval_ms.to_string(),
+            ),
+            (
+                "swap_success_probability",
+                config.swap_success_probability.to_string(),
+            ),
+            (
+                "delivery_cutoff_ms",
+                format!("{:?}", config.delivery_cutoff_ms),
+            ),
+            ("heralding_delay_ms", config.heralding_delay_ms.to_string()),
+            ("swap_processing_ms", config.swap_processing_ms.to_string()),
+            (
+                "classical_correction_delay_ms",
+                config.classical_correction_delay_ms.to_string(),
+            ),
+        ] {
+            report.config.insert(name.to_string(), value);
+        }
+
+        report
+            .config
+            .insert("detail_level".to_string(), self.detail_level.to_string());
+        report.requests.submitted += self.stats.submitted;
+        report.requests.delivered += self.stats.delivered;
+        if self.detail_level == DetailLevel::Analytic {
+            return;
+        }
+
+        report.generation.merge(&self.stats.generation);
+        for (&(channel_id, from, to), stats) in &self.link_generation {
+            report.link_mut(channel_id, from, to).merge(stats);
+        }
+        report.requests.failed_swaps += self.stats.failed_swaps;
+        report.record_samples("fidelity", &self.stats.fidelities);
+        report.record_samples("latency_ms", &self.stats.latencies_ms);
+        if self.detail_level == DetailLevel::StateVector {
+            report.record_samples("state_fidelity", &self.stats.state_fidelities);
+        }
+        for deliveries in self.deliveries.values() {
+            report.record_samples("storage_time_ms", &deliveries.stats().storage_times_ms);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{
+        Backoff, QuantumChannel, QuantumNode, RetryPolicy, StoredPair, WorstFitForShortWaits,
+    };
+    use crate::protocols::swapping::swapped_fidelity;
+    use crate::simulation::{Metric, RunLimits, SimulationEngine};
+
+    #[test]
+    fn test_lossless_chain_delivers_one_pair_per_slot() {
+        let topology = NetworkTopology::new_linear(4, 2, 0.0, 0.2);
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new();
+
+        service.submit(0, 3, 5, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut service);
+
+        let stats = service.stats();
+        assert_eq!(stats.delivered, 5);
+        assert_eq!(stats.pending(), 0);
+        assert_eq!(stats.latencies_ms, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert!(stats.fidelities.iter().all(|&f| (f - 1.0).abs() < 1e-10));
+        assert!(service
+            .topology
+            .nodes()
+            .iter()
+            .all(|node| node.num_stored_pairs() == 0));
+    }
+
+    #[test]
+    fn test_long_link_stretches_the_slot() {
+        // 500 km lossless link: one attempt takes ≈ 2.45 ms, longer than the interval
+        let topology = NetworkTopology::new_linear(2, 2, 500.0, 0.0);
+        let duration = topology.channels()[0].attempt_duration_ms();
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new();
+
+        service.submit(0, 1, 3, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut service);
+
+        assert_eq!(
+            service.stats().latencies_ms,
+            vec![0.0, duration, 2.0 * duration]
+        );
+    }
+
+    #[test]
+    fn test_path_position_hints_choose_slots() {
+        // Ends wait two slots for the far side, the repeater one: with a
+        // 1.5 ms hint the ends take long-coherence slots, the repeater short ones
+        let strategy = WorstFitForShortWaits {
+            expected_wait_hint: 1.5,
+        };
+        let mut topology = NetworkTopology::new_custom();
+        for (id, slots) in [
+            (0, vec![10.0, 1000.0]),
+            (1, vec![1000.0, 1000.0, 10.0, 10.0]),
+            (2, vec![10.0, 1000.0]),
+        ] {
+            let mut node = QuantumNode::with_slots(id, &slots);
+            node.set_slot_strategy(strategy);
+            topology.add_node(node).unwrap();
+        }
+        for (a, b) in [(0, 1), (1, 2)] {
+            topology
+                .add_channel(QuantumChannel::new(a, b, 0.0, 0.2))
+                .unwrap();
+        }
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new();
+        service.submit(0, 2, 1, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut service);
+
+        let stores = |id: usize| -> Vec<usize> {
+            service
+                .topology
+                .get_node(id)
+                .unwrap()
+                .slot_statistics(1.0)
+                .iter()
+                .map(|slot| slot.stores)
+                .collect()
+        };
+        assert_eq!(service.stats().delivered, 1);
+        // Each end stored its link half, then the swapped half
+        assert_eq!(stores(0), vec![0, 2]);
+        assert_eq!(stores(1), vec![0, 0, 1, 1]);
+        assert_eq!(stores(2), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_delivers_the_swapped_pair() {
+        // A better pair already spans the path; the service must deliver the
+        // one it swapped, whose frame it sent as the correction
+        let mut topology = NetworkTopology::new_linear(3, 3, 0.0, 0.2);
+        topology.get_node_mut(1).unwrap().gate_error = 0.1;
+        let (end_a, end_b) = topology.get_node_pair_mut(0, 2).unwrap();
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        let spare = StoredPair::new(2, bell_state.clone(), 0.0, 1e9);
+        let spare_id = spare.pair_id;
+        end_a.store_pair(spare).unwrap();
+        end_b
+            .store_pair(StoredPair::new(0, bell_state, 0.0, 1e9).with_pair_id(spare_id))
+            .unwrap();
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new();
+
+        service.submit(0, 2, 1, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut service);
+
+        assert_eq!(service.stats().delivered, 1);
+        assert!(service.stats().fidelities[0] < 0.99);
+        for node in [0, 2] {
+            let node = service.topology.get_node(node).unwrap();
+            assert_eq!(node.num_stored_pairs(), 1);
+            assert!(node.find_pair_by_id(spare_id).is_some());
+        }
+    }
+
+    #[test]
+    fn test_unconsumed_deliveries_free_node_memory() {
+        // One memory slot per end node: a lingering delivered pair would block the next request
+        let mut topology = NetworkTopology::new_linear(3, 2, 0.0, 0.2);
+        topology.get_node_mut(0).unwrap().memory_capacity = 1;
+        topology.get_node_mut(2).unwrap().memory_capacity = 1;
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new();
+
+        service.submit(0, 2, 3, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut service);
+
+        assert_eq!(service.stats().delivered, 3);
+        assert_eq!(service.delivery_queue(0, 2).unwrap().len(), 3);
+        assert!(service
+            .topology
+            .nodes()
+            .iter()
+            .all(|node| node.num_stored_pairs() == 0));
+
+        let consumed = service.take_delivered(0, 2, 5.0).unwrap();
+        assert_eq!(consumed.delivered_at, 0.0);
+        assert_eq!(consumed.storage_duration_ms(), 5.0);
+    }
+
+    #[test]
+    fn test_classical_latency_dominates_breakdown() {
+        let topology = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        let config = ServiceConfig {
+            heralding_delay_ms: 2.0,
+            swap_processing_ms: 0.5,
+            classical_correction_delay_ms: 50.0,
+            ..ServiceConfig::default()
+        };
+        let mut service = RequestService::new(topology, config);
+        let mut engine = SimulationEngine::new();
+
+        service.submit(0, 2, 5, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut service);
+
+        let stats = service.stats();
+        assert_eq!(stats.delivered, 5);
+        for (pair, &latency) in stats.provenance.iter().zip(&stats.latencies_ms) {
+            let breakdown = pair.end_to_end_breakdown();
+            assert!((breakdown.total_ms() - latency).abs() < 1e-9);
+            assert_eq!(breakdown.classical_corrections_ms, 50.0);
+            for (_, link) in pair.link_breakdowns() {
+                assert!((link.total_ms() - latency).abs() < 1e-9);
+                assert_eq!(link.heralding_ms, 2.0);
+            }
+        }
+
+        // Queueing aside, waiting on classical messages takes most of the time
+        let mean = stats.mean_latency_breakdown();
+        let in_service = mean.total_ms() - mean.queued_ms;
+        assert!(mean.classical_wait_ms() > 0.5 * in_service);
+        assert!(mean.classical_corrections_ms > mean.photon_attempts_ms);
+        assert!((mean.percentages().iter().sum::<f64>() - 100.0).abs() < 1e-9);
+        assert_eq!(stats.mean_link_breakdowns().len(), 2);
+        assert!(stats.latency_breakdown_table().contains("end-to-end"));
+    }
+
+    #[test]
+    fn test_run_report_link_totals_match_global() {
+        let topology = NetworkTopology::new_linear(4, 2, 20.0, 0.2);
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new().with_seed(5);
+
+        service.submit(0, 3, 3, &mut engine.scheduler, 0.0).unwrap();
+        let report = engine.run(&mut service).into_report();
+
+        assert_eq!(report.seed, Some(5));
+        assert_eq!(report.requests.delivered, 3);
+        assert_eq!(report.histogram("fidelity").len(), 3);
+        assert_eq!(report.config["attempt_interval_ms"], "1");
+        assert_eq!(report.links.len(), 3);
+        let per_link = |field: fn(&GenerationStats) -> usize| -> usize {
+            report
+                .links
+                .iter()
+                .map(|link| field(&link.generation))
+                .sum()
+        };
+        assert_eq!(per_link(|g| g.attempts), report.generation.attempts);
+        assert_eq!(per_link(|g| g.successes), report.generation.successes);
+        let channel_uses: usize = service
+            .topology
+            .channel_ids()
+            .iter()
+            .map(|&id| service.topology.channel_uses(id))
+            .sum();
+        assert_eq!(report.generation.attempts, channel_uses);
+        assert!(report.generation.attempts > report.generation.successes);
+        assert_eq!(report.nodes.len(), 4);
+    }
+
+    #[test]
+    fn test_seeded_runs_repeat_exactly() {
+        let run = |seed| {
+            let topology = NetworkTopology::new_linear(3, 2, 20.0, 0.2);
+            let config = ServiceConfig {
+                swap_success_probability: 0.5,
+                ..ServiceConfig::default()
+            };
+            let mut service = RequestService::new(topology, config);
+            let mut engine = SimulationEngine::new().with_seed(seed);
+            service.submit(0, 2, 5, &mut engine.scheduler, 0.0).unwrap();
+            let report = engine.run(&mut service).into_report();
+            (report.fingerprint, service.stats().latencies_ms.clone())
+        };
+
+        assert_eq!(run(11), run(11));
+        assert_ne!(run(11).0, run(12).0);
+    }
+
+    #[test]
+    fn test_link_export_independent_of_construction_history() {
+        let build = |detour: bool| {
+            let mut topology = NetworkTopology::new_custom();
+            for id in 0..3 {
+                topology.add_node(QuantumNode::new(id, 2)).unwrap();
+            }
+            topology
+                .add_channel(QuantumChannel::new(0, 1, 0.0, 0.2))
+                .unwrap();
+            if detour {
+                let id = topology
+                    .add_channel(QuantumChannel::new(0, 2, 0.0, 0.2))
+                    .unwrap();
+                topology.remove_channel(id).unwrap();
+            }
+            topology
+                .add_channel(QuantumChannel::new(1, 2, 0.0, 0.2))
+                .unwrap();
+            topology
+        };
+        let export = |topology: NetworkTopology| {
+            let mut service = RequestService::new(topology, ServiceConfig::default());
+            let mut engine = SimulationEngine::new();
+            service.submit(0, 2, 4, &mut engine.scheduler, 0.0).unwrap();
+            let report = engine.run(&mut service).into_report();
+            format!("{:?}", report.links)
+        };
+        assert_eq!(export(build(false)), export(build(true)));
+    }
+
+    #[test]
+    fn test_rejects_unroutable_request() {
+        let mut topology = NetworkTopology::new_custom();
+        topology
+            .add_node(crate::network::QuantumNode::new(0, 2))
+            .unwrap();
+        topology
+            .add_node(crate::network::QuantumNode::new(1, 2))
+            .unwrap();
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut scheduler = EventScheduler::new();
+
+        assert!(service.submit(0, 1, 1, &mut scheduler, 0.0).is_err());
+        assert!(service.submit(0, 0, 1, &mut scheduler, 0.0).is_err());
+        assert!(!scheduler.has_events());
+    }
+
+    #[test]
+    fn test_retry_backoff_skips_attempt_slots() {
+        let run = |policy: RetryPolicy| {
+            // Far too lossy to ever herald
+            let mut topology = NetworkTopology::new_custom();
+            topology.add_node(QuantumNode::new(0, 2)).unwrap();
+            topology.add_node(QuantumNode::new(1, 2)).unwrap();
+            topology
+                .add_channel(QuantumChannel::new(0, 1, 10.0, 100.0).with_retry_policy(policy))
+                .unwrap();
+            let mut service = RequestService::new(topology, ServiceConfig::default());
+            let mut engine = SimulationEngine::with_limits(RunLimits {
+                max_sim_time: Some(20.0),
+                ..Default::default()
+            });
+            service.submit(0, 1, 1, &mut engine.scheduler, 0.0).unwrap();
+            engine.run(&mut service).into_report()
+        };
+
+        let full_rate = run(RetryPolicy::none());
+        assert_eq!(full_rate.generation.backoff_idle_time, 0.0);
+
+        // Attempts at t = 0, 1, then every 5 ms once the 4 ms backoff applies
+        let backed_off = run(RetryPolicy {
+            max_consecutive_failures: Some(2),
+            backoff: Backoff::Fixed(4.0),
+        });
+        assert!(backed_off.generation.attempts < full_rate.generation.attempts);
+        assert_eq!(backed_off.generation.attempts, 5);
+        assert_eq!(backed_off.generation.backoff_idle_time, 16.0);
+        assert_eq!(backed_off.links[0].generation.backoff_idle_time, 16.0);
+    }
+
+    #[test]
+    fn test_compressed_idle_time_matches_full_run() {
+        let run = |compress_idle_time: bool| {
+            // p ≈ 0.1 per slot, with 40 ms off after every third failure in a row
+            let mut topology = NetworkTopology::new_linear(3, 2, 50.0, 0.2);
+            let policy = RetryPolicy {
+                max_consecutive_failures: Some(3),
+                backoff: Backoff::Exponential {
+                    base: 10.0,
+                    cap: 40.0,
+                },
+            };
+            for id in 0..2 {
+                let channel = topology.channel_mut(ChannelId(id)).unwrap();
+                channel.retry_policy = policy;
+            }
+            let config = ServiceConfig {
+                compress_idle_time,
+                ..ServiceConfig::default()
+            };
+            let mut service = RequestService::new(topology, config);
+            let mut engine = SimulationEngine::new().with_seed(21);
+            service
+                .submit(0, 2, 200, &mut engine.scheduler, 0.0)
+                .unwrap();
+            engine.run(&mut service).into_report()
+        };
+
+        let full = run(false);
+        let mut compressed = run(true);
+        assert!(full.generation.backoff_idle_time > 0.0);
+        assert!(compressed.events_processed < full.events_processed / 2);
+        // Only the event trace differs
+        compressed.events_processed = full.events_processed;
+        compressed.fingerprint = full.fingerprint;
+        compressed.wall_clock = full.wall_clock;
+        assert_eq!(compressed, full);
+    }
+
+    #[test]
+    fn test_retry_policy_override_backs_off_later_segment() {
+        let topology = NetworkTopology::new_custom();
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        service.topology.add_node(QuantumNode::new(0, 2)).unwrap();
+        service.topology.add_node(QuantumNode::new(1, 2)).unwrap();
+        service
+            .topology
+            .add_channel(QuantumChannel::new(0, 1, 10.0, 100.0))
+            .unwrap();
+        let mut engine = SimulationEngine::with_limits(RunLimits {
+            max_sim_time: Some(40.0),
+            ..Default::default()
+        });
+        service.submit(0, 1, 1, &mut engine.scheduler, 0.0).unwrap();
+
+        engine.pause_at(19.5);
+        assert!(engine.run(&mut service).is_paused());
+        let change = ParamOverride::ChannelRetryPolicy {
+            channel_id: ChannelId(0),
+            policy: RetryPolicy {
+                max_consecutive_failures: Some(2),
+                backoff: Backoff::Fixed(4.0),
+            },
+        };
+        engine.apply_override(&mut service, change).unwrap();
+        let report = engine.run(&mut service).into_report();
+
+        let [before, after] = &report.segments[..] else {
+            panic!("expected two segments, got {:?}", report.segments);
+        };
+        assert_eq!(before.generation.attempts, 20);
+        assert_eq!(before.generation.backoff_idle_time, 0.0);
+        // The streak is already past the threshold: attempts at t = 20, 25, 30, 35, 40
+        assert_eq!(after.generation.attempts, 5);
+        assert_eq!(after.generation.backoff_idle_time, 20.0);
+    }
+
+    fn run_link_at(detail_level: DetailLevel) -> RunReport {
+        // p ≈ 0.4 per slot
+        let topology = NetworkTopology::new_linear(2, 2, 20.0, 0.2);
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new().with_detail_level(detail_level);
+        service
+            .submit(0, 1, 2000, &mut engine.scheduler, 0.0)
+            .unwrap();
+        engine.run(&mut service).into_report()
+    }
+
+    #[test]
+    fn test_detail_levels_agree_on_throughput() {
+        let reports: Vec<RunReport> = [
+            DetailLevel::Analytic,
+            DetailLevel::FidelityScalar,
+            DetailLevel::StateVector,
+        ]
+        .into_iter()
+        .map(run_link_at)
+        .collect();
+
+        let analytic = reports[0].throughput_hz();
+        for report in &reports {
+            assert_eq!(report.requests.delivered, 2000);
+            assert!(
+                (report.throughput_hz() - analytic).abs() < 0.1 * analytic,
+                "{} vs analytic {}",
+                report.throughput_hz(),
+                analytic
+            );
+        }
+        assert_eq!(reports[0].detail_level, DetailLevel::Analytic);
+        assert_eq!(reports[0].generation.attempts, 0);
+        assert!(reports[0].nodes.is_empty());
+        let state_fidelities = reports[2]
+            .metric_samples(Metric::StateFidelityHistogram)
+            .unwrap();
+        assert_eq!(state_fidelities.len(), 2000);
+        // Delivered as soon as heralded: no memory noise, so the states agree
+        // with the scalar fidelities pair by pair
+        let scalar = reports[2]
+            .metric_samples(Metric::FidelityHistogram)
+            .unwrap();
+        for (state, scalar) in state_fidelities.iter().zip(scalar) {
+            assert!((state - scalar).abs() < 1e-9, "{} vs {}", state, scalar);
+        }
+    }
+
+    #[test]
+    fn test_state_vectors_follow_channel_noise() {
+        let mut topology = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        for id in 0..2 {
+            topology
+                .channel_mut(ChannelId(id))
+                .unwrap()
+                .depolarization_per_km = 0.005;
+        }
+        let config = ServiceConfig {
+            coherence_time_ms: f64::INFINITY,
+            ..ServiceConfig::default()
+        };
+        let mut service = RequestService::new(topology, config);
+        let mut engine = SimulationEngine::new().with_detail_level(DetailLevel::StateVector);
+        service
+            .submit(0, 2, 50, &mut engine.scheduler, 0.0)
+            .unwrap();
+        let report = engine.run(&mut service).into_report();
+
+        // Without memory noise each state is the swapped Werner-like state
+        let link = 1.0 - 0.995_f64.powi(10);
+        let expected = swapped_fidelity(1.0 - link, 1.0 - link);
+        for &fidelity in report
+            .metric_samples(Metric::StateFidelityHistogram)
+            .unwrap()
+        {
+            assert!((fidelity - expected).abs() < 1e-9, "{}", fidelity);
+        }
+    }
+
+    #[test]
+    fn test_state_vectors_pick_up_memory_errors() {
+        // Each pair waits out the 2 ms herald in memory with T2 = 4 ms
+        let topology = NetworkTopology::new_linear(2, 2, 20.0, 0.2);
+        let config = ServiceConfig {
+            coherence_time_ms: 4.0,
+            heralding_delay_ms: 2.0,
+            ..ServiceConfig::default()
+        };
+        let mut service = RequestService::new(topology, config);
+        let mut engine = SimulationEngine::new()
+            .with_detail_level(DetailLevel::StateVector)
+            .with_seed(3);
+        service
+            .submit(0, 1, 2000, &mut engine.scheduler, 0.0)
+            .unwrap();
+        let report = engine.run(&mut service).into_report();
+
+        // A Z on either half turns |Φ+⟩ into |Φ-⟩; on both it cancels
+        let p_z = (1.0 - (-0.5_f64).exp()) / 2.0;
+        let flip = 2.0 * p_z * (1.0 - p_z);
+        let states = report
+            .metric_samples(Metric::StateFidelityHistogram)
+            .unwrap();
+        let flipped = states.iter().filter(|&&fidelity| fidelity < 1e-9).count();
+        assert!(states
+            .iter()
+            .all(|&fidelity| fidelity < 1e-9 || (fidelity - 1.0).abs() < 1e-9));
+        assert!(
+            (flipped as f64 / states.len() as f64 - flip).abs() < 0.05,
+            "{} of {} flipped, expected {}",
+            flipped,
+            states.len(),
+            flip
+        );
+    }
+
+    #[test]
+    fn test_fidelity_histogram_at_analytic_detail_errors() {
+        let report = run_link_at(DetailLevel::Analytic);
+        assert!(report.require(Metric::Throughput).is_ok());
+        let error = report
+            .metric_samples(Metric::FidelityHistogram)
+            .unwrap_err();
+        assert!(error.contains("fidelity-scalar"), "{}", error);
+        assert!(run_link_at(DetailLevel::FidelityScalar)
+            .metric_samples(Metric::StateFidelityHistogram)
+            .is_err());
+    }
+
+    #[test]
+    fn test_attenuation_override_changes_only_later_segment() {
+        // 20 km at 0.2 dB/km: p ≈ 0.40; at 0.05 dB/km: p ≈ 0.79
+        let topology = NetworkTopology::new_linear(2, 2, 20.0, 0.2);
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new();
+        service
+            .submit(0, 1, 3000, &mut engine.scheduler, 0.0)
+            .unwrap();
+
+        engine.pause_at(2000.0);
+        let outcome = engine.run(&mut service);
+        assert!(outcome.is_paused());
+        let change = ParamOverride::ChannelAttenuation {
+            channel_id: ChannelId(0),
+            db_per_km: 0.05,
+        };
+        engine.apply_override(&mut service, change).unwrap();
+        assert!(engine
+            .apply_override(&mut service, ParamOverride::DetectorEfficiency(0.5))
+            .is_err());
+        let report = engine.run(&mut service).into_report();
+
+        assert_eq!(report.requests.delivered, 3000);
+        assert_eq!(report.overrides.len(), 1);
+        assert_eq!(report.overrides[0].time, 2000.0);
+        assert_eq!(report.overrides[0].change, change);
+        assert!(report.summary().contains("t=2.00 s"));
+
+        let [before, after] = &report.segments[..] else {
+            panic!("expected two segments, got {:?}", report.segments);
+        };
+        assert_eq!((before.start, before.end), (0.0, 2000.0));
+        assert_eq!(after.start, 2000.0);
+        assert_eq!(
+            before.generation.attempts + after.generation.attempts,
+            report.generation.attempts
+        );
+        let rate =
+            |db_per_km: f64| QuantumChannel::new(0, 1, 20.0, db_per_km).success_probability();
+        assert!((before.generation.success_rate() - rate(0.2)).abs() < 0.05);
+        assert!((after.generation.success_rate() - rate(0.05)).abs() < 0.05);
+        assert_eq!(
+            before.links[0].generation.attempts,
+            before.generation.attempts
+        );
+        assert_eq!(
+            before.histograms["fidelity"].len() + after.histograms["fidelity"].len(),
+            3000
+        );
+    }
+}
@@ -0,0 +1,464 @@
+use crate::network::{
+    attempt_entanglement_generation_with_rng, ChannelSelector, NetworkTopology, QuantumChannel,
+};
+use crate::protocols::swapping::perform_entanglement_swap_with_rng;
+use crate::simulation::{unseeded_rng, RunReport};
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Parameters for a slotted star-hub run
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StarConfig {
+    /// Number of leaves (nodes 1..=num_leaves around hub node 0)
+    pub num_leaves: usize,
+    /// Hub-to-leaf fiber length (km)
+    pub link_distance_km: f64,
+    /// Fiber attenuation (dB/km)
+    pub attenuation_db_per_km: f64,
+    /// Memory slots at the hub; each request in service reserves two
+    pub hub_memory: usize,
+    /// Memory slots at each leaf
+    pub leaf_memory: usize,
+    /// Memory coherence time in milliseconds
+    pub coherence_time_ms: f64,
+    /// Time between generation attempts on each link (ms)
+    /// Raised to the links' attempt duration when that is longer
+    pub attempt_interval_ms: f64,
+    /// Number of attempt slots per run
+    pub slots_per_run: usize,
+    /// Probability that the hub's swap succeeds
+    pub swap_success_probability: f64,
+    /// Stop the slot loop once no request is queued or in service
+    /// Requests all arrive at t = 0, so nothing is left to happen; the
+    /// run still reports `slots_per_run` slots of total time
+    pub stop_when_idle: bool,
+}
+
+impl Default for StarConfig {
+    fn default() -> Self {
+        StarConfig {
+            num_leaves: 4,
+            link_distance_km: 10.0,
+            attenuation_db_per_km: 0.2,
+            hub_memory: 4,
+            leaf_memory: 4,
+            coherence_time_ms: 100.0,
+            attempt_interval_ms: 1.0,
+            slots_per_run: 10_000,
+            swap_success_probability: 1.0,
+            stop_when_idle: false,
+        }
+    }
+}
+
+/// Results of one star-hub run
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StarRunStats {
+    /// Pairs delivered per requesting leaf (index = leaf id - 1)
+    pub delivered_per_leaf: Vec<usize>,
+    /// (source, destination) of every delivery, in delivery order
+    pub served: Vec<(usize, usize)>,
+    /// Fidelity of each delivered pair at delivery time
+    pub fidelities: Vec<f64>,
+    /// Time from admission of each request to its delivery (ms)
+    pub latencies_ms: Vec<f64>,
+    /// Slots in which a queued request waited because the hub memory was reserved
+    pub hub_blocked_slots: usize,
+    /// Requests still queued or in service at the end of the run
+    pub unserved: usize,
+    /// Simulated time covered by the run (ms)
+    pub total_time_ms: f64,
+}
+
+impl StarRunStats {
+    /// Delivered pairs per second requested by `leaf_id`
+    pub fn leaf_rate_hz(&self, leaf_id: usize) -> f64 {
+        let delivered = leaf_id
+            .checked_sub(1)
+            .and_then(|index| self.delivered_per_leaf.get(index))
+            .copied()
+            .unwrap_or(0);
+        if self.total_time_ms == 0.0 {
+            0.0
+        } else {
+            delivered as f64 / (self.total_time_ms / 1000.0)
+        }
+    }
+
+    /// Delivered rate of every leaf, in leaf order
+    pub fn leaf_rates_hz(&self) -> Vec<f64> {
+        (1..=self.delivered_per_leaf.len())
+            .map(|leaf_id| self.leaf_rate_hz(leaf_id))
+            .collect()
+    }
+
+    /// Jain's fairness index of the per-leaf delivered rates
+    pub fn fairness(&self) -> f64 {
+        jain_fairness_index(&self.leaf_rates_hz())
+    }
+
+    /// Check if `source` got at least one pair with `destination`
+    pub fn was_served(&self, source: usize, destination: usize) -> bool {
+        self.served.contains(&(source, destination))
+    }
+
+    /// Run report of these stats, echoing `config`
+    pub fn report(&self, config: &StarConfig) -> RunReport {
+        let mut report = RunReport {
+            replications: 1,
+            final_time: self.total_time_ms,
+            ..RunReport::default()
+        };
+        for (name, value) in [
+            ("num_leaves", config.num_leaves.to_string()),
+            ("link_distance_km", config.link_distance_km.to_string()),
+            (
+                "attenuation_db_per_km",
+                config.attenuation_db_per_km.to_string(),
+            ),
+            ("hub_memory", config.hub_memory.to_string()),
+            ("leaf_memory", config.leaf_memory.to_string()),
+            ("coherence_time_ms", config.coherence_time_ms.to_string()),
+            (
+                "attempt_interval_ms",
+                config.attempt_interval_ms.to_string(),
+            ),
+            ("slots_per_run", config.slots_per_run.to_string()),
+            (
+                "swap_success_probability",
+                config.swap_success_probability.to_string(),
+            ),
+        ] {
+            report.config.insert(name.to_string(), value);
+        }
+        report.requests.delivered = self.served.len();
+        report.requests.submitted = self.served.len() + self.unserved;
+        report.record_samples("fidelity", &self.fidelities);
+        report.record_samples("latency_ms", &self.latencies_ms);
+        report.record_samples("leaf_rate_hz", &self.leaf_rates_hz());
+        report
+    }
+}
+
+/// Jain's fairness index (Σx)² / (n·Σx²)
+/// 1 when all values are equal, 1/n when one value takes everything
+pub fn jain_fairness_index(values: &[f64]) -> f64 {
+    let sum: f64 = values.iter().sum();
+    let sum_sq: f64 = values.iter().map(|x| x * x).sum();
+    if sum_sq == 0.0 {
+        1.0
+    } else {
+        sum * sum / (values.len() as f64 * sum_sq)
+    }
+}
+
+/// `rounds` requests per leaf; in round r leaf i asks for the leaf r + 1 places after it
+/// Over `num_leaves - 1` rounds every ordered leaf pair is requested once
+pub fn round_robin_requests(num_leaves: usize, rounds: usize) -> Vec<(usize, usize)> {
+    if num_leaves < 2 {
+        return Vec::new();
+    }
+    let mut requests = Vec::with_capacity(num_leaves * rounds);
+    for round in 0..rounds {
+        let offset = round % (num_leaves - 1) + 1;
+        for leaf in 0..num_leaves {
+            requests.push((leaf + 1, (leaf + offset) % num_leaves + 1));
+        }
+    }
+    requests
+}
+
+/// A request admitted to the hub
+struct InService {
+    source: usize,
+    destination: usize,
+    hub: usize,
+    admitted_at: f64,
+}
+
+/// Run a star network where the hub fuses leaf links on demand
+///
+/// `requests` are (source, destination) leaf ids, queued at their source
+/// leaf at t = 0. Each slot the hub admits queued requests round-robin over
+/// leaves (one in service per leaf) while two free hub slots can be reserved,
+/// attempts generation on every link an admitted request still lacks, then
+/// swaps at the hub for each request whose links are both ready. A failed
+/// swap discards both link pairs and the request retries.
+pub fn run_star(config: &StarConfig, requests: &[(usize, usize)]) -> Result<StarRunStats, String> {
+    run_star_with_rng(config, requests, &mut unseeded_rng("run_star"))
+}
+
+/// `run_star` drawing every generation, swap and BSM outcome from the caller's RNG
+pub fn run_star_with_rng(
+    config: &StarConfig,
+    requests: &[(usize, usize)],
+    rng: &mut impl Rng,
+) -> Result<StarRunStats, String> {
+    let mut network = NetworkTopology::new_star(
+        config.num_leaves + 1,
+        config.leaf_memory,
+        config.link_distance_km,
+        config.attenuation_db_per_km,
+    );
+    network.get_node_mut(0).unwrap().memory_capacity = config.hub_memory;
+
+    let mut queues: Vec<VecDeque<(usize, usize)>> = vec![VecDeque::new(); config.num_leaves];
+    for &(source, destination) in requests {
+        let leaf_ids = 1..=config.num_leaves;
+        if !leaf_ids.contains(&source) || !leaf_ids.contains(&destination) {
+            return Err(format!(
+                "Request {}-{} is not between two leaves",
+                source, destination
+            ));
+        }
+        if source == destination {
+            return Err(format!(
+                "Request {}-{} has identical ends",
+                source, destination
+            ));
+        }
+        queues[source - 1].push_back((source, destination));
+    }
+
+    let mut stats = StarRunStats {
+        delivered_per_leaf: vec![0; config.num_leaves],
+        ..StarRunStats::default()
+    };
+    let mut in_service: Vec<InService> = Vec::new();
+    let mut next_leaf = 0;
+
+    // No link may attempt again before its previous attempt has heralded
+    let slot_ms = network
+        .channels()
+        .iter()
+        .map(QuantumChannel::attempt_duration_ms)
+        .fold(config.attempt_interval_ms, f64::max);
+
+    for slot in 0..config.slots_per_run {
+        let time = slot as f64 * slot_ms;
+
+        // Requests only arrive at t = 0, so an idle hub stays idle
+        let idle = in_service.is_empty() && queues.iter().all(VecDeque::is_empty);
+        if idle && config.stop_when_idle {
+            break;
+        }
+
+        // Admit round-robin, reserving two hub slots per request
+        let mut blocked = false;
+        let first_leaf = next_leaf;
+        for step in 0..config.num_leaves {
+            let leaf = (first_leaf + step) % config.num_leaves;
+            let busy = in_service.iter().any(|request| request.source == leaf + 1);
+            let Some(&(source, destination)) = queues[leaf].front() else {
+                continue;
+            };
+            if busy {
+                continue;
+            }
+            let path = network
+                .find_path(source, destination, ChannelSelector::BestSuccessProbability)
+                .ok_or(format!("No route from {} to {}", source, destination))?;
+            let hub_node = network.get_node_mut(path.nodes[1]).unwrap();
+            if hub_node.reserve(2, time).is_err() {
+                blocked = true;
+                continue;
+            }
+            queues[leaf].pop_front();
+            in_service.push(InService {
+                source,
+                destination,
+                hub: path.nodes[1],
+                admitted_at: time,
+            });
+            next_leaf = (leaf + 1) % config.num_leaves;
+        }
+        if blocked {
+            stats.hub_blocked_slots += 1;
+        }
+
+        // Generate on every link some admitted request is still missing
+        let mut links: Vec<(usize, usize)> = in_service
+            .iter()
+            .flat_map(|request| {
+                [
+                    (request.hub, request.source),
+                    (request.hub, request.destination),
+                ]
+            })
+            .collect();
+        links.sort_unstable();
+        let mut start = 0;
+        while start < links.len() {
+            let (hub, leaf) = links[start];
+            let needed = links[start..]
+                .iter()
+                .take_while(|&&link| link == (hub, leaf))
+                .count();
+            start += needed;
+            if network.get_node(hub).unwrap().count_pairs_with(leaf) >= needed {
+                continue;
+            }
+            let channel = network.find_channel(hub, leaf).unwrap().1.clone();
+            let (node_hub, node_leaf) = network.get_node_pair_mut(hub, leaf).unwrap();
+            let _ = attempt_entanglement_generation_with_rng(
+                node_hub,
+                node_leaf,
+                &channel,
+                time,
+                config.coherence_time_ms,
+                rng,
+            );
+        }
+
+        // Swap at the hub for every request with both links ready, in admission order
+        let mut index = 0;
+        while index < in_service.len() {
+            let request = &in_service[index];
+            let hub_node = network.get_node(request.hub).unwrap();
+            let ready = hub_node.find_pair_with(request.source).is_some()
+                && hub_node.find_pair_with(request.destination).is_some();
+            if !ready {
+                index += 1;
+                continue;
+            }
+
+            if rng.random::<f64>() >= config.swap_success_probability {
+                network.evict_link_pair(request.hub, request.source, time);
+                network.evict_link_pair(request.hub, request.destination, time);
+                index += 1;
+                continue;
+            }
+            perform_entanglement_swap_with_rng(
+                &mut network,
+                request.hub,
+                request.source,
+                request.destination,
+                time,
+                rng,
+            )
+            .expect("both links hold a pair");
+
+            let pair = network
+                .get_node_mut(request.source)
+                .unwrap()
+                .remove_pair_with(request.destination, time)
+                .unwrap();
+            network
+                .get_node_mut(request.destination)
+                .unwrap()
+                .remove_pair_by_id(pair.pair_id, time);
+            network
+                .get_node_mut(request.hub)
+                .unwrap()
+                .release_reservation(2, time)?;
+
+            stats.delivered_per_leaf[request.source - 1] += 1;
+            stats.served.push((request.source, request.destination));
+            stats.fidelities.push(pair.fidelity_at(time));
+            stats
+                .latencies_ms
+                .push(time + slot_ms - request.admitted_at);
+            in_service.remove(index);
+        }
+    }
+
+    stats.unserved = in_service.len() + queues.iter().map(VecDeque::len).sum::<usize>();
+    stats.total_time_ms = config.slots_per_run as f64 * slot_ms;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_covers_every_leaf_pair() {
+        let requests = round_robin_requests(4, 3);
+        assert_eq!(requests.len(), 12);
+        for source in 1..=4 {
+            for destination in (1..=4).filter(|&d| d != source) {
+                assert!(requests.contains(&(source, destination)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_jain_fairness_index_bounds() {
+        assert_eq!(jain_fairness_index(&[2.0, 2.0, 2.0, 2.0]), 1.0);
+        assert_eq!(jain_fairness_index(&[4.0, 0.0, 0.0, 0.0]), 0.25);
+    }
+
+    #[test]
+    fn test_lossless_hub_limited_by_memory() {
+        // Lossless links: every admitted request completes in its admission slot,
+        // so hub memory for one request serves one delivery per slot
+        let config = StarConfig {
+            link_distance_km: 0.0,
+            hub_memory: 2,
+            slots_per_run: 8,
+            ..StarConfig::default()
+        };
+        let stats = run_star(&config, &round_robin_requests(4, 3)).unwrap();
+
+        assert_eq!(stats.served.len(), 8);
+        assert_eq!(stats.delivered_per_leaf, vec![2, 2, 2, 2]);
+        assert_eq!(stats.unserved, 4);
+        assert_eq!(stats.hub_blocked_slots, 8);
+        assert!(stats.fidelities.iter().all(|&f| (f - 1.0).abs() < 1e-10));
+    }
+
+    #[test]
+    fn test_stopping_when_idle_matches_full_run() {
+        let config = StarConfig {
+            link_distance_km: 0.0,
+            slots_per_run: 100_000,
+            ..StarConfig::default()
+        };
+        let requests = round_robin_requests(4, 3);
+        let full = run_star(&config, &requests).unwrap();
+        let stopped = run_star(
+            &StarConfig {
+                stop_when_idle: true,
+                ..config
+            },
+            &requests,
+        )
+        .unwrap();
+
+        assert_eq!(stopped.served, full.served);
+        assert_eq!(stopped.latencies_ms, full.latencies_ms);
+        assert_eq!(stopped.fidelities, full.fidelities);
+        assert_eq!(stopped.hub_blocked_slots, full.hub_blocked_slots);
+        assert_eq!(stopped.total_time_ms, full.total_time_ms);
+        assert_eq!(stopped.leaf_rates_hz(), full.leaf_rates_hz());
+    }
+
+    #[test]
+    fn test_long_links_stretch_the_slot() {
+        let config = StarConfig {
+            link_distance_km: 500.0,
+            attenuation_db_per_km: 0.0,
+            slots_per_run: 10,
+            ..StarConfig::default()
+        };
+        let stats = run_star(&config, &[(1, 2), (3, 4)]).unwrap();
+        let duration = QuantumChannel::new(0, 1, 500.0, 0.0).attempt_duration_ms();
+
+        assert!(duration > config.attempt_interval_ms);
+        assert_eq!(stats.served, vec![(1, 2), (3, 4)]);
+        assert!((stats.total_time_ms - 10.0 * duration).abs() < 1e-9);
+        assert!(stats
+            .latencies_ms
+            .iter()
+            .all(|&l| (l - duration).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_rejects_request_to_hub() {
+        let config = StarConfig::default();
+        assert!(run_star(&config, &[(1, 0)]).is_err());
+        assert!(run_star(&config, &[(2, 2)]).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! C API for embedding the simulator (feature `capi`)
+//!
+//! A handle owns a topology, a `RequestService` and an engine. Every function
+//! returns a status code (or null) instead of panicking across the boundary.
+//! The header is `include/qcomnetsim.h`; regenerate it with
+//! `cbindgen --config cbindgen.toml --output include/qcomnetsim.h`.
+
+use crate::experiment::RequestService;
+use crate::io::SimulationConfig;
+use crate::simulation::SimulationEngine;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+pub const QCS_OK: c_int = 0;
+/// A required pointer argument was null
+pub const QCS_ERR_NULL_POINTER: c_int = -1;
+/// The config string is not valid UTF-8 or not a valid config
+pub const QCS_ERR_INVALID_CONFIG: c_int = -2;
+/// The request has no route or identical ends
+pub const QCS_ERR_INVALID_REQUEST: c_int = -3;
+/// `qcs_run_until` was asked to go back in time
+pub const QCS_ERR_INVALID_TIME: c_int = -4;
+/// The simulator panicked; the handle should be destroyed
+pub const QCS_ERR_PANIC: c_int = -5;
+
+/// Opaque simulation handle
+pub struct QcsSimulation {
+    engine: SimulationEngine,
+    service: RequestService,
+    /// Time the simulation has been run up to (ms)
+    now: f64,
+    /// Backing storage for the last `qcs_get_stats_json` result
+    stats_json: CString,
+}
+
+/// Run `body`, turning a panic into `QCS_ERR_PANIC`
+fn guard(body: impl FnOnce() -> c_int) -> c_int {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or(QCS_ERR_PANIC)
+}
+
+/// Create a simulation from a JSON `io::SimulationConfig`
+///
+/// On success `*out` holds a handle to free with `qcs_destroy`.
+///
+/// # Safety
+/// `config_json` must be null or a NUL-terminated string, and `out` must be
+/// null or valid for writing a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn qcs_create(
+    config_json: *const c_char,
+    out: *mut *mut QcsSimulation,
+) -> c_int {
+    if config_json.is_null() || out.is_null() {
+        return QCS_ERR_NULL_POINTER;
+    }
+    guard(|| {
+        let Ok(json) = CStr::from_ptr(config_json).to_str() else {
+            return QCS_ERR_INVALID_CONFIG;
+        };
+        let Ok(config) = SimulationConfig::from_json(json, "config") else {
+            return QCS_ERR_INVALID_CONFIG;
+        };
+        let Ok(topology) = config.build_topology() else {
+            return QCS_ERR_INVALID_CONFIG;
+        };
+        let service = RequestService::new(topology, config.service_config());
+        let simulation = QcsSimulation {
+            engine: SimulationEngine::new(),
+            service,
+            now: 0.0,
+            stats_json: CString::default(),
+        };
+        *out = Box::into_raw(Box::new(simulation));
+        QCS_OK
+    })
+}
+
+/// Free a handle created by `qcs_create`; null is ignored
+///
+/// # Safety
+/// `handle` must be null or a handle from `qcs_create` not yet destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn qcs_destroy(handle: *mut QcsSimulation) {
+    if !handle.is_null() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(handle))));
+    }
+}
+
+/// Process every event up to and including `time` (ms)
+///
+/// # Safety
+/// `handle` must be null or a live handle from `qcs_create`.
+#[no_mangle]
+pub unsafe extern "C" fn qcs_run_until(handle: *mut QcsSimulation, time: f64) -> c_int {
+    let Some(simulation) = handle.as_mut() else {
+        return QCS_ERR_NULL_POINTER;
+    };
+    if time.is_nan() || time < simulation.now {
+        return QCS_ERR_INVALID_TIME;
+    }
+    guard(|| {
+        simulation.engine.limits.max_sim_time = Some(time);
+        simulation.engine.run(&mut simulation.service);
+        simulation.now = time;
+        QCS_OK
+    })
+}
+
+/// Queue `count` end-to-end pairs between `source` and `destination`
+/// Service starts at the current simulation time
+///
+/// # Safety
+/// `handle` must be null or a live handle from `qcs_create`.
+#[no_mangle]
+pub unsafe extern "C" fn qcs_submit_request(
+    handle: *mut QcsSimulation,
+    source: u32,
+    destination: u32,
+    count: u32,
+) -> c_int {
+    let Some(simulation) = handle.as_mut() else {
+        return QCS_ERR_NULL_POINTER;
+    };
+    guard(|| {
+        match simulation.service.submit(
+            source as usize,
+            destination as usize,
+            count as usize,
+            &mut simulation.engine.scheduler,
+            simulation.now,
+        ) {
+            Ok(()) => QCS_OK,
+            Err(_) => QCS_ERR_INVALID_REQUEST,
+        }
+    })
+}
+
+/// Current statistics as a JSON object
+///
+/// The string is owned by the handle and valid until the next call on it;
+/// returns null on a null handle or a panic.
+///
+/// # Safety
+/// `handle` must be null or a live handle from `qcs_create`.
+#[no_mangle]
+pub unsafe extern "C" fn qcs_get_stats_json(handle: *mut QcsSimulation) -> *const c_char {
+    let Some(simulation) = handle.as_mut() else {
+        return ptr::null();
+    };
+    let status = guard(|| {
+        let json = format!(
+            "{{\"time\":{},\"service\":{}}}",
+            simulation.now,
+            simulation.service.stats().to_json()
+        );
+        // Formatted numbers and keys never contain NUL
+        simulation.stats_json = CString::new(json).unwrap();
+        QCS_OK
+    });
+    if status == QCS_OK {
+        simulation.stats_json.as_ptr()
+    } else {
+        ptr::null()
+    }
+}
+
+/// Static description of a status code
+#[no_mangle]
+pub extern "C" fn qcs_status_message(status: c_int) -> *const c_char {
+    let message = match status {
+        QCS_OK => c"ok",
+        QCS_ERR_NULL_POINTER => c"null pointer argument",
+        QCS_ERR_INVALID_CONFIG => c"invalid config",
+        QCS_ERR_INVALID_REQUEST => c"invalid request",
+        QCS_ERR_INVALID_TIME => c"time is before the current simulation time",
+        QCS_ERR_PANIC => c"internal simulator error",
+        _ => c"unknown status",
+    };
+    message.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create(json: &str) -> Result<*mut QcsSimulation, c_int> {
+        let config = CString::new(json).unwrap();
+        let mut handle = ptr::null_mut();
+        match unsafe { qcs_create(config.as_ptr(), &mut handle) } {
+            QCS_OK => Ok(handle),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_through_c_functions() {
+        let handle =
+            create(r#"{"topology": "linear", "num_nodes": 3, "distance_km": 0.0}"#).unwrap();
+        unsafe {
+            assert_eq!(qcs_submit_request(handle, 0, 2, 4), QCS_OK);
+            assert_eq!(qcs_run_until(handle, 1.5), QCS_OK);
+
+            let stats = CStr::from_ptr(qcs_get_stats_json(handle)).to_str().unwrap();
+            assert!(stats.starts_with("{\"time\":1.5,"));
+            assert!(stats.contains("\"delivered\":2,\"pending\":2"));
+
+            assert_eq!(qcs_run_until(handle, 10.0), QCS_OK);
+            let stats = CStr::from_ptr(qcs_get_stats_json(handle)).to_str().unwrap();
+            assert!(stats.contains("\"delivered\":4,\"pending\":0"));
+            qcs_destroy(handle);
+        }
+    }
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(
+            create(r#"{"topology": "torus"}"#).unwrap_err(),
+            QCS_ERR_INVALID_CONFIG
+        );
+        assert_eq!(
+            create(r#"{"nodes": 3}"#).unwrap_err(),
+            QCS_ERR_INVALID_CONFIG
+        );
+        assert_eq!(create("not json").unwrap_err(), QCS_ERR_INVALID_CONFIG);
+
+        let handle = create(r#"{"num_nodes": 2}"#).unwrap();
+        unsafe {
+            assert_eq!(qcs_submit_request(handle, 0, 9, 1), QCS_ERR_INVALID_REQUEST);
+            assert_eq!(qcs_run_until(handle, 5.0), QCS_OK);
+            assert_eq!(qcs_run_until(handle, 1.0), QCS_ERR_INVALID_TIME);
+
+            assert_eq!(qcs_run_until(ptr::null_mut(), 1.0), QCS_ERR_NULL_POINTER);
+            assert!(qcs_get_stats_json(ptr::null_mut()).is_null());
+            qcs_destroy(ptr::null_mut());
+            qcs_destroy(handle);
+
+            let message = CStr::from_ptr(qcs_status_message(QCS_ERR_INVALID_TIME));
+            assert!(message.to_str().unwrap().contains("time"));
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Engineering-notation strings for printed summaries
+//!
+//! Values print with three significant digits and an SI prefix, e.g.
+//! "312 mpair/s" or "1.25 µs". Output never depends on the locale, and
+//! `parse_engineering` reads it back to within the printed precision.
+
+/// SI prefixes by power of ten
+const PREFIXES: [(i32, &str); 11] = [
+    (-15, "f"),
+    (-12, "p"),
+    (-9, "n"),
+    (-6, "µ"),
+    (-3, "m"),
+    (0, ""),
+    (3, "k"),
+    (6, "M"),
+    (9, "G"),
+    (12, "T"),
+    (15, "P"),
+];
+
+/// Pair rate, e.g. "312 mpair/s"
+pub fn format_rate(pairs_per_sec: f64) -> String {
+    format_engineering(pairs_per_sec, "pair/s")
+}
+
+/// Simulated time given in ms, printed in seconds, e.g. "1.25 µs"
+pub fn format_time(time_ms: f64) -> String {
+    format_engineering(time_ms / 1000.0, "s")
+}
+
+/// Probability as a percentage, e.g. "63.1%"
+pub fn format_probability(p: f64) -> String {
+    let percent = p * 100.0;
+    if percent == 0.0 || !percent.is_finite() {
+        return format!("{}%", percent);
+    }
+    if percent.abs() < 1e-3 {
+        return format!("{:.2e}%", percent);
+    }
+    format!("{}%", three_significant(percent))
+}
+
+/// `value` in `unit` with three significant digits and an SI prefix
+/// Magnitudes outside femto..peta fall back to scientific notation
+pub fn format_engineering(value: f64, unit: &str) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return format!("{} {}", value, unit);
+    }
+    let magnitude = value.abs();
+    let sign = if value < 0.0 { "-" } else { "" };
+    let mut exponent = (magnitude.log10().floor() as i32).div_euclid(3) * 3;
+    let mut mantissa = three_significant(magnitude / 10f64.powi(exponent));
+    // Rounding can carry into the next prefix: 999.7 -> 1000 -> 1.00 k
+    if mantissa.parse::<f64>().unwrap() >= 1000.0 {
+        exponent += 3;
+        mantissa = three_significant(magnitude / 10f64.powi(exponent));
+    }
+    match PREFIXES.iter().find(|(power, _)| *power == exponent) {
+        Some((_, prefix)) => format!("{}{} {}{}", sign, mantissa, prefix, unit),
+        None => format!("{:.2e} {}", value, unit),
+    }
+}
+
+/// Read a `format_engineering` string back into a value in `unit`
+pub fn parse_engineering(text: &str, unit: &str) -> Result<f64, String> {
+    let number = text
+        .trim()
+        .strip_suffix(unit)
+        .ok_or_else(|| format!("'{}' is not in {}", text, unit))?;
+    let (number, scale) = match PREFIXES
+        .iter()
+        .filter(|(_, prefix)| !prefix.is_empty())
+        .find(|(_, prefix)| number.ends_with(prefix))
+    {
+        Some((power, prefix)) => (&number[..number.len() - prefix.len()], 10f64.powi(*power)),
+        None => (number, 1.0),
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .map(|value| value * scale)
+        .map_err(|_| format!("'{}' is not a number", text))
+}
+
+/// Positive `x` rounded to three significant digits, without an exponent
+fn three_significant(x: f64) -> String {
+    let digits = x.log10().floor() as i32;
+    let scale = 10f64.powi(2 - digits);
+    let rounded = (x * scale).round() / scale;
+    let decimals = (2 - rounded.log10().floor() as i32).max(0) as usize;
+    format!("{:.*}", decimals, rounded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(format_rate(0.312), "312 mpair/s");
+        assert_eq!(format_time(1.25e-3), "1.25 µs");
+        assert_eq!(format_probability(0.631), "63.1%");
+        assert_eq!(format_rate(1.0), "1.00 pair/s");
+        assert_eq!(format_time(2000.0), "2.00 s");
+        assert_eq!(format_probability(1.0), "100%");
+        assert_eq!(format_probability(0.00123), "0.123%");
+    }
+
+    #[test]
+    fn test_boundary_magnitudes() {
+        assert_eq!(format_rate(0.0), "0 pair/s");
+        assert_eq!(format_probability(0.0), "0%");
+        // Sub-micro
+        assert_eq!(format_time(3.2e-7), "320 ps");
+        assert_eq!(format_engineering(9.9999e-7, "s"), "1.00 µs");
+        // Giga
+        assert_eq!(format_rate(4.56e9), "4.56 Gpair/s");
+        assert_eq!(format_rate(999.96), "1.00 kpair/s");
+        assert_eq!(format_engineering(-0.0042, "s"), "-4.20 ms");
+        assert_eq!(format_engineering(1e-20, "s"), "1.00e-20 s");
+        assert_eq!(format_engineering(f64::INFINITY, "s"), "inf s");
+        assert_eq!(format_probability(1e-9), "1.00e-7%");
+    }
+
+    #[test]
+    fn test_round_trip_within_printed_precision() {
+        let mut value = 1.234_567e-17;
+        while value < 1e18 {
+            for unit in ["pair/s", "s"] {
+                let text = format_engineering(value, unit);
+                let parsed = parse_engineering(&text, unit).unwrap();
+                // Three significant digits: half a unit in the third digit
+                assert!(
+                    ((parsed - value) / value).abs() <= 5e-3,
+                    "{} -> {} -> {}",
+                    value,
+                    text,
+                    parsed
+                );
+            }
+            value *= 3.7;
+        }
+        assert!(parse_engineering("1.25 µs", "pair/s").is_err());
+        assert_eq!(parse_engineering("312 mpair/s", "pair/s"), Ok(0.312));
+    }
+}
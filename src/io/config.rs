@@ -0,0 +1,222 @@
+use super::versioning::{from_versioned_json, load_versioned, save_versioned, Format};
+use crate::experiment::ServiceConfig;
+use crate::network::NetworkTopology;
+use crate::simulation::{IgnoredParameter, ParameterUsage, StrictConfig};
+use serde::{Deserialize, Serialize};
+
+/// Topology and request-service parameters of a simulation
+///
+/// Stored as a versioned JSON object (`Format::SimulationConfig`); missing
+/// fields take defaults. Also the config accepted by the C API. Loading is
+/// strict: values the selected topology never reads are an error unless
+/// allowed with `StrictConfig::allowing_ignored`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationConfig {
+    /// "linear", "star" or "mesh"
+    pub topology: String,
+    pub num_nodes: usize,
+    pub memory_per_node: usize,
+    /// Length of each channel (km)
+    pub link_distance_km: f64,
+    pub attenuation_db_per_km: f64,
+    pub coherence_time_ms: f64,
+    pub attempt_interval_ms: f64,
+    pub swap_success_probability: f64,
+    pub delivery_cutoff_ms: Option<f64>,
+    pub heralding_delay_ms: f64,
+    pub swap_processing_ms: f64,
+    pub classical_correction_delay_ms: f64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        let service = ServiceConfig::default();
+        SimulationConfig {
+            topology: "linear".to_string(),
+            num_nodes: 2,
+            memory_per_node: 2,
+            link_distance_km: 10.0,
+            attenuation_db_per_km: 0.2,
+            coherence_time_ms: service.coherence_time_ms,
+            attempt_interval_ms: service.attempt_interval_ms,
+            swap_success_probability: service.swap_success_probability,
+            delivery_cutoff_ms: service.delivery_cutoff_ms,
+            heralding_delay_ms: service.heralding_delay_ms,
+            swap_processing_ms: service.swap_processing_ms,
+            classical_correction_delay_ms: service.classical_correction_delay_ms,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Parse and validate a config, migrating older format versions
+    /// `source` names the origin in error messages
+    pub fn from_json(json: &str, source: &str) -> Result<Self, String> {
+        Self::from_json_with(json, source, StrictConfig::strict())
+    }
+
+    /// `from_json` with a choice of what to do about ignored parameters
+    pub fn from_json_with(json: &str, source: &str, strict: StrictConfig) -> Result<Self, String> {
+        let config: SimulationConfig = from_versioned_json(Format::SimulationConfig, json, source)?;
+        config
+            .checked(strict)
+            .map_err(|error| format!("{}: {}", source, error))
+    }
+
+    /// Load and validate a config file
+    pub fn load(path: &str) -> Result<Self, String> {
+        Self::load_with(path, StrictConfig::strict())
+    }
+
+    /// `load` with a choice of what to do about ignored parameters
+    pub fn load_with(path: &str, strict: StrictConfig) -> Result<Self, String> {
+        let config: SimulationConfig = load_versioned(Format::SimulationConfig, path)?;
+        config
+            .checked(strict)
+            .map_err(|error| format!("{}: {}", path, error))
+    }
+
+    /// Write the config in the current format version
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        save_versioned(Format::SimulationConfig, self, path)
+    }
+
+    fn checked(self, strict: StrictConfig) -> Result<Self, String> {
+        self.validate()?;
+        strict.check(&self)?;
+        Ok(self)
+    }
+
+    /// Whether some request may be routed through a repeater
+    fn may_swap(&self) -> bool {
+        match self.topology.as_str() {
+            // Every pair of nodes shares a direct channel
+            "mesh" => false,
+            "linear" | "star" => self.num_nodes > 2,
+            _ => true,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.num_nodes < 2 {
+            return Err("num_nodes must be at least 2".to_string());
+        }
+        if self.attempt_interval_ms <= 0.0 {
+            return Err("attempt_interval_ms must be positive".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn build_topology(&self) -> Result<NetworkTopology, String> {
+        let build = match self.topology.as_str() {
+            "linear" => NetworkTopology::new_linear,
+            "star" => NetworkTopology::new_star,
+            "mesh" => NetworkTopology::new_mesh,
+            other => return Err(format!("Unknown topology '{}'", other)),
+        };
+        Ok(build(
+            self.num_nodes,
+            self.memory_per_node,
+            self.link_distance_km,
+            self.attenuation_db_per_km,
+        ))
+    }
+
+    pub fn service_config(&self) -> ServiceConfig {
+        ServiceConfig {
+            coherence_time_ms: self.coherence_time_ms,
+            attempt_interval_ms: self.attempt_interval_ms,
+            swap_success_probability: self.swap_success_probability,
+            delivery_cutoff_ms: self.delivery_cutoff_ms,
+            heralding_delay_ms: self.heralding_delay_ms,
+            swap_processing_ms: self.swap_processing_ms,
+            classical_correction_delay_ms: self.classical_correction_delay_ms,
+            ..ServiceConfig::default()
+        }
+    }
+}
+
+impl ParameterUsage for SimulationConfig {
+    fn ignored_parameters(&self) -> Vec<IgnoredParameter> {
+        let mut ignored = Vec::new();
+        if !self.may_swap() {
+            let defaults = ServiceConfig::default();
+            for (field, value, default) in [
+                (
+                    "swap_success_probability",
+                    self.swap_success_probability,
+                    defaults.swap_success_probability,
+                ),
+                (
+                    "swap_processing_ms",
+                    self.swap_processing_ms,
+                    defaults.swap_processing_ms,
+                ),
+                (
+                    "classical_correction_delay_ms",
+                    self.classical_correction_delay_ms,
+                    defaults.classical_correction_delay_ms,
+                ),
+            ] {
+                if value != default {
+                    ignored.push(IgnoredParameter::new(
+                        "SimulationConfig",
+                        field,
+                        &format!(
+                            "a {}-node {} topology has no repeaters to swap at",
+                            self.num_nodes, self.topology
+                        ),
+                    ));
+                }
+            }
+        }
+        ignored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = std::env::temp_dir().join(format!("qcs_config_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let config = SimulationConfig {
+            topology: "star".to_string(),
+            num_nodes: 5,
+            delivery_cutoff_ms: Some(20.0),
+            ..SimulationConfig::default()
+        };
+
+        config.save(path).unwrap();
+        let written = std::fs::read_to_string(path).unwrap();
+        assert!(written.contains("\"format_version\": 2"));
+        assert_eq!(SimulationConfig::load(path).unwrap(), config);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_values_name_the_source() {
+        let error = SimulationConfig::from_json(r#"{"num_nodes": 1}"#, "cli").unwrap_err();
+        assert_eq!(error, "cli: num_nodes must be at least 2");
+    }
+
+    #[test]
+    fn test_swap_parameters_without_repeaters_are_rejected() {
+        let json = r#"{"topology": "mesh", "num_nodes": 3, "swap_success_probability": 0.5}"#;
+        let error = SimulationConfig::from_json(json, "cli").unwrap_err();
+        assert!(error.contains("SimulationConfig.swap_success_probability"));
+
+        let config =
+            SimulationConfig::from_json_with(json, "cli", StrictConfig::allowing_ignored())
+                .unwrap();
+        let ignored = config.ignored_parameters();
+        assert_eq!(ignored.len(), 1);
+        assert_eq!(ignored[0].field, "swap_success_probability");
+
+        let chain = r#"{"topology": "linear", "num_nodes": 3, "swap_success_probability": 0.5}"#;
+        assert!(SimulationConfig::from_json(chain, "cli").is_ok());
+    }
+}
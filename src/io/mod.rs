@@ -0,0 +1,10 @@
+//! Versioned JSON files (feature `serde`)
+
+pub mod config;
+pub mod versioning;
+
+pub use config::SimulationConfig;
+pub use versioning::{
+    check_version, from_versioned_json, load_versioned, migrate, save_versioned, to_versioned_json,
+    Format, FORMAT_VERSION_KEY,
+};
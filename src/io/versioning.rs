@@ -0,0 +1,226 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Key holding the format version in every versioned file
+pub const FORMAT_VERSION_KEY: &str = "format_version";
+
+/// A versioned on-disk format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `io::SimulationConfig`, also accepted by the C API
+    SimulationConfig,
+    /// `network::TopologySnapshot`
+    TopologySnapshot,
+}
+
+/// Rewrites a file's JSON object from one version to the next
+type Migration = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+impl Format {
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::SimulationConfig => "simulation config",
+            Format::TopologySnapshot => "topology snapshot",
+        }
+    }
+
+    /// Version written by this build
+    pub fn current_version(self) -> u32 {
+        match self {
+            Format::SimulationConfig => 2,
+            Format::TopologySnapshot => 1,
+        }
+    }
+
+    /// Oldest version that still loads
+    pub fn oldest_supported_version(self) -> u32 {
+        1
+    }
+
+    /// Version assumed for files written before versioning, if any
+    fn unversioned(self) -> Option<u32> {
+        match self {
+            Format::SimulationConfig => Some(1),
+            Format::TopologySnapshot => None,
+        }
+    }
+
+    /// Migration from `version` to `version + 1`
+    fn migration(self, version: u32) -> Option<Migration> {
+        match (self, version) {
+            (Format::SimulationConfig, 1) => Some(config_v1_to_v2),
+            _ => None,
+        }
+    }
+}
+
+/// Config v1 -> v2: `distance_km` was renamed `link_distance_km`, since it
+/// is the length of each channel rather than of the whole network
+fn config_v1_to_v2(object: &mut Map<String, Value>) -> Result<(), String> {
+    if let Some(distance) = object.remove("distance_km") {
+        if object.contains_key("link_distance_km") {
+            return Err("both distance_km and link_distance_km are set".to_string());
+        }
+        object.insert("link_distance_km".to_string(), distance);
+    }
+    Ok(())
+}
+
+/// Error unless `found` is a version of `format` this build can load
+/// `source` names the file (or other origin) in the message
+pub fn check_version(format: Format, found: u32, source: &str) -> Result<(), String> {
+    let oldest = format.oldest_supported_version();
+    let current = format.current_version();
+    if found < oldest || found > current {
+        return Err(format!(
+            "{}: {} format version {} is not supported (supported versions: {}-{})",
+            source,
+            format.name(),
+            found,
+            oldest,
+            current
+        ));
+    }
+    Ok(())
+}
+
+/// Bring a file's JSON object up to the current version of `format`
+/// The returned object has `format_version` set to the current version
+pub fn migrate(format: Format, value: Value, source: &str) -> Result<Value, String> {
+    let Value::Object(mut object) = value else {
+        return Err(format!(
+            "{}: {} must be a JSON object",
+            source,
+            format.name()
+        ));
+    };
+
+    let found = match object.get(FORMAT_VERSION_KEY) {
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or(format!(
+                "{}: {} is not a version number: {}",
+                source, FORMAT_VERSION_KEY, version
+            ))?,
+        None => format.unversioned().ok_or(format!(
+            "{}: {} has no {} field",
+            source,
+            format.name(),
+            FORMAT_VERSION_KEY
+        ))?,
+    };
+    check_version(format, found, source)?;
+
+    for version in found..format.current_version() {
+        let migration = format
+            .migration(version)
+            .expect("every supported version has a migration to the next");
+        migration(&mut object).map_err(|error| {
+            format!(
+                "{}: migrating {} from version {} to {}: {}",
+                source,
+                format.name(),
+                version,
+                version + 1,
+                error
+            )
+        })?;
+    }
+    object.insert(
+        FORMAT_VERSION_KEY.to_string(),
+        Value::from(format.current_version()),
+    );
+    Ok(Value::Object(object))
+}
+
+/// Serialize `value` as a JSON object tagged with the current format version
+pub fn to_versioned_json<T: Serialize>(format: Format, value: &T) -> Result<String, String> {
+    let Value::Object(mut object) = serde_json::to_value(value).map_err(|e| e.to_string())? else {
+        return Err(format!("{} must serialize to a JSON object", format.name()));
+    };
+    object.insert(
+        FORMAT_VERSION_KEY.to_string(),
+        Value::from(format.current_version()),
+    );
+    serde_json::to_string_pretty(&object).map_err(|e| e.to_string())
+}
+
+/// Parse, migrate and deserialize a versioned JSON object
+pub fn from_versioned_json<T: DeserializeOwned>(
+    format: Format,
+    json: &str,
+    source: &str,
+) -> Result<T, String> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|error| format!("{}: {}", source, error))?;
+    let Value::Object(mut object) = migrate(format, value, source)? else {
+        unreachable!("migrate returns an object");
+    };
+    object.remove(FORMAT_VERSION_KEY);
+    serde_json::from_value(Value::Object(object))
+        .map_err(|error| format!("{}: invalid {}: {}", source, format.name(), error))
+}
+
+/// Write `value` to `path` in the current version of `format`
+pub fn save_versioned<T: Serialize>(format: Format, value: &T, path: &str) -> Result<(), String> {
+    let json = to_versioned_json(format, value)?;
+    std::fs::write(path, json).map_err(|error| format!("{}: {}", path, error))
+}
+
+/// Read a `format` file from `path`, migrating older versions
+pub fn load_versioned<T: DeserializeOwned>(format: Format, path: &str) -> Result<T, String> {
+    let json = std::fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+    from_versioned_json(format, &json, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check_version_range() {
+        assert!(check_version(Format::SimulationConfig, 1, "a.json").is_ok());
+        assert!(check_version(Format::SimulationConfig, 2, "a.json").is_ok());
+        assert_eq!(
+            check_version(Format::SimulationConfig, 0, "a.json").unwrap_err(),
+            "a.json: simulation config format version 0 is not supported (supported versions: 1-2)"
+        );
+    }
+
+    #[test]
+    fn test_unversioned_config_is_migrated_from_v1() {
+        let migrated = migrate(
+            Format::SimulationConfig,
+            json!({"num_nodes": 3, "distance_km": 5.0}),
+            "old.json",
+        )
+        .unwrap();
+        assert_eq!(
+            migrated,
+            json!({"num_nodes": 3, "link_distance_km": 5.0, "format_version": 2})
+        );
+    }
+
+    #[test]
+    fn test_unversioned_snapshot_is_rejected() {
+        let error = migrate(Format::TopologySnapshot, json!({"nodes": []}), "s.json");
+        assert_eq!(
+            error.unwrap_err(),
+            "s.json: topology snapshot has no format_version field"
+        );
+    }
+
+    #[test]
+    fn test_conflicting_v1_fields() {
+        let error = migrate(
+            Format::SimulationConfig,
+            json!({"format_version": 1, "distance_km": 5.0, "link_distance_km": 4.0}),
+            "c.json",
+        )
+        .unwrap_err();
+        assert!(error.starts_with("c.json: migrating simulation config from version 1 to 2"));
+    }
+}
@@ -1,5 +1,41 @@
+//! Discrete-event simulator for quantum communication networks
+//!
+//! # Thread safety
+//!
+//! Plain data is `Send + Sync`: states (`Qubit`, `TwoQubitState`), channels,
+//! nodes, `NetworkTopology`, protocol configs and stats types. Share one
+//! topology read-only across threads with `network::TopologyView`, and `fork`
+//! it where a trial needs to mutate memory.
+//!
+//! Sampling functions without an RNG argument draw from the calling thread's
+//! `rand::rng()` (via `simulation::unseeded_rng`). For reproducible parallel
+//! trials use the `*_with_rng` variants with one seeded RNG per thread;
+//! `SimulationEngine::with_strict_randomness` flags unseeded draws in debug builds.
+//!
+//! # Features
+//!
+//! The default build is the core simulator. Opt-in: `serde` (derives on
+//! configs, stats, quantum states and node memories, and the `io` module), `parallel` (`experiment::run_replications_parallel`),
+//! `telemetry` (the `telemetry` module) and `capi` (the `ffi` module).
+//! Gated items keep their paths, so enabling a feature never moves anything.
+//!
+//! Not `Sync` by design: `SimulationEngine` (holds a `Send` callback) and the
+//! `testing` fakes (interior `Cell`). Both are `Send`, so create one per thread.
+
+pub mod analysis;
+pub mod applications;
+pub mod experiment;
+#[cfg(feature = "capi")]
+pub mod ffi;
+pub mod format;
+#[cfg(feature = "serde")]
+pub mod io;
+pub mod linalg;
 pub mod network;
 pub mod protocols;
 pub mod quantum;
 pub mod simulation;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+pub mod testing;
 // pub mod validation;
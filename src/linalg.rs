@@ -0,0 +1,9 @@
+//! Linear-algebra types used in the public API
+//!
+//! Re-exported so downstream crates use exactly the `ndarray` and
+//! `num_complex` versions this crate was built against.
+
+pub use ndarray;
+pub use ndarray::{Array1, Array2};
+pub use num_complex;
+pub use num_complex::Complex64;
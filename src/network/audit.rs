@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+
+/// Kind of memory mutation recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuditOp {
+    /// A pair was stored
+    Store,
+    /// A pair was taken out for use (consumed, swapped or delivered)
+    Remove,
+    /// A pair was discarded unused (failed swap, cleared memory, removed partner)
+    Evict,
+    /// A pair was dropped by a cutoff
+    Expire,
+    /// Slots were reserved for pairs still to come
+    Reserve(usize),
+    /// Reserved slots were given back
+    Release(usize),
+}
+
+impl AuditOp {
+    /// Whether this op takes a stored pair out of memory
+    pub fn removes_pair(&self) -> bool {
+        matches!(self, AuditOp::Remove | AuditOp::Evict | AuditOp::Expire)
+    }
+}
+
+/// One memory mutation at a node
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditEntry {
+    /// Position in the node's full mutation history
+    pub sequence: u64,
+    /// Simulated time of the mutation (ms)
+    pub time: f64,
+    pub op: AuditOp,
+    /// Affected pair (None for reservation changes)
+    pub pair_id: Option<u64>,
+    /// Partner of the affected pair
+    pub partner_node_id: Option<usize>,
+    /// Number of stored pairs right after the mutation
+    pub occupancy_after: usize,
+    /// Number of reserved slots right after the mutation
+    pub reserved_after: usize,
+}
+
+/// Mismatch found by `MemoryAuditLog::verify`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditInconsistency {
+    /// Occupancy implied by replaying the log
+    pub replayed_occupancy: i64,
+    /// Occupancy actually observed
+    pub actual_occupancy: usize,
+    /// Entries that removed a pair the replay did not consider stored, or
+    /// released more slots than were reserved
+    pub offending_entries: Vec<AuditEntry>,
+}
+
+/// Per-node record of memory mutations, optionally bounded (ring buffer)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryAuditLog {
+    entries: VecDeque<AuditEntry>,
+    capacity: Option<usize>,
+    next_sequence: u64,
+    /// Pairs held before the oldest retained entry, by pair id
+    baseline: Vec<u64>,
+    /// Slots reserved before the oldest retained entry
+    baseline_reserved: usize,
+}
+
+impl MemoryAuditLog {
+    /// Unbounded log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Log keeping only the most recent `capacity` entries
+    pub fn bounded(capacity: usize) -> Self {
+        MemoryAuditLog {
+            capacity: Some(capacity.max(1)),
+            ..Self::default()
+        }
+    }
+
+    /// Start auditing a node that already holds the pairs `pair_ids` and
+    /// has `reserved` slots reserved
+    pub fn with_baseline(mut self, pair_ids: Vec<u64>, reserved: usize) -> Self {
+        self.baseline = pair_ids;
+        self.baseline_reserved = reserved;
+        self
+    }
+
+    /// Append a mutation; `sequence` is filled in by the log
+    pub fn record(&mut self, entry: AuditEntry) {
+        self.entries.push_back(AuditEntry {
+            sequence: self.next_sequence,
+            ..entry
+        });
+        self.next_sequence += 1;
+
+        if let Some(capacity) = self.capacity {
+            while self.entries.len() > capacity {
+                // Fold the dropped entry into the baseline so replay stays exact
+                let dropped = self.entries.pop_front().unwrap();
+                Self::apply(&mut self.baseline, &mut self.baseline_reserved, &dropped);
+            }
+        }
+    }
+
+    /// Retained entries, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &AuditEntry> {
+        self.entries.iter()
+    }
+
+    /// Number of retained entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no entries are retained
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Replay the log and compare the implied occupancy with `actual_occupancy`
+    pub fn verify(&self, actual_occupancy: usize) -> Result<(), AuditInconsistency> {
+        let mut live = self.baseline.clone();
+        let mut reserved = self.baseline_reserved;
+        let mut replayed = live.len() as i64;
+        let mut offending = Vec::new();
+
+        for entry in &self.entries {
+            match entry.op {
+                AuditOp::Store => replayed += 1,
+                op if op.removes_pair() => replayed -= 1,
+                _ => {}
+            }
+            if !Self::apply(&mut live, &mut reserved, entry) {
+                offending.push(*entry);
+            }
+        }
+
+        if offending.is_empty() && replayed == actual_occupancy as i64 {
+            Ok(())
+        } else {
+            Err(AuditInconsistency {
+                replayed_occupancy: replayed,
+                actual_occupancy,
+                offending_entries: offending,
+            })
+        }
+    }
+
+    /// Apply an entry to the live pairs and reserved count; false if it
+    /// removes an unknown pair or releases more than is reserved
+    fn apply(live: &mut Vec<u64>, reserved: &mut usize, entry: &AuditEntry) -> bool {
+        match (entry.op, entry.pair_id) {
+            (AuditOp::Store, Some(pair_id)) => {
+                live.push(pair_id);
+                true
+            }
+            (op, Some(pair_id)) if op.removes_pair() => {
+                match live.iter().position(|&id| id == pair_id) {
+                    Some(index) => {
+                        live.remove(index);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            (AuditOp::Reserve(count), _) => {
+                *reserved += count;
+                true
+            }
+            (AuditOp::Release(count), _) => match reserved.checked_sub(count) {
+                Some(left) => {
+                    *reserved = left;
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+}
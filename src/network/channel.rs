@@ -1,4 +1,82 @@
+use super::retry::RetryPolicy;
+use crate::simulation::{unseeded_rng, RandomnessDecl, RandomnessSource};
+use rand::Rng;
+
+/// Speed of light in vacuum (km/ms)
+pub const SPEED_OF_LIGHT_KM_PER_MS: f64 = 299.792458;
+
+/// Refractive index of standard telecom fiber
+pub const FIBER_REFRACTIVE_INDEX: f64 = 1.47;
+
+/// Physical medium of a channel and its distance-dependent loss
+///
+/// Every model also applies `QuantumChannel::attenuation_db_per_km` over the
+/// channel length: fibre attenuation, or atmospheric extinction in free space
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChannelModel {
+    /// Guided optical fibre: exponential loss only
+    #[default]
+    Fiber,
+    /// Ground-to-ground free-space optical link
+    FreeSpace {
+        /// Full-angle beam divergence (mrad)
+        beam_divergence_mrad: f64,
+        /// Receiver telescope aperture diameter (m)
+        receiver_aperture_m: f64,
+        /// Fixed pointing and tracking loss (dB)
+        pointing_loss_db: f64,
+    },
+    /// Satellite downlink over a slant range of `distance_km`
+    Satellite {
+        /// Elevation of the satellite above the horizon (degrees)
+        elevation_deg: f64,
+        /// Atmospheric loss at zenith (dB), scaled by the air mass
+        atmospheric_db: f64,
+    },
+}
+
+impl ChannelModel {
+    /// Transmittance over `distance_km` beyond the per-km attenuation
+    pub fn transmittance(&self, distance_km: f64) -> f64 {
+        match *self {
+            ChannelModel::Fiber => 1.0,
+            ChannelModel::FreeSpace {
+                beam_divergence_mrad,
+                receiver_aperture_m,
+                pointing_loss_db,
+            } => {
+                // Diffraction-limited spot: diameter (m) = L (km) × θ (mrad)
+                let beam_diameter_m = distance_km * beam_divergence_mrad;
+                let geometric = if beam_diameter_m <= receiver_aperture_m {
+                    1.0
+                } else {
+                    (receiver_aperture_m / beam_diameter_m).powi(2)
+                };
+                geometric * db_to_transmittance(pointing_loss_db)
+            }
+            ChannelModel::Satellite {
+                elevation_deg,
+                atmospheric_db,
+            } => {
+                if elevation_deg <= 0.0 {
+                    // Below the horizon
+                    return 0.0;
+                }
+                let air_mass = 1.0 / elevation_deg.min(90.0).to_radians().sin();
+                db_to_transmittance(atmospheric_db * air_mass)
+            }
+        }
+    }
+}
+
+fn db_to_transmittance(loss_db: f64) -> f64 {
+    10.0_f64.powf(-loss_db / 10.0)
+}
+
 /// A quantum channel connecting two nodes
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuantumChannel {
     /// ID of the first node
     pub node_a: usize,
@@ -8,25 +86,235 @@ pub struct QuantumChannel {
     pub distance_km: f64,
     /// Attenuation coefficient (dB/km) - typical: 0.2 for telecom fiber
     pub attenuation_db_per_km: f64,
+    /// Depolarization probability per km for a transmitted photon (0.0 = none)
+    pub depolarization_per_km: f64,
+    /// Backoff behaviour after repeated generation failures on this link
+    pub retry_policy: RetryPolicy,
+    /// Refractive index of the medium; light travels at c/n
+    #[cfg_attr(feature = "serde", serde(default = "fiber_refractive_index"))]
+    pub refractive_index: f64,
+    /// False while the link is down (e.g. a fibre cut): nothing gets through
+    #[cfg_attr(feature = "serde", serde(default = "active"))]
+    pub is_active: bool,
+    /// (time_ms, dB/km) steps overriding `attenuation_db_per_km` from each
+    /// time on, sorted by time; empty for a static channel
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attenuation_schedule: Vec<(f64, f64)>,
+    /// Loss model of the medium
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub model: ChannelModel,
+    /// Fraction of photons coupled from the memory into the channel
+    #[cfg_attr(feature = "serde", serde(default = "perfect_coupling"))]
+    pub coupling_efficiency: f64,
+    /// Fixed loss at splices and connectors (dB)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub insertion_loss_db: f64,
+    /// Temporal/spectral modes sent per attempt, each heralded independently
+    #[cfg_attr(feature = "serde", serde(default = "single_mode"))]
+    pub num_modes: usize,
+}
+
+#[cfg(feature = "serde")]
+fn fiber_refractive_index() -> f64 {
+    FIBER_REFRACTIVE_INDEX
+}
+
+#[cfg(feature = "serde")]
+fn active() -> bool {
+    true
+}
+
+#[cfg(feature = "serde")]
+fn perfect_coupling() -> f64 {
+    1.0
+}
+
+#[cfg(feature = "serde")]
+fn single_mode() -> usize {
+    1
 }
 
 impl QuantumChannel {
-    /// Create a new quantum channel
+    /// Create a new quantum channel (fibre; see `new_fiber`)
     pub fn new(node_a: usize, node_b: usize, distance_km: f64, attenuation_db_per_km: f64) -> Self {
+        Self::new_fiber(node_a, node_b, distance_km, attenuation_db_per_km)
+    }
+
+    /// Fibre channel with exponential loss
+    pub fn new_fiber(
+        node_a: usize,
+        node_b: usize,
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Self {
         QuantumChannel {
             node_a,
             node_b,
             distance_km,
             attenuation_db_per_km,
+            depolarization_per_km: 0.0,
+            retry_policy: RetryPolicy::none(),
+            refractive_index: FIBER_REFRACTIVE_INDEX,
+            is_active: true,
+            attenuation_schedule: Vec::new(),
+            model: ChannelModel::Fiber,
+            coupling_efficiency: 1.0,
+            insertion_loss_db: 0.0,
+            num_modes: 1,
+        }
+    }
+
+    /// Builder for a fibre channel between `node_a` and `node_b`
+    pub fn builder(node_a: usize, node_b: usize) -> QuantumChannelBuilder {
+        QuantumChannelBuilder {
+            channel: Self::new_fiber(node_a, node_b, 0.0, 0.2),
+        }
+    }
+
+    /// Free-space optical channel with diffraction-limited geometric loss
+    /// and no atmospheric extinction (see `attenuation_db_per_km`)
+    pub fn new_free_space(
+        node_a: usize,
+        node_b: usize,
+        distance_km: f64,
+        beam_divergence_mrad: f64,
+        receiver_aperture_m: f64,
+        pointing_loss_db: f64,
+    ) -> Self {
+        QuantumChannel {
+            refractive_index: 1.0,
+            model: ChannelModel::FreeSpace {
+                beam_divergence_mrad,
+                receiver_aperture_m,
+                pointing_loss_db,
+            },
+            ..Self::new_fiber(node_a, node_b, distance_km, 0.0)
         }
     }
 
+    /// Satellite downlink over a slant range of `distance_km`
+    pub fn new_satellite(
+        node_a: usize,
+        node_b: usize,
+        distance_km: f64,
+        elevation_deg: f64,
+        atmospheric_db: f64,
+    ) -> Self {
+        QuantumChannel {
+            refractive_index: 1.0,
+            model: ChannelModel::Satellite {
+                elevation_deg,
+                atmospheric_db,
+            },
+            ..Self::new_fiber(node_a, node_b, distance_km, 0.0)
+        }
+    }
+
+    /// Set the per-km depolarization probability of transmitted photons
+    /// Errors unless the rate lies in [0, 1]
+    pub fn with_depolarization(mut self, depolarization_per_km: f64) -> Result<Self, String> {
+        if !(0.0..=1.0).contains(&depolarization_per_km) {
+            return Err(format!(
+                "Depolarization per km must be in [0, 1], got {}",
+                depolarization_per_km
+            ));
+        }
+        self.depolarization_per_km = depolarization_per_km;
+        Ok(self)
+    }
+
+    /// Probability that a photon is depolarized over the full channel length
+    /// p = 1 - (1 - r)^L for a per-km rate r
+    pub fn depolarization_probability(&self) -> f64 {
+        1.0 - (1.0 - self.depolarization_per_km).powf(self.distance_km)
+    }
+
+    /// Set the retry/backoff policy for this link
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the fraction of photons coupled into the channel
+    pub fn with_coupling_efficiency(mut self, coupling_efficiency: f64) -> Self {
+        self.coupling_efficiency = coupling_efficiency;
+        self
+    }
+
+    /// Set the fixed splice and connector loss (dB)
+    pub fn with_insertion_loss_db(mut self, insertion_loss_db: f64) -> Self {
+        self.insertion_loss_db = insertion_loss_db;
+        self
+    }
+
+    /// Set the number of modes multiplexed into each attempt
+    pub fn with_num_modes(mut self, num_modes: usize) -> Self {
+        self.num_modes = num_modes;
+        self
+    }
+
+    /// Set the refractive index of the medium (1.0 for free space)
+    pub fn with_refractive_index(mut self, refractive_index: f64) -> Self {
+        self.refractive_index = refractive_index;
+        self
+    }
+
+    /// Set a time-varying attenuation as (time_ms, dB/km) steps
+    /// Before the first step the channel uses `attenuation_db_per_km`;
+    /// an infinite attenuation blocks the link for that step
+    pub fn with_attenuation_schedule(mut self, mut schedule: Vec<(f64, f64)>) -> Self {
+        schedule.sort_by(|a, b| a.0.total_cmp(&b.0));
+        self.attenuation_schedule = schedule;
+        self
+    }
+
+    /// Attenuation (dB/km) in effect at `time_ms`
+    pub fn attenuation_at(&self, time_ms: f64) -> f64 {
+        self.attenuation_schedule
+            .iter()
+            .take_while(|&&(start_ms, _)| start_ms <= time_ms)
+            .last()
+            .map_or(self.attenuation_db_per_km, |&(_, db_per_km)| db_per_km)
+    }
+
+    /// Time for light to cross the full channel (ms)
+    pub fn propagation_delay_ms(&self) -> f64 {
+        self.distance_km * self.refractive_index / SPEED_OF_LIGHT_KM_PER_MS
+    }
+
+    /// Minimum time between attempts (ms): photons travel to a midpoint
+    /// station and the herald travels back, together one channel length
+    ///
+    /// This is the one definition of attempt duration; generation protocols
+    /// and every slotted runner (`BankingLinkLayer`, `RequestService`,
+    /// `run_chain`, `run_star`) space attempts at least this far apart.
+    pub fn attempt_duration_ms(&self) -> f64 {
+        self.propagation_delay_ms()
+    }
+
     /// Calculate success probability using exponential loss model
-    /// p = e^(-α*L) where α is attenuation and L is distance
+    /// p = η·e^(-α*L) where α is attenuation, L is distance and η combines
+    /// coupling, insertion loss and the model's own transmittance; 0 when
+    /// inactive
     pub fn success_probability(&self) -> f64 {
+        self.transmittance(self.attenuation_db_per_km)
+    }
+
+    /// `success_probability` with the attenuation scheduled for `time_ms`
+    pub fn success_probability_at(&self, time_ms: f64) -> f64 {
+        self.transmittance(self.attenuation_at(time_ms))
+    }
+
+    fn transmittance(&self, attenuation_db_per_km: f64) -> f64 {
+        if !self.is_active || attenuation_db_per_km == f64::INFINITY {
+            return 0.0;
+        }
         // Convert dB/km to Neper/km: α = (ln(10)/10) * attenuation_dB
-        let alpha = (10.0_f64.ln() / 10.0) * self.attenuation_db_per_km;
-        (-alpha * self.distance_km).exp()
+        let alpha = (10.0_f64.ln() / 10.0) * attenuation_db_per_km;
+        self.coupling_efficiency
+            * db_to_transmittance(self.insertion_loss_db)
+            * self.model.transmittance(self.distance_km)
+            * (-alpha * self.distance_km).exp()
     }
 
     /// Check if this channel connects to a specific node
@@ -48,10 +336,164 @@ impl QuantumChannel {
     /// Attempt entanglement generation (returns true if successful based on probability)
     /// This is a simple probabilistic model - will be enhanced later
     pub fn attempt_generation(&self) -> bool {
-        use rand::Rng;
-        let mut rng = rand::rng();
+        self.attempt_generation_with_rng(&mut unseeded_rng("QuantumChannel"))
+    }
+
+    /// Attempt entanglement generation with the caller's RNG
+    /// Lets parallel trials sharing a channel each use their own seeded RNG
+    pub fn attempt_generation_with_rng(&self, rng: &mut impl Rng) -> bool {
         rng.random::<f64>() < self.success_probability()
     }
+
+    /// Attempt entanglement generation at simulation time `time_ms`
+    pub fn attempt_generation_at(&self, time_ms: f64) -> bool {
+        self.attempt_generation_at_with_rng(time_ms, &mut unseeded_rng("QuantumChannel"))
+    }
+
+    /// `attempt_generation_at` drawing from the caller's RNG
+    pub fn attempt_generation_at_with_rng(&self, time_ms: f64, rng: &mut impl Rng) -> bool {
+        rng.random::<f64>() < self.success_probability_at(time_ms)
+    }
+
+    /// Send all `num_modes` modes in one attempt
+    /// Returns how many of them got through
+    pub fn attempt_generation_multimode(&self) -> usize {
+        self.attempt_generation_multimode_with_rng(&mut unseeded_rng("QuantumChannel"))
+    }
+
+    /// `attempt_generation_multimode` drawing from the caller's RNG
+    /// One draw per mode, so the count is Binomial(num_modes, p)
+    pub fn attempt_generation_multimode_with_rng(&self, rng: &mut impl Rng) -> usize {
+        let p = self.success_probability();
+        (0..self.num_modes)
+            .filter(|_| rng.random::<f64>() < p)
+            .count()
+    }
+}
+
+/// Step-by-step construction of a fibre `QuantumChannel`
+///
+/// Starts from a 0 km link at 0.2 dB/km with perfect coupling
+#[derive(Debug, Clone)]
+pub struct QuantumChannelBuilder {
+    channel: QuantumChannel,
+}
+
+impl QuantumChannelBuilder {
+    /// Physical distance in kilometers
+    pub fn distance_km(mut self, distance_km: f64) -> Self {
+        self.channel.distance_km = distance_km;
+        self
+    }
+
+    /// Attenuation coefficient (dB/km)
+    pub fn attenuation(mut self, attenuation_db_per_km: f64) -> Self {
+        self.channel.attenuation_db_per_km = attenuation_db_per_km;
+        self
+    }
+
+    /// Fraction of photons coupled into the channel
+    pub fn coupling_efficiency(mut self, coupling_efficiency: f64) -> Self {
+        self.channel.coupling_efficiency = coupling_efficiency;
+        self
+    }
+
+    /// Fixed splice and connector loss (dB)
+    pub fn insertion_loss_db(mut self, insertion_loss_db: f64) -> Self {
+        self.channel.insertion_loss_db = insertion_loss_db;
+        self
+    }
+
+    /// Modes multiplexed into each attempt
+    pub fn num_modes(mut self, num_modes: usize) -> Self {
+        self.channel.num_modes = num_modes;
+        self
+    }
+
+    /// Finish the channel; errors on unphysical parameters
+    pub fn build(self) -> Result<QuantumChannel, String> {
+        let channel = self.channel;
+        if !(channel.distance_km.is_finite() && channel.distance_km >= 0.0) {
+            return Err(format!(
+                "Distance must be finite and non-negative, got {}",
+                channel.distance_km
+            ));
+        }
+        if !(channel.attenuation_db_per_km.is_finite() && channel.attenuation_db_per_km >= 0.0) {
+            return Err(format!(
+                "Attenuation must be finite and non-negative, got {}",
+                channel.attenuation_db_per_km
+            ));
+        }
+        if !(0.0..=1.0).contains(&channel.coupling_efficiency) {
+            return Err(format!(
+                "Coupling efficiency must be in [0, 1], got {}",
+                channel.coupling_efficiency
+            ));
+        }
+        if !(channel.insertion_loss_db.is_finite() && channel.insertion_loss_db >= 0.0) {
+            return Err(format!(
+                "Insertion loss must be finite and non-negative, got {}",
+                channel.insertion_loss_db
+            ));
+        }
+        if channel.num_modes == 0 {
+            return Err("A channel needs at least one mode".to_string());
+        }
+        Ok(channel)
+    }
+}
+
+/// Anything that can carry photons for entanglement generation attempts
+///
+/// Implemented by `QuantumChannel` and by scripted test doubles
+/// (see `testing::FakeChannel`)
+pub trait LinkChannel {
+    /// Probability that a single attempt succeeds
+    fn success_probability(&self) -> f64;
+
+    /// Sample one generation attempt
+    fn attempt_generation(&self) -> bool;
+
+    /// Sample one generation attempt at simulation time `time_ms`
+    /// Time-independent channels ignore the time
+    fn attempt_generation_at(&self, _time_ms: f64) -> bool {
+        self.attempt_generation()
+    }
+
+    /// Probability that a transmitted photon is depolarized
+    fn depolarization_probability(&self) -> f64 {
+        0.0
+    }
+}
+
+impl RandomnessSource for QuantumChannel {
+    fn randomness(&self) -> Vec<RandomnessDecl> {
+        let component = format!("QuantumChannel[{}-{}]", self.node_a, self.node_b);
+        vec![RandomnessDecl::bernoulli(
+            &component,
+            "transmission",
+            self.success_probability(),
+        )]
+    }
+}
+
+impl LinkChannel for QuantumChannel {
+    fn success_probability(&self) -> f64 {
+        QuantumChannel::success_probability(self)
+    }
+
+    fn attempt_generation(&self) -> bool {
+        QuantumChannel::attempt_generation(self)
+    }
+
+    fn attempt_generation_at(&self, time_ms: f64) -> bool {
+        QuantumChannel::attempt_generation_at(self, time_ms)
+    }
+
+    fn depolarization_probability(&self) -> f64 {
+        QuantumChannel::depolarization_probability(self)
+    }
 }
 
 #[cfg(test)]
@@ -86,6 +528,20 @@ mod tests {
         assert!((prob - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_depolarization_probability() {
+        let channel = QuantumChannel::new(0, 1, 50.0, 0.2);
+        assert_eq!(channel.depolarization_probability(), 0.0);
+
+        let channel = channel.with_depolarization(0.01).unwrap();
+        assert!((channel.depolarization_probability() - (1.0 - 0.99_f64.powi(50))).abs() < 1e-12);
+
+        for rate in [-0.1, 1.5, f64::NAN] {
+            assert!(channel.clone().with_depolarization(rate).is_err());
+        }
+        assert!(channel.with_depolarization(1.0).is_ok());
+    }
+
     #[test]
     fn test_connects_to() {
         let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
@@ -101,4 +557,140 @@ mod tests {
         assert_eq!(channel.get_partner(1), Some(0));
         assert_eq!(channel.get_partner(2), None);
     }
+
+    #[test]
+    fn test_propagation_delay() {
+        // 50 km of fiber at c/1.47 ≈ 204 km/ms
+        let channel = QuantumChannel::new(0, 1, 50.0, 0.2);
+        assert!((channel.propagation_delay_ms() - 50.0 * 1.47 / 299.792458).abs() < 1e-12);
+        assert_eq!(
+            channel.attempt_duration_ms(),
+            channel.propagation_delay_ms()
+        );
+
+        let free_space = channel.with_refractive_index(1.0);
+        assert!(
+            (free_space.propagation_delay_ms() - 50.0 / SPEED_OF_LIGHT_KM_PER_MS).abs() < 1e-12
+        );
+        assert_eq!(
+            QuantumChannel::new(0, 1, 0.0, 0.2).attempt_duration_ms(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_attenuation_schedule_steps() {
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2)
+            .with_attenuation_schedule(vec![(20.0, 0.5), (10.0, 0.0)]);
+        assert_eq!(channel.attenuation_at(5.0), 0.2);
+        assert_eq!(channel.attenuation_at(10.0), 0.0);
+        assert_eq!(channel.attenuation_at(19.9), 0.0);
+        assert_eq!(channel.attenuation_at(30.0), 0.5);
+        assert_eq!(
+            channel.success_probability_at(5.0),
+            channel.success_probability()
+        );
+        assert_eq!(channel.success_probability_at(15.0), 1.0);
+        assert!((channel.success_probability_at(30.0) - 10.0_f64.powf(-0.5)).abs() < 1e-12);
+
+        let cut = channel.with_attenuation_schedule(vec![(50.0, f64::INFINITY)]);
+        assert_eq!(cut.success_probability_at(50.0), 0.0);
+        assert!(!cut.attempt_generation_at(60.0));
+    }
+
+    #[test]
+    fn test_inactive_channel_never_transmits() {
+        let mut channel = QuantumChannel::new(0, 1, 0.0, 0.0);
+        assert!(channel.attempt_generation_at(0.0));
+        channel.is_active = false;
+        assert_eq!(channel.success_probability(), 0.0);
+        assert_eq!(channel.success_probability_at(0.0), 0.0);
+        assert!(!channel.attempt_generation());
+    }
+
+    #[test]
+    fn test_fiber_is_the_default_model() {
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+        assert_eq!(channel.model, ChannelModel::Fiber);
+        let fiber = QuantumChannel::new_fiber(0, 1, 10.0, 0.2);
+        assert_eq!(fiber.success_probability(), channel.success_probability());
+        assert!((channel.success_probability() - 10.0_f64.powf(-0.2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_free_space_loss_is_geometric() {
+        // 0.1 mrad beam into a 0.5 m aperture: the spot outgrows it at 5 km
+        let near = QuantumChannel::new_free_space(0, 1, 2.0, 0.1, 0.5, 3.0);
+        assert!((near.success_probability() - 10.0_f64.powf(-0.3)).abs() < 1e-12);
+
+        let at_10 = QuantumChannel::new_free_space(0, 1, 10.0, 0.1, 0.5, 0.0);
+        let at_20 = QuantumChannel::new_free_space(0, 1, 20.0, 0.1, 0.5, 0.0);
+        let at_40 = QuantumChannel::new_free_space(0, 1, 40.0, 0.1, 0.5, 0.0);
+        assert!((at_10.success_probability() - 0.25).abs() < 1e-12);
+        // Doubling the distance quarters the transmittance, each time
+        let first = at_10.success_probability() / at_20.success_probability();
+        let second = at_20.success_probability() / at_40.success_probability();
+        assert!((first - 4.0).abs() < 1e-12 && (second - 4.0).abs() < 1e-12);
+        assert_eq!(at_10.refractive_index, 1.0);
+    }
+
+    #[test]
+    fn test_satellite_loss_follows_air_mass() {
+        let zenith = QuantumChannel::new_satellite(0, 1, 500.0, 90.0, 3.0);
+        assert!((zenith.success_probability() - 10.0_f64.powf(-0.3)).abs() < 1e-12);
+        // Twice the air mass at 30° elevation
+        let low = QuantumChannel::new_satellite(0, 1, 1000.0, 30.0, 3.0);
+        assert!((low.success_probability() - 10.0_f64.powf(-0.6)).abs() < 1e-12);
+        let set = QuantumChannel::new_satellite(0, 1, 2500.0, -5.0, 3.0);
+        assert_eq!(set.success_probability(), 0.0);
+    }
+
+    #[test]
+    fn test_insertion_loss_and_coupling() {
+        let channel = QuantumChannel::builder(0, 1)
+            .insertion_loss_db(3.0)
+            .build()
+            .unwrap();
+        assert!((channel.success_probability() - 0.5).abs() < 0.002);
+
+        let channel = QuantumChannel::builder(0, 1)
+            .distance_km(10.0)
+            .attenuation(0.2)
+            .coupling_efficiency(0.8)
+            .insertion_loss_db(1.5)
+            .build()
+            .unwrap();
+        let fiber = QuantumChannel::new(0, 1, 10.0, 0.2);
+        let expected = 0.8 * 10.0_f64.powf(-0.15) * fiber.success_probability();
+        assert!((channel.success_probability() - expected).abs() < 1e-12);
+
+        assert!(QuantumChannel::builder(0, 1)
+            .coupling_efficiency(1.2)
+            .build()
+            .is_err());
+        assert!(QuantumChannel::builder(0, 1)
+            .insertion_loss_db(-1.0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_multimode_attempt_counts_modes() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let lossless = QuantumChannel::new(0, 1, 0.0, 0.2).with_num_modes(100);
+        assert_eq!(
+            lossless.attempt_generation_multimode_with_rng(&mut rng),
+            100
+        );
+
+        // 50 km at 0.2 dB/km: p = 0.1 per mode
+        let channel = QuantumChannel::new(0, 1, 50.0, 0.2).with_num_modes(1000);
+        let successes: usize = (0..100)
+            .map(|_| channel.attempt_generation_multimode_with_rng(&mut rng))
+            .sum();
+        let mean = successes as f64 / 100.0;
+        assert!((mean - 100.0).abs() < 3.0, "mean {}", mean);
+        assert!(QuantumChannel::builder(0, 1).num_modes(0).build().is_err());
+    }
 }
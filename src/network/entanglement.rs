@@ -0,0 +1,200 @@
+use super::topology::NetworkTopology;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Pairs currently shared by two nodes
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntanglementEdge {
+    /// Lower node id
+    pub node_a: usize,
+    pub node_b: usize,
+    /// Pairs held between the two nodes
+    pub count: usize,
+    /// Mean fidelity of those pairs at the graph time
+    pub mean_fidelity: f64,
+}
+
+/// Who shares entanglement with whom at one instant
+///
+/// Built by `NetworkTopology::entanglement_graph`. Distances count hops
+/// over entanglement edges, not over physical channels.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntanglementGraph {
+    pub time: f64,
+    /// Every node of the topology, entangled or not, by id
+    pub nodes: Vec<usize>,
+    /// Edges sorted by (node_a, node_b)
+    pub edges: Vec<EntanglementEdge>,
+}
+
+impl EntanglementGraph {
+    /// Graph of the pairs stored in `topology` at `current_time`
+    pub fn from_topology(topology: &NetworkTopology, current_time: f64) -> Self {
+        // Each pair is stored at both ends; count it once from the lower id
+        let mut fidelities: BTreeMap<(usize, usize), Vec<f64>> = BTreeMap::new();
+        for pair in topology.iter_all_pairs(current_time) {
+            if pair.node_id < pair.partner_node_id {
+                fidelities
+                    .entry((pair.node_id, pair.partner_node_id))
+                    .or_default()
+                    .push(pair.fidelity);
+            }
+        }
+        let mut nodes: Vec<usize> = topology.nodes().iter().map(|node| node.id).collect();
+        nodes.sort_unstable();
+        EntanglementGraph {
+            time: current_time,
+            nodes,
+            edges: fidelities
+                .into_iter()
+                .map(|((node_a, node_b), fidelities)| EntanglementEdge {
+                    node_a,
+                    node_b,
+                    count: fidelities.len(),
+                    mean_fidelity: fidelities.iter().sum::<f64>() / fidelities.len() as f64,
+                })
+                .collect(),
+        }
+    }
+
+    /// Edge between `node_a` and `node_b`, in either order
+    pub fn edge(&self, node_a: usize, node_b: usize) -> Option<&EntanglementEdge> {
+        let key = (node_a.min(node_b), node_a.max(node_b));
+        self.edges
+            .binary_search_by_key(&key, |edge| (edge.node_a, edge.node_b))
+            .ok()
+            .map(|index| &self.edges[index])
+    }
+
+    /// Whether the two nodes share at least one pair
+    pub fn are_entangled(&self, node_a: usize, node_b: usize) -> bool {
+        self.edge(node_a, node_b).is_some()
+    }
+
+    /// Nodes sharing a pair with `node_id`, ascending
+    pub fn neighbors(&self, node_id: usize) -> Vec<usize> {
+        let neighbors: BTreeSet<usize> = self
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                if edge.node_a == node_id {
+                    Some(edge.node_b)
+                } else if edge.node_b == node_id {
+                    Some(edge.node_a)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        neighbors.into_iter().collect()
+    }
+
+    /// Fewest entanglement edges between the two nodes
+    /// Some(0) for a node with itself; None if unreachable
+    pub fn entanglement_distance(&self, from: usize, to: usize) -> Option<usize> {
+        if from == to {
+            return Some(0);
+        }
+        let mut visited = BTreeSet::from([from]);
+        let mut queue = VecDeque::from([(from, 0)]);
+        while let Some((node, hops)) = queue.pop_front() {
+            for neighbor in self.neighbors(node) {
+                if neighbor == to {
+                    return Some(hops + 1);
+                }
+                if visited.insert(neighbor) {
+                    queue.push_back((neighbor, hops + 1));
+                }
+            }
+        }
+        None
+    }
+
+    /// Graphviz DOT of `topology` with this graph overlaid
+    ///
+    /// Physical channels are solid and labelled with their channel id;
+    /// entanglement edges are dashed and labelled "count @ mean fidelity"
+    pub fn to_dot(&self, topology: &NetworkTopology) -> String {
+        let mut dot = String::from("graph network {\n");
+        for node in &self.nodes {
+            dot.push_str(&format!("  {};\n", node));
+        }
+        for (channel_id, channel) in topology.iter_channels() {
+            dot.push_str(&format!(
+                "  {} -- {} [label=\"{}\"];\n",
+                channel.node_a, channel.node_b, channel_id
+            ));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "  {} -- {} [style=dashed, color=blue, label=\"{} @ {:.3}\"];\n",
+                edge.node_a, edge.node_b, edge.count, edge.mean_fidelity
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::attempt_entanglement_generation;
+    use crate::protocols::swapping::perform_entanglement_swap;
+
+    #[test]
+    fn test_swap_moves_edge_to_end_nodes() {
+        // Lossless links always generate
+        let mut topology = NetworkTopology::new_linear(3, 2, 0.0, 0.2);
+        for (a, b) in [(0, 1), (1, 2)] {
+            let channel = topology.find_channel(a, b).unwrap().1.clone();
+            let (node_a, node_b) = topology.get_node_pair_mut(a, b).unwrap();
+            assert_eq!(
+                attempt_entanglement_generation(node_a, node_b, &channel, 0.0, 100.0),
+                Ok(true)
+            );
+        }
+        let before = topology.entanglement_graph(0.0);
+        assert!(before.are_entangled(1, 0) && before.are_entangled(1, 2));
+        assert_eq!(before.entanglement_distance(0, 2), Some(2));
+
+        perform_entanglement_swap(&mut topology, 1, 0, 2, 1.0).unwrap();
+        let graph = topology.entanglement_graph(1.0);
+        assert_eq!(graph.edges.len(), 1);
+        assert!(graph.are_entangled(0, 2));
+        assert!(!graph.are_entangled(0, 1));
+        assert!(!graph.are_entangled(1, 2));
+        assert_eq!(graph.edge(2, 0).unwrap().count, 1);
+        assert_eq!(graph.entanglement_distance(0, 2), Some(1));
+        assert_eq!(graph.entanglement_distance(0, 1), None);
+        assert_eq!(graph.neighbors(1), Vec::<usize>::new());
+
+        let dot = graph.to_dot(&topology);
+        assert!(dot.contains("  0 -- 1 [label="));
+        assert!(dot.contains("  0 -- 2 [style=dashed, color=blue, label=\"1 @ "));
+        assert!(!dot.contains("  1 -- 2 [style=dashed"));
+    }
+
+    #[test]
+    fn test_edge_counts_and_mean_fidelity() {
+        let mut topology = NetworkTopology::new_linear(2, 3, 0.0, 0.2);
+        let channel = topology.find_channel(0, 1).unwrap().1.clone();
+        for time in [0.0, 50.0] {
+            let (node_a, node_b) = topology.get_node_pair_mut(0, 1).unwrap();
+            attempt_entanglement_generation(node_a, node_b, &channel, time, 100.0).unwrap();
+        }
+        let graph = topology.entanglement_graph(100.0);
+        let edge = graph.edge(0, 1).unwrap();
+        assert_eq!(edge.count, 2);
+        let expected: f64 = topology
+            .get_node(0)
+            .unwrap()
+            .iter_pairs(100.0)
+            .map(|pair| pair.fidelity)
+            .sum::<f64>()
+            / 2.0;
+        assert!((edge.mean_fidelity - expected).abs() < 1e-12);
+        assert_eq!(graph.nodes, vec![0, 1]);
+    }
+}
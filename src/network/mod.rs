@@ -1,9 +1,35 @@
+pub mod audit;
 pub mod channel;
+pub mod entanglement;
 pub mod node;
 pub mod operations;
+pub mod pairs;
+pub mod retry;
+pub mod slots;
+pub mod snapshot;
 pub mod topology;
+pub mod view;
 
-pub use channel::QuantumChannel;
-pub use node::{QuantumNode, StoredPair};
-pub use operations::{attempt_entanglement_generation, GenerationStats};
-pub use topology::{NetworkTopology, TopologyType};
+pub use audit::{AuditEntry, AuditInconsistency, AuditOp, MemoryAuditLog};
+pub use channel::{
+    ChannelModel, LinkChannel, QuantumChannel, QuantumChannelBuilder, FIBER_REFRACTIVE_INDEX,
+    SPEED_OF_LIGHT_KM_PER_MS,
+};
+pub use entanglement::{EntanglementEdge, EntanglementGraph};
+pub use node::{next_pair_id, Cutoff, NodeHardware, NodeRole, PairHandle, QuantumNode, StoredPair};
+pub use operations::{
+    attempt_entanglement_generation, attempt_entanglement_generation_with_hints,
+    attempt_entanglement_generation_with_rng, GenerationStats,
+};
+pub use pairs::{FidelitySummary, PairView};
+pub use retry::{Backoff, LinkRetryState, RetryPolicy};
+pub use slots::{
+    expected_wait_from_path_position, BestCoherenceFirst, FirstFree, MemorySlot,
+    SlotAssignmentStrategy, SlotStats, WorstFitForShortWaits,
+};
+pub use snapshot::{ChannelSnapshot, NodeSnapshot, TopologyChange, TopologyDiff, TopologySnapshot};
+pub use topology::{
+    ChannelId, ChannelSelector, NetworkTopology, PathHop, PathMetric, RoutePath, TopologyError,
+    TopologyType,
+};
+pub use view::TopologyView;
@@ -1,9 +1,38 @@
-use crate::quantum::noise::fidelity_after_decoherence;
-use crate::quantum::TwoQubitState;
+use super::audit::{AuditEntry, AuditInconsistency, AuditOp, MemoryAuditLog};
+use super::pairs::PairView;
+use super::slots::{FirstFree, MemorySlot, SlotAssignmentStrategy, SlotStats};
+use crate::quantum::noise::{
+    apply_pauli_channel, apply_pauli_twirl, fidelity_after_decoherence, NoiseModel, PauliErrorRates,
+};
+use crate::quantum::{DensityMatrix, PairHalf, PauliFrame, TwoQubitState};
+use rand::Rng;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+static NEXT_PAIR_ID: AtomicU64 = AtomicU64::new(1);
+static NEXT_MEMORY_VERSION: AtomicU64 = AtomicU64::new(1);
+
+/// Memory version unique within the process, so no two node states share one
+fn next_memory_version() -> u64 {
+    NEXT_MEMORY_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Fresh pair id, unique within the process
+pub fn next_pair_id() -> u64 {
+    NEXT_PAIR_ID.fetch_add(1, Ordering::Relaxed)
+}
 
 /// A quantum entangled pair stored in node memory
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StoredPair {
+    /// Shared by the two halves of one pair, unique otherwise
+    /// 0 for halves restored from data written before pairs had ids
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "restored_pair_id::deserialize")
+    )]
+    pub pair_id: u64,
     /// ID of the partner node this qubit is entangled with
     pub partner_node_id: usize,
     /// The quantum state of this entangled pair
@@ -13,7 +42,25 @@ pub struct StoredPair {
     /// Current fidelity of this pair
     pub fidelity: f64,
     /// Coherence time in milliseconds
+    #[cfg_attr(feature = "serde", serde(with = "infinite_as_null"))]
     pub coherence_time_ms: f64,
+    /// Memory relaxation time T1 in milliseconds (bit-flip-like errors)
+    #[cfg_attr(feature = "serde", serde(with = "infinite_as_null"))]
+    pub t1_ms: f64,
+    /// Memory dephasing time T2 in milliseconds (phase-flip-like errors)
+    #[cfg_attr(feature = "serde", serde(with = "infinite_as_null"))]
+    pub t2_ms: f64,
+    /// Physical slot holding this pair (nodes with a slot layout only)
+    pub slot: Option<usize>,
+    /// Correction still owed by the end nodes; `state` is the corrected state
+    pub pauli_frame: PauliFrame,
+    /// Mixed state of the pair at creation, when it is not the pure `state`
+    /// Memory noise is applied on read (see `density_matrix_at`)
+    pub density_matrix: Option<DensityMatrix>,
+    /// Memory decoherence law; None decays exponentially with `coherence_time_ms`
+    /// Not serialized: a restored pair decays exponentially until given a model
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub noise_model: Option<Arc<dyn NoiseModel + Send + Sync>>,
 }
 
 impl StoredPair {
@@ -29,18 +76,137 @@ impl StoredPair {
         let fidelity = state.fidelity(&ideal_bell);
 
         StoredPair {
+            pair_id: next_pair_id(),
             partner_node_id,
             state,
             creation_time,
             fidelity,
             coherence_time_ms,
+            t1_ms: f64::INFINITY,
+            t2_ms: coherence_time_ms,
+            slot: None,
+            pauli_frame: PauliFrame::IDENTITY,
+            density_matrix: None,
+            noise_model: None,
+        }
+    }
+
+    /// Create a pair of fidelity `fidelity` against |Φ+⟩
+    ///
+    /// `state` is `TwoQubitState::werner_approx(fidelity)`, so the stored
+    /// state and the scalar agree; imperfect pairs also carry the Werner state
+    pub fn new_with_fidelity(
+        partner_node_id: usize,
+        fidelity: f64,
+        creation_time: f64,
+        coherence_time_ms: f64,
+    ) -> Self {
+        let mut pair = Self::new(
+            partner_node_id,
+            TwoQubitState::werner_approx(fidelity),
+            creation_time,
+            coherence_time_ms,
+        );
+        pair.fidelity = fidelity;
+        if fidelity < 1.0 {
+            pair.density_matrix = Some(DensityMatrix::werner_with_fidelity(fidelity));
+        }
+        pair
+    }
+
+    /// The other half of the pair `pair_id`
+    pub fn with_pair_id(mut self, pair_id: u64) -> Self {
+        self.pair_id = pair_id;
+        self
+    }
+
+    /// Handle of this pair, valid at both of its nodes
+    pub fn handle(&self) -> PairHandle {
+        PairHandle(self.pair_id)
+    }
+
+    /// Decay in memory by `model` instead of exponentially
+    pub fn with_noise_model(mut self, model: impl NoiseModel + Send + Sync + 'static) -> Self {
+        self.noise_model = Some(Arc::new(model));
+        self
+    }
+
+    /// `initial` fidelity after `elapsed_ms` under this pair's decoherence law
+    fn decayed_fidelity(&self, initial: f64, elapsed_ms: f64) -> f64 {
+        match &self.noise_model {
+            Some(model) => model.fidelity(initial, elapsed_ms),
+            None => fidelity_after_decoherence(initial, elapsed_ms, self.coherence_time_ms),
         }
     }
 
+    /// Carry a mixed state; the initial fidelity is taken against |Φ+⟩
+    pub fn with_density_matrix(mut self, density_matrix: DensityMatrix) -> Self {
+        self.fidelity = density_matrix.fidelity_with_pure(&TwoQubitState::new_bell_phi_plus());
+        self.density_matrix = Some(density_matrix);
+        self
+    }
+
+    /// Mixed state at creation: `density_matrix`, else Werner at `fidelity`
+    pub fn initial_density_matrix(&self) -> DensityMatrix {
+        self.density_matrix
+            .clone()
+            .unwrap_or_else(|| DensityMatrix::werner_with_fidelity(self.fidelity))
+    }
+
+    /// Mixed state at `current_time`: the initial state with this half's
+    /// memory Pauli errors (see `memory_errors_at`) applied to both qubits
+    pub fn density_matrix_at(&self, current_time: f64) -> DensityMatrix {
+        let mut rho = self.initial_density_matrix();
+        let errors = self.memory_errors_at(current_time);
+        apply_pauli_channel(&mut rho, PairHalf::A, &errors);
+        apply_pauli_channel(&mut rho, PairHalf::B, &errors);
+        rho
+    }
+
+    /// Set memory T1/T2 used for basis-dependent errors
+    pub fn with_memory_times(mut self, t1_ms: f64, t2_ms: f64) -> Self {
+        self.t1_ms = t1_ms;
+        self.t2_ms = t2_ms;
+        self
+    }
+
+    /// Corrupt `state` with one trajectory of memory noise over `elapsed_ms`
+    ///
+    /// `noise` holds per-millisecond rates, compounded over the elapsed time
+    /// and applied independently to both qubits. `fidelity` is left alone: it
+    /// keeps tracking the ensemble average
+    pub fn apply_memory_noise(
+        &mut self,
+        elapsed_ms: f64,
+        noise: &PauliErrorRates,
+        rng: &mut impl Rng,
+    ) {
+        let rates = noise.repeated(elapsed_ms);
+        apply_pauli_twirl(&mut self.state, rates.p_x, rates.p_y, rates.p_z, rng);
+    }
+
+    /// Pauli errors this half has picked up in memory by `current_time`
+    pub fn memory_errors_at(&self, current_time: f64) -> PauliErrorRates {
+        let elapsed = current_time - self.creation_time;
+        PauliErrorRates::from_t1_t2(elapsed, self.t1_ms, self.t2_ms)
+    }
+
     /// Update fidelity based on current time (apply decoherence)
     pub fn update_fidelity(&mut self, current_time: f64) {
+        debug_assert!(
+            current_time >= self.creation_time,
+            "update_fidelity at t={} before pair creation at t={}",
+            current_time,
+            self.creation_time
+        );
         let elapsed = current_time - self.creation_time;
-        self.fidelity = fidelity_after_decoherence(self.fidelity, elapsed, self.coherence_time_ms);
+        self.fidelity = self.decayed_fidelity(self.fidelity, elapsed);
+    }
+
+    /// Fidelity at a given time, without modifying the stored value
+    pub fn fidelity_at(&self, current_time: f64) -> f64 {
+        let elapsed = current_time - self.creation_time;
+        self.decayed_fidelity(self.fidelity, elapsed)
     }
 
     /// Check if pair is still usable (above fidelity threshold)
@@ -49,26 +215,399 @@ impl StoredPair {
     }
 }
 
+/// When a stored pair is no longer worth keeping
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cutoff {
+    /// Drop pairs stored for longer than this (ms)
+    MaxAge(f64),
+    /// Drop pairs whose current fidelity has decayed below this
+    MinFidelity(f64),
+}
+
+impl Cutoff {
+    /// Whether `pair` has expired by `current_time`
+    pub fn is_expired(&self, pair: &StoredPair, current_time: f64) -> bool {
+        match *self {
+            Cutoff::MaxAge(max_age_ms) => current_time - pair.creation_time > max_age_ms,
+            Cutoff::MinFidelity(min_fidelity) => pair.fidelity_at(current_time) < min_fidelity,
+        }
+    }
+}
+
+/// Stable handle to a stored pair, keyed on its `pair_id`
+///
+/// A handle stays valid while other pairs are stored and removed, and
+/// names the same pair at both of the nodes holding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairHandle(pub u64);
+
+/// Hardware profile of a node's quantum memory and processor
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeHardware {
+    /// Maximum number of qubits the node can store
+    pub memory_capacity: usize,
+    /// Probability that a memory emits a photon when an attempt asks it to
+    pub memory_efficiency: f64,
+    /// Coherence time of stored pairs (ms)
+    pub coherence_time_ms: f64,
+    /// Two-qubit gate depolarizing probability
+    pub gate_error_rate: f64,
+}
+
+impl NodeHardware {
+    /// Ideal hardware with `memory_capacity` slots
+    pub fn new(memory_capacity: usize) -> Self {
+        NodeHardware {
+            memory_capacity,
+            memory_efficiency: 1.0,
+            coherence_time_ms: f64::INFINITY,
+            gate_error_rate: 0.0,
+        }
+    }
+
+    /// Set the photon emission efficiency of the memory
+    pub fn with_memory_efficiency(mut self, memory_efficiency: f64) -> Self {
+        self.memory_efficiency = memory_efficiency;
+        self
+    }
+
+    /// Set the coherence time of stored pairs (ms)
+    pub fn with_coherence_time(mut self, coherence_time_ms: f64) -> Self {
+        self.coherence_time_ms = coherence_time_ms;
+        self
+    }
+
+    /// Set the two-qubit gate depolarizing probability
+    pub fn with_gate_error_rate(mut self, gate_error_rate: f64) -> Self {
+        self.gate_error_rate = gate_error_rate;
+        self
+    }
+
+    /// Check the profile is physically meaningful
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.memory_efficiency) {
+            return Err(format!(
+                "Memory efficiency must be in [0, 1], got {}",
+                self.memory_efficiency
+            ));
+        }
+        if self.coherence_time_ms.is_nan() || self.coherence_time_ms <= 0.0 {
+            return Err(format!(
+                "Coherence time must be positive, got {}",
+                self.coherence_time_ms
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.gate_error_rate) {
+            return Err(format!(
+                "Gate error rate must be in [0, 1], got {}",
+                self.gate_error_rate
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// What a node is allowed to do in the network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NodeRole {
+    /// Application endpoint: consumes pairs, never swaps
+    EndNode,
+    /// Stores pairs and swaps them onward
+    #[default]
+    Repeater,
+    /// Midpoint station performing heralding Bell-state measurements
+    BsmStation,
+}
+
+impl NodeRole {
+    /// Whether entanglement swapping may happen at a node with this role
+    pub fn can_swap(self) -> bool {
+        self != NodeRole::EndNode
+    }
+}
+
 /// A quantum network node (processor or repeater)
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QuantumNode {
     /// Unique identifier for this node
     pub id: usize,
     /// Maximum number of qubits this node can store
     pub memory_capacity: usize,
-    /// Currently stored entangled pairs
-    pub stored_pairs: Vec<StoredPair>,
+    /// Operations this node may perform
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub role: NodeRole,
+    /// Currently stored entangled pairs; mutated only through methods so the
+    /// audit log sees every change
+    stored_pairs: Vec<StoredPair>,
+    /// Slots set aside for pairs still to come (see `reserve`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    reserved: usize,
+    /// Two-qubit gate depolarizing probability (used by swapping)
+    pub gate_error: f64,
+    /// Photon emission efficiency of the memory (None: the generation
+    /// protocol's own value)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub memory_efficiency: Option<f64>,
+    /// Coherence time given to pairs stored here (None: the caller's value)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub coherence_time_ms: Option<f64>,
+    /// Optional record of every memory mutation
+    audit: Option<MemoryAuditLog>,
+    /// Heterogeneous slot layout; empty means uniform memory
+    slots: Vec<MemorySlot>,
+    /// Not serialized: a restored node assigns slots first-free
+    #[cfg_attr(feature = "serde", serde(skip, default = "first_free"))]
+    slot_strategy: Arc<dyn SlotAssignmentStrategy + Send + Sync>,
+    /// Decoherence law given to stored pairs that carry none of their own
+    #[cfg_attr(feature = "serde", serde(skip))]
+    noise_model: Option<Arc<dyn NoiseModel + Send + Sync>>,
+    /// Renewed by every store, removal and mutable pair access
+    #[cfg_attr(feature = "serde", serde(skip, default = "next_memory_version"))]
+    memory_version: u64,
+}
+
+/// Times that may be infinite (disabled), as null in JSON
+#[cfg(feature = "serde")]
+mod infinite_as_null {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(time: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        time.is_finite().then_some(*time).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+    }
+}
+
+/// Pair ids read back from saved data
+#[cfg(feature = "serde")]
+mod restored_pair_id {
+    use serde::{Deserialize, Deserializer};
+    use std::sync::atomic::Ordering;
+
+    /// Moves `next_pair_id` past the restored id so new pairs cannot reuse it
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let pair_id = u64::deserialize(deserializer)?;
+        super::NEXT_PAIR_ID.fetch_max(pair_id.saturating_add(1), Ordering::Relaxed);
+        Ok(pair_id)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn first_free() -> Arc<dyn SlotAssignmentStrategy + Send + Sync> {
+    Arc::new(FirstFree)
 }
 
 impl QuantumNode {
     /// Create a new quantum node with empty memory
     pub fn new(id: usize, memory_capacity: usize) -> Self {
+        Self::new_with_role(id, memory_capacity, NodeRole::Repeater)
+    }
+
+    /// Create a node with empty memory and the given role
+    pub fn new_with_role(id: usize, memory_capacity: usize, role: NodeRole) -> Self {
         QuantumNode {
             id,
             memory_capacity,
+            role,
             stored_pairs: Vec::new(),
+            reserved: 0,
+            gate_error: 0.0,
+            memory_efficiency: None,
+            coherence_time_ms: None,
+            audit: None,
+            slots: Vec::new(),
+            slot_strategy: Arc::new(FirstFree),
+            noise_model: None,
+            memory_version: next_memory_version(),
+        }
+    }
+
+    /// Create a node with the given hardware profile
+    pub fn with_hardware(id: usize, hardware: &NodeHardware) -> Self {
+        let mut node = Self::new(id, hardware.memory_capacity);
+        node.gate_error = hardware.gate_error_rate;
+        node.memory_efficiency = Some(hardware.memory_efficiency);
+        node.coherence_time_ms = Some(hardware.coherence_time_ms);
+        node
+    }
+
+    /// Create a node with one memory slot per coherence time
+    /// Stored pairs take the coherence time of the slot they land in
+    pub fn with_slots(id: usize, slot_coherence_times_ms: &[f64]) -> Self {
+        let mut node = Self::new(id, slot_coherence_times_ms.len());
+        node.slots = slot_coherence_times_ms
+            .iter()
+            .map(|&coherence_time_ms| MemorySlot::new(coherence_time_ms))
+            .collect();
+        node
+    }
+
+    /// Strategy choosing the slot for each stored pair
+    pub fn set_slot_strategy(
+        &mut self,
+        strategy: impl SlotAssignmentStrategy + Send + Sync + 'static,
+    ) {
+        self.slot_strategy = Arc::new(strategy);
+    }
+
+    /// Decoherence law of this node's memory
+    /// Applies to pairs stored from now on that carry no model of their own
+    pub fn set_noise_model(&mut self, model: impl NoiseModel + Send + Sync + 'static) {
+        self.noise_model = Some(Arc::new(model));
+    }
+
+    /// Physical slots (empty for uniform memory)
+    pub fn slots(&self) -> &[MemorySlot] {
+        &self.slots
+    }
+
+    /// Per-slot occupancy and idle time up to `current_time`
+    pub fn slot_statistics(&self, current_time: f64) -> Vec<SlotStats> {
+        self.slots
+            .iter()
+            .map(|slot| {
+                let current = slot
+                    .occupied_since
+                    .map_or(0.0, |since| (current_time - since).max(0.0));
+                let busy_time_ms = slot.busy_time_ms + current;
+                SlotStats {
+                    coherence_time_ms: slot.coherence_time_ms,
+                    stores: slot.stores,
+                    busy_time_ms,
+                    idle_time_ms: (current_time - busy_time_ms).max(0.0),
+                }
+            })
+            .collect()
+    }
+
+    /// Start recording memory mutations (bounded to `capacity` entries if given)
+    pub fn enable_audit(&mut self, capacity: Option<usize>) {
+        let log = match capacity {
+            Some(capacity) => MemoryAuditLog::bounded(capacity),
+            None => MemoryAuditLog::new(),
+        };
+        let baseline = self.stored_pairs.iter().map(|pair| pair.pair_id).collect();
+        self.audit = Some(log.with_baseline(baseline, self.reserved));
+    }
+
+    /// Audit log, if auditing is enabled
+    pub fn audit_log(&self) -> Option<&MemoryAuditLog> {
+        self.audit.as_ref()
+    }
+
+    /// Recompute occupancy from the audit log and compare with actual memory
+    /// Always Ok when auditing is disabled
+    pub fn verify_consistency(&self) -> Result<(), AuditInconsistency> {
+        match &self.audit {
+            Some(log) => log.verify(self.stored_pairs.len()),
+            None => Ok(()),
+        }
+    }
+
+    /// Record a mutation in the audit log without touching memory
+    /// Test hook for exercising the consistency checker
+    #[doc(hidden)]
+    pub fn inject_audit_entry(&mut self, op: AuditOp, pair_id: Option<u64>, time: f64) {
+        self.audit(op, pair_id.map(|id| (id, None)), time);
+    }
+
+    /// Log `op` on the pair (id, partner) if given, at `time`
+    fn audit(&mut self, op: AuditOp, pair: Option<(u64, Option<usize>)>, time: f64) {
+        let (occupancy_after, reserved_after) = (self.stored_pairs.len(), self.reserved);
+        if let Some(log) = self.audit.as_mut() {
+            log.record(AuditEntry {
+                sequence: 0,
+                time,
+                op,
+                pair_id: pair.map(|(id, _)| id),
+                partner_node_id: pair.and_then(|(_, partner)| partner),
+                occupancy_after,
+                reserved_after,
+            });
+        }
+    }
+
+    /// Stored pairs, in storage order
+    pub fn stored_pairs(&self) -> &[StoredPair] {
+        &self.stored_pairs
+    }
+
+    /// Give legacy id-0 halves shared with `partner` fresh ids, the same at both ends
+    ///
+    /// Halves are matched by partner and creation time in storage order, as
+    /// they were before pairs carried ids.
+    pub(crate) fn relink_legacy_pairs(&mut self, partner: &mut QuantumNode) {
+        let (id, partner_id) = (self.id, partner.id);
+        for pair in &mut self.stored_pairs {
+            if pair.pair_id != 0 || pair.partner_node_id != partner_id {
+                continue;
+            }
+            let other_half = partner.stored_pairs.iter_mut().find(|other| {
+                other.pair_id == 0
+                    && other.partner_node_id == id
+                    && other.creation_time == pair.creation_time
+            });
+            if let Some(other_half) = other_half {
+                pair.pair_id = next_pair_id();
+                other_half.pair_id = pair.pair_id;
+            }
         }
     }
 
+    /// Rewrite partner ids after the topology drops a node
+    pub(crate) fn renumber_partners(&mut self, renumber: impl Fn(usize) -> usize) {
+        for pair in &mut self.stored_pairs {
+            pair.partner_node_id = renumber(pair.partner_node_id);
+        }
+    }
+
+    /// Slots currently reserved
+    pub fn reserved_slots(&self) -> usize {
+        self.reserved
+    }
+
+    /// Capacity not claimed by a reservation
+    pub fn unreserved_memory(&self) -> usize {
+        self.memory_capacity.saturating_sub(self.reserved)
+    }
+
+    /// Claim `count` slots of capacity at `current_time` for one consumer
+    /// Its pairs count against the claim once stored; reservations gate
+    /// admission only, `store_pair` does not check them
+    pub fn reserve(&mut self, count: usize, current_time: f64) -> Result<(), String> {
+        if self.unreserved_memory() < count {
+            return Err(format!(
+                "Node {} cannot reserve {} slots ({} unreserved)",
+                self.id,
+                count,
+                self.unreserved_memory()
+            ));
+        }
+        self.reserved += count;
+        self.audit(AuditOp::Reserve(count), None, current_time);
+        Ok(())
+    }
+
+    /// Give back `count` reserved slots at `current_time`
+    pub fn release_reservation(&mut self, count: usize, current_time: f64) -> Result<(), String> {
+        if count > self.reserved {
+            return Err(format!(
+                "Node {} cannot release {} slots ({} reserved)",
+                self.id, count, self.reserved
+            ));
+        }
+        self.reserved -= count;
+        self.audit(AuditOp::Release(count), None, current_time);
+        Ok(())
+    }
+
     /// Check if node has available memory
     pub fn has_memory_available(&self) -> bool {
         self.stored_pairs.len() < self.memory_capacity
@@ -81,6 +620,15 @@ impl QuantumNode {
 
     /// Store an entangled pair (if memory available)
     pub fn store_pair(&mut self, pair: StoredPair) -> Result<(), String> {
+        self.store_pair_with_hint(pair, None)
+    }
+
+    /// Store a pair, telling the slot strategy how long it is expected to wait
+    pub fn store_pair_with_hint(
+        &mut self,
+        mut pair: StoredPair,
+        expected_wait_ms: Option<f64>,
+    ) -> Result<(), String> {
         if !self.has_memory_available() {
             return Err(format!(
                 "Node {} memory full ({}/{})",
@@ -90,40 +638,242 @@ impl QuantumNode {
             ));
         }
 
+        if !self.slots.is_empty() {
+            let free: Vec<usize> = (0..self.slots.len())
+                .filter(|&index| self.slots[index].is_free())
+                .collect();
+            let index = self
+                .slot_strategy
+                .choose(&self.slots, &free, expected_wait_ms)
+                .filter(|index| free.contains(index))
+                .ok_or_else(|| format!("Node {} has no free slot", self.id))?;
+            self.slots[index].occupy(pair.creation_time);
+            pair.slot = Some(index);
+            pair.coherence_time_ms = self.slots[index].coherence_time_ms;
+        }
+
+        if pair.noise_model.is_none() {
+            pair.noise_model = self.noise_model.clone();
+        }
+
+        let (id, partner, time) = (pair.pair_id, pair.partner_node_id, pair.creation_time);
         self.stored_pairs.push(pair);
+        self.memory_version = next_memory_version();
+        self.audit(AuditOp::Store, Some((id, Some(partner))), time);
         Ok(())
     }
 
     /// Find a stored pair with a specific partner node
-    pub fn find_pair_with(&self, partner_id: usize) -> Option<usize> {
+    pub fn find_pair_with(&self, partner_id: usize) -> Option<PairHandle> {
         self.stored_pairs
             .iter()
-            .position(|pair| pair.partner_node_id == partner_id)
+            .find(|pair| pair.partner_node_id == partner_id)
+            .map(StoredPair::handle)
     }
 
-    /// Remove and return a stored pair with a specific partner
-    pub fn remove_pair_with(&mut self, partner_id: usize) -> Option<StoredPair> {
-        if let Some(index) = self.find_pair_with(partner_id) {
-            Some(self.stored_pairs.remove(index))
-        } else {
-            None
+    /// The pair behind a handle, if this node holds it
+    pub fn pair(&self, handle: PairHandle) -> Option<&StoredPair> {
+        self.stored_pairs
+            .iter()
+            .find(|pair| pair.handle() == handle)
+    }
+
+    /// Mutable access to the pair behind a handle
+    pub fn pair_mut(&mut self, handle: PairHandle) -> Option<&mut StoredPair> {
+        self.memory_version = next_memory_version();
+        self.stored_pairs
+            .iter_mut()
+            .find(|pair| pair.handle() == handle)
+    }
+
+    /// Remove and return a stored pair with a specific partner at `current_time`
+    /// With several such pairs, remove the partner's half by `pair_id`
+    pub fn remove_pair_with(&mut self, partner_id: usize, current_time: f64) -> Option<StoredPair> {
+        let handle = self.find_pair_with(partner_id)?;
+        self.remove_pair_at(handle, current_time)
+    }
+
+    /// All stored pairs with a specific partner, in storage order
+    pub fn find_pairs_with(&self, partner_id: usize) -> Vec<&StoredPair> {
+        self.stored_pairs
+            .iter()
+            .filter(|pair| pair.partner_node_id == partner_id)
+            .collect()
+    }
+
+    /// Handle of this node's half of pair `pair_id`
+    pub fn find_pair_by_id(&self, pair_id: u64) -> Option<PairHandle> {
+        self.pair(PairHandle(pair_id)).map(StoredPair::handle)
+    }
+
+    /// Remove and return this node's half of pair `pair_id` at `current_time`
+    pub fn remove_pair_by_id(&mut self, pair_id: u64, current_time: f64) -> Option<StoredPair> {
+        self.remove_pair_at(PairHandle(pair_id), current_time)
+    }
+
+    /// Remove and return the pair behind a handle at `current_time`
+    /// The pair's slot accrues the time it was held as busy time
+    pub fn remove_pair_at(&mut self, handle: PairHandle, current_time: f64) -> Option<StoredPair> {
+        self.take_pair(handle, current_time, AuditOp::Remove)
+    }
+
+    /// Discard the pair behind a handle unused, at `current_time`
+    pub fn evict_pair(&mut self, handle: PairHandle, current_time: f64) -> Option<StoredPair> {
+        self.take_pair(handle, current_time, AuditOp::Evict)
+    }
+
+    fn take_pair(
+        &mut self,
+        handle: PairHandle,
+        current_time: f64,
+        op: AuditOp,
+    ) -> Option<StoredPair> {
+        let index = self
+            .stored_pairs
+            .iter()
+            .position(|pair| pair.handle() == handle)?;
+        let pair = self.stored_pairs.remove(index);
+        self.memory_version = next_memory_version();
+        if let Some(slot) = pair.slot {
+            self.slots[slot].release(current_time);
         }
+        self.audit(
+            op,
+            Some((pair.pair_id, Some(pair.partner_node_id))),
+            current_time,
+        );
+        Some(pair)
     }
 
-    /// Clear all stored pairs (useful for testing or reset)
-    pub fn clear_memory(&mut self) {
-        self.stored_pairs.clear();
+    /// Drop every pair that has expired under `cutoff` by `current_time`
+    /// Returns the number dropped; partner halves are left to their nodes
+    /// (see `NetworkTopology::purge_expired`)
+    pub fn purge_expired(&mut self, current_time: f64, cutoff: Cutoff) -> usize {
+        self.purge_where(current_time, |pair| cutoff.is_expired(pair, current_time))
+    }
+
+    /// Remove the pairs matching `expired` at `current_time`
+    pub(crate) fn purge_where(
+        &mut self,
+        current_time: f64,
+        expired: impl Fn(&StoredPair) -> bool,
+    ) -> usize {
+        let handles: Vec<PairHandle> = self
+            .stored_pairs
+            .iter()
+            .filter(|pair| expired(pair))
+            .map(StoredPair::handle)
+            .collect();
+        for &handle in &handles {
+            self.take_pair(handle, current_time, AuditOp::Expire);
+        }
+        handles.len()
+    }
+
+    /// Pair with `partner_id` that has the highest fidelity at `current_time`
+    /// Ties go to the pair stored first
+    pub fn best_pair_with(&self, partner_id: usize, current_time: f64) -> Option<PairHandle> {
+        let mut best: Option<(PairHandle, f64)> = None;
+        for pair in &self.stored_pairs {
+            if pair.partner_node_id != partner_id {
+                continue;
+            }
+            let fidelity = pair.fidelity_at(current_time);
+            if best.is_none_or(|(_, best_fidelity)| fidelity > best_fidelity) {
+                best = Some((pair.handle(), fidelity));
+            }
+        }
+        best.map(|(handle, _)| handle)
+    }
+
+    /// Most recently created pair with `partner_id`
+    /// Ties go to the pair stored first
+    pub fn freshest_pair_with(&self, partner_id: usize) -> Option<PairHandle> {
+        let mut freshest: Option<(PairHandle, f64)> = None;
+        for pair in &self.stored_pairs {
+            if pair.partner_node_id != partner_id {
+                continue;
+            }
+            if freshest.is_none_or(|(_, time)| pair.creation_time > time) {
+                freshest = Some((pair.handle(), pair.creation_time));
+            }
+        }
+        freshest.map(|(handle, _)| handle)
+    }
+
+    /// All pairs whose fidelity at `current_time` is at least `threshold`, in storage order
+    pub fn pairs_above_fidelity(&self, threshold: f64, current_time: f64) -> Vec<PairHandle> {
+        self.stored_pairs
+            .iter()
+            .filter(|pair| pair.fidelity_at(current_time) >= threshold)
+            .map(StoredPair::handle)
+            .collect()
+    }
+
+    /// Age of the longest-held pair at `current_time`, None if memory is empty
+    pub fn oldest_pair_age(&self, current_time: f64) -> Option<f64> {
+        self.stored_pairs
+            .iter()
+            .map(|pair| current_time - pair.creation_time)
+            .max_by(f64::total_cmp)
+    }
+
+    /// Creation time of the longest-held pair, None if memory is empty
+    pub fn oldest_pair_creation_time(&self) -> Option<f64> {
+        self.stored_pairs
+            .iter()
+            .map(|pair| pair.creation_time)
+            .min_by(f64::total_cmp)
+    }
+
+    /// Changes whenever the stored pairs may have changed
+    ///
+    /// Lets observers skip nodes whose memory an event left untouched.
+    pub fn memory_version(&self) -> u64 {
+        self.memory_version
+    }
+
+    /// Pairs held longer than `threshold` at `current_time`, in storage order
+    pub fn stale_pairs(&self, current_time: f64, threshold: f64) -> Vec<PairHandle> {
+        self.stored_pairs
+            .iter()
+            .filter(|pair| current_time - pair.creation_time > threshold)
+            .map(StoredPair::handle)
+            .collect()
+    }
+
+    /// Number of stored pairs with `partner_id`
+    pub fn count_pairs_with(&self, partner_id: usize) -> usize {
+        self.stored_pairs
+            .iter()
+            .filter(|pair| pair.partner_node_id == partner_id)
+            .count()
+    }
+
+    /// Evict all stored pairs at `current_time` (useful for testing or reset)
+    pub fn clear_memory(&mut self, current_time: f64) {
+        while let Some(handle) = self.stored_pairs.first().map(StoredPair::handle) {
+            self.evict_pair(handle, current_time);
+        }
     }
 
     /// Get total number of stored pairs
     pub fn num_stored_pairs(&self) -> usize {
         self.stored_pairs.len()
     }
+
+    /// Stored pairs with their age and decohered fidelity at `current_time`
+    pub fn iter_pairs(&self, current_time: f64) -> impl Iterator<Item = PairView> + '_ {
+        self.stored_pairs
+            .iter()
+            .map(move |pair| PairView::new(self.id, pair, current_time))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::network::slots::{BestCoherenceFirst, WorstFitForShortWaits};
 
     #[test]
     fn test_node_creation() {
@@ -153,6 +903,134 @@ mod tests {
         assert_eq!(node.free_memory(), 1);
     }
 
+    #[test]
+    fn test_werner_pair_fidelity() {
+        let pair = StoredPair::new(1, TwoQubitState::new_bell_phi_plus(), 0.0, 100.0)
+            .with_density_matrix(DensityMatrix::werner(0.6));
+        assert!((pair.fidelity - 0.7).abs() < 1e-12);
+        assert!(pair.density_matrix.is_some());
+    }
+
+    #[test]
+    fn test_pair_with_fidelity_matches_state() {
+        let ideal = TwoQubitState::new_bell_phi_plus();
+        for f in [0.25, 0.7, 0.95, 1.0] {
+            let pair = StoredPair::new_with_fidelity(1, f, 0.0, 100.0);
+            assert_eq!(pair.fidelity, f);
+            assert!((pair.state.fidelity(&ideal) - pair.fidelity).abs() < 1e-10);
+        }
+        let rho = StoredPair::new_with_fidelity(1, 0.7, 0.0, 100.0).density_matrix;
+        assert!((rho.unwrap().fidelity_with_pure(&ideal) - 0.7).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_density_matrix_dephases_in_memory() {
+        let ideal = TwoQubitState::new_bell_phi_plus();
+        let pair = StoredPair::new_with_fidelity(1, 1.0, 2.0, 100.0)
+            .with_memory_times(f64::INFINITY, 10.0);
+        let fresh = pair.density_matrix_at(2.0);
+        assert!((fresh.fidelity_with_pure(&ideal) - 1.0).abs() < 1e-12);
+
+        // Each half flips phase with q = (1 - e^(-t/T2))/2; the pair with 2q(1-q)
+        let rho = pair.density_matrix_at(7.0);
+        let q = (1.0 - (-0.5_f64).exp()) / 2.0;
+        assert!((rho.fidelity_with_pure(&ideal) - (1.0 - 2.0 * q * (1.0 - q))).abs() < 1e-12);
+        assert!(rho.purity() < 1.0);
+        // The stored initial state is untouched
+        assert!(pair.density_matrix.is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut node = QuantumNode::with_slots(4, &[50.0, 200.0]);
+        node.enable_audit(None);
+        node.store_pair(StoredPair::new_with_fidelity(1, 0.9, 1.5, 100.0))
+            .unwrap();
+        node.store_pair(
+            StoredPair::new(2, TwoQubitState::new_bell_psi_minus(), 2.0, 100.0)
+                .with_memory_times(300.0, 80.0),
+        )
+        .unwrap();
+
+        let json = serde_json::to_string(&node).unwrap();
+        let parsed: QuantumNode = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.id, 4);
+        assert_eq!(parsed.slots(), node.slots());
+        assert_eq!(parsed.audit_log().unwrap().len(), 2);
+        assert_eq!(parsed.num_stored_pairs(), 2);
+        for (restored, original) in parsed.stored_pairs.iter().zip(&node.stored_pairs) {
+            assert_eq!(restored.partner_node_id, original.partner_node_id);
+            assert_eq!(restored.state.state, original.state.state);
+            assert_eq!(restored.fidelity, original.fidelity);
+            assert_eq!(restored.creation_time, original.creation_time);
+            assert_eq!(
+                (restored.t1_ms, restored.t2_ms),
+                (original.t1_ms, original.t2_ms)
+            );
+            assert_eq!(restored.slot, original.slot);
+            assert_eq!(
+                restored.density_matrix.as_ref().map(|rho| &rho.matrix),
+                original.density_matrix.as_ref().map(|rho| &rho.matrix)
+            );
+        }
+    }
+
+    #[test]
+    fn test_memory_noise_corrupts_stored_state() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7790);
+        let ideal = TwoQubitState::new_bell_phi_plus();
+        let per_ms = PauliErrorRates {
+            p_x: 0.0,
+            p_y: 0.0,
+            p_z: 0.002,
+        };
+        let trials = 4000;
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let mut pair = StoredPair::new(1, ideal.clone(), 0.0, 100.0);
+            let fidelity = pair.fidelity;
+            pair.apply_memory_noise(50.0, &per_ms, &mut rng);
+            assert_eq!(pair.fidelity, fidelity);
+            total += pair.state.fidelity(&ideal);
+        }
+        let rates = per_ms.repeated(50.0);
+        let expected = rates.then(&rates).p_identity();
+        assert!((total / trials as f64 - expected).abs() < 0.02);
+
+        let mut untouched = StoredPair::new(1, ideal.clone(), 0.0, 100.0);
+        untouched.apply_memory_noise(0.0, &per_ms, &mut rng);
+        assert!(untouched.state.approx_eq(&ideal, 1e-12));
+    }
+
+    #[test]
+    fn test_node_noise_model_applies_to_stored_pairs() {
+        use crate::quantum::noise::{ExponentialDecay, GaussianDephasing};
+        let mut node = QuantumNode::new(0, 3);
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        node.store_pair(StoredPair::new(1, bell_state.clone(), 0.0, 100.0))
+            .unwrap();
+        node.set_noise_model(GaussianDephasing { t2_star_ms: 100.0 });
+        node.store_pair(StoredPair::new(2, bell_state.clone(), 0.0, 100.0))
+            .unwrap();
+        // A pair's own model wins over the node's
+        let own = StoredPair::new(3, bell_state, 0.0, 100.0).with_noise_model(ExponentialDecay {
+            coherence_time_ms: 100.0,
+        });
+        node.store_pair(own).unwrap();
+
+        let fidelities: Vec<f64> = node
+            .stored_pairs
+            .iter()
+            .map(|pair| pair.fidelity_at(100.0))
+            .collect();
+        let exponential = (-1.0_f64).exp();
+        assert!((fidelities[0] - exponential).abs() < 1e-12);
+        assert!((fidelities[1] - (1.0 + exponential) / 2.0).abs() < 1e-12);
+        assert!((fidelities[2] - exponential).abs() < 1e-12);
+    }
+
     #[test]
     fn test_memory_full() {
         let mut node = QuantumNode::new(0, 1);
@@ -194,7 +1072,7 @@ mod tests {
         node.store_pair(pair).unwrap();
         assert_eq!(node.num_stored_pairs(), 1);
 
-        let removed = node.remove_pair_with(1);
+        let removed = node.remove_pair_with(1, 0.0);
         assert!(removed.is_some());
         assert_eq!(removed.unwrap().partner_node_id, 1);
         assert_eq!(node.num_stored_pairs(), 0);
@@ -209,6 +1087,262 @@ mod tests {
         assert!((pair.fidelity - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_fidelity_at_does_not_mutate() {
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        let pair = StoredPair::new(1, bell_state, 0.0, 100.0);
+
+        let decayed = pair.fidelity_at(100.0);
+        assert!((decayed - (-1.0_f64).exp()).abs() < 1e-10);
+        assert!((pair.fidelity - 1.0).abs() < 1e-10);
+    }
+
+    fn node_with_mixed_pairs() -> QuantumNode {
+        let mut node = QuantumNode::new(0, 10);
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+
+        // (partner, creation_time, initial fidelity)
+        for (partner, created, fidelity) in [
+            (1, 0.0, 0.95),
+            (1, 50.0, 0.90),
+            (2, 10.0, 0.99),
+            (1, 50.0, 0.90),
+            (1, 20.0, 0.97),
+        ] {
+            let mut pair = StoredPair::new(partner, bell_state.clone(), created, 100.0);
+            pair.fidelity = fidelity;
+            node.store_pair(pair).unwrap();
+        }
+        node
+    }
+
+    /// Handles of the stored pairs, in storage order
+    fn handles(node: &QuantumNode) -> Vec<PairHandle> {
+        node.stored_pairs.iter().map(StoredPair::handle).collect()
+    }
+
+    #[test]
+    fn test_best_pair_with() {
+        let node = node_with_mixed_pairs();
+        let handles = handles(&node);
+
+        // At t=50: 0.95e^-0.5 ≈ 0.576, 0.90, 0.90, 0.97e^-0.3 ≈ 0.719
+        // (tie between the second and fourth pair, first stored wins)
+        assert_eq!(node.best_pair_with(1, 50.0), Some(handles[1]));
+        assert_eq!(node.best_pair_with(2, 50.0), Some(handles[2]));
+        assert_eq!(node.best_pair_with(3, 50.0), None);
+    }
+
+    #[test]
+    fn test_freshest_pair_with() {
+        let node = node_with_mixed_pairs();
+        let handles = handles(&node);
+        // Tie between the second and fourth pair, first stored wins
+        assert_eq!(node.freshest_pair_with(1), Some(handles[1]));
+        assert_eq!(node.freshest_pair_with(2), Some(handles[2]));
+        assert_eq!(node.freshest_pair_with(3), None);
+    }
+
+    #[test]
+    fn test_pairs_above_fidelity_and_count() {
+        let node = node_with_mixed_pairs();
+        let handles = handles(&node);
+
+        // At t=50: [0.576, 0.90, 0.99e^-0.4 ≈ 0.664, 0.90, 0.719]
+        assert_eq!(
+            node.pairs_above_fidelity(0.7, 50.0),
+            vec![handles[1], handles[3], handles[4]]
+        );
+        assert_eq!(node.count_pairs_with(1), 4);
+        assert_eq!(node.count_pairs_with(2), 1);
+        assert_eq!(node.count_pairs_with(3), 0);
+    }
+
+    #[test]
+    fn test_pair_ages() {
+        let node = node_with_mixed_pairs();
+        let handles = handles(&node);
+
+        // Created at [0, 50, 10, 50, 20]; ages at t=60 are [60, 10, 50, 10, 40]
+        assert_eq!(node.oldest_pair_age(60.0), Some(60.0));
+        assert_eq!(
+            node.stale_pairs(60.0, 30.0),
+            vec![handles[0], handles[2], handles[4]]
+        );
+    }
+
+    #[test]
+    fn test_handles_survive_earlier_removals() {
+        let mut node = node_with_mixed_pairs();
+        let stale = node.stale_pairs(60.0, 30.0);
+
+        // Removing the first stale pair shifts the others in memory
+        for &handle in &stale {
+            let pair = node.remove_pair_at(handle, 60.0).unwrap();
+            assert_eq!(pair.handle(), handle);
+        }
+        assert_eq!(node.num_stored_pairs(), 2);
+        assert!(stale.iter().all(|&handle| node.pair(handle).is_none()));
+        assert!(node.remove_pair_at(stale[0], 60.0).is_none());
+        assert_eq!(QuantumNode::new(1, 2).oldest_pair_age(60.0), None);
+    }
+
+    #[test]
+    fn test_audit_log_tracks_mutations() {
+        let mut node = QuantumNode::new(0, 5);
+        node.enable_audit(None);
+
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        node.store_pair(StoredPair::new(1, bell_state.clone(), 0.0, 100.0))
+            .unwrap();
+        node.store_pair(StoredPair::new(2, bell_state, 1.0, 100.0))
+            .unwrap();
+        node.remove_pair_with(1, 2.0);
+
+        let log = node.audit_log().unwrap();
+        let ops: Vec<AuditOp> = log.entries().map(|entry| entry.op).collect();
+        assert_eq!(ops, vec![AuditOp::Store, AuditOp::Store, AuditOp::Remove]);
+        assert_eq!(log.entries().last().unwrap().occupancy_after, 1);
+        assert!(node.verify_consistency().is_ok());
+    }
+
+    #[test]
+    fn test_audit_detects_double_remove() {
+        let mut node = QuantumNode::new(0, 5);
+        node.enable_audit(None);
+
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        node.store_pair(StoredPair::new(1, bell_state, 3.0, 100.0))
+            .unwrap();
+        let pair_id = node.remove_pair_with(1, 4.0).unwrap().pair_id;
+
+        // Second removal of the same pair, as a buggy protocol might log it
+        node.inject_audit_entry(AuditOp::Remove, Some(pair_id), 4.0);
+
+        let error = node.verify_consistency().unwrap_err();
+        assert_eq!(error.replayed_occupancy, -1);
+        assert_eq!(error.actual_occupancy, 0);
+        assert_eq!(error.offending_entries.len(), 1);
+        assert_eq!(error.offending_entries[0].sequence, 2);
+    }
+
+    #[test]
+    fn test_audit_log_records_evictions_expiries_and_reservations() {
+        let mut node = QuantumNode::new(0, 4);
+        node.enable_audit(None);
+
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        for (partner, time) in [(1, 0.0), (2, 5.0), (3, 8.0)] {
+            node.store_pair(StoredPair::new(partner, bell_state.clone(), time, 100.0))
+                .unwrap();
+        }
+        node.reserve(1, 9.0).unwrap();
+        assert_eq!(node.unreserved_memory(), 3);
+        assert!(node.reserve(4, 9.0).is_err());
+        assert_eq!(node.purge_expired(10.0, Cutoff::MaxAge(9.0)), 1);
+        let evicted = node.find_pair_with(2).unwrap();
+        node.evict_pair(evicted, 11.0);
+        node.release_reservation(1, 12.0).unwrap();
+        assert!(node.release_reservation(1, 12.0).is_err());
+
+        let log = node.audit_log().unwrap();
+        let tail: Vec<(AuditOp, f64, Option<usize>)> = log
+            .entries()
+            .skip(3)
+            .map(|entry| (entry.op, entry.time, entry.partner_node_id))
+            .collect();
+        assert_eq!(
+            tail,
+            vec![
+                (AuditOp::Reserve(1), 9.0, None),
+                (AuditOp::Expire, 10.0, Some(1)),
+                (AuditOp::Evict, 11.0, Some(2)),
+                (AuditOp::Release(1), 12.0, None),
+            ]
+        );
+        assert_eq!(log.entries().last().unwrap().reserved_after, 0);
+        assert!(node.verify_consistency().is_ok());
+
+        // A release nobody reserved is flagged
+        node.inject_audit_entry(AuditOp::Release(1), None, 13.0);
+        let error = node.verify_consistency().unwrap_err();
+        assert_eq!(error.offending_entries[0].op, AuditOp::Release(1));
+    }
+
+    #[test]
+    fn test_bounded_audit_log_still_verifies() {
+        let mut node = QuantumNode::new(0, 10);
+        node.enable_audit(Some(3));
+
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        for i in 0..6 {
+            node.store_pair(StoredPair::new(1, bell_state.clone(), i as f64, 100.0))
+                .unwrap();
+        }
+        node.remove_pair_with(1, 6.0);
+
+        assert_eq!(node.audit_log().unwrap().len(), 3);
+        assert!(node.verify_consistency().is_ok());
+    }
+
+    /// Store short- then long-wait pairs and return mean fidelity at consumption
+    fn mean_consumed_fidelity(
+        strategy: impl SlotAssignmentStrategy + Send + Sync + 'static,
+    ) -> f64 {
+        let mut node = QuantumNode::with_slots(0, &[100.0, 100.0, 2.0, 2.0]);
+        node.set_slot_strategy(strategy);
+
+        // (partner, expected and actual wait in ms)
+        let schedule = [(1, 1.0), (2, 1.0), (3, 20.0), (4, 20.0)];
+        for &(partner, wait) in &schedule {
+            let pair = StoredPair::new(partner, TwoQubitState::new_bell_phi_plus(), 0.0, 100.0);
+            node.store_pair_with_hint(pair, Some(wait)).unwrap();
+        }
+
+        let mut total = 0.0;
+        for &(partner, wait) in &schedule {
+            let handle = node.find_pair_with(partner).unwrap();
+            let pair = node.remove_pair_at(handle, wait).unwrap();
+            total += pair.fidelity_at(wait);
+        }
+        total / schedule.len() as f64
+    }
+
+    #[test]
+    fn test_worst_fit_beats_first_free() {
+        let first_free = mean_consumed_fidelity(FirstFree);
+        let worst_fit = mean_consumed_fidelity(WorstFitForShortWaits {
+            expected_wait_hint: 10.0,
+        });
+        assert!(
+            worst_fit > first_free + 0.2,
+            "worst fit {} vs first free {}",
+            worst_fit,
+            first_free
+        );
+    }
+
+    #[test]
+    fn test_slot_assignment_and_statistics() {
+        let mut node = QuantumNode::with_slots(0, &[10.0, 50.0]);
+        node.set_slot_strategy(BestCoherenceFirst);
+        assert_eq!(node.memory_capacity, 2);
+
+        let pair = StoredPair::new(1, TwoQubitState::new_bell_phi_plus(), 2.0, 999.0);
+        node.store_pair(pair).unwrap();
+        assert_eq!(node.stored_pairs[0].slot, Some(1));
+        assert_eq!(node.stored_pairs[0].coherence_time_ms, 50.0);
+
+        let handle = node.stored_pairs[0].handle();
+        node.remove_pair_at(handle, 6.0);
+        let stats = node.slot_statistics(10.0);
+        assert_eq!(stats[1].stores, 1);
+        assert_eq!(stats[1].busy_time_ms, 4.0);
+        assert_eq!(stats[1].idle_time_ms, 6.0);
+        assert_eq!(stats[0].stores, 0);
+        assert!(node.slots().iter().all(MemorySlot::is_free));
+    }
+
     #[test]
     fn test_clear_memory() {
         let mut node = QuantumNode::new(0, 5);
@@ -221,8 +1355,70 @@ mod tests {
 
         assert_eq!(node.num_stored_pairs(), 2);
 
-        node.clear_memory();
+        node.clear_memory(1.0);
         assert_eq!(node.num_stored_pairs(), 0);
         assert_eq!(node.free_memory(), 5);
     }
+
+    #[test]
+    fn test_iter_pairs_decoheres_without_mutating() {
+        let mut node = QuantumNode::with_slots(3, &[100.0, 40.0]);
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        node.store_pair(StoredPair::new(1, bell_state.clone(), 0.0, 100.0))
+            .unwrap();
+        node.store_pair(StoredPair::new(2, bell_state, 5.0, 100.0))
+            .unwrap();
+        let stored: Vec<f64> = node.stored_pairs.iter().map(|pair| pair.fidelity).collect();
+
+        let views: Vec<PairView> = node.iter_pairs(20.0).collect();
+        assert_eq!(views.len(), 2);
+        for (view, pair) in views.iter().zip(&node.stored_pairs) {
+            assert_eq!(view.fidelity, pair.fidelity_at(20.0));
+            assert_eq!(view.node_id, 3);
+            assert_eq!(view.partner_node_id, pair.partner_node_id);
+            assert_eq!(view.slot, pair.slot);
+            assert!(!view.correction_pending);
+        }
+        assert_eq!(
+            [views[0].handle, views[1].handle],
+            [node.stored_pairs[0].handle(), node.stored_pairs[1].handle()]
+        );
+        assert_eq!((views[0].age_ms, views[1].age_ms), (20.0, 15.0));
+        assert!(views[1].fidelity < views[0].fidelity);
+
+        let after: Vec<f64> = node.stored_pairs.iter().map(|pair| pair.fidelity).collect();
+        assert_eq!(after, stored);
+    }
+
+    #[test]
+    fn test_purge_frees_decohered_memory() {
+        let mut node = QuantumNode::new(0, 4);
+        for partner in 1..=4 {
+            node.store_pair(StoredPair::new_with_fidelity(partner, 0.99, 0.0, 10.0))
+                .unwrap();
+        }
+        assert!(!node.has_memory_available());
+        assert_eq!(node.purge_expired(5.0, Cutoff::MinFidelity(0.5)), 0);
+
+        // Past five coherence times the pairs are close to fully mixed
+        assert_eq!(node.purge_expired(51.0, Cutoff::MinFidelity(0.5)), 4);
+        assert_eq!(node.free_memory(), 4);
+    }
+
+    #[test]
+    fn test_max_age_cutoff() {
+        let mut node = QuantumNode::new(0, 3);
+        for creation_time in [0.0, 10.0, 20.0] {
+            node.store_pair(StoredPair::new_with_fidelity(
+                1,
+                1.0,
+                creation_time,
+                f64::INFINITY,
+            ))
+            .unwrap();
+        }
+        assert_eq!(node.purge_expired(25.0, Cutoff::MaxAge(20.0)), 1);
+        assert_eq!(node.stored_pairs[0].creation_time, 10.0);
+        assert_eq!(node.num_stored_pairs(), 2);
+    }
 }
@@ -1,16 +1,83 @@
+use crate::format::{format_probability, format_time};
 use crate::network::node::StoredPair;
-use crate::network::{QuantumChannel, QuantumNode};
-use crate::quantum::TwoQubitState;
+use crate::network::{LinkChannel, QuantumChannel, QuantumNode};
+use crate::quantum::fidelity_after_depolarization;
+use rand::Rng;
 
 /// Attempt to generate an entangled pair between two nodes
 ///
 /// Returns Ok(true) if generation succeeded, Ok(false) if failed due to channel loss
 pub fn attempt_entanglement_generation(
+    node_a: &mut QuantumNode,
+    node_b: &mut QuantumNode,
+    channel: &impl LinkChannel,
+    current_time: f64,
+    coherence_time_ms: f64,
+) -> Result<bool, String> {
+    generate_pair(
+        node_a,
+        node_b,
+        channel.depolarization_probability(),
+        current_time,
+        coherence_time_ms,
+        [None, None],
+        || channel.attempt_generation_at(current_time),
+    )
+}
+
+/// `attempt_entanglement_generation` drawing from the caller's RNG
+/// One draw per attempt that reaches the channel
+pub fn attempt_entanglement_generation_with_rng(
+    node_a: &mut QuantumNode,
+    node_b: &mut QuantumNode,
+    channel: &QuantumChannel,
+    current_time: f64,
+    coherence_time_ms: f64,
+    rng: &mut impl Rng,
+) -> Result<bool, String> {
+    attempt_entanglement_generation_with_hints(
+        node_a,
+        node_b,
+        channel,
+        current_time,
+        coherence_time_ms,
+        [None, None],
+        rng,
+    )
+}
+
+/// `attempt_entanglement_generation_with_rng` telling each node's slot
+/// strategy how long its half is expected to wait
+/// (see `expected_wait_from_path_position`)
+pub fn attempt_entanglement_generation_with_hints(
     node_a: &mut QuantumNode,
     node_b: &mut QuantumNode,
     channel: &QuantumChannel,
     current_time: f64,
     coherence_time_ms: f64,
+    expected_waits_ms: [Option<f64>; 2],
+    rng: &mut impl Rng,
+) -> Result<bool, String> {
+    generate_pair(
+        node_a,
+        node_b,
+        channel.depolarization_probability(),
+        current_time,
+        coherence_time_ms,
+        expected_waits_ms,
+        || channel.attempt_generation_at_with_rng(current_time, rng),
+    )
+}
+
+/// Store a pair at both nodes if memory allows and `attempt` succeeds
+fn generate_pair(
+    node_a: &mut QuantumNode,
+    node_b: &mut QuantumNode,
+    depolarization_probability: f64,
+    current_time: f64,
+    coherence_time_ms: f64,
+    [wait_a_ms, wait_b_ms]: [Option<f64>; 2],
+    attempt: impl FnOnce() -> bool,
 ) -> Result<bool, String> {
     // Check if both nodes have memory available
     if !node_a.has_memory_available() {
@@ -21,37 +88,41 @@ pub fn attempt_entanglement_generation(
     }
 
     // Attempt generation based on channel success probability
-    let success = channel.attempt_generation();
-
-    if success {
-        // Generate Bell pair |Φ+⟩ = (|00⟩ + |11⟩)/√2
-        let bell_state = TwoQubitState::new_bell_phi_plus();
-
-        // Store in both nodes
-        let pair_a = StoredPair::new(
-            node_b.id,
-            bell_state.clone(),
-            current_time,
-            coherence_time_ms,
-        );
-        let pair_b = StoredPair::new(node_a.id, bell_state, current_time, coherence_time_ms);
+    if !attempt() {
+        return Ok(false);
+    }
 
-        node_a.store_pair(pair_a)?;
-        node_b.store_pair(pair_b)?;
+    // One photon crosses the channel
+    let fidelity = fidelity_after_depolarization(1.0, depolarization_probability);
+    let pair_a =
+        StoredPair::new_with_fidelity(node_b.id, fidelity, current_time, coherence_time_ms);
+    let pair_b =
+        StoredPair::new_with_fidelity(node_a.id, fidelity, current_time, coherence_time_ms)
+            .with_pair_id(pair_a.pair_id);
 
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+    node_a.store_pair_with_hint(pair_a, wait_a_ms)?;
+    node_b.store_pair_with_hint(pair_b, wait_b_ms)?;
+
+    Ok(true)
 }
 
 /// Statistics for entanglement generation experiments
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GenerationStats {
     pub attempts: usize,
     pub successes: usize,
     pub channel_failures: usize,
     pub memory_full_errors: usize,
+    /// Simulated time the link spent idle because of retry backoff
+    pub backoff_idle_time: f64,
+    /// Pairs handed to a consumer
+    pub deliveries: usize,
+    /// Summed time delivered pairs spent in memory (ms)
+    pub total_storage_time_ms: f64,
+    /// Pairs dropped by a cutoff before anyone consumed them
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub expired: usize,
 }
 
 impl GenerationStats {
@@ -59,6 +130,46 @@ impl GenerationStats {
         Self::default()
     }
 
+    /// Count one attempt by the result of `attempt_entanglement_generation`
+    pub fn record(&mut self, result: &Result<bool, String>) {
+        self.attempts += 1;
+        match result {
+            Ok(true) => self.successes += 1,
+            Ok(false) => self.channel_failures += 1,
+            Err(_) => self.memory_full_errors += 1,
+        }
+    }
+
+    /// Add another set of counters to these
+    pub fn merge(&mut self, other: &GenerationStats) {
+        self.attempts += other.attempts;
+        self.successes += other.successes;
+        self.channel_failures += other.channel_failures;
+        self.memory_full_errors += other.memory_full_errors;
+        self.backoff_idle_time += other.backoff_idle_time;
+        self.deliveries += other.deliveries;
+        self.total_storage_time_ms += other.total_storage_time_ms;
+        self.expired += other.expired;
+    }
+
+    /// Counters accumulated after `earlier`, a previous reading of these stats
+    pub fn since(&self, earlier: &GenerationStats) -> GenerationStats {
+        GenerationStats {
+            attempts: self.attempts.saturating_sub(earlier.attempts),
+            successes: self.successes.saturating_sub(earlier.successes),
+            channel_failures: self
+                .channel_failures
+                .saturating_sub(earlier.channel_failures),
+            memory_full_errors: self
+                .memory_full_errors
+                .saturating_sub(earlier.memory_full_errors),
+            backoff_idle_time: self.backoff_idle_time - earlier.backoff_idle_time,
+            deliveries: self.deliveries.saturating_sub(earlier.deliveries),
+            total_storage_time_ms: self.total_storage_time_ms - earlier.total_storage_time_ms,
+            expired: self.expired.saturating_sub(earlier.expired),
+        }
+    }
+
     pub fn success_rate(&self) -> f64 {
         if self.attempts == 0 {
             0.0
@@ -67,17 +178,41 @@ impl GenerationStats {
         }
     }
 
+    /// Mean time delivered pairs spent in memory (0 if none delivered)
+    pub fn mean_storage_time_ms(&self) -> f64 {
+        if self.deliveries == 0 {
+            0.0
+        } else {
+            self.total_storage_time_ms / self.deliveries as f64
+        }
+    }
+
     pub fn print_summary(&self) {
-        println!("\n=== Entanglement Generation Statistics ===");
-        println!("Total attempts:     {}", self.attempts);
-        println!(
-            "Successful:         {} ({:.1}%)",
+        println!("\n{}", self.summary());
+    }
+
+    /// Text printed by `print_summary`
+    pub fn summary(&self) -> String {
+        let mut text = String::from("=== Entanglement Generation Statistics ===\n");
+        text.push_str(&format!("Total attempts:     {}\n", self.attempts));
+        text.push_str(&format!(
+            "Successful:         {} ({})\n",
             self.successes,
-            self.success_rate() * 100.0
-        );
-        println!("Channel failures:   {}", self.channel_failures);
-        println!("Memory full:        {}", self.memory_full_errors);
-        println!("==========================================\n");
+            format_probability(self.success_rate())
+        ));
+        text.push_str(&format!("Channel failures:   {}\n", self.channel_failures));
+        text.push_str(&format!(
+            "Memory full:        {}\n",
+            self.memory_full_errors
+        ));
+        text.push_str(&format!(
+            "Backoff idle time:  {}\n",
+            format_time(self.backoff_idle_time)
+        ));
+        text.push_str(&format!("Deliveries:         {}\n", self.deliveries));
+        text.push_str(&format!("Expired:            {}\n", self.expired));
+        text.push_str("==========================================\n");
+        text
     }
 }
 
@@ -85,6 +220,8 @@ impl GenerationStats {
 mod tests {
     use super::*;
     use crate::network::channel::QuantumChannel;
+    use crate::quantum::TwoQubitState;
+    use crate::testing::FakeChannel;
 
     #[test]
     fn test_successful_generation() {
@@ -102,11 +239,60 @@ mod tests {
     }
 
     #[test]
-    fn test_channel_loss() {
+    fn test_consuming_one_of_two_pairs_keeps_halves_matched() {
         let mut node_a = QuantumNode::new(0, 10);
         let mut node_b = QuantumNode::new(1, 10);
-        // Very lossy channel
-        let channel = QuantumChannel::new(0, 1, 100.0, 0.5);
+        let channel = QuantumChannel::new(0, 1, 0.0, 0.0);
+        for time in [0.0, 5.0] {
+            attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, time, 100.0)
+                .unwrap();
+        }
+        let ids: Vec<u64> = node_a
+            .find_pairs_with(1)
+            .iter()
+            .map(|pair| pair.pair_id)
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert_ne!(ids[0], ids[1]);
+        assert!(ids.iter().all(|&id| node_b.find_pair_by_id(id).is_some()));
+
+        // Consume the older pair at A, then exactly its partner half at B
+        let consumed = node_a.remove_pair_by_id(ids[0], 6.0).unwrap();
+        let partner_half = node_b.remove_pair_by_id(consumed.pair_id, 6.0).unwrap();
+        assert_eq!(partner_half.creation_time, 0.0);
+        assert_eq!(node_a.stored_pairs()[0].pair_id, ids[1]);
+        assert_eq!(node_b.stored_pairs()[0].pair_id, ids[1]);
+        assert!(node_b.remove_pair_by_id(ids[0], 6.0).is_none());
+    }
+
+    #[test]
+    fn test_depolarizing_channel_mixes_stored_state() {
+        let mut node_a = QuantumNode::new(0, 10);
+        let mut node_b = QuantumNode::new(1, 10);
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.0)
+            .with_depolarization(0.01)
+            .unwrap();
+        attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0).unwrap();
+
+        let pair = &node_a.stored_pairs()[0];
+        let rho = pair.density_matrix.as_ref().unwrap();
+        let bell = TwoQubitState::new_bell_phi_plus();
+        assert!((rho.fidelity_with_pure(&bell) - pair.fidelity).abs() < 1e-12);
+        assert!(rho.purity() < 1.0);
+        assert!((pair.state.fidelity(&bell) - pair.fidelity).abs() < 1e-12);
+
+        // A noiseless channel keeps the pure state only
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.0);
+        attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0).unwrap();
+        assert!(node_a.stored_pairs()[1].density_matrix.is_none());
+    }
+
+    #[test]
+    fn test_channel_loss() {
+        let node_a = QuantumNode::new(0, 10);
+        let node_b = QuantumNode::new(1, 10);
+        // Lossy channel (5 dB total)
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.5);
 
         let mut successes = 0;
         let attempts = 100;
@@ -147,4 +333,66 @@ mod tests {
             attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0);
         assert!(result2.is_err());
     }
+
+    #[test]
+    fn test_channel_loss_scripted() {
+        let node_a = QuantumNode::new(0, 10);
+        let node_b = QuantumNode::new(1, 10);
+        let channel = FakeChannel::scripted(vec![true, false, false, true, false]);
+
+        let mut successes = 0;
+        for _ in 0..10 {
+            let mut test_node_a = node_a.clone();
+            let mut test_node_b = node_b.clone();
+            if let Ok(true) = attempt_entanglement_generation(
+                &mut test_node_a,
+                &mut test_node_b,
+                &channel,
+                0.0,
+                100.0,
+            ) {
+                successes += 1;
+            }
+        }
+
+        // Script repeats: exactly 2 successes per 5 attempts
+        assert_eq!(successes, 4);
+    }
+
+    #[test]
+    fn test_memory_full_scripted() {
+        let mut node_a = QuantumNode::new(0, 1);
+        let mut node_b = QuantumNode::new(1, 10);
+        let channel = FakeChannel::scripted(vec![false, true]);
+
+        // Failure leaves memory untouched, success fills node A's only slot
+        assert_eq!(
+            attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0),
+            Ok(false)
+        );
+        assert_eq!(
+            attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, 1.0, 100.0),
+            Ok(true)
+        );
+
+        // Memory is checked before the channel is sampled
+        let result =
+            attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, 2.0, 100.0);
+        assert_eq!(result, Err("Node 0 memory full".to_string()));
+        assert_eq!(channel.attempts(), 2);
+    }
+
+    #[test]
+    fn test_summary_formats_rate_and_idle_time() {
+        let stats = GenerationStats {
+            attempts: 8,
+            successes: 5,
+            channel_failures: 3,
+            backoff_idle_time: 0.0125,
+            ..GenerationStats::default()
+        };
+        let summary = stats.summary();
+        assert!(summary.contains("Successful:         5 (62.5%)"));
+        assert!(summary.contains("Backoff idle time:  12.5 µs"));
+    }
 }
@@ -0,0 +1,96 @@
+use super::node::{PairHandle, StoredPair};
+
+/// Read-only view of one stored pair half at a given time
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairView {
+    /// Node holding this half
+    pub node_id: usize,
+    pub handle: PairHandle,
+    pub partner_node_id: usize,
+    /// Time the pair was created (ms)
+    pub creation_time: f64,
+    /// Time spent in memory at the view time (ms)
+    pub age_ms: f64,
+    /// Fidelity decohered to the view time
+    pub fidelity: f64,
+    /// Physical slot holding this half (nodes with a slot layout only)
+    pub slot: Option<usize>,
+    /// A Pauli correction is still owed by the end nodes
+    pub correction_pending: bool,
+}
+
+impl PairView {
+    pub(crate) fn new(node_id: usize, pair: &StoredPair, current_time: f64) -> Self {
+        PairView {
+            node_id,
+            handle: pair.handle(),
+            partner_node_id: pair.partner_node_id,
+            creation_time: pair.creation_time,
+            age_ms: current_time - pair.creation_time,
+            fidelity: pair.fidelity_at(current_time),
+            slot: pair.slot,
+            correction_pending: !pair.pauli_frame.is_identity(),
+        }
+    }
+}
+
+/// Distribution of pair fidelities at one time
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FidelitySummary {
+    pub count: usize,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    /// 10th percentile
+    pub p10: f64,
+    pub median: f64,
+    /// 90th percentile
+    pub p90: f64,
+}
+
+impl FidelitySummary {
+    /// Summary of `fidelities`; None if empty
+    /// Percentiles interpolate linearly between the closest ranks
+    pub fn from_samples(fidelities: &[f64]) -> Option<Self> {
+        if fidelities.is_empty() {
+            return None;
+        }
+        let mut sorted = fidelities.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let percentile = |q: f64| {
+            let rank = q * (sorted.len() - 1) as f64;
+            let below = rank.floor() as usize;
+            let above = rank.ceil() as usize;
+            sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64)
+        };
+        Some(FidelitySummary {
+            count: sorted.len(),
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            p10: percentile(0.1),
+            median: percentile(0.5),
+            p90: percentile(0.9),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_percentiles_interpolate() {
+        let summary = FidelitySummary::from_samples(&[0.9, 0.5, 0.7, 0.6, 0.8]).unwrap();
+        assert_eq!(summary.count, 5);
+        assert!((summary.mean - 0.7).abs() < 1e-12);
+        assert_eq!((summary.min, summary.max), (0.5, 0.9));
+        assert_eq!(summary.median, 0.7);
+        // Rank 0.4 between 0.5 and 0.6, rank 3.6 between 0.8 and 0.9
+        assert!((summary.p10 - 0.54).abs() < 1e-12);
+        assert!((summary.p90 - 0.86).abs() < 1e-12);
+        assert_eq!(FidelitySummary::from_samples(&[]), None);
+    }
+}
@@ -0,0 +1,190 @@
+/// Delay strategy applied once a link exceeds its failure threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backoff {
+    /// Keep attempting at the normal rate
+    None,
+    /// Wait a fixed extra time before each retry
+    Fixed(f64),
+    /// Double the extra wait after each failure: base, 2*base, 4*base ... up to cap
+    Exponential { base: f64, cap: f64 },
+}
+
+/// Retry behaviour of a link after repeated generation failures
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetryPolicy {
+    /// Consecutive failures tolerated before backing off (None = never back off)
+    pub max_consecutive_failures: Option<u32>,
+    /// Delay strategy once the threshold is reached
+    pub backoff: Backoff,
+}
+
+impl RetryPolicy {
+    /// Never back off (attempt at full rate regardless of failures)
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_consecutive_failures: None,
+            backoff: Backoff::None,
+        }
+    }
+
+    /// Check the backoff delays are finite and non-negative
+    pub fn validate(&self) -> Result<(), String> {
+        let delays = match self.backoff {
+            Backoff::None => return Ok(()),
+            Backoff::Fixed(delay) => [delay, delay],
+            Backoff::Exponential { base, cap } => [base, cap],
+        };
+        if delays
+            .iter()
+            .all(|delay| delay.is_finite() && *delay >= 0.0)
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "Backoff delays must be finite and non-negative, got {:?}",
+                self.backoff
+            ))
+        }
+    }
+
+    /// Extra delay before the next attempt after `consecutive_failures` failures in a row
+    pub fn delay_after(&self, consecutive_failures: u32) -> f64 {
+        let threshold = match self.max_consecutive_failures {
+            Some(threshold) => threshold.max(1),
+            None => return 0.0,
+        };
+        if consecutive_failures < threshold {
+            return 0.0;
+        }
+
+        match self.backoff {
+            Backoff::None => 0.0,
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { base, cap } => {
+                // Number of failures past the threshold (0 for the first backoff)
+                let exponent = (consecutive_failures - threshold).min(63) as i32;
+                (base * 2.0_f64.powi(exponent)).min(cap)
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Per-link retry bookkeeping, driven by the caller after every attempt
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkRetryState {
+    /// Failures since the last success
+    pub consecutive_failures: u32,
+    /// Total simulated time spent waiting because of backoff
+    pub idle_time: f64,
+}
+
+impl LinkRetryState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of an attempt and return the extra delay before the next one
+    pub fn record_attempt(&mut self, success: bool, policy: &RetryPolicy) -> f64 {
+        if success {
+            self.consecutive_failures = 0;
+            return 0.0;
+        }
+
+        self.consecutive_failures += 1;
+        let delay = policy.delay_after(self.consecutive_failures);
+        self.idle_time += delay;
+        delay
+    }
+
+    /// Time of the next attempt, given the nominal attempt interval
+    pub fn next_attempt_time(
+        &mut self,
+        current_time: f64,
+        attempt_interval: f64,
+        success: bool,
+        policy: &RetryPolicy,
+    ) -> f64 {
+        current_time + attempt_interval + self.record_attempt(success, policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{Event, EventScheduler, EventType};
+
+    #[test]
+    fn test_no_policy_never_delays() {
+        let policy = RetryPolicy::none();
+        let mut state = LinkRetryState::new();
+
+        for _ in 0..10 {
+            assert_eq!(state.record_attempt(false, &policy), 0.0);
+        }
+        assert_eq!(state.idle_time, 0.0);
+    }
+
+    #[test]
+    fn test_fixed_backoff_after_threshold() {
+        let policy = RetryPolicy {
+            max_consecutive_failures: Some(3),
+            backoff: Backoff::Fixed(5.0),
+        };
+        let mut state = LinkRetryState::new();
+
+        assert_eq!(state.record_attempt(false, &policy), 0.0);
+        assert_eq!(state.record_attempt(false, &policy), 0.0);
+        assert_eq!(state.record_attempt(false, &policy), 5.0);
+        assert_eq!(state.record_attempt(false, &policy), 5.0);
+        assert_eq!(state.idle_time, 10.0);
+    }
+
+    #[test]
+    fn test_exponential_backoff_from_event_trace() {
+        let policy = RetryPolicy {
+            max_consecutive_failures: Some(1),
+            backoff: Backoff::Exponential {
+                base: 1.0,
+                cap: 8.0,
+            },
+        };
+        let attempt_interval = 1.0;
+
+        // Scripted outcomes: six failures, one success, then one failure
+        let outcomes = [false, false, false, false, false, false, true, false];
+
+        let mut scheduler = EventScheduler::new();
+        let mut state = LinkRetryState::new();
+        let mut trace = Vec::new();
+
+        scheduler.schedule(Event::new(0.0, EventType::EntanglementGeneration, 0));
+        while let Some(event) = scheduler.next_event() {
+            trace.push(event.time);
+            if trace.len() > outcomes.len() {
+                break;
+            }
+            let success = outcomes[trace.len() - 1];
+            let next = state.next_attempt_time(event.time, attempt_interval, success, &policy);
+            scheduler.schedule(Event::new(next, EventType::EntanglementGeneration, 0));
+        }
+
+        let gaps: Vec<f64> = trace.windows(2).map(|w| w[1] - w[0]).collect();
+
+        // Gap doubles after each failure (1+1, 1+2, 1+4, 1+8) and stops at the cap
+        assert_eq!(gaps[..6], [2.0, 3.0, 5.0, 9.0, 9.0, 9.0]);
+        // Success resets to the nominal interval, next failure restarts at base
+        assert_eq!(gaps[6], 1.0);
+        assert_eq!(gaps[7], 2.0);
+
+        assert_eq!(state.idle_time, 1.0 + 2.0 + 4.0 + 8.0 + 8.0 + 8.0 + 1.0);
+    }
+}
@@ -0,0 +1,157 @@
+/// One physical memory slot with its own coherence time
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemorySlot {
+    /// Coherence time of pairs held in this slot (ms)
+    pub coherence_time_ms: f64,
+    /// Time the current pair was stored, if occupied
+    pub occupied_since: Option<f64>,
+    /// Number of pairs stored in this slot so far
+    pub stores: usize,
+    /// Occupied time accumulated by released pairs (ms)
+    pub busy_time_ms: f64,
+}
+
+impl MemorySlot {
+    pub fn new(coherence_time_ms: f64) -> Self {
+        MemorySlot {
+            coherence_time_ms,
+            occupied_since: None,
+            stores: 0,
+            busy_time_ms: 0.0,
+        }
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.occupied_since.is_none()
+    }
+
+    pub(crate) fn occupy(&mut self, time: f64) {
+        self.occupied_since = Some(time);
+        self.stores += 1;
+    }
+
+    /// Free the slot at `time`, accruing the time it was occupied
+    pub(crate) fn release(&mut self, time: f64) {
+        if let Some(since) = self.occupied_since {
+            self.busy_time_ms += (time - since).max(0.0);
+        }
+        self.occupied_since = None;
+    }
+}
+
+/// Occupancy statistics of one slot up to some time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlotStats {
+    pub coherence_time_ms: f64,
+    pub stores: usize,
+    /// Time spent holding a pair (ms)
+    pub busy_time_ms: f64,
+    /// Time spent empty (ms)
+    pub idle_time_ms: f64,
+}
+
+/// Picks the slot a new pair is stored in
+pub trait SlotAssignmentStrategy {
+    /// Choose one of `free` (indices into `slots`)
+    /// `expected_wait_ms` is the caller's estimate of how long the pair will be held
+    fn choose(
+        &self,
+        slots: &[MemorySlot],
+        free: &[usize],
+        expected_wait_ms: Option<f64>,
+    ) -> Option<usize>;
+}
+
+/// Lowest-index free slot
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstFree;
+
+impl SlotAssignmentStrategy for FirstFree {
+    fn choose(
+        &self,
+        _slots: &[MemorySlot],
+        free: &[usize],
+        _expected_wait_ms: Option<f64>,
+    ) -> Option<usize> {
+        free.first().copied()
+    }
+}
+
+/// Free slot with the longest coherence time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BestCoherenceFirst;
+
+impl SlotAssignmentStrategy for BestCoherenceFirst {
+    fn choose(
+        &self,
+        slots: &[MemorySlot],
+        free: &[usize],
+        _expected_wait_ms: Option<f64>,
+    ) -> Option<usize> {
+        longest_coherence(slots, free)
+    }
+}
+
+/// Short waits go to the worst free slot, saving long-coherence slots
+///
+/// Pairs with an expected wait below `expected_wait_hint` (ms) take the
+/// shortest-coherence free slot; longer or unknown waits take the longest
+#[derive(Debug, Clone, Copy)]
+pub struct WorstFitForShortWaits {
+    pub expected_wait_hint: f64,
+}
+
+impl SlotAssignmentStrategy for WorstFitForShortWaits {
+    fn choose(
+        &self,
+        slots: &[MemorySlot],
+        free: &[usize],
+        expected_wait_ms: Option<f64>,
+    ) -> Option<usize> {
+        match expected_wait_ms {
+            Some(wait) if wait < self.expected_wait_hint => {
+                free.iter().copied().min_by(|&a, &b| {
+                    slots[a]
+                        .coherence_time_ms
+                        .total_cmp(&slots[b].coherence_time_ms)
+                })
+            }
+            _ => longest_coherence(slots, free),
+        }
+    }
+}
+
+/// Ties go to the lowest index
+fn longest_coherence(slots: &[MemorySlot], free: &[usize]) -> Option<usize> {
+    free.iter().copied().reduce(|best, index| {
+        if slots[index].coherence_time_ms > slots[best].coherence_time_ms {
+            index
+        } else {
+            best
+        }
+    })
+}
+
+/// Expected wait of a pair at `position` on a path of `path_len` nodes
+///
+/// Heuristic for routing: a pair is held until the farther end of the path
+/// is ready, roughly one `per_hop_ms` per remaining hop
+pub fn expected_wait_from_path_position(position: usize, path_len: usize, per_hop_ms: f64) -> f64 {
+    let last = path_len.saturating_sub(1);
+    let hops_to_far_end = position.max(last.saturating_sub(position));
+    hops_to_far_end as f64 * per_hop_ms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_wait_from_path_position() {
+        // 5-node path: ends wait for 4 hops, the middle node for 2
+        assert_eq!(expected_wait_from_path_position(0, 5, 1.5), 6.0);
+        assert_eq!(expected_wait_from_path_position(2, 5, 1.5), 3.0);
+        assert_eq!(expected_wait_from_path_position(4, 5, 1.5), 6.0);
+    }
+}
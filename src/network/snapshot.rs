@@ -0,0 +1,334 @@
+use super::topology::ChannelId;
+use super::NetworkTopology;
+
+/// Parameters of one channel at snapshot time
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelSnapshot {
+    pub id: ChannelId,
+    pub node_a: usize,
+    pub node_b: usize,
+    pub distance_km: f64,
+    pub attenuation_db_per_km: f64,
+    pub depolarization_per_km: f64,
+}
+
+/// Parameters and memory occupancy of one node at snapshot time
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeSnapshot {
+    pub id: usize,
+    pub memory_capacity: usize,
+    pub gate_error: f64,
+    /// Number of stored pairs
+    pub occupancy: usize,
+}
+
+/// Copy of a topology's structure, parameters and memory occupancy
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopologySnapshot {
+    pub nodes: Vec<NodeSnapshot>,
+    pub channels: Vec<ChannelSnapshot>,
+}
+
+/// One difference between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub enum TopologyChange {
+    NodeAdded(usize),
+    NodeRemoved(usize),
+    ChannelAdded {
+        node_a: usize,
+        node_b: usize,
+    },
+    ChannelRemoved {
+        node_a: usize,
+        node_b: usize,
+    },
+    /// A numeric parameter of a node or channel changed
+    ParameterChanged {
+        /// "node 3" or "channel 1-2"
+        element: String,
+        parameter: &'static str,
+        old: f64,
+        new: f64,
+    },
+    /// Number of stored pairs at a node changed
+    OccupancyChanged {
+        node_id: usize,
+        old: usize,
+        new: usize,
+    },
+}
+
+/// Changes from one snapshot to a later one
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TopologyDiff {
+    pub changes: Vec<TopologyChange>,
+}
+
+#[cfg(feature = "serde")]
+impl TopologySnapshot {
+    /// Write as a versioned JSON file (`io::Format::TopologySnapshot`)
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        crate::io::save_versioned(crate::io::Format::TopologySnapshot, self, path)
+    }
+
+    /// Read a file written by `save`
+    pub fn load(path: &str) -> Result<Self, String> {
+        crate::io::load_versioned(crate::io::Format::TopologySnapshot, path)
+    }
+}
+
+impl TopologySnapshot {
+    /// Capture the current state of a topology
+    pub fn capture(topology: &NetworkTopology) -> Self {
+        TopologySnapshot {
+            nodes: topology
+                .nodes()
+                .iter()
+                .map(|node| NodeSnapshot {
+                    id: node.id,
+                    memory_capacity: node.memory_capacity,
+                    gate_error: node.gate_error,
+                    occupancy: node.num_stored_pairs(),
+                })
+                .collect(),
+            channels: topology
+                .iter_channels()
+                .map(|(id, channel)| ChannelSnapshot {
+                    id,
+                    node_a: channel.node_a,
+                    node_b: channel.node_b,
+                    distance_km: channel.distance_km,
+                    attenuation_db_per_km: channel.attenuation_db_per_km,
+                    depolarization_per_km: channel.depolarization_per_km,
+                })
+                .collect(),
+        }
+    }
+
+    /// Changes needed to go from `self` to `other`
+    pub fn diff(&self, other: &TopologySnapshot) -> TopologyDiff {
+        let mut changes = Vec::new();
+
+        for old in &self.nodes {
+            match other.nodes.iter().find(|node| node.id == old.id) {
+                None => changes.push(TopologyChange::NodeRemoved(old.id)),
+                Some(new) => {
+                    let element = format!("node {}", old.id);
+                    let parameters = [
+                        (
+                            "memory_capacity",
+                            old.memory_capacity as f64,
+                            new.memory_capacity as f64,
+                        ),
+                        ("gate_error", old.gate_error, new.gate_error),
+                    ];
+                    push_parameter_changes(&mut changes, &element, &parameters);
+                    if old.occupancy != new.occupancy {
+                        changes.push(TopologyChange::OccupancyChanged {
+                            node_id: old.id,
+                            old: old.occupancy,
+                            new: new.occupancy,
+                        });
+                    }
+                }
+            }
+        }
+        for new in &other.nodes {
+            if !self.nodes.iter().any(|node| node.id == new.id) {
+                changes.push(TopologyChange::NodeAdded(new.id));
+            }
+        }
+
+        for old in &self.channels {
+            match other.channels.iter().find(|channel| channel.id == old.id) {
+                None => changes.push(TopologyChange::ChannelRemoved {
+                    node_a: old.node_a,
+                    node_b: old.node_b,
+                }),
+                Some(new) => {
+                    let element = format!("channel {}-{}", old.node_a, old.node_b);
+                    let parameters = [
+                        ("distance_km", old.distance_km, new.distance_km),
+                        (
+                            "attenuation_db_per_km",
+                            old.attenuation_db_per_km,
+                            new.attenuation_db_per_km,
+                        ),
+                        (
+                            "depolarization_per_km",
+                            old.depolarization_per_km,
+                            new.depolarization_per_km,
+                        ),
+                    ];
+                    push_parameter_changes(&mut changes, &element, &parameters);
+                }
+            }
+        }
+        for new in &other.channels {
+            if !self.channels.iter().any(|channel| channel.id == new.id) {
+                changes.push(TopologyChange::ChannelAdded {
+                    node_a: new.node_a,
+                    node_b: new.node_b,
+                });
+            }
+        }
+
+        TopologyDiff { changes }
+    }
+}
+
+fn push_parameter_changes(
+    changes: &mut Vec<TopologyChange>,
+    element: &str,
+    parameters: &[(&'static str, f64, f64)],
+) {
+    for &(parameter, old, new) in parameters {
+        if old != new {
+            changes.push(TopologyChange::ParameterChanged {
+                element: element.to_string(),
+                parameter,
+                old,
+                new,
+            });
+        }
+    }
+}
+
+impl TopologyDiff {
+    /// Check if the snapshots were identical
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// One line per change
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for change in &self.changes {
+            let line = match change {
+                TopologyChange::NodeAdded(id) => format!("+ node {}", id),
+                TopologyChange::NodeRemoved(id) => format!("- node {}", id),
+                TopologyChange::ChannelAdded { node_a, node_b } => {
+                    format!("+ channel {}-{}", node_a, node_b)
+                }
+                TopologyChange::ChannelRemoved { node_a, node_b } => {
+                    format!("- channel {}-{}", node_a, node_b)
+                }
+                TopologyChange::ParameterChanged {
+                    element,
+                    parameter,
+                    old,
+                    new,
+                } => format!("~ {} {}: {} -> {}", element, parameter, old, new),
+                TopologyChange::OccupancyChanged { node_id, old, new } => format!(
+                    "~ node {} occupancy: {} -> {} ({:+})",
+                    node_id,
+                    old,
+                    new,
+                    *new as i64 - *old as i64
+                ),
+            };
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text
+    }
+
+    /// JSON array with one object per change
+    pub fn to_json(&self) -> String {
+        let entries: Vec<String> = self
+            .changes
+            .iter()
+            .map(|change| match change {
+                TopologyChange::NodeAdded(id) => {
+                    format!("{{\"change\":\"node_added\",\"node_id\":{}}}", id)
+                }
+                TopologyChange::NodeRemoved(id) => {
+                    format!("{{\"change\":\"node_removed\",\"node_id\":{}}}", id)
+                }
+                TopologyChange::ChannelAdded { node_a, node_b } => format!(
+                    "{{\"change\":\"channel_added\",\"node_a\":{},\"node_b\":{}}}",
+                    node_a, node_b
+                ),
+                TopologyChange::ChannelRemoved { node_a, node_b } => format!(
+                    "{{\"change\":\"channel_removed\",\"node_a\":{},\"node_b\":{}}}",
+                    node_a, node_b
+                ),
+                TopologyChange::ParameterChanged {
+                    element,
+                    parameter,
+                    old,
+                    new,
+                } => format!(
+                    "{{\"change\":\"parameter\",\"element\":\"{}\",\"parameter\":\"{}\",\"old\":{},\"new\":{}}}",
+                    element, parameter, old, new
+                ),
+                TopologyChange::OccupancyChanged { node_id, old, new } => format!(
+                    "{{\"change\":\"occupancy\",\"node_id\":{},\"old\":{},\"new\":{}}}",
+                    node_id, old, new
+                ),
+            })
+            .collect();
+        format!("[{}]", entries.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::attempt_entanglement_generation;
+
+    #[test]
+    fn test_identical_snapshots_have_empty_diff() {
+        let topology = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        let snapshot = topology.snapshot();
+        let diff = snapshot.diff(&topology.snapshot());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_json(), "[]");
+    }
+
+    #[test]
+    fn test_diff_after_outage_and_generation() {
+        let mut topology = NetworkTopology::new_linear(2, 2, 0.0, 0.2);
+        let before = topology.snapshot();
+
+        // Outage raises attenuation; a 0 km link still succeeds deterministically
+        topology
+            .find_channel_mut(0, 1)
+            .unwrap()
+            .1
+            .attenuation_db_per_km = 5.0;
+        let channel = topology.find_channel(0, 1).unwrap().1.clone();
+        let (node_a, node_b) = topology.get_node_pair_mut(0, 1).unwrap();
+        assert!(attempt_entanglement_generation(node_a, node_b, &channel, 1.0, 100.0).unwrap());
+
+        let diff = before.diff(&topology.snapshot());
+        assert_eq!(
+            diff.changes,
+            vec![
+                TopologyChange::OccupancyChanged {
+                    node_id: 0,
+                    old: 0,
+                    new: 1
+                },
+                TopologyChange::OccupancyChanged {
+                    node_id: 1,
+                    old: 0,
+                    new: 1
+                },
+                TopologyChange::ParameterChanged {
+                    element: "channel 0-1".to_string(),
+                    parameter: "attenuation_db_per_km",
+                    old: 0.2,
+                    new: 5.0
+                },
+            ]
+        );
+        assert!(diff.to_text().contains("occupancy: 0 -> 1 (+1)"));
+        assert!(diff
+            .to_json()
+            .contains("\"parameter\":\"attenuation_db_per_km\""));
+    }
+}
@@ -1,4 +1,13 @@
-use super::{QuantumChannel, QuantumNode};
+use super::snapshot::TopologySnapshot;
+use super::{
+    Cutoff, EntanglementGraph, FidelitySummary, NodeHardware, NodeRole, PairView, QuantumChannel,
+    QuantumNode, StoredPair,
+};
+use rand::Rng;
+use std::collections::{HashSet, VecDeque};
+
+/// Redraws allowed when a random topology must come out connected
+pub const MAX_RANDOM_TOPOLOGY_DRAWS: usize = 1000;
 
 /// Types of network topologies
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -6,13 +15,150 @@ pub enum TopologyType {
     Linear,
     Star,
     Mesh,
+    Ring,
+    Grid,
+    /// Generated by `new_random_gnp` or `new_waxman`
+    Random,
     Custom,
 }
 
+/// Identifier of a channel, assigned when it is added to a topology
+///
+/// A channel keeps its id until it is removed; a new channel takes the
+/// lowest free id, so topologies built by the same additions get the same
+/// ids whatever was added and removed in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct ChannelId(pub usize);
+
+impl std::fmt::Display for ChannelId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Structural problem found by `NetworkTopology::validate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopologyError {
+    /// A channel connects a node to itself
+    SelfLoop { channel: ChannelId, node: usize },
+    /// A channel repeats the endpoints and parameters of an earlier one
+    DuplicateChannel {
+        channel: ChannelId,
+        duplicate_of: ChannelId,
+    },
+    /// A node no channel connects to
+    IsolatedNode(usize),
+    /// A channel names a node the topology does not have
+    MissingNode { channel: ChannelId, node: usize },
+}
+
+impl std::fmt::Display for TopologyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TopologyError::SelfLoop { channel, node } => {
+                write!(f, "Channel {} connects node {} to itself", channel, node)
+            }
+            TopologyError::DuplicateChannel {
+                channel,
+                duplicate_of,
+            } => write!(f, "Channel {} duplicates channel {}", channel, duplicate_of),
+            TopologyError::IsolatedNode(node) => write!(f, "Node {} has no channels", node),
+            TopologyError::MissingNode { channel, node } => {
+                write!(f, "Channel {} references missing node {}", channel, node)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TopologyError {}
+
+/// Same endpoints (in either order) and the same physical parameters
+fn is_duplicate(first: &QuantumChannel, second: &QuantumChannel) -> bool {
+    let same_ends = (first.node_a, first.node_b) == (second.node_a, second.node_b)
+        || (first.node_a, first.node_b) == (second.node_b, second.node_a);
+    same_ends
+        && first.distance_km == second.distance_km
+        && first.attenuation_db_per_km == second.attenuation_db_per_km
+        && first.depolarization_per_km == second.depolarization_per_km
+        && first.refractive_index == second.refractive_index
+        && first.is_active == second.is_active
+        && first.attenuation_schedule == second.attenuation_schedule
+        && first.model == second.model
+        && first.coupling_efficiency == second.coupling_efficiency
+        && first.insertion_loss_db == second.insertion_loss_db
+        && first.num_modes == second.num_modes
+}
+
+/// How to pick among parallel channels between the same two nodes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChannelSelector {
+    /// A specific channel; hops it does not connect fall back to their
+    /// unique channel
+    ById(ChannelId),
+    /// Highest `success_probability` (ties: lowest id)
+    BestSuccessProbability,
+    /// Fewest recorded uses (ties: lowest id)
+    LeastLoaded,
+}
+
+/// Edge weight for weighted routing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathMetric {
+    /// Every channel costs 1
+    Hops,
+    /// Channel length in km
+    Distance,
+    /// -ln(success probability), so path cost is total transmission loss
+    Loss,
+}
+
+impl PathMetric {
+    /// Cost of crossing `channel`
+    pub fn cost(&self, channel: &QuantumChannel) -> f64 {
+        match self {
+            PathMetric::Hops => 1.0,
+            PathMetric::Distance => channel.distance_km,
+            PathMetric::Loss => -channel.success_probability().ln(),
+        }
+    }
+}
+
+/// One hop of a routed path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathHop {
+    pub from: usize,
+    pub to: usize,
+    /// Channel the hop uses
+    pub channel_id: ChannelId,
+}
+
+/// Result of routing between two nodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePath {
+    /// Nodes from source to destination
+    pub nodes: Vec<usize>,
+    pub hops: Vec<PathHop>,
+}
+
 /// Network topology containing nodes and channels
+///
+/// Channels are undirected: (a, b) and (b, a) name the same link.
+/// Several channels may connect the same nodes (e.g. wavelength multiplexing).
+/// Channels are always kept and iterated in `ChannelId` order.
+/// `Send + Sync`; share one across threads with `TopologyView`.
+#[derive(Clone)]
 pub struct NetworkTopology {
     nodes: Vec<QuantumNode>,       // Private - controlled access only
     channels: Vec<QuantumChannel>, // Private - controlled access only
+    /// Id of each channel, ascending, parallel to `channels`
+    channel_ids: Vec<ChannelId>,
+    /// Uses recorded per channel, for `ChannelSelector::LeastLoaded`
+    channel_uses: Vec<usize>,
     pub topology_type: TopologyType,
 }
 
@@ -23,6 +169,7 @@ impl NetworkTopology {
 
     /// Create a linear topology: 0 -- 1 -- 2 -- 3
     /// All channels have the same distance and attenuation
+    /// (see `new_linear_with_links` for per-span parameters)
     pub fn new_linear(
         num_nodes: usize,
         memory_per_node: usize,
@@ -31,29 +178,61 @@ impl NetworkTopology {
     ) -> Self {
         assert!(num_nodes >= 2, "Linear topology requires at least 2 nodes");
 
-        let mut nodes = Vec::new();
-        let mut channels = Vec::new();
+        // Create channels connecting adjacent nodes
+        let channels = (0..(num_nodes - 1))
+            .map(|i| QuantumChannel::new(i, i + 1, distance_km, attenuation_db_per_km))
+            .collect();
+        Self::predefined(num_nodes, memory_per_node, channels, TopologyType::Linear)
+    }
 
-        // Create nodes
-        for i in 0..num_nodes {
-            nodes.push(QuantumNode::new(i, memory_per_node));
-        }
+    /// `new_linear` with nodes 0 and n-1 as end nodes and repeaters between
+    pub fn new_linear_repeater_chain(
+        num_nodes: usize,
+        memory_per_node: usize,
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Self {
+        let mut topology = Self::new_linear(
+            num_nodes,
+            memory_per_node,
+            distance_km,
+            attenuation_db_per_km,
+        );
+        topology.nodes[0].role = NodeRole::EndNode;
+        topology.nodes[num_nodes - 1].role = NodeRole::EndNode;
+        topology
+    }
 
-        // Create channels connecting adjacent nodes
-        for i in 0..(num_nodes - 1) {
-            channels.push(QuantumChannel::new(
-                i,
-                i + 1,
-                distance_km,
-                attenuation_db_per_km,
-            ));
-        }
+    /// Linear topology whose span i (node i to i + 1) is `distances[i]` km long
+    /// All spans share one attenuation
+    pub fn new_linear_with_distances(
+        distances: &[f64],
+        memory_per_node: usize,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        let links: Vec<(f64, f64)> = distances
+            .iter()
+            .map(|&distance_km| (distance_km, attenuation_db_per_km))
+            .collect();
+        Self::new_linear_with_links(&links, memory_per_node)
+    }
 
-        NetworkTopology {
-            nodes,
-            channels,
-            topology_type: TopologyType::Linear,
+    /// Linear topology with `links.len() + 1` nodes; span i has parameters
+    /// `links[i] = (distance_km, attenuation_db_per_km)`
+    pub fn new_linear_with_links(
+        links: &[(f64, f64)],
+        memory_per_node: usize,
+    ) -> Result<Self, String> {
+        if links.is_empty() {
+            return Err("Linear topology requires at least 2 nodes (1 link)".to_string());
         }
+        let channels = Self::channels_from_links(links, (0..links.len()).map(|i| (i, i + 1)))?;
+        Ok(Self::predefined(
+            links.len() + 1,
+            memory_per_node,
+            channels,
+            TopologyType::Linear,
+        ))
     }
 
     /// Create a star topology: central node (0) connected to all others
@@ -70,29 +249,29 @@ impl NetworkTopology {
     ) -> Self {
         assert!(num_nodes >= 2, "Star topology requires at least 2 nodes");
 
-        let mut nodes = Vec::new();
-        let mut channels = Vec::new();
-
-        // Create nodes (node 0 is the center)
-        for i in 0..num_nodes {
-            nodes.push(QuantumNode::new(i, memory_per_node));
-        }
-
         // Connect center (node 0) to all other nodes
-        for i in 1..num_nodes {
-            channels.push(QuantumChannel::new(
-                0,
-                i,
-                distance_km,
-                attenuation_db_per_km,
-            ));
-        }
+        let channels = (1..num_nodes)
+            .map(|i| QuantumChannel::new(0, i, distance_km, attenuation_db_per_km))
+            .collect();
+        Self::predefined(num_nodes, memory_per_node, channels, TopologyType::Star)
+    }
 
-        NetworkTopology {
-            nodes,
-            channels,
-            topology_type: TopologyType::Star,
+    /// Star topology with `links.len() + 1` nodes; the link from the center
+    /// to node i has parameters `links[i - 1] = (distance_km, attenuation_db_per_km)`
+    pub fn new_star_with_links(
+        links: &[(f64, f64)],
+        memory_per_node: usize,
+    ) -> Result<Self, String> {
+        if links.is_empty() {
+            return Err("Star topology requires at least 2 nodes (1 link)".to_string());
         }
+        let channels = Self::channels_from_links(links, (1..=links.len()).map(|i| (0, i)))?;
+        Ok(Self::predefined(
+            links.len() + 1,
+            memory_per_node,
+            channels,
+            TopologyType::Star,
+        ))
     }
 
     /// Create a fully-connected mesh topology
@@ -110,30 +289,453 @@ impl NetworkTopology {
     ) -> Self {
         assert!(num_nodes >= 2, "Mesh topology requires at least 2 nodes");
 
-        let mut nodes = Vec::new();
+        // Create channels between all pairs of nodes
+        let channels = Self::mesh_pairs(num_nodes)
+            .map(|(i, j)| QuantumChannel::new(i, j, distance_km, attenuation_db_per_km))
+            .collect();
+        Self::predefined(num_nodes, memory_per_node, channels, TopologyType::Mesh)
+    }
+
+    /// Fully-connected mesh with per-link `(distance_km, attenuation_db_per_km)`
+    ///
+    /// `links` lists the n(n-1)/2 pairs in order (0,1), (0,2), .., (0,n-1),
+    /// (1,2), ..
+    pub fn new_mesh_with_links(
+        num_nodes: usize,
+        memory_per_node: usize,
+        links: &[(f64, f64)],
+    ) -> Result<Self, String> {
+        if num_nodes < 2 {
+            return Err("Mesh topology requires at least 2 nodes".to_string());
+        }
+        let expected = num_nodes * (num_nodes - 1) / 2;
+        if links.len() != expected {
+            return Err(format!(
+                "Mesh of {} nodes needs {} links, got {}",
+                num_nodes,
+                expected,
+                links.len()
+            ));
+        }
+        let channels = Self::channels_from_links(links, Self::mesh_pairs(num_nodes))?;
+        Ok(Self::predefined(
+            num_nodes,
+            memory_per_node,
+            channels,
+            TopologyType::Mesh,
+        ))
+    }
+
+    /// `new_linear` where node i has `memory_capacities[i]` slots
+    pub fn new_linear_with_capacities(
+        num_nodes: usize,
+        memory_capacities: &[usize],
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        let nodes = Self::nodes_with_capacities(num_nodes, memory_capacities)?;
+        Self::new_linear_with_nodes(nodes, distance_km, attenuation_db_per_km)
+    }
+
+    /// `new_linear` where node i is built from `hardware[i]`
+    pub fn new_linear_with_hardware(
+        num_nodes: usize,
+        hardware: &[NodeHardware],
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        let nodes = Self::nodes_with_hardware(num_nodes, hardware)?;
+        Self::new_linear_with_nodes(nodes, distance_km, attenuation_db_per_km)
+    }
+
+    /// `new_star` where node i has `memory_capacities[i]` slots
+    pub fn new_star_with_capacities(
+        num_nodes: usize,
+        memory_capacities: &[usize],
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        let nodes = Self::nodes_with_capacities(num_nodes, memory_capacities)?;
+        Self::new_star_with_nodes(nodes, distance_km, attenuation_db_per_km)
+    }
+
+    /// `new_star` where node i is built from `hardware[i]`
+    pub fn new_star_with_hardware(
+        num_nodes: usize,
+        hardware: &[NodeHardware],
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        let nodes = Self::nodes_with_hardware(num_nodes, hardware)?;
+        Self::new_star_with_nodes(nodes, distance_km, attenuation_db_per_km)
+    }
+
+    /// `new_mesh` where node i has `memory_capacities[i]` slots
+    pub fn new_mesh_with_capacities(
+        num_nodes: usize,
+        memory_capacities: &[usize],
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        let nodes = Self::nodes_with_capacities(num_nodes, memory_capacities)?;
+        Self::new_mesh_with_nodes(nodes, distance_km, attenuation_db_per_km)
+    }
+
+    /// `new_mesh` where node i is built from `hardware[i]`
+    pub fn new_mesh_with_hardware(
+        num_nodes: usize,
+        hardware: &[NodeHardware],
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        let nodes = Self::nodes_with_hardware(num_nodes, hardware)?;
+        Self::new_mesh_with_nodes(nodes, distance_km, attenuation_db_per_km)
+    }
+
+    /// Create a ring topology: a line 0 -- 1 -- .. -- n-1 closed by n-1 -- 0
+    /// All channels have the same distance and attenuation
+    pub fn new_ring(
+        num_nodes: usize,
+        memory_per_node: usize,
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        if num_nodes < 3 {
+            return Err(format!(
+                "Ring topology requires at least 3 nodes, got {}",
+                num_nodes
+            ));
+        }
+        let channels = (0..num_nodes)
+            .map(|i| {
+                QuantumChannel::new(i, (i + 1) % num_nodes, distance_km, attenuation_db_per_km)
+            })
+            .collect();
+        Ok(Self::predefined(
+            num_nodes,
+            memory_per_node,
+            channels,
+            TopologyType::Ring,
+        ))
+    }
+
+    /// Create a `rows` × `cols` grid; node (r, c) has id r*cols + c and is
+    /// connected to its right and lower neighbours
+    ///     0 - 1 - 2
+    ///     |   |   |
+    ///     3 - 4 - 5
+    pub fn new_grid(
+        rows: usize,
+        cols: usize,
+        memory_per_node: usize,
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        if rows == 0 || cols == 0 || rows * cols < 2 {
+            return Err(format!(
+                "Grid topology requires at least 2 nodes, got {}x{}",
+                rows, cols
+            ));
+        }
         let mut channels = Vec::new();
+        for r in 0..rows {
+            for c in 0..cols {
+                let id = r * cols + c;
+                if c + 1 < cols {
+                    channels.push(QuantumChannel::new(
+                        id,
+                        id + 1,
+                        distance_km,
+                        attenuation_db_per_km,
+                    ));
+                }
+                if r + 1 < rows {
+                    channels.push(QuantumChannel::new(
+                        id,
+                        id + cols,
+                        distance_km,
+                        attenuation_db_per_km,
+                    ));
+                }
+            }
+        }
+        Ok(Self::predefined(
+            rows * cols,
+            memory_per_node,
+            channels,
+            TopologyType::Grid,
+        ))
+    }
 
-        // Create nodes
-        for i in 0..num_nodes {
-            nodes.push(QuantumNode::new(i, memory_per_node));
+    /// Erdős–Rényi G(n, p): each pair of nodes is connected with probability `p`
+    ///
+    /// With `ensure_connected`, graphs are redrawn until connected (up to
+    /// `MAX_RANDOM_TOPOLOGY_DRAWS` times)
+    pub fn new_random_gnp(
+        num_nodes: usize,
+        p: f64,
+        memory_per_node: usize,
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+        ensure_connected: bool,
+        rng: &mut impl Rng,
+    ) -> Result<Self, String> {
+        if num_nodes < 2 {
+            return Err("Random topology requires at least 2 nodes".to_string());
+        }
+        if !(0.0..=1.0).contains(&p) {
+            return Err(format!("Edge probability must be in [0, 1], got {}", p));
         }
+        let channels = Self::draw_random_channels(num_nodes, ensure_connected, || {
+            Self::mesh_pairs(num_nodes)
+                .filter(|_| rng.random::<f64>() < p)
+                .map(|(i, j)| QuantumChannel::new(i, j, distance_km, attenuation_db_per_km))
+                .collect()
+        })?;
+        Ok(Self::predefined(
+            num_nodes,
+            memory_per_node,
+            channels,
+            TopologyType::Random,
+        ))
+    }
 
-        // Create channels between all pairs of nodes
-        for i in 0..num_nodes {
-            for j in (i + 1)..num_nodes {
-                channels.push(QuantumChannel::new(
-                    i,
-                    j,
+    /// Waxman graph: nodes placed uniformly in an `area_km` × `area_km`
+    /// square, pairs at distance d connected with probability
+    /// β·exp(-d / (α·L)), L the largest pairwise distance
+    ///
+    /// Each channel is as long as the distance between its nodes. With
+    /// `ensure_connected`, placements are redrawn until connected (up to
+    /// `MAX_RANDOM_TOPOLOGY_DRAWS` times)
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_waxman(
+        num_nodes: usize,
+        alpha: f64,
+        beta: f64,
+        area_km: f64,
+        memory_per_node: usize,
+        attenuation_db_per_km: f64,
+        ensure_connected: bool,
+        rng: &mut impl Rng,
+    ) -> Result<Self, String> {
+        if num_nodes < 2 {
+            return Err("Random topology requires at least 2 nodes".to_string());
+        }
+        if alpha <= 0.0 || !(0.0..=1.0).contains(&beta) {
+            return Err(format!(
+                "Waxman parameters need alpha > 0 and beta in [0, 1], got {} and {}",
+                alpha, beta
+            ));
+        }
+        if !(area_km.is_finite() && area_km > 0.0) {
+            return Err(format!("Area must be positive, got {} km", area_km));
+        }
+        let channels = Self::draw_random_channels(num_nodes, ensure_connected, || {
+            let positions: Vec<(f64, f64)> = (0..num_nodes)
+                .map(|_| (area_km * rng.random::<f64>(), area_km * rng.random::<f64>()))
+                .collect();
+            let distance = |i: usize, j: usize| {
+                let (dx, dy) = (
+                    positions[i].0 - positions[j].0,
+                    positions[i].1 - positions[j].1,
+                );
+                dx.hypot(dy)
+            };
+            let longest = Self::mesh_pairs(num_nodes)
+                .map(|(i, j)| distance(i, j))
+                .fold(0.0, f64::max);
+            Self::mesh_pairs(num_nodes)
+                .filter_map(|(i, j)| {
+                    let d = distance(i, j);
+                    let probability = beta * (-d / (alpha * longest)).exp();
+                    (rng.random::<f64>() < probability)
+                        .then(|| QuantumChannel::new(i, j, d, attenuation_db_per_km))
+                })
+                .collect()
+        })?;
+        Ok(Self::predefined(
+            num_nodes,
+            memory_per_node,
+            channels,
+            TopologyType::Random,
+        ))
+    }
+
+    /// Call `draw` once, or until its channels connect all nodes
+    fn draw_random_channels(
+        num_nodes: usize,
+        ensure_connected: bool,
+        mut draw: impl FnMut() -> Vec<QuantumChannel>,
+    ) -> Result<Vec<QuantumChannel>, String> {
+        for _ in 0..MAX_RANDOM_TOPOLOGY_DRAWS {
+            let channels = draw();
+            if !ensure_connected || Self::connects_all(num_nodes, &channels) {
+                return Ok(channels);
+            }
+        }
+        Err(format!(
+            "No connected graph in {} draws; raise the edge probability",
+            MAX_RANDOM_TOPOLOGY_DRAWS
+        ))
+    }
+
+    /// Whether `channels` connect all of nodes 0..num_nodes
+    fn connects_all(num_nodes: usize, channels: &[QuantumChannel]) -> bool {
+        if num_nodes == 0 {
+            return true;
+        }
+        let mut visited = vec![false; num_nodes];
+        let mut queue = VecDeque::from([0]);
+        visited[0] = true;
+        while let Some(current) = queue.pop_front() {
+            for next in channels
+                .iter()
+                .filter_map(|channel| channel.get_partner(current))
+            {
+                if next < num_nodes && !visited[next] {
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        visited.into_iter().all(|seen| seen)
+    }
+
+    /// Node pairs (i, j), i < j, in mesh channel order
+    fn mesh_pairs(num_nodes: usize) -> impl Iterator<Item = (usize, usize)> {
+        (0..num_nodes).flat_map(move |i| ((i + 1)..num_nodes).map(move |j| (i, j)))
+    }
+
+    /// Channels over `pairs` with the matching `(distance, attenuation)`,
+    /// rejecting negative or non-finite parameters
+    fn channels_from_links(
+        links: &[(f64, f64)],
+        pairs: impl Iterator<Item = (usize, usize)>,
+    ) -> Result<Vec<QuantumChannel>, String> {
+        links
+            .iter()
+            .zip(pairs)
+            .map(|(&(distance_km, attenuation_db_per_km), (a, b))| {
+                if !(distance_km.is_finite() && distance_km >= 0.0) {
+                    return Err(format!(
+                        "Link {}-{}: distance must be finite and non-negative, got {}",
+                        a, b, distance_km
+                    ));
+                }
+                if !(attenuation_db_per_km.is_finite() && attenuation_db_per_km >= 0.0) {
+                    return Err(format!(
+                        "Link {}-{}: attenuation must be finite and non-negative, got {}",
+                        a, b, attenuation_db_per_km
+                    ));
+                }
+                Ok(QuantumChannel::new(
+                    a,
+                    b,
                     distance_km,
                     attenuation_db_per_km,
-                ));
-            }
+                ))
+            })
+            .collect()
+    }
+
+    fn new_linear_with_nodes(
+        nodes: Vec<QuantumNode>,
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        if nodes.len() < 2 {
+            return Err("Linear topology requires at least 2 nodes".to_string());
+        }
+        let mut topology = Self::new_linear(nodes.len(), 0, distance_km, attenuation_db_per_km);
+        topology.nodes = nodes;
+        Ok(topology)
+    }
+
+    fn new_star_with_nodes(
+        nodes: Vec<QuantumNode>,
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        if nodes.len() < 2 {
+            return Err("Star topology requires at least 2 nodes".to_string());
         }
+        let mut topology = Self::new_star(nodes.len(), 0, distance_km, attenuation_db_per_km);
+        topology.nodes = nodes;
+        Ok(topology)
+    }
 
+    fn new_mesh_with_nodes(
+        nodes: Vec<QuantumNode>,
+        distance_km: f64,
+        attenuation_db_per_km: f64,
+    ) -> Result<Self, String> {
+        if nodes.len() < 2 {
+            return Err("Mesh topology requires at least 2 nodes".to_string());
+        }
+        let mut topology = Self::new_mesh(nodes.len(), 0, distance_km, attenuation_db_per_km);
+        topology.nodes = nodes;
+        Ok(topology)
+    }
+
+    /// Empty nodes 0..num_nodes with one capacity each
+    fn nodes_with_capacities(
+        num_nodes: usize,
+        memory_capacities: &[usize],
+    ) -> Result<Vec<QuantumNode>, String> {
+        if memory_capacities.len() != num_nodes {
+            return Err(format!(
+                "Expected {} memory capacities, got {}",
+                num_nodes,
+                memory_capacities.len()
+            ));
+        }
+        Ok(memory_capacities
+            .iter()
+            .enumerate()
+            .map(|(id, &capacity)| QuantumNode::new(id, capacity))
+            .collect())
+    }
+
+    /// Empty nodes 0..num_nodes with one validated hardware profile each
+    fn nodes_with_hardware(
+        num_nodes: usize,
+        hardware: &[NodeHardware],
+    ) -> Result<Vec<QuantumNode>, String> {
+        if hardware.len() != num_nodes {
+            return Err(format!(
+                "Expected {} hardware profiles, got {}",
+                num_nodes,
+                hardware.len()
+            ));
+        }
+        hardware
+            .iter()
+            .enumerate()
+            .map(|(id, profile)| {
+                profile
+                    .validate()
+                    .map_err(|e| format!("Node {}: {}", id, e))?;
+                Ok(QuantumNode::with_hardware(id, profile))
+            })
+            .collect()
+    }
+
+    /// Predefined topology of `num_nodes` empty nodes and `channels`, with
+    /// ids in channel order
+    fn predefined(
+        num_nodes: usize,
+        memory_per_node: usize,
+        channels: Vec<QuantumChannel>,
+        topology_type: TopologyType,
+    ) -> Self {
         NetworkTopology {
-            nodes,
+            nodes: (0..num_nodes)
+                .map(|i| QuantumNode::new(i, memory_per_node))
+                .collect(),
+            channel_ids: (0..channels.len()).map(ChannelId).collect(),
+            channel_uses: vec![0; channels.len()],
             channels,
-            topology_type: TopologyType::Mesh,
+            topology_type,
         }
     }
 
@@ -147,12 +749,17 @@ impl NetworkTopology {
         NetworkTopology {
             nodes: Vec::new(),
             channels: Vec::new(),
+            channel_ids: Vec::new(),
+            channel_uses: Vec::new(),
             topology_type: TopologyType::Custom,
         }
     }
 
     /// Add a node to a custom topology
     /// Returns error if topology is not Custom
+    ///
+    /// Pairs restored from data without pair ids get fresh ids shared with
+    /// their other halves at nodes already added.
     pub fn add_node(&mut self, node: QuantumNode) -> Result<(), String> {
         if self.topology_type != TopologyType::Custom {
             return Err(format!(
@@ -161,12 +768,18 @@ impl NetworkTopology {
             ));
         }
         self.nodes.push(node);
+        let (added, existing) = self.nodes.split_last_mut().unwrap();
+        for other in existing {
+            added.relink_legacy_pairs(other);
+        }
         Ok(())
     }
 
-    /// Add a channel to a custom topology
-    /// Returns error if topology is not Custom or if channel references invalid nodes
-    pub fn add_channel(&mut self, channel: QuantumChannel) -> Result<(), String> {
+    /// Add a channel to a custom topology; returns its id (the lowest free one)
+    /// Returns error if topology is not Custom, if channel references invalid
+    /// nodes, is a self-loop, or exactly duplicates an existing channel
+    /// (parallel channels must differ in some parameter)
+    pub fn add_channel(&mut self, channel: QuantumChannel) -> Result<ChannelId, String> {
         if self.topology_type != TopologyType::Custom {
             return Err(format!(
                 "Cannot modify {:?} topology. Use new_custom() for custom topologies.",
@@ -181,9 +794,150 @@ impl NetworkTopology {
         if channel.node_b >= self.nodes.len() {
             return Err(format!("Node {} does not exist", channel.node_b));
         }
+        if channel.node_a == channel.node_b {
+            return Err(format!(
+                "Channel would connect node {} to itself",
+                channel.node_a
+            ));
+        }
+        if let Some((existing, _)) = self
+            .iter_channels()
+            .find(|(_, other)| is_duplicate(other, &channel))
+        {
+            return Err(format!(
+                "Channel {}-{} duplicates channel {}",
+                channel.node_a, channel.node_b, existing
+            ));
+        }
 
-        self.channels.push(channel);
-        Ok(())
+        // Ids are ascending, so the first gap is the lowest free id
+        let index = self
+            .channel_ids
+            .iter()
+            .enumerate()
+            .position(|(index, id)| id.0 != index)
+            .unwrap_or(self.channel_ids.len());
+        let id = ChannelId(index);
+        self.insert_channel(id, channel);
+        Ok(id)
+    }
+
+    /// Remove a channel from a custom topology
+    /// Other channels keep their ids; the removed id is free for reuse
+    pub fn remove_channel(&mut self, id: ChannelId) -> Result<QuantumChannel, String> {
+        if self.topology_type != TopologyType::Custom {
+            return Err(format!(
+                "Cannot modify {:?} topology. Use new_custom() for custom topologies.",
+                self.topology_type
+            ));
+        }
+        let index = self
+            .channel_index(id)
+            .ok_or(format!("Channel {} does not exist", id))?;
+        self.channel_ids.remove(index);
+        self.channel_uses.remove(index);
+        Ok(self.channels.remove(index))
+    }
+
+    /// Remove a node from a custom topology, with every channel at it
+    ///
+    /// Node ids are positions, so nodes after `node_id` move down by one:
+    /// their ids, channel endpoints and pair partners are renumbered. Pairs
+    /// other nodes held with the removed node are discarded; topology edits
+    /// have no clock, so their slots accrue no busy time for them.
+    pub fn remove_node(&mut self, node_id: usize) -> Result<QuantumNode, String> {
+        if self.topology_type != TopologyType::Custom {
+            return Err(format!(
+                "Cannot modify {:?} topology. Use new_custom() for custom topologies.",
+                self.topology_type
+            ));
+        }
+        if !self.has_node(node_id) {
+            return Err(format!("Node {} does not exist", node_id));
+        }
+
+        let incident: Vec<ChannelId> = self
+            .iter_channels()
+            .filter(|(_, channel)| channel.connects_to(node_id))
+            .map(|(id, _)| id)
+            .collect();
+        for id in incident {
+            self.remove_channel(id)?;
+        }
+        let removed = self.nodes.remove(node_id);
+
+        let renumber = |id: usize| if id > node_id { id - 1 } else { id };
+        for node in &mut self.nodes {
+            node.id = renumber(node.id);
+            while let Some(orphan) = node.find_pair_with(node_id) {
+                let stored_at = node.pair(orphan).unwrap().creation_time;
+                node.evict_pair(orphan, stored_at);
+            }
+            node.renumber_partners(renumber);
+        }
+        for channel in &mut self.channels {
+            channel.node_a = renumber(channel.node_a);
+            channel.node_b = renumber(channel.node_b);
+        }
+
+        debug_assert!(
+            self.validate().err().is_none_or(|errors| errors
+                .iter()
+                .all(|error| matches!(error, TopologyError::IsolatedNode(_)))),
+            "Node removal left an inconsistent topology"
+        );
+        Ok(removed)
+    }
+
+    /// Rebuild a topology from a snapshot as a custom topology
+    ///
+    /// Nodes and channels keep their ids and parameters; node memories start
+    /// empty and channels get the default retry policy.
+    pub fn from_snapshot(snapshot: &TopologySnapshot) -> Result<Self, String> {
+        let mut topology = NetworkTopology::new_custom();
+        for (index, node) in snapshot.nodes.iter().enumerate() {
+            if node.id != index {
+                return Err(format!(
+                    "Node ids must be 0..n, found {} at {}",
+                    node.id, index
+                ));
+            }
+            let mut restored = QuantumNode::new(node.id, node.memory_capacity);
+            restored.gate_error = node.gate_error;
+            topology.add_node(restored)?;
+        }
+        for channel in &snapshot.channels {
+            if topology.channel_index(channel.id).is_some() {
+                return Err(format!("Duplicate channel id {}", channel.id));
+            }
+            for node in [channel.node_a, channel.node_b] {
+                if !topology.has_node(node) {
+                    return Err(format!("Node {} does not exist", node));
+                }
+            }
+            let restored = QuantumChannel::new(
+                channel.node_a,
+                channel.node_b,
+                channel.distance_km,
+                channel.attenuation_db_per_km,
+            )
+            .with_depolarization(channel.depolarization_per_km)?;
+            topology.insert_channel(channel.id, restored);
+        }
+        Ok(topology)
+    }
+
+    /// Insert a channel under a free `id`, keeping id order
+    fn insert_channel(&mut self, id: ChannelId, channel: QuantumChannel) {
+        let index = self.channel_ids.partition_point(|&other| other < id);
+        self.channel_ids.insert(index, id);
+        self.channel_uses.insert(index, 0);
+        self.channels.insert(index, channel);
+    }
+
+    /// Position of channel `id` in `channels()`
+    fn channel_index(&self, id: ChannelId) -> Option<usize> {
+        self.channel_ids.binary_search(&id).ok()
     }
 
     // ============================================
@@ -201,22 +955,513 @@ impl NetworkTopology {
         self.nodes.get_mut(id)
     }
 
+    /// Get mutable references to two distinct nodes at once
+    /// Needed by operations that touch both ends of a link
+    pub fn get_node_pair_mut(
+        &mut self,
+        id_a: usize,
+        id_b: usize,
+    ) -> Option<(&mut QuantumNode, &mut QuantumNode)> {
+        if id_a == id_b || id_a >= self.nodes.len() || id_b >= self.nodes.len() {
+            return None;
+        }
+
+        if id_a < id_b {
+            let (left, right) = self.nodes.split_at_mut(id_b);
+            Some((&mut left[id_a], &mut right[0]))
+        } else {
+            let (left, right) = self.nodes.split_at_mut(id_a);
+            Some((&mut right[0], &mut left[id_b]))
+        }
+    }
+
+    /// Nodes with the given role, in id order
+    pub fn nodes_with_role(&self, role: NodeRole) -> Vec<&QuantumNode> {
+        self.nodes.iter().filter(|node| node.role == role).collect()
+    }
+
     /// Get all nodes (immutable)
     pub fn nodes(&self) -> &[QuantumNode] {
         &self.nodes
     }
 
-    /// Get all channels (immutable)
+    /// All channels, sorted by id; `channel_ids()` holds the id at each position
     pub fn channels(&self) -> &[QuantumChannel] {
         &self.channels
     }
 
-    /// Find channel between two nodes
-    pub fn find_channel(&self, node_a: usize, node_b: usize) -> Option<&QuantumChannel> {
-        self.channels.iter().find(|ch| {
-            (ch.node_a == node_a && ch.node_b == node_b)
-                || (ch.node_a == node_b && ch.node_b == node_a)
-        })
+    /// Ids of all channels, ascending
+    pub fn channel_ids(&self) -> &[ChannelId] {
+        &self.channel_ids
+    }
+
+    /// Every stored pair half in the network at `current_time`, node by node
+    /// Each entangled pair appears once per end node that holds it
+    pub fn iter_all_pairs(&self, current_time: f64) -> impl Iterator<Item = PairView> + '_ {
+        self.nodes
+            .iter()
+            .flat_map(move |node| node.iter_pairs(current_time))
+    }
+
+    /// Fidelity distribution of the stored pairs at `current_time`
+    /// Each pair counts once, from its lower-id end; None without pairs
+    pub fn network_fidelity_summary(&self, current_time: f64) -> Option<FidelitySummary> {
+        let fidelities: Vec<f64> = self
+            .iter_all_pairs(current_time)
+            .filter(|pair| pair.node_id < pair.partner_node_id)
+            .map(|pair| pair.fidelity)
+            .collect();
+        FidelitySummary::from_samples(&fidelities)
+    }
+
+    /// Who shares entanglement with whom at `current_time`
+    pub fn entanglement_graph(&self, current_time: f64) -> EntanglementGraph {
+        EntanglementGraph::from_topology(self, current_time)
+    }
+
+    /// All channels with their ids, sorted by id
+    pub fn iter_channels(&self) -> impl Iterator<Item = (ChannelId, &QuantumChannel)> {
+        self.channel_ids.iter().copied().zip(&self.channels)
+    }
+
+    /// Look up a channel by id
+    pub fn channel(&self, id: ChannelId) -> Option<&QuantumChannel> {
+        self.channel_index(id).map(|index| &self.channels[index])
+    }
+
+    /// Mutable channel with id `id`
+    pub fn channel_mut(&mut self, id: ChannelId) -> Option<&mut QuantumChannel> {
+        let index = self.channel_index(id)?;
+        Some(&mut self.channels[index])
+    }
+
+    /// Find the channel between two nodes, with its id
+    /// Returns None if there is no channel or several parallel ones;
+    /// use `select_channel` to choose among parallel channels
+    pub fn find_channel(
+        &self,
+        node_a: usize,
+        node_b: usize,
+    ) -> Option<(ChannelId, &QuantumChannel)> {
+        match self.channels_between(node_a, node_b)[..] {
+            [id] => Some((id, self.channel(id)?)),
+            _ => None,
+        }
+    }
+
+    /// Find the channel between two nodes (mutable, e.g. to model degradation)
+    /// Same ambiguity rules as `find_channel`
+    pub fn find_channel_mut(
+        &mut self,
+        node_a: usize,
+        node_b: usize,
+    ) -> Option<(ChannelId, &mut QuantumChannel)> {
+        match self.channels_between(node_a, node_b)[..] {
+            [id] => {
+                let index = self.channel_index(id)?;
+                Some((id, &mut self.channels[index]))
+            }
+            _ => None,
+        }
+    }
+
+    /// Ids of all channels connecting two nodes, in either direction, ascending
+    pub fn channels_between(&self, node_a: usize, node_b: usize) -> Vec<ChannelId> {
+        self.iter_channels()
+            .filter(|(_, ch)| {
+                (ch.node_a == node_a && ch.node_b == node_b)
+                    || (ch.node_a == node_b && ch.node_b == node_a)
+            })
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Choose one channel between two nodes
+    pub fn select_channel(
+        &self,
+        node_a: usize,
+        node_b: usize,
+        selector: ChannelSelector,
+    ) -> Option<ChannelId> {
+        let candidates = self.channels_between(node_a, node_b);
+        match selector {
+            ChannelSelector::ById(id) if candidates.contains(&id) => Some(id),
+            ChannelSelector::ById(_) => match candidates[..] {
+                [id] => Some(id),
+                _ => None,
+            },
+            ChannelSelector::BestSuccessProbability => candidates.into_iter().reduce(|best, id| {
+                let probability = |id| self.channel(id).unwrap().success_probability();
+                if probability(id) > probability(best) {
+                    id
+                } else {
+                    best
+                }
+            }),
+            ChannelSelector::LeastLoaded => candidates
+                .into_iter()
+                .min_by_key(|&id| self.channel_uses(id)),
+        }
+    }
+
+    /// Record that a channel was used (drives `ChannelSelector::LeastLoaded`)
+    pub fn record_channel_use(&mut self, id: ChannelId) {
+        if let Some(index) = self.channel_index(id) {
+            self.channel_uses[index] += 1;
+        }
+    }
+
+    /// Uses recorded for a channel
+    pub fn channel_uses(&self, id: ChannelId) -> usize {
+        self.channel_index(id)
+            .map_or(0, |index| self.channel_uses[index])
+    }
+
+    /// Whether every node can reach every other over channels
+    /// (true for an empty topology)
+    pub fn is_connected(&self) -> bool {
+        Self::connects_all(self.nodes.len(), &self.channels)
+    }
+
+    /// Every structural problem: self-loops, duplicate channels, isolated
+    /// nodes and channels to missing nodes
+    pub fn validate(&self) -> Result<(), Vec<TopologyError>> {
+        let mut errors = Vec::new();
+        let channels: Vec<(ChannelId, &QuantumChannel)> = self.iter_channels().collect();
+        for (index, &(id, channel)) in channels.iter().enumerate() {
+            for node in [channel.node_a, channel.node_b] {
+                if !self.has_node(node) {
+                    errors.push(TopologyError::MissingNode { channel: id, node });
+                }
+            }
+            if channel.node_a == channel.node_b {
+                errors.push(TopologyError::SelfLoop {
+                    channel: id,
+                    node: channel.node_a,
+                });
+            }
+            if let Some(&(duplicate_of, _)) = channels[..index]
+                .iter()
+                .find(|(_, earlier)| is_duplicate(earlier, channel))
+            {
+                errors.push(TopologyError::DuplicateChannel {
+                    channel: id,
+                    duplicate_of,
+                });
+            }
+        }
+        if self.nodes.len() > 1 {
+            for node in 0..self.nodes.len() {
+                if !self
+                    .channels
+                    .iter()
+                    .any(|channel| channel.connects_to(node))
+                {
+                    errors.push(TopologyError::IsolatedNode(node));
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Fewest-hop path from `source` to `destination`
+    /// Each hop's channel is chosen with `selector`; hops with no selectable
+    /// channel are not traversed
+    pub fn find_path(
+        &self,
+        source: usize,
+        destination: usize,
+        selector: ChannelSelector,
+    ) -> Option<RoutePath> {
+        if !self.has_node(source) || !self.has_node(destination) {
+            return None;
+        }
+
+        // Breadth-first search, remembering the hop used to reach each node
+        let mut reached_by: Vec<Option<PathHop>> = vec![None; self.nodes.len()];
+        let mut visited = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::from([source]);
+        visited[source] = true;
+
+        while let Some(current) = queue.pop_front() {
+            if current == destination {
+                break;
+            }
+            for next in 0..self.nodes.len() {
+                if visited[next] {
+                    continue;
+                }
+                if let Some(channel_id) = self.select_channel(current, next, selector) {
+                    visited[next] = true;
+                    reached_by[next] = Some(PathHop {
+                        from: current,
+                        to: next,
+                        channel_id,
+                    });
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if !visited[destination] {
+            return None;
+        }
+        let mut hops = Vec::new();
+        let mut current = destination;
+        while let Some(hop) = reached_by[current] {
+            hops.push(hop);
+            current = hop.from;
+        }
+        hops.reverse();
+
+        let mut nodes = vec![source];
+        nodes.extend(hops.iter().map(|hop| hop.to));
+        Some(RoutePath { nodes, hops })
+    }
+
+    /// Distinct nodes sharing at least one channel with `node_id`, ascending
+    pub fn neighbors(&self, node_id: usize) -> Vec<usize> {
+        let mut neighbors: Vec<usize> = self
+            .channels
+            .iter()
+            .filter_map(|channel| channel.get_partner(node_id))
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors
+    }
+
+    /// Number of channels at `node_id`; parallel channels count separately
+    pub fn degree(&self, node_id: usize) -> usize {
+        self.channels
+            .iter()
+            .filter(|channel| channel.connects_to(node_id))
+            .count()
+    }
+
+    /// Cheapest path from `source` to `destination` under `metric` (Dijkstra)
+    ///
+    /// Parallel channels cost their cheapest member; ties go to the path
+    /// through lower node ids
+    pub fn shortest_path(
+        &self,
+        source: usize,
+        destination: usize,
+        metric: PathMetric,
+    ) -> Option<Vec<usize>> {
+        self.shortest_path_avoiding(source, destination, metric, &[], &[])
+            .map(|(path, _)| path)
+    }
+
+    /// Up to `k` cheapest loopless paths from `source` to `destination`
+    /// under `metric`, cheapest first (Yen's algorithm)
+    ///
+    /// Equal-cost paths are ordered by their node sequence. `source ==
+    /// destination` gives the single trivial path; unreachable pairs none
+    pub fn k_shortest_paths(
+        &self,
+        source: usize,
+        destination: usize,
+        k: usize,
+        metric: PathMetric,
+    ) -> Vec<Vec<usize>> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let Some(first) = self.shortest_path_avoiding(source, destination, metric, &[], &[]) else {
+            return Vec::new();
+        };
+        let mut found = vec![first];
+        let mut candidates: Vec<(Vec<usize>, f64)> = Vec::new();
+
+        while found.len() < k {
+            let (previous, _) = &found[found.len() - 1];
+            for spur_index in 0..previous.len().saturating_sub(1) {
+                let root = &previous[..=spur_index];
+                let spur = root[spur_index];
+                // Edges leaving the root along already found paths
+                let banned_edges: Vec<(usize, usize)> = found
+                    .iter()
+                    .map(|(path, _)| path)
+                    .filter(|path| path.len() > spur_index + 1 && path[..=spur_index] == *root)
+                    .map(|path| (path[spur_index], path[spur_index + 1]))
+                    .collect();
+                let banned_nodes = &root[..spur_index];
+                if let Some((tail, _)) = self.shortest_path_avoiding(
+                    spur,
+                    destination,
+                    metric,
+                    banned_nodes,
+                    &banned_edges,
+                ) {
+                    let mut path = root[..spur_index].to_vec();
+                    path.extend(tail);
+                    let already_known = found.iter().any(|(known, _)| *known == path)
+                        || candidates.iter().any(|(known, _)| *known == path);
+                    if !already_known {
+                        let cost = self.path_cost(&path, metric);
+                        candidates.push((path, cost));
+                    }
+                }
+            }
+
+            let best = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, (a, cost_a)), (_, (b, cost_b))| {
+                    cost_a.total_cmp(cost_b).then_with(|| a.cmp(b))
+                })
+                .map(|(index, _)| index);
+            match best {
+                Some(index) => found.push(candidates.swap_remove(index)),
+                None => break,
+            }
+        }
+        found.into_iter().map(|(path, _)| path).collect()
+    }
+
+    /// Cheapest channel between two nodes under `metric`
+    fn edge_cost(&self, node_a: usize, node_b: usize, metric: PathMetric) -> Option<f64> {
+        self.channels
+            .iter()
+            .filter(|channel| channel.get_partner(node_a) == Some(node_b))
+            .map(|channel| metric.cost(channel))
+            .min_by(f64::total_cmp)
+    }
+
+    fn path_cost(&self, path: &[usize], metric: PathMetric) -> f64 {
+        path.windows(2)
+            .filter_map(|hop| self.edge_cost(hop[0], hop[1], metric))
+            .sum()
+    }
+
+    /// Dijkstra that never visits `banned_nodes` nor crosses `banned_edges`
+    /// (undirected); returns the path and its cost
+    fn shortest_path_avoiding(
+        &self,
+        source: usize,
+        destination: usize,
+        metric: PathMetric,
+        banned_nodes: &[usize],
+        banned_edges: &[(usize, usize)],
+    ) -> Option<(Vec<usize>, f64)> {
+        if !self.has_node(source) || !self.has_node(destination) {
+            return None;
+        }
+        let n = self.nodes.len();
+        let mut cost = vec![f64::INFINITY; n];
+        let mut previous: Vec<Option<usize>> = vec![None; n];
+        let mut settled = vec![false; n];
+        for &node in banned_nodes {
+            if node < n {
+                settled[node] = true;
+            }
+        }
+        cost[source] = 0.0;
+
+        // Settle the cheapest node (lowest id on ties) until the destination
+        while let Some(current) = (0..n)
+            .filter(|&node| !settled[node] && cost[node].is_finite())
+            .min_by(|&a, &b| cost[a].total_cmp(&cost[b]))
+        {
+            settled[current] = true;
+            if current == destination {
+                break;
+            }
+            for next in self.neighbors(current) {
+                let banned = banned_edges
+                    .iter()
+                    .any(|&(a, b)| (a, b) == (current, next) || (a, b) == (next, current));
+                if settled[next] || banned {
+                    continue;
+                }
+                if let Some(step) = self.edge_cost(current, next, metric) {
+                    if cost[current] + step < cost[next] {
+                        cost[next] = cost[current] + step;
+                        previous[next] = Some(current);
+                    }
+                }
+            }
+        }
+
+        if !cost[destination].is_finite() {
+            return None;
+        }
+        let mut path = vec![destination];
+        let mut current = destination;
+        while let Some(before) = previous[current] {
+            path.push(before);
+            current = before;
+        }
+        path.reverse();
+        Some((path, cost[destination]))
+    }
+
+    /// Remove the best pair `node_a` holds with `node_b` and its matching half
+    /// at `node_b` (same pair id); returns `node_a`'s half
+    pub fn discard_link_pair(
+        &mut self,
+        node_a: usize,
+        node_b: usize,
+        current_time: f64,
+    ) -> Option<StoredPair> {
+        let (first, second) = self.get_node_pair_mut(node_a, node_b)?;
+        let handle = first.best_pair_with(node_b, current_time)?;
+        let pair = first.remove_pair_at(handle, current_time)?;
+        second.remove_pair_by_id(pair.pair_id, current_time);
+        Some(pair)
+    }
+
+    /// Like `discard_link_pair`, but logged as an eviction: the pair is
+    /// thrown away unused (e.g. after a failed swap)
+    pub fn evict_link_pair(
+        &mut self,
+        node_a: usize,
+        node_b: usize,
+        current_time: f64,
+    ) -> Option<StoredPair> {
+        let (first, second) = self.get_node_pair_mut(node_a, node_b)?;
+        let handle = first.best_pair_with(node_b, current_time)?;
+        let pair = first.evict_pair(handle, current_time)?;
+        second.evict_pair(pair.handle(), current_time);
+        Some(pair)
+    }
+
+    /// Drop every pair with a half expired under `cutoff`, at both nodes
+    /// Returns the number of pairs dropped
+    pub fn purge_expired(&mut self, current_time: f64, cutoff: Cutoff) -> usize {
+        let expired: HashSet<u64> = self
+            .nodes
+            .iter()
+            .flat_map(|node| node.stored_pairs())
+            .filter(|pair| cutoff.is_expired(pair, current_time))
+            .map(|pair| pair.pair_id)
+            .collect();
+        if !expired.is_empty() {
+            for node in &mut self.nodes {
+                node.purge_where(current_time, |pair| expired.contains(&pair.pair_id));
+            }
+        }
+        expired.len()
+    }
+
+    /// Capture structure, parameters and memory occupancy for later diffing
+    pub fn snapshot(&self) -> TopologySnapshot {
+        TopologySnapshot::capture(self)
+    }
+
+    /// Run the audit consistency check on every node
+    /// Returns a description of the first inconsistency found
+    pub fn verify_memory_consistency(&self) -> Result<(), String> {
+        for node in &self.nodes {
+            node.verify_consistency()
+                .map_err(|error| format!("Node {}: {:?}", node.id, error))?;
+        }
+        Ok(())
     }
 
     /// Get number of nodes in the network
@@ -281,6 +1526,24 @@ mod tests {
             .contains("Cannot modify Linear topology"));
     }
 
+    #[test]
+    fn test_linear_with_distances() {
+        let network =
+            NetworkTopology::new_linear_with_distances(&[5.0, 20.0, 50.0], 10, 0.2).unwrap();
+        assert_eq!(network.topology_type, TopologyType::Linear);
+        assert_eq!(network.num_nodes(), 4);
+        for (a, distance_km) in [(0, 5.0), (1, 20.0), (2, 50.0)] {
+            let (_, channel) = network.find_channel(a + 1, a).unwrap();
+            let expected = QuantumChannel::new(a, a + 1, distance_km, 0.2).success_probability();
+            assert_eq!(channel.success_probability(), expected);
+        }
+        assert!(network.find_channel(0, 2).is_none());
+
+        assert!(NetworkTopology::new_linear_with_distances(&[], 10, 0.2).is_err());
+        let negative = NetworkTopology::new_linear_with_distances(&[5.0, -1.0], 10, 0.2);
+        assert!(negative.err().unwrap().contains("Link 1-2"));
+    }
+
     // ===== STAR TOPOLOGY TESTS =====
 
     #[test]
@@ -305,6 +1568,124 @@ mod tests {
         assert_eq!(network.num_channels(), 4); // Center to 4 periphery nodes
     }
 
+    #[test]
+    fn test_star_and_mesh_with_links() {
+        let star = NetworkTopology::new_star_with_links(&[(1.0, 0.2), (2.0, 0.3)], 4).unwrap();
+        assert_eq!(star.num_nodes(), 3);
+        let (_, spoke) = star.find_channel(0, 2).unwrap();
+        assert_eq!((spoke.distance_km, spoke.attenuation_db_per_km), (2.0, 0.3));
+
+        let links = [(1.0, 0.2), (2.0, 0.2), (3.0, 0.25)];
+        let mesh = NetworkTopology::new_mesh_with_links(3, 4, &links).unwrap();
+        assert_eq!(mesh.topology_type, TopologyType::Mesh);
+        let (_, channel) = mesh.find_channel(2, 1).unwrap();
+        assert_eq!(
+            (channel.distance_km, channel.attenuation_db_per_km),
+            (3.0, 0.25)
+        );
+
+        let short = NetworkTopology::new_mesh_with_links(4, 4, &links);
+        assert_eq!(short.err().unwrap(), "Mesh of 4 nodes needs 6 links, got 3");
+        assert!(NetworkTopology::new_star_with_links(&[(1.0, f64::NAN)], 4).is_err());
+    }
+
+    // ===== RING AND GRID TOPOLOGY TESTS =====
+
+    #[test]
+    fn test_ring_4_nodes() {
+        let network = NetworkTopology::new_ring(4, 10, 10.0, 0.2).unwrap();
+        assert_eq!(network.topology_type, TopologyType::Ring);
+        assert_eq!(network.num_nodes(), 4);
+        assert_eq!(network.num_channels(), 4);
+        // The closing edge is found in either direction
+        assert!(network.find_channel(3, 0).is_some());
+        assert!(network.find_channel(0, 3).is_some());
+        assert!(network.find_channel(0, 2).is_none());
+        assert!((0..4).all(|node| network.degree(node) == 2));
+
+        assert!(NetworkTopology::new_ring(2, 10, 10.0, 0.2).is_err());
+    }
+
+    #[test]
+    fn test_grid_3x3() {
+        let network = NetworkTopology::new_grid(3, 3, 10, 10.0, 0.2).unwrap();
+        assert_eq!(network.topology_type, TopologyType::Grid);
+        assert_eq!(network.num_nodes(), 9);
+        assert_eq!(network.num_channels(), 12);
+        for corner in [0, 2, 6, 8] {
+            assert_eq!(network.degree(corner), 2);
+        }
+        assert_eq!(network.degree(1), 3);
+        assert_eq!(network.degree(4), 4);
+        assert!(network.find_channel(2, 3).is_none()); // No wrap between rows
+
+        assert!(NetworkTopology::new_grid(0, 3, 10, 10.0, 0.2).is_err());
+        assert!(NetworkTopology::new_grid(1, 1, 10, 10.0, 0.2).is_err());
+        assert_eq!(
+            NetworkTopology::new_grid(1, 4, 10, 10.0, 0.2)
+                .unwrap()
+                .num_channels(),
+            3
+        );
+    }
+
+    // ===== RANDOM TOPOLOGY TESTS =====
+
+    #[test]
+    fn test_random_gnp() {
+        use rand::SeedableRng;
+        let build = |p: f64, ensure_connected: bool, seed: u64| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            NetworkTopology::new_random_gnp(10, p, 4, 10.0, 0.2, ensure_connected, &mut rng)
+        };
+        let network = build(0.3, false, 790).unwrap();
+        assert_eq!(network.topology_type, TopologyType::Random);
+        assert_eq!(network.num_nodes(), 10);
+        assert_eq!(
+            network.num_channels(),
+            build(0.3, false, 790).unwrap().num_channels()
+        );
+        // Fixed by the seed; about 0.3 × 45 on average
+        assert_eq!(network.num_channels(), 14);
+
+        assert_eq!(build(0.0, false, 1).unwrap().num_channels(), 0);
+        assert_eq!(build(1.0, false, 1).unwrap().num_channels(), 45);
+        assert!(build(0.0, true, 1).is_err());
+        assert!(build(1.5, false, 1).is_err());
+
+        for seed in 0..20 {
+            let network = build(0.25, true, seed).unwrap();
+            assert!(network.is_connected());
+        }
+    }
+
+    #[test]
+    fn test_waxman() {
+        use rand::SeedableRng;
+        let build = |ensure_connected: bool, seed: u64| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            NetworkTopology::new_waxman(12, 0.4, 0.6, 100.0, 4, 0.2, ensure_connected, &mut rng)
+                .unwrap()
+        };
+        let network = build(false, 790);
+        assert_eq!(network.num_channels(), build(false, 790).num_channels());
+        assert_eq!(network.num_channels(), 9);
+        // Channel lengths are distances within the square
+        let diagonal = 100.0 * 2.0_f64.sqrt();
+        assert!(network
+            .channels()
+            .iter()
+            .all(|channel| channel.distance_km > 0.0 && channel.distance_km <= diagonal));
+
+        for seed in 0..20 {
+            let network = build(true, seed);
+            assert!(network.is_connected());
+        }
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        assert!(NetworkTopology::new_waxman(5, 0.0, 0.5, 100.0, 4, 0.2, false, &mut rng).is_err());
+    }
+
     // ===== MESH TOPOLOGY TESTS =====
 
     #[test]
@@ -327,6 +1708,149 @@ mod tests {
         assert_eq!(network.num_channels(), 6);
     }
 
+    // ===== ROUTING TESTS =====
+
+    #[test]
+    fn test_neighbors_and_degree() {
+        let mut network = custom_nodes(3);
+        for channel in [
+            QuantumChannel::new(0, 1, 10.0, 0.2),
+            QuantumChannel::new(1, 0, 12.0, 0.2),
+            QuantumChannel::new(2, 0, 10.0, 0.2),
+        ] {
+            network.add_channel(channel).unwrap();
+        }
+        assert_eq!(network.neighbors(0), vec![1, 2]);
+        assert_eq!(network.degree(0), 3);
+        assert_eq!(network.neighbors(2), vec![0]);
+        assert!(network.neighbors(7).is_empty());
+    }
+
+    #[test]
+    fn test_k_shortest_paths_on_mesh() {
+        let network = NetworkTopology::new_mesh(4, 10, 10.0, 0.2);
+        let paths = network.k_shortest_paths(0, 3, 10, PathMetric::Hops);
+        assert_eq!(
+            paths,
+            vec![
+                vec![0, 3],
+                vec![0, 1, 3],
+                vec![0, 2, 3],
+                vec![0, 1, 2, 3],
+                vec![0, 2, 1, 3],
+            ]
+        );
+        assert_eq!(
+            network.k_shortest_paths(0, 3, 2, PathMetric::Loss),
+            paths[..2]
+        );
+        assert_eq!(
+            network.k_shortest_paths(2, 2, 3, PathMetric::Hops),
+            vec![vec![2]]
+        );
+        assert!(network
+            .k_shortest_paths(0, 3, 0, PathMetric::Hops)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_weighted_shortest_path() {
+        // The two-hop route is shorter than the direct 50 km link
+        let mut network = custom_nodes(4);
+        for (a, b, distance_km) in [(0, 1, 10.0), (1, 2, 10.0), (0, 2, 50.0)] {
+            network
+                .add_channel(QuantumChannel::new(a, b, distance_km, 0.2))
+                .unwrap();
+        }
+        assert_eq!(
+            network.shortest_path(0, 2, PathMetric::Hops),
+            Some(vec![0, 2])
+        );
+        assert_eq!(
+            network.shortest_path(0, 2, PathMetric::Distance),
+            Some(vec![0, 1, 2])
+        );
+        assert_eq!(
+            network.k_shortest_paths(0, 2, 5, PathMetric::Distance),
+            vec![vec![0, 1, 2], vec![0, 2]]
+        );
+        // Node 3 is isolated
+        assert_eq!(network.shortest_path(0, 3, PathMetric::Hops), None);
+        assert!(network
+            .k_shortest_paths(0, 3, 5, PathMetric::Hops)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_cut_channel_reroutes() {
+        let mut network = custom_nodes(3);
+        let direct = network
+            .add_channel(QuantumChannel::new(0, 2, 10.0, 0.2))
+            .unwrap();
+        for (a, b) in [(0, 1), (1, 2)] {
+            network
+                .add_channel(QuantumChannel::new(a, b, 10.0, 0.2))
+                .unwrap();
+        }
+        assert_eq!(
+            network.shortest_path(0, 2, PathMetric::Hops),
+            Some(vec![0, 2])
+        );
+
+        // Degrade the direct link, then cut it
+        network.channel_mut(direct).unwrap().attenuation_db_per_km = 5.0;
+        assert_eq!(
+            network.shortest_path(0, 2, PathMetric::Loss),
+            Some(vec![0, 1, 2])
+        );
+        let cut = network.remove_channel(direct).unwrap();
+        assert_eq!((cut.node_a, cut.node_b), (0, 2));
+        assert_eq!(
+            network.shortest_path(0, 2, PathMetric::Hops),
+            Some(vec![0, 1, 2])
+        );
+        assert!(network.remove_channel(direct).is_err());
+    }
+
+    #[test]
+    fn test_remove_node_renumbers() {
+        let mut network = custom_nodes(4);
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0)] {
+            network
+                .add_channel(QuantumChannel::new(a, b, 10.0 + a as f64, 0.2))
+                .unwrap();
+        }
+        for (a, b) in [(0, 1), (2, 3)] {
+            let (node_a, node_b) = network.get_node_pair_mut(a, b).unwrap();
+            node_a
+                .store_pair(StoredPair::new_with_fidelity(b, 1.0, 0.0, 100.0))
+                .unwrap();
+            node_b
+                .store_pair(StoredPair::new_with_fidelity(a, 1.0, 0.0, 100.0))
+                .unwrap();
+        }
+
+        let removed = network.remove_node(1).unwrap();
+        assert_eq!(removed.id, 1);
+        assert_eq!(network.num_nodes(), 3);
+        assert_eq!(network.num_channels(), 2);
+        // Old nodes 2 and 3 are now 1 and 2; the 2-3 link and pair follow
+        assert_eq!(network.get_node(2).unwrap().id, 2);
+        assert_eq!(network.find_channel(1, 2).unwrap().1.distance_km, 12.0);
+        assert_eq!(network.find_channel(2, 0).unwrap().1.distance_km, 13.0);
+        assert_eq!(
+            network.get_node(1).unwrap().stored_pairs()[0].partner_node_id,
+            2
+        );
+        // Node 0's pair with the removed node is gone
+        assert!(network.get_node(0).unwrap().stored_pairs().is_empty());
+        assert_eq!(network.validate(), Ok(()));
+
+        assert!(network.remove_node(5).is_err());
+        let mut linear = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        assert!(linear.remove_node(0).is_err());
+    }
+
     // ===== CUSTOM TOPOLOGY TESTS =====
 
     #[test]
@@ -373,6 +1897,83 @@ mod tests {
         assert!(result.unwrap_err().contains("does not exist"));
     }
 
+    #[test]
+    fn test_connectivity() {
+        let mut network = custom_nodes(3);
+        network
+            .add_channel(QuantumChannel::new(0, 1, 10.0, 0.2))
+            .unwrap();
+        assert!(!network.is_connected());
+        assert_eq!(
+            network.validate(),
+            Err(vec![TopologyError::IsolatedNode(2)])
+        );
+
+        network
+            .add_channel(QuantumChannel::new(2, 1, 10.0, 0.2))
+            .unwrap();
+        assert!(network.is_connected());
+        assert_eq!(network.validate(), Ok(()));
+        assert!(NetworkTopology::new_custom().is_connected());
+        assert!(NetworkTopology::new_ring(5, 2, 10.0, 0.2)
+            .unwrap()
+            .is_connected());
+    }
+
+    #[test]
+    fn test_add_channel_rejects_self_loops_and_duplicates() {
+        let mut network = custom_nodes(2);
+        let self_loop = network.add_channel(QuantumChannel::new(1, 1, 10.0, 0.2));
+        assert!(self_loop.unwrap_err().contains("to itself"));
+
+        let first = network
+            .add_channel(QuantumChannel::new(0, 1, 10.0, 0.2))
+            .unwrap();
+        let duplicate = network.add_channel(QuantumChannel::new(0, 1, 10.0, 0.2));
+        assert_eq!(
+            duplicate.unwrap_err(),
+            format!("Channel 0-1 duplicates channel {}", first)
+        );
+        assert!(network
+            .add_channel(QuantumChannel::new(1, 0, 10.0, 0.2))
+            .is_err());
+        // A parallel channel with different parameters is fine
+        assert!(network
+            .add_channel(QuantumChannel::new(0, 1, 12.0, 0.2))
+            .is_ok());
+        assert_eq!(network.num_channels(), 2);
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let mut network = custom_nodes(3);
+        // Bypass add_channel's checks to build a broken topology
+        for (id, (a, b)) in [(0, 1), (0, 1), (1, 1), (1, 5)].into_iter().enumerate() {
+            network.insert_channel(ChannelId(id), QuantumChannel::new(a, b, 10.0, 0.2));
+        }
+        let errors = network.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                TopologyError::DuplicateChannel {
+                    channel: ChannelId(1),
+                    duplicate_of: ChannelId(0)
+                },
+                TopologyError::SelfLoop {
+                    channel: ChannelId(2),
+                    node: 1
+                },
+                TopologyError::MissingNode {
+                    channel: ChannelId(3),
+                    node: 5
+                },
+                TopologyError::IsolatedNode(2),
+            ]
+        );
+        assert_eq!(errors[3].to_string(), "Node 2 has no channels");
+        assert_eq!(errors[0].to_string(), "Channel 1 duplicates channel 0");
+    }
+
     // ===== GENERAL ACCESS TESTS =====
 
     #[test]
@@ -395,6 +1996,18 @@ mod tests {
         assert_eq!(node.memory_capacity, 10);
     }
 
+    #[test]
+    fn test_get_node_pair_mut() {
+        let mut network = NetworkTopology::new_linear(3, 10, 10.0, 0.2);
+
+        let (a, b) = network.get_node_pair_mut(2, 0).unwrap();
+        assert_eq!(a.id, 2);
+        assert_eq!(b.id, 0);
+
+        assert!(network.get_node_pair_mut(1, 1).is_none());
+        assert!(network.get_node_pair_mut(0, 3).is_none());
+    }
+
     #[test]
     fn test_has_node() {
         let network = NetworkTopology::new_linear(2, 10, 10.0, 0.2);
@@ -402,4 +2015,387 @@ mod tests {
         assert!(network.has_node(1));
         assert!(!network.has_node(2));
     }
+
+    // ===== PARALLEL CHANNEL TESTS =====
+
+    /// 0 == 1 over two channels (id 0: 50 km lossy, id 1: 10 km), then 1 -- 2
+    fn multi_edge_network() -> NetworkTopology {
+        let mut network = NetworkTopology::new_custom();
+        for id in 0..3 {
+            network.add_node(QuantumNode::new(id, 10)).unwrap();
+        }
+        network
+            .add_channel(QuantumChannel::new(0, 1, 50.0, 0.2))
+            .unwrap();
+        network
+            .add_channel(QuantumChannel::new(1, 0, 10.0, 0.2))
+            .unwrap();
+        network
+            .add_channel(QuantumChannel::new(1, 2, 10.0, 0.2))
+            .unwrap();
+        network
+    }
+
+    #[test]
+    fn test_find_channel_is_ambiguous_on_multi_edge() {
+        let network = multi_edge_network();
+        assert_eq!(
+            network.channels_between(1, 0),
+            vec![ChannelId(0), ChannelId(1)]
+        );
+        assert!(network.find_channel(0, 1).is_none());
+        assert!(network.find_channel(2, 1).is_some());
+    }
+
+    #[test]
+    fn test_channel_selectors() {
+        let mut network = multi_edge_network();
+        assert_eq!(
+            network.select_channel(0, 1, ChannelSelector::ById(ChannelId(0))),
+            Some(ChannelId(0))
+        );
+        assert_eq!(
+            network.select_channel(0, 1, ChannelSelector::BestSuccessProbability),
+            Some(ChannelId(1))
+        );
+
+        assert_eq!(
+            network.select_channel(0, 1, ChannelSelector::LeastLoaded),
+            Some(ChannelId(0))
+        );
+        network.record_channel_use(ChannelId(0));
+        assert_eq!(
+            network.select_channel(1, 0, ChannelSelector::LeastLoaded),
+            Some(ChannelId(1))
+        );
+    }
+
+    #[test]
+    fn test_path_reports_channel_per_hop() {
+        let network = multi_edge_network();
+
+        let path = network
+            .find_path(0, 2, ChannelSelector::BestSuccessProbability)
+            .unwrap();
+        assert_eq!(path.nodes, vec![0, 1, 2]);
+        assert_eq!(
+            path.hops,
+            vec![
+                PathHop {
+                    from: 0,
+                    to: 1,
+                    channel_id: ChannelId(1)
+                },
+                PathHop {
+                    from: 1,
+                    to: 2,
+                    channel_id: ChannelId(2)
+                },
+            ]
+        );
+
+        // By-id pins the lossy edge; the single-edge hop falls back to its channel
+        let path = network
+            .find_path(0, 2, ChannelSelector::ById(ChannelId(0)))
+            .unwrap();
+        let channel_ids: Vec<ChannelId> = path.hops.iter().map(|hop| hop.channel_id).collect();
+        assert_eq!(channel_ids, vec![ChannelId(0), ChannelId(2)]);
+    }
+
+    // ===== CHANNEL ID TESTS =====
+
+    fn custom_nodes(count: usize) -> NetworkTopology {
+        let mut network = NetworkTopology::new_custom();
+        for id in 0..count {
+            network.add_node(QuantumNode::new(id, 10)).unwrap();
+        }
+        network
+    }
+
+    #[test]
+    fn test_channel_ids_stable_across_add_and_remove() {
+        let mut network = custom_nodes(4);
+        let a = network
+            .add_channel(QuantumChannel::new(0, 1, 1.0, 0.2))
+            .unwrap();
+        let b = network
+            .add_channel(QuantumChannel::new(1, 2, 2.0, 0.2))
+            .unwrap();
+        let c = network
+            .add_channel(QuantumChannel::new(2, 3, 3.0, 0.2))
+            .unwrap();
+        assert_eq!((a, b, c), (ChannelId(0), ChannelId(1), ChannelId(2)));
+
+        let removed = network.remove_channel(b).unwrap();
+        assert_eq!(removed.distance_km, 2.0);
+        assert!(network.channel(b).is_none());
+        assert_eq!(network.channel(c).unwrap().distance_km, 3.0);
+        assert_eq!(network.find_channel(2, 3).unwrap().0, c);
+        assert!(network.remove_channel(b).is_err());
+
+        // The freed id is reused and iteration stays in id order
+        let d = network
+            .add_channel(QuantumChannel::new(0, 3, 4.0, 0.2))
+            .unwrap();
+        assert_eq!(d, b);
+        assert_eq!(network.channel_ids(), &[a, b, c]);
+        let distances: Vec<f64> = network.channels().iter().map(|ch| ch.distance_km).collect();
+        assert_eq!(distances, vec![1.0, 4.0, 3.0]);
+
+        let mut linear = NetworkTopology::new_linear(3, 2, 1.0, 0.2);
+        assert!(linear.remove_channel(ChannelId(0)).is_err());
+    }
+
+    #[test]
+    fn test_channel_ids_survive_snapshot_round_trip() {
+        let mut network = custom_nodes(3);
+        for (a, b) in [(0, 1), (1, 2), (0, 2)] {
+            network
+                .add_channel(QuantumChannel::new(a, b, 5.0, 0.2))
+                .unwrap();
+        }
+        network.remove_channel(ChannelId(0)).unwrap();
+
+        let restored = NetworkTopology::from_snapshot(&network.snapshot()).unwrap();
+        assert_eq!(restored.channel_ids(), &[ChannelId(1), ChannelId(2)]);
+        assert_eq!(restored.find_channel(0, 2).unwrap().0, ChannelId(2));
+        assert_eq!(restored.snapshot(), network.snapshot());
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&network.snapshot()).unwrap();
+            let parsed: TopologySnapshot = serde_json::from_str(&json).unwrap();
+            let restored = NetworkTopology::from_snapshot(&parsed).unwrap();
+            assert_eq!(restored.channel_ids(), network.channel_ids());
+            assert_eq!(serde_json::to_string(&restored.snapshot()).unwrap(), json);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_restored_pairs_keep_ids_unique_and_relink_legacy_halves() {
+        use crate::network::{attempt_entanglement_generation, next_pair_id, StoredPair};
+        use crate::quantum::TwoQubitState;
+
+        let bell = TwoQubitState::new_bell_phi_plus();
+        let saved_id = next_pair_id() + 1_000_000;
+        let mut nodes = [QuantumNode::new(0, 4), QuantumNode::new(1, 4)];
+        for (node, partner) in [(0, 1), (1, 0)] {
+            let current = StoredPair::new(partner, bell.clone(), 0.0, 100.0).with_pair_id(saved_id);
+            nodes[node].store_pair(current).unwrap();
+            for time in [1.0, 2.0] {
+                let legacy = StoredPair::new(partner, bell.clone(), time, 100.0);
+                nodes[node].store_pair(legacy).unwrap();
+            }
+        }
+        // Saved before pairs had ids: only the first pair keeps its id
+        let mut network = NetworkTopology::new_custom();
+        for node in &nodes {
+            let mut json = serde_json::to_value(node).unwrap();
+            for pair in json["stored_pairs"]
+                .as_array_mut()
+                .unwrap()
+                .iter_mut()
+                .skip(1)
+            {
+                pair.as_object_mut().unwrap().remove("pair_id");
+            }
+            let restored: QuantumNode = serde_json::from_value(json).unwrap();
+            network.add_node(restored).unwrap();
+        }
+        network
+            .add_channel(QuantumChannel::new(0, 1, 0.0, 0.0))
+            .unwrap();
+
+        let ids = |network: &NetworkTopology, node: usize| -> Vec<u64> {
+            let pairs = network.get_node(node).unwrap().stored_pairs();
+            pairs.iter().map(|pair| pair.pair_id).collect()
+        };
+        let restored_ids = ids(&network, 0);
+        assert_eq!(restored_ids, ids(&network, 1));
+        assert_eq!(restored_ids[0], saved_id);
+        assert!(restored_ids[1] != 0 && restored_ids[2] != 0);
+        assert_ne!(restored_ids[1], restored_ids[2]);
+        // Halves were matched by creation time
+        let creation = |node: usize, index: usize| {
+            network.get_node(node).unwrap().stored_pairs()[index].creation_time
+        };
+        assert_eq!(creation(0, 2), creation(1, 2));
+
+        let channel = network.find_channel(0, 1).unwrap().1.clone();
+        let (node_a, node_b) = network.get_node_pair_mut(0, 1).unwrap();
+        assert!(attempt_entanglement_generation(node_a, node_b, &channel, 3.0, 100.0).unwrap());
+        let new_id = ids(&network, 0)[3];
+        assert!(new_id > saved_id);
+        assert!(!restored_ids.contains(&new_id));
+        assert!(network.verify_memory_consistency().is_ok());
+    }
+
+    #[test]
+    fn test_exports_ignore_intermediate_removals() {
+        let mut direct = custom_nodes(3);
+        direct
+            .add_channel(QuantumChannel::new(0, 1, 5.0, 0.2))
+            .unwrap();
+        direct
+            .add_channel(QuantumChannel::new(1, 2, 6.0, 0.2))
+            .unwrap();
+
+        let mut detour = custom_nodes(3);
+        detour
+            .add_channel(QuantumChannel::new(0, 1, 5.0, 0.2))
+            .unwrap();
+        let temporary = detour
+            .add_channel(QuantumChannel::new(0, 2, 9.0, 0.2))
+            .unwrap();
+        detour.remove_channel(temporary).unwrap();
+        detour
+            .add_channel(QuantumChannel::new(1, 2, 6.0, 0.2))
+            .unwrap();
+
+        assert_eq!(
+            format!("{:?}", direct.snapshot()),
+            format!("{:?}", detour.snapshot())
+        );
+        assert_eq!(
+            direct.find_path(0, 2, ChannelSelector::BestSuccessProbability),
+            detour.find_path(0, 2, ChannelSelector::BestSuccessProbability)
+        );
+    }
+
+    #[test]
+    fn test_network_fidelity_summary() {
+        let mut topology = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        // (end a, end b, created at, fidelity, coherence time)
+        let layout = [
+            (0, 1, 0.0, 0.9, 100.0),
+            (1, 2, 50.0, 0.8, 100.0),
+            (0, 2, 100.0, 0.7, 1000.0),
+        ];
+        for (a, b, created, fidelity, coherence) in layout {
+            for (holder, partner) in [(a, b), (b, a)] {
+                let mut pair = StoredPair::new(
+                    partner,
+                    crate::quantum::TwoQubitState::new_bell_phi_plus(),
+                    created,
+                    coherence,
+                );
+                pair.fidelity = fidelity;
+                topology
+                    .get_node_mut(holder)
+                    .unwrap()
+                    .store_pair(pair)
+                    .unwrap();
+            }
+        }
+
+        // Both halves of every pair, node by node
+        assert_eq!(topology.iter_all_pairs(200.0).count(), 6);
+        let nodes: Vec<usize> = topology
+            .iter_all_pairs(200.0)
+            .map(|pair| pair.node_id)
+            .collect();
+        assert_eq!(nodes, vec![0, 0, 1, 1, 2, 2]);
+
+        // At t=200: 0.9 e^-2, 0.8 e^-1.5, 0.7 e^-0.1
+        let mut expected = [
+            0.9 * (-2.0_f64).exp(),
+            0.8 * (-1.5_f64).exp(),
+            0.7 * (-0.1_f64).exp(),
+        ];
+        expected.sort_by(f64::total_cmp);
+        let summary = topology.network_fidelity_summary(200.0).unwrap();
+        assert_eq!(summary.count, 3);
+        assert!((summary.mean - expected.iter().sum::<f64>() / 3.0).abs() < 1e-12);
+        assert_eq!(summary.min, expected[0]);
+        assert_eq!(summary.max, expected[2]);
+        assert_eq!(summary.median, expected[1]);
+        assert!((summary.p10 - (expected[0] + 0.2 * (expected[1] - expected[0]))).abs() < 1e-12);
+
+        assert_eq!(
+            NetworkTopology::new_linear(2, 1, 1.0, 0.2).network_fidelity_summary(0.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_per_node_capacities_and_hardware() {
+        let topology =
+            NetworkTopology::new_linear_with_capacities(3, &[1, 10, 1], 10.0, 0.2).unwrap();
+        let capacities: Vec<usize> = topology
+            .nodes()
+            .iter()
+            .map(|node| node.memory_capacity)
+            .collect();
+        assert_eq!(capacities, vec![1, 10, 1]);
+        assert_eq!(topology.num_channels(), 2);
+        assert!(NetworkTopology::new_star_with_capacities(4, &[5, 1, 1], 10.0, 0.2).is_err());
+        assert!(NetworkTopology::new_mesh_with_capacities(1, &[5], 10.0, 0.2).is_err());
+
+        let repeater = NodeHardware::new(10)
+            .with_memory_efficiency(0.8)
+            .with_coherence_time(50.0)
+            .with_gate_error_rate(0.01);
+        let end = NodeHardware::new(1);
+        let topology =
+            NetworkTopology::new_star_with_hardware(3, &[repeater, end, end], 10.0, 0.2).unwrap();
+        let hub = topology.get_node(0).unwrap();
+        assert_eq!(hub.memory_capacity, 10);
+        assert_eq!(hub.memory_efficiency, Some(0.8));
+        assert_eq!(hub.coherence_time_ms, Some(50.0));
+        assert_eq!(hub.gate_error, 0.01);
+
+        let broken = NodeHardware::new(1).with_memory_efficiency(1.5);
+        assert!(NetworkTopology::new_mesh_with_hardware(2, &[end, broken], 10.0, 0.2).is_err());
+    }
+
+    #[test]
+    fn test_repeater_chain_roles() {
+        let chain = NetworkTopology::new_linear_repeater_chain(4, 2, 10.0, 0.2);
+        let ends: Vec<usize> = chain
+            .nodes_with_role(NodeRole::EndNode)
+            .iter()
+            .map(|node| node.id)
+            .collect();
+        let repeaters: Vec<usize> = chain
+            .nodes_with_role(NodeRole::Repeater)
+            .iter()
+            .map(|node| node.id)
+            .collect();
+        assert_eq!(ends, vec![0, 3]);
+        assert_eq!(repeaters, vec![1, 2]);
+        assert!(chain.nodes_with_role(NodeRole::BsmStation).is_empty());
+
+        // Plain constructors default to repeaters
+        let line = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        assert_eq!(line.nodes_with_role(NodeRole::Repeater).len(), 3);
+
+        let mut custom = NetworkTopology::new_custom();
+        custom
+            .add_node(QuantumNode::new_with_role(0, 1, NodeRole::BsmStation))
+            .unwrap();
+        assert_eq!(custom.nodes_with_role(NodeRole::BsmStation)[0].id, 0);
+    }
+
+    #[test]
+    fn test_purge_drops_both_halves() {
+        let mut topology = NetworkTopology::new_linear(3, 4, 0.0, 0.0);
+        for (a, b, time) in [(0, 1, 0.0), (1, 2, 0.0), (0, 1, 40.0)] {
+            let channel = topology.find_channel(a, b).unwrap().1.clone();
+            let (node_a, node_b) = topology.get_node_pair_mut(a, b).unwrap();
+            crate::network::attempt_entanglement_generation(node_a, node_b, &channel, time, 100.0)
+                .unwrap();
+        }
+        assert_eq!(topology.purge_expired(50.0, Cutoff::MaxAge(20.0)), 2);
+        let occupancy: Vec<usize> = topology
+            .nodes()
+            .iter()
+            .map(|node| node.num_stored_pairs())
+            .collect();
+        assert_eq!(occupancy, vec![1, 1, 0]);
+        assert_eq!(
+            topology.get_node(0).unwrap().stored_pairs()[0].creation_time,
+            40.0
+        );
+    }
 }
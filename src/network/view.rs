@@ -0,0 +1,41 @@
+use super::NetworkTopology;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Shared read-only handle to a topology
+///
+/// Cloning is cheap (one `Arc`), and only `&NetworkTopology` is reachable,
+/// so a view can be handed to many threads at once, e.g. one per parallel
+/// replication. A trial that needs to store pairs takes its own copy with
+/// `fork`.
+#[derive(Clone)]
+pub struct TopologyView {
+    topology: Arc<NetworkTopology>,
+}
+
+impl TopologyView {
+    pub fn new(topology: NetworkTopology) -> Self {
+        TopologyView {
+            topology: Arc::new(topology),
+        }
+    }
+
+    /// Independent mutable copy for one trial
+    pub fn fork(&self) -> NetworkTopology {
+        NetworkTopology::clone(&self.topology)
+    }
+}
+
+impl Deref for TopologyView {
+    type Target = NetworkTopology;
+
+    fn deref(&self) -> &NetworkTopology {
+        &self.topology
+    }
+}
+
+impl From<NetworkTopology> for TopologyView {
+    fn from(topology: NetworkTopology) -> Self {
+        Self::new(topology)
+    }
+}
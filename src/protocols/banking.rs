@@ -0,0 +1,631 @@
+use super::generation::{AttemptOutcome, GenerationProtocol};
+use super::occupancy::MemoryOccupancyModel;
+use crate::network::{Cutoff, LinkRetryState, NetworkTopology, StoredPair};
+use crate::simulation::{
+    Event, EventHandler, EventScheduler, EventType, HandlerRng, ParamOverride, RandomnessDecl,
+    RandomnessSource, RunReport,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Standing reserve of pairs a node keeps with one partner
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BankingPolicy {
+    /// Partner node the pairs are shared with
+    pub partner: usize,
+    /// Number of pairs to keep banked
+    pub target_count: usize,
+    /// Pairs below this fidelity (at the current time) do not count
+    pub min_fidelity: f64,
+}
+
+/// Bank occupancy of one policy at one point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BankSample {
+    pub time: f64,
+    pub node_id: usize,
+    pub partner: usize,
+    /// Pairs counted towards the target
+    pub banked: usize,
+}
+
+/// Link layer that keeps banked pairs topped up
+///
+/// Generation events for a link are scheduled only while the bank is below
+/// target; a full bank idles until `consume` or `replenish` notices a deficit.
+/// Each link backs off after repeated failures as its channel's
+/// `retry_policy` prescribes.
+/// With a cutoff, `Decoherence` events purge expired pairs periodically, so
+/// the run keeps going until a run limit or pause stops it.
+pub struct BankingLinkLayer<P: GenerationProtocol> {
+    pub topology: NetworkTopology,
+    pub protocol: P,
+    /// Time between attempts on an active link (ms)
+    /// Raised to the protocol's attempt duration on long links
+    pub attempt_interval_ms: f64,
+    /// Memory coherence time for generated pairs (ms)
+    pub coherence_time_ms: f64,
+    policies: HashMap<usize, Vec<BankingPolicy>>,
+    /// Links (node, partner) with a generation event in flight
+    active: HashSet<(usize, usize)>,
+    /// Failure streak and backoff idle time per link (node, partner)
+    retry: HashMap<(usize, usize), LinkRetryState>,
+    history: Vec<BankSample>,
+    occupancy: Option<MemoryOccupancyModel>,
+    /// Expiry rule and the time between purges (ms)
+    cutoff: Option<(Cutoff, f64)>,
+    purge_scheduled: bool,
+    /// Pairs withdrawn by `consume`, and their summed storage time (ms)
+    consumed: usize,
+    consumed_storage_time_ms: f64,
+    /// Pairs dropped by the cutoff
+    expired: usize,
+    /// Passed to the protocol for every attempt; seeded by the engine
+    rng: HandlerRng,
+}
+
+impl<P: GenerationProtocol> BankingLinkLayer<P> {
+    pub fn new(
+        topology: NetworkTopology,
+        protocol: P,
+        attempt_interval_ms: f64,
+        coherence_time_ms: f64,
+    ) -> Self {
+        BankingLinkLayer {
+            topology,
+            protocol,
+            attempt_interval_ms,
+            coherence_time_ms,
+            policies: HashMap::new(),
+            active: HashSet::new(),
+            retry: HashMap::new(),
+            history: Vec::new(),
+            occupancy: None,
+            cutoff: None,
+            purge_scheduled: false,
+            consumed: 0,
+            consumed_storage_time_ms: 0.0,
+            expired: 0,
+            rng: HandlerRng::unseeded("BankingLinkLayer"),
+        }
+    }
+
+    /// Purge pairs expired under `cutoff` every `purge_interval_ms`
+    pub fn with_cutoff(mut self, cutoff: Cutoff, purge_interval_ms: f64) -> Self {
+        self.cutoff = Some((cutoff, purge_interval_ms));
+        self
+    }
+
+    /// Pairs dropped by the cutoff so far
+    pub fn expired_count(&self) -> usize {
+        self.expired
+    }
+
+    /// Pairs withdrawn by `consume` so far
+    pub fn consumed_count(&self) -> usize {
+        self.consumed
+    }
+
+    /// Retry bookkeeping of the link from `node_id` to `partner`, once it has attempted
+    pub fn retry_state(&self, node_id: usize, partner: usize) -> Option<&LinkRetryState> {
+        self.retry.get(&(node_id, partner))
+    }
+
+    /// Simulated time all links spent idle because of retry backoff (ms)
+    pub fn backoff_idle_time(&self) -> f64 {
+        self.retry.values().map(|state| state.idle_time).sum()
+    }
+
+    /// Share memory slots between storage and attempts (see `MemoryOccupancyModel`)
+    pub fn with_occupancy_model(mut self, model: MemoryOccupancyModel) -> Self {
+        self.occupancy = Some(model);
+        self
+    }
+
+    /// Memory duty-cycle model, if one is set
+    pub fn occupancy_model(&self) -> Option<&MemoryOccupancyModel> {
+        self.occupancy.as_ref()
+    }
+
+    /// Add a banking policy for `node_id`
+    pub fn add_policy(&mut self, node_id: usize, policy: BankingPolicy) -> Result<(), String> {
+        if self
+            .topology
+            .find_channel(node_id, policy.partner)
+            .is_none()
+        {
+            return Err(format!(
+                "No channel between node {} and node {}",
+                node_id, policy.partner
+            ));
+        }
+        self.policies.entry(node_id).or_default().push(policy);
+        Ok(())
+    }
+
+    /// Policies registered for `node_id`
+    pub fn policies(&self, node_id: usize) -> &[BankingPolicy] {
+        self.policies.get(&node_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Pairs at `node_id` with `partner` counting towards a bank
+    pub fn banked_count(
+        &self,
+        node_id: usize,
+        partner: usize,
+        min_fidelity: f64,
+        current_time: f64,
+    ) -> usize {
+        self.topology.get_node(node_id).map_or(0, |node| {
+            node.stored_pairs()
+                .iter()
+                .filter(|pair| {
+                    pair.partner_node_id == partner
+                        && pair.fidelity_at(current_time) >= min_fidelity
+                })
+                .count()
+        })
+    }
+
+    /// Bank occupancy samples, in time order
+    pub fn occupancy_history(&self) -> &[BankSample] {
+        &self.history
+    }
+
+    /// Schedule generation on every idle link whose bank is below target
+    pub fn replenish(&mut self, scheduler: &mut EventScheduler, current_time: f64) {
+        let mut deficits = Vec::new();
+        for (&node_id, policies) in &self.policies {
+            for policy in policies {
+                let key = (node_id, policy.partner);
+                if self.active.contains(&key) {
+                    continue;
+                }
+                let banked =
+                    self.banked_count(node_id, policy.partner, policy.min_fidelity, current_time);
+                if banked < policy.target_count {
+                    deficits.push(key);
+                }
+            }
+        }
+
+        // Deterministic scheduling order regardless of HashMap iteration
+        deficits.sort_unstable();
+        for (node_id, partner) in deficits {
+            self.active.insert((node_id, partner));
+            let mut event = Event::new(current_time, EventType::EntanglementGeneration, node_id);
+            event.target_node_id = Some(partner);
+            scheduler.schedule(event);
+        }
+
+        if let (Some((_, interval_ms)), false) = (self.cutoff, self.purge_scheduled) {
+            self.purge_scheduled = true;
+            scheduler.schedule(Event::new(
+                current_time + interval_ms,
+                EventType::Decoherence,
+                0,
+            ));
+        }
+    }
+
+    /// Drop expired pairs, restart links whose banks ran low and schedule
+    /// the next purge
+    fn purge(&mut self, scheduler: &mut EventScheduler, current_time: f64) {
+        let Some((cutoff, _)) = self.cutoff else {
+            return;
+        };
+        self.expired += self.topology.purge_expired(current_time, cutoff);
+        self.purge_scheduled = false;
+        self.replenish(scheduler, current_time);
+    }
+
+    /// Withdraw the best banked pair between `node_id` and `partner`
+    /// Removes both halves and schedules regeneration if the bank runs low
+    pub fn consume(
+        &mut self,
+        node_id: usize,
+        partner: usize,
+        scheduler: &mut EventScheduler,
+        current_time: f64,
+    ) -> Option<StoredPair> {
+        let (node, partner_node) = self.topology.get_node_pair_mut(node_id, partner)?;
+        let handle = match &self.occupancy {
+            Some(model) => model.best_consumable_pair(node, partner, current_time)?,
+            None => node.best_pair_with(partner, current_time)?,
+        };
+        let pair = node.remove_pair_at(handle, current_time)?;
+
+        partner_node.remove_pair_by_id(pair.pair_id, current_time);
+        self.consumed += 1;
+        self.consumed_storage_time_ms += current_time - pair.creation_time;
+
+        self.record(node_id, partner, current_time);
+        self.replenish(scheduler, current_time);
+        Some(pair)
+    }
+
+    fn policy(&self, node_id: usize, partner: usize) -> Option<BankingPolicy> {
+        self.policies(node_id)
+            .iter()
+            .find(|policy| policy.partner == partner)
+            .copied()
+    }
+
+    fn record(&mut self, node_id: usize, partner: usize, current_time: f64) {
+        if let Some(policy) = self.policy(node_id, partner) {
+            let banked = self.banked_count(node_id, partner, policy.min_fidelity, current_time);
+            self.history.push(BankSample {
+                time: current_time,
+                node_id,
+                partner,
+                banked,
+            });
+        }
+    }
+}
+
+impl<P: GenerationProtocol> RandomnessSource for BankingLinkLayer<P> {
+    fn randomness(&self) -> Vec<RandomnessDecl> {
+        self.topology
+            .channels()
+            .iter()
+            .flat_map(|channel| self.protocol.randomness(channel))
+            .collect()
+    }
+}
+
+impl<P: GenerationProtocol> EventHandler for BankingLinkLayer<P> {
+    fn check_invariants(&self) -> Result<(), String> {
+        self.topology.verify_memory_consistency()
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rng.seed(seed);
+    }
+
+    fn topology(&self) -> Option<&NetworkTopology> {
+        Some(&self.topology)
+    }
+
+    fn report(&self, report: &mut RunReport) {
+        report
+            .ignored_parameters
+            .extend(self.protocol.ignored_parameters());
+        report.generation.deliveries += self.consumed;
+        report.generation.total_storage_time_ms += self.consumed_storage_time_ms;
+        report.generation.expired += self.expired;
+        report.generation.backoff_idle_time += self.backoff_idle_time();
+    }
+
+    fn apply_override(
+        &mut self,
+        change: &ParamOverride,
+        scheduler: &mut EventScheduler,
+        current_time: f64,
+    ) -> Result<(), String> {
+        if change.apply_to_topology(&mut self.topology)? {
+            return Ok(());
+        }
+        match *change {
+            ParamOverride::AttemptInterval(interval_ms) => {
+                self.attempt_interval_ms = interval_ms;
+                Ok(())
+            }
+            ParamOverride::BankingPolicy { node_id, policy } => {
+                let existing = self
+                    .policies
+                    .get_mut(&node_id)
+                    .and_then(|policies| policies.iter_mut().find(|p| p.partner == policy.partner))
+                    .ok_or_else(|| {
+                        format!(
+                            "Node {} has no banking policy with node {}",
+                            node_id, policy.partner
+                        )
+                    })?;
+                *existing = policy;
+                // A raised target must restart generation on an idle link
+                self.replenish(scheduler, current_time);
+                Ok(())
+            }
+            _ => self.protocol.apply_override(change),
+        }
+    }
+
+    fn handle(&mut self, event: &Event, scheduler: &mut EventScheduler) {
+        if event.event_type == EventType::Decoherence {
+            self.purge(scheduler, event.time);
+            return;
+        }
+        if event.event_type != EventType::EntanglementGeneration {
+            return;
+        }
+        let Some(partner) = event.target_node_id else {
+            return;
+        };
+        let Some(policy) = self.policy(event.node_id, partner) else {
+            return;
+        };
+
+        let banked = self.banked_count(event.node_id, partner, policy.min_fidelity, event.time);
+        // Outcome of this slot's attempt, if one was made
+        let mut outcome = None;
+        if let (true, Some(model)) = (banked < policy.target_count, &mut self.occupancy) {
+            // No idle slot means no attempt this interval
+            outcome = model
+                .attempt(
+                    &self.protocol,
+                    &mut self.topology,
+                    event.node_id,
+                    partner,
+                    event.time,
+                    self.coherence_time_ms,
+                    &mut self.rng,
+                )
+                .ok()
+                .flatten();
+            self.record(event.node_id, partner, event.time);
+        } else if banked < policy.target_count {
+            let channel = self
+                .topology
+                .find_channel(event.node_id, partner)
+                .map(|(_, channel)| channel.clone());
+            let nodes = self.topology.get_node_pair_mut(event.node_id, partner);
+            if let (Some(channel), Some((node, partner_node))) = (channel, nodes) {
+                // A memory-full error just means this attempt is skipped
+                outcome = self
+                    .protocol
+                    .attempt(
+                        node,
+                        partner_node,
+                        &channel,
+                        event.time,
+                        self.coherence_time_ms,
+                        &mut self.rng,
+                    )
+                    .ok();
+            }
+            self.record(event.node_id, partner, event.time);
+        }
+
+        // The next attempt cannot start before this one has heralded back,
+        // and waits longer still while the link backs off
+        let Some((_, channel)) = self.topology.find_channel(event.node_id, partner) else {
+            return;
+        };
+        let attempt_duration_ms = self.protocol.attempt_duration_ms(channel);
+        let backoff_ms = match outcome {
+            Some(outcome) => self
+                .retry
+                .entry((event.node_id, partner))
+                .or_default()
+                .record_attempt(outcome == AttemptOutcome::Success, &channel.retry_policy),
+            None => 0.0,
+        };
+        let banked = self.banked_count(event.node_id, partner, policy.min_fidelity, event.time);
+        if banked < policy.target_count {
+            let mut next = Event::new(
+                event.time + self.attempt_interval_ms.max(attempt_duration_ms) + backoff_ms,
+                EventType::EntanglementGeneration,
+                event.node_id,
+            );
+            next.target_node_id = Some(partner);
+            scheduler.schedule(next);
+        } else {
+            self.active.remove(&(event.node_id, partner));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{Backoff, QuantumChannel, QuantumNode, RetryPolicy};
+    use crate::protocols::generation::AttemptOutcome;
+    use crate::simulation::SimulationEngine;
+    use crate::testing::FakeGenerationProtocol;
+
+    fn two_node_bank(target_count: usize) -> BankingLinkLayer<FakeGenerationProtocol> {
+        let mut topology = NetworkTopology::new_custom();
+        topology.add_node(QuantumNode::new(0, 5)).unwrap();
+        topology.add_node(QuantumNode::new(1, 5)).unwrap();
+        topology
+            .add_channel(QuantumChannel::new(0, 1, 10.0, 0.2))
+            .unwrap();
+
+        let protocol = FakeGenerationProtocol::scripted(vec![AttemptOutcome::Success], 0.95);
+        let mut link = BankingLinkLayer::new(topology, protocol, 1.0, 1000.0);
+        link.add_policy(
+            0,
+            BankingPolicy {
+                partner: 1,
+                target_count,
+                min_fidelity: 0.9,
+            },
+        )
+        .unwrap();
+        link
+    }
+
+    #[test]
+    fn test_bank_fills_idles_and_refills() {
+        let mut link = two_node_bank(3);
+        let mut engine = SimulationEngine::new();
+
+        link.replenish(&mut engine.scheduler, 0.0);
+        engine.run(&mut link);
+
+        // Full bank: the link idles and the queue drains
+        assert_eq!(link.banked_count(0, 1, 0.9, 2.0), 3);
+        assert!(!engine.scheduler.has_events());
+        assert_eq!(link.protocol.attempts(), 3);
+
+        // Consumer drains two pairs, which restarts generation
+        assert!(link.consume(0, 1, &mut engine.scheduler, 10.0).is_some());
+        assert!(link.consume(0, 1, &mut engine.scheduler, 10.0).is_some());
+        assert_eq!(engine.scheduler.pending_events(), 1);
+        engine.run(&mut link);
+
+        assert_eq!(link.banked_count(0, 1, 0.9, 12.0), 3);
+        assert_eq!(link.topology.get_node(1).unwrap().num_stored_pairs(), 3);
+        assert_eq!(link.protocol.attempts(), 5);
+
+        let occupancy: Vec<usize> = link
+            .occupancy_history()
+            .iter()
+            .map(|sample| sample.banked)
+            .collect();
+        assert_eq!(occupancy, vec![1, 2, 3, 2, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_single_slot_duty_cycle_blocks_generation_until_consumed() {
+        let mut topology = NetworkTopology::new_custom();
+        topology.add_node(QuantumNode::new(0, 1)).unwrap();
+        topology.add_node(QuantumNode::new(1, 1)).unwrap();
+        topology
+            .add_channel(QuantumChannel::new(0, 1, 10.0, 0.2))
+            .unwrap();
+        let protocol = FakeGenerationProtocol::scripted(vec![AttemptOutcome::Success], 0.95);
+        let mut link = BankingLinkLayer::new(topology, protocol, 1.0, 1000.0)
+            .with_occupancy_model(MemoryOccupancyModel::new(0.5));
+        // The bank never fills, so only the duty cycle stops attempts
+        link.add_policy(
+            0,
+            BankingPolicy {
+                partner: 1,
+                target_count: 2,
+                min_fidelity: 0.9,
+            },
+        )
+        .unwrap();
+        let mut engine = SimulationEngine::new();
+        link.replenish(&mut engine.scheduler, 0.0);
+
+        engine.pause_at(5.5);
+        assert!(engine.run(&mut link).is_paused());
+        assert_eq!(link.protocol.attempts(), 1);
+        assert_eq!(link.occupancy_model().unwrap().blocked_attempts, 5);
+
+        assert!(link.consume(0, 1, &mut engine.scheduler, 5.5).is_some());
+        engine.pause_at(6.5);
+        engine.run(&mut link);
+        assert_eq!(link.protocol.attempts(), 2);
+        assert_eq!(link.banked_count(0, 1, 0.9, 6.5), 1);
+    }
+
+    #[test]
+    fn test_policy_requires_channel() {
+        let mut link = two_node_bank(1);
+        let policy = BankingPolicy {
+            partner: 7,
+            target_count: 1,
+            min_fidelity: 0.5,
+        };
+        assert!(link.add_policy(0, policy).is_err());
+    }
+
+    #[test]
+    fn test_raised_target_override_restarts_idle_link() {
+        let mut link = two_node_bank(1);
+        let mut engine = SimulationEngine::new();
+        link.replenish(&mut engine.scheduler, 0.0);
+        // An unrelated later event keeps the queue alive past the pause
+        engine
+            .scheduler
+            .schedule(Event::new(20.0, EventType::Measurement, 0));
+
+        engine.pause_at(5.0);
+        assert!(engine.run(&mut link).is_paused());
+        assert_eq!(link.banked_count(0, 1, 0.9, 5.0), 1);
+        let policy = BankingPolicy {
+            partner: 1,
+            target_count: 3,
+            min_fidelity: 0.9,
+        };
+        engine
+            .apply_override(
+                &mut link,
+                ParamOverride::BankingPolicy { node_id: 0, policy },
+            )
+            .unwrap();
+        assert_eq!(link.policies(0), &[policy]);
+
+        // The fake protocol has no efficiencies to override
+        assert!(engine
+            .apply_override(&mut link, ParamOverride::DetectorEfficiency(0.5))
+            .is_err());
+
+        engine.run(&mut link);
+        assert_eq!(link.banked_count(0, 1, 0.9, 8.0), 3);
+        assert_eq!(engine.overrides().len(), 1);
+    }
+
+    #[test]
+    fn test_cutoff_expires_stale_bank_and_refills() {
+        let mut link = two_node_bank(2).with_cutoff(Cutoff::MaxAge(10.0), 5.0);
+        let mut engine = SimulationEngine::with_limits(crate::simulation::RunLimits {
+            max_sim_time: Some(32.0),
+            ..Default::default()
+        });
+        link.replenish(&mut engine.scheduler, 0.0);
+
+        engine.pause_at(12.0);
+        engine.run(&mut link);
+        assert!(link.consume(0, 1, &mut engine.scheduler, 12.0).is_some());
+        let report = engine.run(&mut link).into_report();
+
+        // Purges every 5 ms drop the pairs from t = 0, 12 and 15 (the t = 1
+        // pair was consumed); each drop restarts generation on the link
+        assert_eq!(link.expired_count(), 3);
+        assert_eq!(link.consumed_count(), 1);
+        assert_eq!(report.generation.expired, 3);
+        assert_eq!(report.generation.deliveries, 1);
+        assert_eq!(link.banked_count(0, 1, 0.9, 32.0), 2);
+    }
+
+    #[test]
+    fn test_retry_backoff_slows_failing_link() {
+        let run = |policy: RetryPolicy| {
+            let mut topology = NetworkTopology::new_custom();
+            topology.add_node(QuantumNode::new(0, 5)).unwrap();
+            topology.add_node(QuantumNode::new(1, 5)).unwrap();
+            topology
+                .add_channel(QuantumChannel::new(0, 1, 10.0, 0.2).with_retry_policy(policy))
+                .unwrap();
+            let protocol = FakeGenerationProtocol::scripted(vec![AttemptOutcome::Failure], 0.95);
+            let mut link = BankingLinkLayer::new(topology, protocol, 1.0, 1000.0);
+            link.add_policy(
+                0,
+                BankingPolicy {
+                    partner: 1,
+                    target_count: 1,
+                    min_fidelity: 0.9,
+                },
+            )
+            .unwrap();
+            let mut engine = SimulationEngine::with_limits(crate::simulation::RunLimits {
+                max_sim_time: Some(20.0),
+                ..Default::default()
+            });
+            link.replenish(&mut engine.scheduler, 0.0);
+            let report = engine.run(&mut link).into_report();
+            (
+                link.protocol.attempts(),
+                report.generation.backoff_idle_time,
+            )
+        };
+
+        let (full_rate, idle) = run(RetryPolicy::none());
+        assert_eq!(idle, 0.0);
+
+        // Two failures in a row, then 4 ms extra before each retry:
+        // attempts at t = 0, 1, 6, 11, 16
+        let (backed_off, idle) = run(RetryPolicy {
+            max_consecutive_failures: Some(2),
+            backoff: Backoff::Fixed(4.0),
+        });
+        assert!(backed_off < full_rate);
+        assert_eq!(backed_off, 5);
+        assert_eq!(idle, 16.0);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::network::node::StoredPair;
 use crate::network::{QuantumChannel, QuantumNode};
-use crate::quantum::TwoQubitState;
+use crate::quantum::{fidelity_after_depolarization, Detector};
+use crate::simulation::{unseeded_rng, IgnoredParameter, ParameterUsage, RandomnessDecl};
 use rand::Rng;
 
 /// Barrett-Kok entanglement generation protocol
@@ -9,38 +10,132 @@ use rand::Rng;
 /// - Photon emission from both nodes
 /// - Midpoint BSM (Bell State Measurement)
 /// - Detector clicks signal success
+#[derive(Debug, Clone)]
 pub struct BarrettKokProtocol {
     /// BSM (beam splitter) success rate (0.5 for single-atom, 1.0 for ideal)
     pub bsm_efficiency: f64,
 
-    /// Detector efficiency (0.0 to 1.0)
-    pub detector_efficiency: f64,
+    /// Detector behind output port A of the midpoint beam splitter
+    /// Its efficiency and dark counts are modeled; dead time and jitter are
+    /// not (see `ignored_parameters`)
+    pub detector_a: Detector,
 
-    /// Detector dark count rate (false positives)
-    pub dark_count_rate: f64,
+    /// Detector behind output port B
+    pub detector_b: Detector,
+
+    /// Model false heralds from the detectors' dark counts
+    pub model_dark_counts: bool,
 
     /// Initial fidelity after generation (accounting for imperfections)
     pub initial_fidelity: f64,
+
+    /// Per-photon collection/coupling efficiency into the fiber (0.0 to 1.0)
+    pub collection_efficiency: f64,
+
+    /// Probability that each memory emits its photon (0.0 to 1.0)
+    /// Used for nodes without their own `memory_efficiency`
+    pub memory_efficiency: f64,
+}
+
+/// Fidelity to |Φ+⟩ of a falsely heralded, uncorrelated pair
+pub const DARK_COUNT_FIDELITY: f64 = 0.25;
+
+/// Outcome of one Barrett-Kok attempt and how long it took
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttemptResult {
+    /// A pair was heralded (truly or by a dark count) and stored
+    pub success: bool,
+    /// Time from emission until the herald is back at the nodes (ms)
+    pub duration_ms: f64,
+}
+
+/// Outcome of one multiplexed Barrett-Kok attempt
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MultiplexedAttemptResult {
+    /// Modes that heralded (truly or by a dark count)
+    pub heralded: usize,
+    /// Pairs stored, at most the free memory of either node
+    pub stored: usize,
+    /// Time from emission until the heralds are back at the nodes (ms)
+    pub duration_ms: f64,
+}
+
+/// Multiplicative factors making up `theoretical_success_rate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateBreakdown {
+    /// (factor name, value) in the order they are applied
+    pub factors: Vec<(&'static str, f64)>,
+}
+
+impl RateBreakdown {
+    /// Product of all factors
+    pub fn total(&self) -> f64 {
+        self.factors.iter().map(|(_, value)| value).product()
+    }
 }
 
 impl BarrettKokProtocol {
     /// Create protocol matching SeQUeNCe parameters
     pub fn sequence_parameters() -> Self {
         BarrettKokProtocol {
-            bsm_efficiency: 0.5,       // Single-atom BSM
-            detector_efficiency: 0.90, // From SeQUeNCe
-            dark_count_rate: 0.0,      // SeQUeNCe doesn't model this
-            initial_fidelity: 0.95,    // From SeQUeNCe
+            bsm_efficiency: 0.5,                         // Single-atom BSM
+            detector_a: Detector::with_efficiency(0.90), // From SeQUeNCe
+            detector_b: Detector::with_efficiency(0.90),
+            model_dark_counts: false, // SeQUeNCe doesn't model this
+            initial_fidelity: 0.95,   // From SeQUeNCe
+            collection_efficiency: 1.0,
+            memory_efficiency: 0.9, // From SeQUeNCe Memory parameter
         }
     }
 
     /// Create realistic protocol (QComNetSim)
     pub fn realistic() -> Self {
+        // 100 Hz dark counts (realistic SNSPD)
+        let detector = Detector::new(0.90, 100.0, 0.0, 0.0);
         BarrettKokProtocol {
             bsm_efficiency: 0.5,
-            detector_efficiency: 0.90,
-            dark_count_rate: 0.01, // 1% dark counts (realistic)
+            detector_a: detector,
+            detector_b: detector,
+            model_dark_counts: false,
             initial_fidelity: 0.95,
+            collection_efficiency: 1.0,
+            memory_efficiency: 0.9,
+        }
+    }
+
+    /// Create protocol with every stage lossless and a perfect initial pair
+    ///
+    /// Only channel loss and depolarization remain. Both photons cross the
+    /// channel, so this matches `attempt_entanglement_generation` over a
+    /// channel twice as long (see `testing::model_equivalence`).
+    pub fn ideal() -> Self {
+        BarrettKokProtocol {
+            bsm_efficiency: 1.0,
+            detector_a: Detector::ideal(),
+            detector_b: Detector::ideal(),
+            model_dark_counts: false,
+            initial_fidelity: 1.0,
+            collection_efficiency: 1.0,
+            memory_efficiency: 1.0,
+        }
+    }
+
+    /// Copy of this protocol whose detectors both fire dark counts at
+    /// `dark_count_rate_hz`, with false heralds modeled
+    pub fn with_dark_counts(mut self, dark_count_rate_hz: f64) -> Self {
+        self.detector_a.dark_count_rate_hz = dark_count_rate_hz;
+        self.detector_b.dark_count_rate_hz = dark_count_rate_hz;
+        self.model_dark_counts = true;
+        self
+    }
+
+    /// Probability that a failed attempt is falsely heralded: both detectors
+    /// fire a dark count within their gate (0 unless `model_dark_counts`)
+    pub fn false_herald_probability(&self) -> f64 {
+        if self.model_dark_counts {
+            self.detector_a.dark_count_probability() * self.detector_b.dark_count_probability()
+        } else {
+            0.0
         }
     }
 
@@ -52,9 +147,28 @@ impl BarrettKokProtocol {
         channel: &QuantumChannel,
         current_time: f64,
         coherence_time_ms: f64,
-    ) -> Result<bool, String> {
-        let mut rng = rand::rng();
+    ) -> Result<AttemptResult, String> {
+        self.attempt_generation_with_rng(
+            node_a,
+            node_b,
+            channel,
+            current_time,
+            coherence_time_ms,
+            &mut unseeded_rng("BarrettKokProtocol"),
+        )
+    }
 
+    /// `attempt_generation` drawing from the caller's RNG
+    /// Lets parallel trials sharing one protocol each use their own seeded RNG
+    pub fn attempt_generation_with_rng(
+        &self,
+        node_a: &mut QuantumNode,
+        node_b: &mut QuantumNode,
+        channel: &QuantumChannel,
+        current_time: f64,
+        coherence_time_ms: f64,
+        rng: &mut impl Rng,
+    ) -> Result<AttemptResult, String> {
         // Memory checks
         if !node_a.has_memory_available() {
             return Err(format!("Node {} memory full", node_a.id));
@@ -63,77 +177,313 @@ impl BarrettKokProtocol {
             return Err(format!("Node {} memory full", node_b.id));
         }
 
-        // Match SeQUeNCe's complete model:
-        let transmission_prob = channel.success_probability();
-        let memory_efficiency = 0.9; // From SeQUeNCe Memory parameter
+        // Match SeQUeNCe's complete model. The stages are independent, so
+        // one draw against their product samples the same distribution and
+        // consumes the RNG like `attempt_entanglement_generation`
+        let herald_probability =
+            self.node_herald_probability(node_a, node_b, channel, current_time);
+        let heralded = rng.random::<f64>() < herald_probability;
 
-        // Step 1: Memory emission (both nodes must emit successfully)
-        if rng.random::<f64>() >= memory_efficiency {
-            return Ok(false); // Node A emission failed
-        }
-        if rng.random::<f64>() >= memory_efficiency {
-            return Ok(false); // Node B emission failed
-        }
+        let fidelity = if heralded {
+            self.heralded_fidelity(channel)
+        } else if self.model_dark_counts && rng.random::<f64>() < self.false_herald_probability() {
+            // Dark counts herald a pair that was never entangled
+            DARK_COUNT_FIDELITY
+        } else {
+            return Ok(AttemptResult {
+                success: false,
+                duration_ms: self.attempt_duration_ms(channel),
+            });
+        };
+
+        // Heralded: store the pair at both nodes
+        let pair_coherence_ms = pair_coherence_time(node_a, node_b, coherence_time_ms);
+        let pair_a =
+            StoredPair::new_with_fidelity(node_b.id, fidelity, current_time, pair_coherence_ms);
+        let pair_b =
+            StoredPair::new_with_fidelity(node_a.id, fidelity, current_time, pair_coherence_ms)
+                .with_pair_id(pair_a.pair_id);
+
+        node_a.store_pair(pair_a)?;
+        node_b.store_pair(pair_b)?;
+
+        Ok(AttemptResult {
+            success: true,
+            duration_ms: self.attempt_duration_ms(channel),
+        })
+    }
+
+    /// Attempt generation over all `channel.num_modes` modes at once
+    pub fn attempt_generation_multiplexed(
+        &self,
+        node_a: &mut QuantumNode,
+        node_b: &mut QuantumNode,
+        channel: &QuantumChannel,
+        current_time: f64,
+        coherence_time_ms: f64,
+    ) -> Result<MultiplexedAttemptResult, String> {
+        self.attempt_generation_multiplexed_with_rng(
+            node_a,
+            node_b,
+            channel,
+            current_time,
+            coherence_time_ms,
+            &mut unseeded_rng("BarrettKokProtocol"),
+        )
+    }
 
-        // Step 2: Channel transmission (both photons travel to BSM)
-        if rng.random::<f64>() >= transmission_prob {
-            return Ok(false); // Photon A lost
+    /// `attempt_generation_multiplexed` drawing from the caller's RNG
+    ///
+    /// Each mode draws like a single-mode attempt, so one mode consumes
+    /// the RNG exactly as `attempt_generation_with_rng`
+    pub fn attempt_generation_multiplexed_with_rng(
+        &self,
+        node_a: &mut QuantumNode,
+        node_b: &mut QuantumNode,
+        channel: &QuantumChannel,
+        current_time: f64,
+        coherence_time_ms: f64,
+        rng: &mut impl Rng,
+    ) -> Result<MultiplexedAttemptResult, String> {
+        if !node_a.has_memory_available() {
+            return Err(format!("Node {} memory full", node_a.id));
         }
-        if rng.random::<f64>() >= transmission_prob {
-            return Ok(false); // Photon B lost
+        if !node_b.has_memory_available() {
+            return Err(format!("Node {} memory full", node_b.id));
         }
 
-        // Step 3: BSM measurement
-        if rng.random::<f64>() >= self.bsm_efficiency {
-            return Ok(false); // BSM failed
-        }
+        let herald_probability =
+            self.node_herald_probability(node_a, node_b, channel, current_time);
+        let heralded_fidelity = self.heralded_fidelity(channel);
+        let fidelities: Vec<f64> = (0..channel.num_modes)
+            .filter_map(|_| {
+                if rng.random::<f64>() < herald_probability {
+                    Some(heralded_fidelity)
+                } else if self.model_dark_counts
+                    && rng.random::<f64>() < self.false_herald_probability()
+                {
+                    Some(DARK_COUNT_FIDELITY)
+                } else {
+                    None
+                }
+            })
+            .collect();
 
-        // Step 4: Detector clicks (both detectors)
-        if rng.random::<f64>() >= self.detector_efficiency {
-            return Ok(false); // Detector A failed
-        }
-        if rng.random::<f64>() >= self.detector_efficiency {
-            return Ok(false); // Detector B failed
+        // Heralded modes beyond the free memory are lost
+        let stored = fidelities
+            .len()
+            .min(node_a.free_memory())
+            .min(node_b.free_memory());
+        let pair_coherence_ms = pair_coherence_time(node_a, node_b, coherence_time_ms);
+        for &fidelity in &fidelities[..stored] {
+            let pair_a =
+                StoredPair::new_with_fidelity(node_b.id, fidelity, current_time, pair_coherence_ms);
+            let pair_b =
+                StoredPair::new_with_fidelity(node_a.id, fidelity, current_time, pair_coherence_ms)
+                    .with_pair_id(pair_a.pair_id);
+            node_a.store_pair(pair_a)?;
+            node_b.store_pair(pair_b)?;
         }
 
-        // Success! Create entangled pair
-        let bell_state = TwoQubitState::new_bell_phi_plus();
+        Ok(MultiplexedAttemptResult {
+            heralded: fidelities.len(),
+            stored,
+            duration_ms: self.attempt_duration_ms(channel),
+        })
+    }
 
-        let mut pair_a = StoredPair::new(
-            node_b.id,
-            bell_state.clone(),
-            current_time,
-            coherence_time_ms,
-        );
-        let mut pair_b = StoredPair::new(node_a.id, bell_state, current_time, coherence_time_ms);
+    /// Duration of one attempt over `channel` (ms)
+    /// Same as `QuantumChannel::attempt_duration_ms`: the detection station
+    /// sits at the channel midpoint
+    pub fn attempt_duration_ms(&self, channel: &QuantumChannel) -> f64 {
+        channel.attempt_duration_ms()
+    }
 
-        pair_a.fidelity = self.initial_fidelity;
-        pair_b.fidelity = self.initial_fidelity;
+    /// Fidelity of a heralded pair over `channel`
+    pub fn heralded_fidelity(&self, channel: &QuantumChannel) -> f64 {
+        // Both photons cross the channel and may be depolarized
+        let p_depol = channel.depolarization_probability();
+        fidelity_after_depolarization(
+            fidelity_after_depolarization(self.initial_fidelity, p_depol),
+            p_depol,
+        )
+    }
 
-        node_a.store_pair(pair_a)?;
-        node_b.store_pair(pair_b)?;
+    /// Probability that an attempt is truly heralded: memory emission at
+    /// both nodes times `theoretical_success_rate`
+    pub fn herald_probability(&self, channel: &QuantumChannel) -> f64 {
+        self.memory_efficiency * self.memory_efficiency * self.theoretical_success_rate(channel)
+    }
 
-        Ok(true)
+    /// `herald_probability` with the channel attenuation scheduled for `time_ms`
+    pub fn herald_probability_at(&self, channel: &QuantumChannel, time_ms: f64) -> f64 {
+        self.memory_efficiency
+            * self.memory_efficiency
+            * self
+                .rate_breakdown(channel.success_probability_at(time_ms))
+                .total()
+    }
+
+    /// `herald_probability_at` with each node's own memory efficiency
+    fn node_herald_probability(
+        &self,
+        node_a: &QuantumNode,
+        node_b: &QuantumNode,
+        channel: &QuantumChannel,
+        time_ms: f64,
+    ) -> f64 {
+        node_a.memory_efficiency.unwrap_or(self.memory_efficiency)
+            * node_b.memory_efficiency.unwrap_or(self.memory_efficiency)
+            * self
+                .rate_breakdown(channel.success_probability_at(time_ms))
+                .total()
     }
 
-    /// Calculate theoretical success probability
+    /// Calculate theoretical success probability (true heralds only)
     pub fn theoretical_success_rate(&self, channel: &QuantumChannel) -> f64 {
+        // Both photons collected and arrive × BSM works × both detectors click
+        self.explain_rate(channel).total()
+    }
+
+    /// Per-factor breakdown of `theoretical_success_rate`
+    pub fn explain_rate(&self, channel: &QuantumChannel) -> RateBreakdown {
+        self.rate_breakdown(channel.success_probability())
+    }
+
+    /// Herald stages for a per-photon transmission probability `p_trans`
+    fn rate_breakdown(&self, p_trans: f64) -> RateBreakdown {
+        RateBreakdown {
+            factors: vec![
+                ("collection A", self.collection_efficiency),
+                ("collection B", self.collection_efficiency),
+                ("transmission A", p_trans),
+                ("transmission B", p_trans),
+                ("BSM", self.bsm_efficiency),
+                ("detector A", self.detector_a.efficiency),
+                ("detector B", self.detector_b.efficiency),
+            ],
+        }
+    }
+
+    /// Stochastic decision points of an attempt over `channel`
+    ///
+    /// The five herald stages are sampled by one draw against their
+    /// product, but each is listed with its own probability; modelled dark
+    /// counts add a sixth draw on failed attempts
+    pub fn decision_points(&self, channel: &QuantumChannel) -> Vec<RandomnessDecl> {
+        let component = format!("BarrettKokProtocol[{}-{}]", channel.node_a, channel.node_b);
         let p_trans = channel.success_probability();
+        let mut decls = vec![
+            RandomnessDecl::bernoulli(
+                &component,
+                "memory_emission",
+                self.memory_efficiency * self.memory_efficiency,
+            ),
+            RandomnessDecl::bernoulli(
+                &component,
+                "collection",
+                self.collection_efficiency * self.collection_efficiency,
+            ),
+            RandomnessDecl::bernoulli(&component, "transmission", p_trans * p_trans),
+            RandomnessDecl::bernoulli(&component, "bsm", self.bsm_efficiency),
+            RandomnessDecl::bernoulli(
+                &component,
+                "detection",
+                self.detector_a.efficiency * self.detector_b.efficiency,
+            ),
+        ];
+        if self.model_dark_counts {
+            decls.push(RandomnessDecl::bernoulli(
+                &component,
+                "dark_count",
+                self.false_herald_probability(),
+            ));
+        }
+        decls
+    }
 
-        // Both photons arrive × BSM works × both detectors click
-        let p_success = p_trans
-            * p_trans
-            * self.bsm_efficiency
-            * self.detector_efficiency
-            * self.detector_efficiency;
+    /// Copy of this protocol with `collection_efficiency` chosen so that
+    /// `theoretical_success_rate(channel)` equals `measured_success_rate`
+    ///
+    /// All other parameters are kept; errors if the target exceeds the rate
+    /// reachable with perfect collection
+    pub fn calibrate_to_link_rate(
+        &self,
+        channel: &QuantumChannel,
+        measured_success_rate: f64,
+    ) -> Result<Self, String> {
+        let mut calibrated = self.clone();
+        calibrated.collection_efficiency = 1.0;
+        let maximum = calibrated.theoretical_success_rate(channel);
+
+        if measured_success_rate <= 0.0 {
+            return Err(format!(
+                "Measured success rate must be positive, got {}",
+                measured_success_rate
+            ));
+        }
+        if measured_success_rate > maximum {
+            return Err(format!(
+                "Measured success rate {:.3e} exceeds the physical maximum {:.3e} for this channel",
+                measured_success_rate, maximum
+            ));
+        }
+
+        // Rate scales with the square of the per-photon collection efficiency
+        calibrated.collection_efficiency = (measured_success_rate / maximum).sqrt();
+        Ok(calibrated)
+    }
+}
+
+/// Coherence time shared by both halves of a new pair: the shorter of the
+/// two memories (each falling back to `default_ms`), as for swapped pairs
+fn pair_coherence_time(node_a: &QuantumNode, node_b: &QuantumNode, default_ms: f64) -> f64 {
+    node_a
+        .coherence_time_ms
+        .unwrap_or(default_ms)
+        .min(node_b.coherence_time_ms.unwrap_or(default_ms))
+}
 
-        p_success
+impl ParameterUsage for BarrettKokProtocol {
+    fn ignored_parameters(&self) -> Vec<IgnoredParameter> {
+        let mut ignored = Vec::new();
+        for (name, detector) in [
+            ("detector_a", &self.detector_a),
+            ("detector_b", &self.detector_b),
+        ] {
+            if detector.dark_count_rate_hz > 0.0 && !self.model_dark_counts {
+                ignored.push(IgnoredParameter::new(
+                    "BarrettKokProtocol",
+                    &format!("{}.dark_count_rate_hz", name),
+                    "dark counts are not modeled unless model_dark_counts is set",
+                ));
+            }
+            if detector.dead_time_ns > 0.0 {
+                ignored.push(IgnoredParameter::new(
+                    "BarrettKokProtocol",
+                    &format!("{}.dead_time_ns", name),
+                    "one detection gate per attempt, so dead time never blinds a detector",
+                ));
+            }
+            if detector.jitter_ns > 0.0 {
+                ignored.push(IgnoredParameter::new(
+                    "BarrettKokProtocol",
+                    &format!("{}.jitter_ns", name),
+                    "heralds are not time-resolved, so jitter has no effect",
+                ));
+            }
+        }
+        ignored
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::experiment::{RequestService, ServiceConfig};
+    use crate::protocols::banking::{BankingLinkLayer, BankingPolicy};
+    use crate::quantum::TwoQubitState;
+    use crate::simulation::{RunLimits, SimulationEngine, StrictConfig};
 
     #[test]
     fn test_theoretical_rate() {
@@ -143,4 +493,537 @@ mod tests {
         let rate = protocol.theoretical_success_rate(&channel);
         assert!(rate > 0.0 && rate < 1.0);
     }
+
+    #[test]
+    fn test_channel_coupling_loss_hits_both_photons() {
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+        let coupled = channel.clone().with_coupling_efficiency(0.5);
+        let ratio = protocol.theoretical_success_rate(&coupled)
+            / protocol.theoretical_success_rate(&channel);
+        assert!((ratio - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_each_detector_scales_rate() {
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+        let ideal = BarrettKokProtocol::ideal();
+        let protocol = BarrettKokProtocol {
+            detector_a: Detector::with_efficiency(0.8),
+            detector_b: Detector::with_efficiency(0.5),
+            ..BarrettKokProtocol::ideal()
+        };
+
+        let ratio =
+            protocol.theoretical_success_rate(&channel) / ideal.theoretical_success_rate(&channel);
+        assert!((ratio - 0.4).abs() < 1e-12);
+    }
+
+    fn sampled_heralded_fidelity(protocol: &BarrettKokProtocol, channel: &QuantumChannel) -> f64 {
+        // Keep attempting until one succeeds (p ≈ 0.33 on a lossless channel)
+        for _ in 0..1000 {
+            let mut node_a = QuantumNode::new(0, 1);
+            let mut node_b = QuantumNode::new(1, 1);
+            if let Ok(AttemptResult { success: true, .. }) =
+                protocol.attempt_generation(&mut node_a, &mut node_b, channel, 0.0, 100.0)
+            {
+                return node_a.stored_pairs()[0].fidelity;
+            }
+        }
+        panic!("No successful generation in 1000 attempts");
+    }
+
+    #[test]
+    fn test_zero_depolarization_keeps_fidelity() {
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let channel = QuantumChannel::new(0, 1, 50.0, 0.0);
+
+        let fidelity = sampled_heralded_fidelity(&protocol, &channel);
+        assert_eq!(fidelity, protocol.heralded_fidelity(&channel));
+        assert!((fidelity - protocol.initial_fidelity).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_seeded_attempts_repeat() {
+        use rand::SeedableRng;
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+        let outcomes = || {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+            let mut node_a = QuantumNode::new(0, 1000);
+            let mut node_b = QuantumNode::new(1, 1000);
+            (0..500)
+                .map(|i| {
+                    protocol
+                        .attempt_generation_with_rng(
+                            &mut node_a,
+                            &mut node_b,
+                            &channel,
+                            i as f64,
+                            100.0,
+                            &mut rng,
+                        )
+                        .unwrap()
+                        .success
+                })
+                .collect::<Vec<bool>>()
+        };
+        let first = outcomes();
+        assert_eq!(first, outcomes());
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[test]
+    fn test_heralded_state_matches_fidelity() {
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let channel = QuantumChannel::new(0, 1, 0.0, 0.0);
+        for _ in 0..1000 {
+            let mut node_a = QuantumNode::new(0, 1);
+            let mut node_b = QuantumNode::new(1, 1);
+            if let Ok(AttemptResult { success: true, .. }) =
+                protocol.attempt_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0)
+            {
+                let pair = &node_a.stored_pairs()[0];
+                let state_fidelity = pair.state.fidelity(&TwoQubitState::new_bell_phi_plus());
+                assert!((state_fidelity - pair.fidelity).abs() < 1e-10);
+                return;
+            }
+        }
+        panic!("No successful generation in 1000 attempts");
+    }
+
+    #[test]
+    fn test_depolarization_degrades_heralded_fidelity() {
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let channel = QuantumChannel::new(0, 1, 50.0, 0.0)
+            .with_depolarization(0.01)
+            .unwrap();
+
+        let p = 1.0 - 0.99_f64.powi(50);
+        let once = 0.95 * (1.0 - p) + 0.05 * p / 3.0;
+        let expected = once * (1.0 - p) + (1.0 - once) * p / 3.0;
+
+        let fidelity = sampled_heralded_fidelity(&protocol, &channel);
+        assert!((fidelity - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calibration_round_trips_target() {
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+        let target = 0.1;
+
+        let calibrated = protocol.calibrate_to_link_rate(&channel, target).unwrap();
+        assert!((calibrated.theoretical_success_rate(&channel) - target).abs() < 1e-12);
+        assert!(calibrated.collection_efficiency < 1.0);
+
+        let breakdown = calibrated.explain_rate(&channel);
+        assert_eq!(breakdown.factors.len(), 7);
+        assert!((breakdown.total() - target).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_impossible_calibration_reports_maximum() {
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+        let maximum = protocol.theoretical_success_rate(&channel);
+
+        let error = protocol.calibrate_to_link_rate(&channel, 0.5).unwrap_err();
+        assert!(error.contains(&format!("{:.3e}", maximum)), "{}", error);
+    }
+
+    #[test]
+    fn test_unmodeled_dark_counts_are_reported() {
+        let protocol = BarrettKokProtocol::realistic();
+        let ignored = protocol.ignored_parameters();
+        let fields: Vec<&str> = ignored.iter().map(|param| param.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "detector_a.dark_count_rate_hz",
+                "detector_b.dark_count_rate_hz"
+            ]
+        );
+        assert!(StrictConfig::strict().check(&protocol).is_err());
+
+        // The same config through a run shows up in the report
+        let topology = crate::network::NetworkTopology::new_linear(2, 2, 1.0, 0.2);
+        let mut link = BankingLinkLayer::new(topology, protocol.clone(), 1.0, 100.0);
+        link.add_policy(
+            0,
+            BankingPolicy {
+                partner: 1,
+                target_count: 1,
+                min_fidelity: 0.0,
+            },
+        )
+        .unwrap();
+        let mut engine = SimulationEngine::new();
+        link.replenish(&mut engine.scheduler, 0.0);
+        let report = engine.run(&mut link).into_report();
+        assert_eq!(report.ignored_parameters, ignored);
+        assert!(report
+            .summary()
+            .contains("BarrettKokProtocol.detector_a.dark_count_rate_hz"));
+
+        let modeled = protocol.with_dark_counts(100.0);
+        assert!(modeled.ignored_parameters().is_empty());
+        assert!(StrictConfig::strict().check(&modeled).is_ok());
+    }
+
+    #[test]
+    fn test_detector_timing_is_reported_as_ignored() {
+        let detector = Detector::new(0.9, 0.0, 50.0, 0.1);
+        let protocol = BarrettKokProtocol {
+            detector_a: detector,
+            ..BarrettKokProtocol::ideal()
+        };
+        let fields: Vec<String> = protocol
+            .ignored_parameters()
+            .into_iter()
+            .map(|param| param.field)
+            .collect();
+        assert_eq!(fields, ["detector_a.dead_time_ns", "detector_a.jitter_ns"]);
+        assert!(StrictConfig::strict().check(&protocol).is_err());
+    }
+
+    #[test]
+    fn test_false_heralds_follow_detector_dark_counts() {
+        let protocol = BarrettKokProtocol::realistic();
+        assert_eq!(protocol.false_herald_probability(), 0.0);
+        let modeled = protocol.with_dark_counts(100.0);
+        let per_detector = modeled.detector_a.dark_count_probability();
+        assert!(per_detector > 0.0);
+        assert!((modeled.false_herald_probability() - per_detector * per_detector).abs() < 1e-24);
+    }
+
+    fn link_manifest(protocol: BarrettKokProtocol) -> Vec<RandomnessDecl> {
+        let topology = crate::network::NetworkTopology::new_linear(2, 2, 10.0, 0.2);
+        let link = BankingLinkLayer::new(topology, protocol, 1.0, 100.0);
+        SimulationEngine::new()
+            .with_randomness(&link)
+            .randomness_manifest()
+    }
+
+    #[test]
+    fn test_randomness_manifest_lists_decision_points() {
+        let manifest = link_manifest(BarrettKokProtocol::sequence_parameters());
+        let labels: Vec<&str> = manifest.iter().map(|decl| decl.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "memory_emission",
+                "collection",
+                "transmission",
+                "bsm",
+                "detection"
+            ]
+        );
+        assert!(manifest
+            .iter()
+            .all(|decl| decl.component == "BarrettKokProtocol[0-1]"));
+        assert_eq!(manifest[3].distribution_string(), "Bernoulli(p=0.5)");
+
+        let with_dark_counts =
+            link_manifest(BarrettKokProtocol::sequence_parameters().with_dark_counts(100.0));
+        assert_eq!(with_dark_counts.len(), manifest.len() + 1);
+        assert_eq!(&with_dark_counts[..5], &manifest[..]);
+        assert_eq!(with_dark_counts[5].label, "dark_count");
+    }
+
+    #[test]
+    fn test_manifest_is_echoed_in_report() {
+        let topology = crate::network::NetworkTopology::new_linear(2, 2, 10.0, 0.2);
+        let mut link = BankingLinkLayer::new(topology, BarrettKokProtocol::ideal(), 1.0, 100.0);
+        let mut engine = SimulationEngine::new().with_randomness(&link);
+        let report = engine.run(&mut link).into_report();
+        assert_eq!(report.randomness, engine.randomness_manifest());
+        assert_eq!(
+            report.config["randomness.BarrettKokProtocol[0-1].bsm"],
+            "Bernoulli(p=1)"
+        );
+        assert!(report
+            .summary()
+            .contains("BarrettKokProtocol[0-1].detection: Bernoulli(p=1)"));
+    }
+
+    /// Barrett-Kok banking link keeping one pair between nodes 0 and 1
+    fn single_pair_bank() -> BankingLinkLayer<BarrettKokProtocol> {
+        let topology = crate::network::NetworkTopology::new_linear(2, 2, 10.0, 0.2);
+        let mut link = BankingLinkLayer::new(topology, BarrettKokProtocol::ideal(), 1.0, 100.0);
+        link.add_policy(
+            0,
+            BankingPolicy {
+                partner: 1,
+                target_count: 1,
+                min_fidelity: 0.0,
+            },
+        )
+        .unwrap();
+        link
+    }
+
+    #[test]
+    fn test_strict_randomness_passes_seeded_handlers() {
+        let mut link = single_pair_bank();
+        let mut engine = SimulationEngine::new()
+            .with_seed(7)
+            .with_randomness(&link)
+            .with_strict_randomness();
+        link.replenish(&mut engine.scheduler, 0.0);
+        assert!(!engine.run(&mut link).is_timed_out());
+        assert_eq!(link.topology.get_node(0).unwrap().num_stored_pairs(), 1);
+
+        let topology = crate::network::NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new()
+            .with_seed(7)
+            .with_strict_randomness();
+        service.submit(0, 2, 3, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut service);
+        assert_eq!(service.stats().delivered, 3);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "BankingLinkLayer drew from an unseeded RNG")]
+    fn test_strict_randomness_flags_unseeded_draws() {
+        // No engine seed, so the link's draws cannot be reproduced
+        let mut link = single_pair_bank();
+        let mut engine = SimulationEngine::new()
+            .with_randomness(&link)
+            .with_strict_randomness();
+        link.replenish(&mut engine.scheduler, 0.0);
+        engine.run(&mut link);
+    }
+
+    #[test]
+    fn test_modeled_dark_counts_herald_uncorrelated_pairs() {
+        // No photon ever reaches the detectors, so every herald is a dark count
+        let protocol = BarrettKokProtocol {
+            collection_efficiency: 0.0,
+            ..BarrettKokProtocol::realistic()
+        }
+        .with_dark_counts(1e12);
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+        let mut node_a = QuantumNode::new(0, 1);
+        let mut node_b = QuantumNode::new(1, 1);
+
+        assert_eq!(
+            protocol
+                .attempt_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0)
+                .map(|attempt| attempt.success),
+            Ok(true)
+        );
+        assert_eq!(node_a.stored_pairs()[0].fidelity, DARK_COUNT_FIDELITY);
+
+        let unmodeled = BarrettKokProtocol {
+            model_dark_counts: false,
+            ..protocol
+        };
+        let mut node_a = QuantumNode::new(0, 1);
+        let mut node_b = QuantumNode::new(1, 1);
+        assert_eq!(
+            unmodeled
+                .attempt_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0)
+                .map(|attempt| attempt.success),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn test_attempts_report_midpoint_round_trip_duration() {
+        let protocol = BarrettKokProtocol::ideal();
+        let channel = QuantumChannel::new(0, 1, 50.0, 0.2);
+        let mut node_a = QuantumNode::new(0, 1);
+        let mut node_b = QuantumNode::new(1, 1);
+        let attempt = protocol
+            .attempt_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0)
+            .unwrap();
+        // Out to the midpoint over 25 km of fiber and back: ≈ 0.25 ms
+        assert!((attempt.duration_ms - 50.0 * 1.47 / 299.792458).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_scheduler_respects_attempt_duration() {
+        // Attempts requested back to back on a 50 km link for one second
+        let topology = crate::network::NetworkTopology::new_linear(2, 1000, 50.0, 0.2);
+        let mut link = BankingLinkLayer::new(topology, BarrettKokProtocol::ideal(), 0.0, 100.0);
+        link.add_policy(
+            0,
+            BankingPolicy {
+                partner: 1,
+                target_count: usize::MAX,
+                min_fidelity: 0.0,
+            },
+        )
+        .unwrap();
+        let mut engine = SimulationEngine::with_limits(RunLimits {
+            max_sim_time: Some(1000.0),
+            ..RunLimits::default()
+        });
+        link.replenish(&mut engine.scheduler, 0.0);
+        engine.run(&mut link);
+
+        // At most c/L midpoint round trips per second, slower still in fiber
+        let attempts = link.occupancy_history().len() as f64;
+        let bound = crate::network::SPEED_OF_LIGHT_KM_PER_MS * 1000.0 / 50.0;
+        assert!(attempts <= bound, "{} attempts", attempts);
+        assert!(
+            (attempts - bound / 1.47).abs() < 2.0,
+            "{} attempts",
+            attempts
+        );
+    }
+
+    #[test]
+    fn test_channel_cut_stops_heralds_in_scheduled_run() {
+        // Lossless 10 km link that is cut at t = 50 ms
+        let mut topology = crate::network::NetworkTopology::new_custom();
+        topology.add_node(QuantumNode::new(0, 1000)).unwrap();
+        topology.add_node(QuantumNode::new(1, 1000)).unwrap();
+        topology
+            .add_channel(
+                QuantumChannel::new(0, 1, 10.0, 0.0)
+                    .with_attenuation_schedule(vec![(50.0, f64::INFINITY)]),
+            )
+            .unwrap();
+        let mut link = BankingLinkLayer::new(topology, BarrettKokProtocol::ideal(), 1.0, 1e9);
+        link.add_policy(
+            0,
+            BankingPolicy {
+                partner: 1,
+                target_count: usize::MAX,
+                min_fidelity: 0.0,
+            },
+        )
+        .unwrap();
+        let mut engine = SimulationEngine::with_limits(RunLimits {
+            max_sim_time: Some(100.0),
+            ..RunLimits::default()
+        });
+        link.replenish(&mut engine.scheduler, 0.0);
+        engine.run(&mut link);
+
+        let pairs = &link.topology.get_node(0).unwrap().stored_pairs();
+        assert_eq!(pairs.len(), 50);
+        assert!(pairs.iter().all(|pair| pair.creation_time < 50.0));
+        assert!(link.occupancy_history().len() > 90);
+    }
+
+    #[test]
+    fn test_multiplexing_boosts_throughput() {
+        let stored_pairs = |num_modes: usize| {
+            let mut topology = crate::network::NetworkTopology::new_custom();
+            topology.add_node(QuantumNode::new(0, 10_000)).unwrap();
+            topology.add_node(QuantumNode::new(1, 10_000)).unwrap();
+            topology
+                .add_channel(QuantumChannel::new(0, 1, 50.0, 0.2).with_num_modes(num_modes))
+                .unwrap();
+            let mut link = BankingLinkLayer::new(topology, BarrettKokProtocol::ideal(), 1.0, 1e9);
+            link.add_policy(
+                0,
+                BankingPolicy {
+                    partner: 1,
+                    target_count: usize::MAX,
+                    min_fidelity: 0.0,
+                },
+            )
+            .unwrap();
+            let mut engine = SimulationEngine::with_limits(RunLimits {
+                max_sim_time: Some(100.0),
+                ..RunLimits::default()
+            });
+            link.replenish(&mut engine.scheduler, 0.0);
+            engine.run(&mut link);
+            link.topology.get_node(0).unwrap().num_stored_pairs()
+        };
+
+        // 1% herald probability per mode and 100 attempts
+        let single = stored_pairs(1);
+        let multiplexed = stored_pairs(100);
+        assert!(single < 10, "{} single-mode pairs", single);
+        assert!(multiplexed > 50, "{} multiplexed pairs", multiplexed);
+    }
+
+    #[test]
+    fn test_multiplexed_storage_is_capped_by_memory() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let channel = QuantumChannel::new(0, 1, 0.0, 0.2).with_num_modes(10);
+        let mut node_a = QuantumNode::new(0, 4);
+        let mut node_b = QuantumNode::new(1, 3);
+        let attempt = BarrettKokProtocol::ideal()
+            .attempt_generation_multiplexed_with_rng(
+                &mut node_a,
+                &mut node_b,
+                &channel,
+                0.0,
+                100.0,
+                &mut rng,
+            )
+            .unwrap();
+        assert_eq!(attempt.heralded, 10);
+        assert_eq!(attempt.stored, 3);
+        assert_eq!(node_a.num_stored_pairs(), 3);
+        assert_eq!(node_b.num_stored_pairs(), 3);
+    }
+
+    #[test]
+    fn test_small_end_nodes_fill_before_repeater() {
+        let mut topology =
+            crate::network::NetworkTopology::new_linear_with_capacities(3, &[1, 10, 1], 0.0, 0.2)
+                .unwrap();
+        let protocol = BarrettKokProtocol::ideal();
+        let mut errors = Vec::new();
+        for _ in 0..3 {
+            for (a, b) in [(0, 1), (1, 2)] {
+                let channel = topology.find_channel(a, b).unwrap().1.clone();
+                let (node_a, node_b) = topology.get_node_pair_mut(a, b).unwrap();
+                if let Err(e) = protocol.attempt_generation(node_a, node_b, &channel, 0.0, 100.0) {
+                    errors.push(e);
+                }
+            }
+        }
+        assert_eq!(errors.len(), 4);
+        assert!(errors
+            .iter()
+            .all(|e| e == "Node 0 memory full" || e == "Node 2 memory full"));
+        assert_eq!(topology.get_node(1).unwrap().num_stored_pairs(), 2);
+    }
+
+    #[test]
+    fn test_node_hardware_overrides_protocol_memory() {
+        use crate::network::NodeHardware;
+        let channel = QuantumChannel::new(0, 1, 0.0, 0.2);
+        let protocol = BarrettKokProtocol::ideal();
+
+        // A memory that never emits cannot herald, whatever the protocol says
+        let dead = NodeHardware::new(1).with_memory_efficiency(0.0);
+        let mut node_a = QuantumNode::with_hardware(0, &dead);
+        let mut node_b = QuantumNode::new(1, 1);
+        let attempt = protocol
+            .attempt_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0)
+            .unwrap();
+        assert!(!attempt.success);
+
+        // Both halves decay with the shorter-lived memory
+        let short_lived = NodeHardware::new(2).with_coherence_time(5.0);
+        let mut node_a = QuantumNode::new(0, 2);
+        let mut node_b = QuantumNode::with_hardware(1, &short_lived);
+        protocol
+            .attempt_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0)
+            .unwrap();
+        protocol
+            .attempt_generation_multiplexed(&mut node_a, &mut node_b, &channel, 0.0, 100.0)
+            .unwrap();
+        for node in [&node_a, &node_b] {
+            assert!(node
+                .stored_pairs()
+                .iter()
+                .all(|pair| pair.coherence_time_ms == 5.0));
+        }
+        let (half_a, half_b) = (&node_a.stored_pairs()[0], &node_b.stored_pairs()[0]);
+        assert_eq!(half_a.fidelity_at(3.0), half_b.fidelity_at(3.0));
+    }
 }
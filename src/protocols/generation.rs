@@ -0,0 +1,182 @@
+use super::barrett_kok::BarrettKokProtocol;
+use crate::network::{attempt_entanglement_generation_with_rng, QuantumChannel, QuantumNode};
+use crate::simulation::{
+    IgnoredParameter, ParamOverride, ParameterUsage, RandomnessDecl, RandomnessSource,
+};
+use rand::Rng;
+
+/// Result of a single generation attempt that did not error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    /// A pair was heralded and stored at both nodes
+    Success,
+    /// The attempt failed (loss, detector miss, ...)
+    Failure,
+}
+
+impl From<bool> for AttemptOutcome {
+    fn from(success: bool) -> Self {
+        if success {
+            AttemptOutcome::Success
+        } else {
+            AttemptOutcome::Failure
+        }
+    }
+}
+
+/// Common interface for entanglement generation protocols
+pub trait GenerationProtocol {
+    /// Attempt to generate and store a pair between two nodes, drawing
+    /// from `rng`
+    /// Returns Err if either node has no free memory
+    fn attempt(
+        &self,
+        node_a: &mut QuantumNode,
+        node_b: &mut QuantumNode,
+        channel: &QuantumChannel,
+        current_time: f64,
+        coherence_time_ms: f64,
+        rng: &mut impl Rng,
+    ) -> Result<AttemptOutcome, String>;
+
+    /// Attempt generation without storing anything in node memory
+    /// Returns the heralded pair fidelity on success
+    ///
+    /// The default runs `attempt` against scratch single-slot nodes, so
+    /// memory capacity and decoherence play no part
+    fn herald(
+        &self,
+        channel: &QuantumChannel,
+        current_time: f64,
+        rng: &mut impl Rng,
+    ) -> Option<f64> {
+        let mut node_a = QuantumNode::new(channel.node_a, 1);
+        let mut node_b = QuantumNode::new(channel.node_b, 1);
+        match self.attempt(
+            &mut node_a,
+            &mut node_b,
+            channel,
+            current_time,
+            f64::INFINITY,
+            rng,
+        ) {
+            Ok(AttemptOutcome::Success) => Some(node_a.stored_pairs()[0].fidelity),
+            _ => None,
+        }
+    }
+
+    /// Time one attempt over `channel` occupies the link (ms)
+    /// The next attempt cannot start earlier
+    fn attempt_duration_ms(&self, channel: &QuantumChannel) -> f64 {
+        channel.attempt_duration_ms()
+    }
+
+    /// Configured parameters this protocol will not read
+    fn ignored_parameters(&self) -> Vec<IgnoredParameter> {
+        Vec::new()
+    }
+
+    /// Decision points an attempt over `channel` draws for
+    fn randomness(&self, _channel: &QuantumChannel) -> Vec<RandomnessDecl> {
+        Vec::new()
+    }
+
+    /// Change a protocol parameter mid-run; errors for parameters the protocol lacks
+    fn apply_override(&mut self, change: &ParamOverride) -> Result<(), String> {
+        Err(format!(
+            "Protocol has no parameter for override: {}",
+            change
+        ))
+    }
+}
+
+/// Pure channel-loss model (`attempt_entanglement_generation`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LossOnlyProtocol;
+
+impl GenerationProtocol for LossOnlyProtocol {
+    fn attempt(
+        &self,
+        node_a: &mut QuantumNode,
+        node_b: &mut QuantumNode,
+        channel: &QuantumChannel,
+        current_time: f64,
+        coherence_time_ms: f64,
+        rng: &mut impl Rng,
+    ) -> Result<AttemptOutcome, String> {
+        attempt_entanglement_generation_with_rng(
+            node_a,
+            node_b,
+            channel,
+            current_time,
+            coherence_time_ms,
+            rng,
+        )
+        .map(AttemptOutcome::from)
+    }
+
+    fn randomness(&self, channel: &QuantumChannel) -> Vec<RandomnessDecl> {
+        RandomnessSource::randomness(channel)
+    }
+}
+
+impl GenerationProtocol for BarrettKokProtocol {
+    fn attempt(
+        &self,
+        node_a: &mut QuantumNode,
+        node_b: &mut QuantumNode,
+        channel: &QuantumChannel,
+        current_time: f64,
+        coherence_time_ms: f64,
+        rng: &mut impl Rng,
+    ) -> Result<AttemptOutcome, String> {
+        if channel.num_modes > 1 {
+            // Every heralded mode that fits in memory is stored
+            return self
+                .attempt_generation_multiplexed_with_rng(
+                    node_a,
+                    node_b,
+                    channel,
+                    current_time,
+                    coherence_time_ms,
+                    rng,
+                )
+                .map(|attempt| AttemptOutcome::from(attempt.stored > 0));
+        }
+        self.attempt_generation_with_rng(
+            node_a,
+            node_b,
+            channel,
+            current_time,
+            coherence_time_ms,
+            rng,
+        )
+        .map(|attempt| AttemptOutcome::from(attempt.success))
+    }
+
+    fn ignored_parameters(&self) -> Vec<IgnoredParameter> {
+        ParameterUsage::ignored_parameters(self)
+    }
+
+    fn randomness(&self, channel: &QuantumChannel) -> Vec<RandomnessDecl> {
+        self.decision_points(channel)
+    }
+
+    fn apply_override(&mut self, change: &ParamOverride) -> Result<(), String> {
+        match *change {
+            ParamOverride::DetectorEfficiency(value) => {
+                self.detector_a.efficiency = value;
+                self.detector_b.efficiency = value;
+            }
+            ParamOverride::CollectionEfficiency(value) => self.collection_efficiency = value,
+            ParamOverride::BsmEfficiency(value) => self.bsm_efficiency = value,
+            _ => {
+                return Err(format!(
+                    "Barrett-Kok protocol has no parameter for override: {}",
+                    change
+                ))
+            }
+        }
+        Ok(())
+    }
+}
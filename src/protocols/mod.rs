@@ -1 +1,6 @@
+pub mod banking;
 pub mod barrett_kok;
+pub mod generation;
+pub mod occupancy;
+pub mod qkd;
+pub mod swapping;
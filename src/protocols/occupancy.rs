@@ -0,0 +1,300 @@
+use super::generation::{AttemptOutcome, GenerationProtocol};
+use crate::network::{NetworkTopology, PairHandle, QuantumNode, StoredPair};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Duty cycle of memory qubits shared between storage and generation
+///
+/// An attempt engages one slot at each end from emission until the herald
+/// arrives `herald_delay_ms` later. An engaged slot can neither store
+/// another pair nor hand its pair out for consumption, so a node whose
+/// slots are all storing or engaged cannot attempt at all: with one slot
+/// per node, generation stops while a pair waits and resumes once it is
+/// consumed.
+///
+/// With `attempt_dephasing` > 0, every attempt at a node also phase-flips
+/// each qubit already stored there with that probability, modelling
+/// crosstalk from the neighbouring slot's emission.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryOccupancyModel {
+    /// Time from emission until the herald reaches the nodes (ms)
+    pub herald_delay_ms: f64,
+    /// Phase-flip probability per attempt for each stored qubit at the node
+    pub attempt_dephasing: f64,
+    /// Attempts not made because an end node had no idle slot
+    pub blocked_attempts: usize,
+    /// End times of slot engagements by node id
+    engaged: BTreeMap<usize, Vec<f64>>,
+}
+
+impl MemoryOccupancyModel {
+    pub fn new(herald_delay_ms: f64) -> Self {
+        MemoryOccupancyModel {
+            herald_delay_ms,
+            attempt_dephasing: 0.0,
+            blocked_attempts: 0,
+            engaged: BTreeMap::new(),
+        }
+    }
+
+    /// Copy of this model where each attempt dephases neighbouring stored qubits
+    pub fn with_attempt_dephasing(mut self, probability: f64) -> Self {
+        self.attempt_dephasing = probability;
+        self
+    }
+
+    /// Slots of `node_id` engaged in an attempt at `current_time`
+    pub fn engaged_slots(&self, node_id: usize, current_time: f64) -> usize {
+        self.engaged.get(&node_id).map_or(0, |ends| {
+            ends.iter().filter(|&&end| end > current_time).count()
+        })
+    }
+
+    /// Slots of `node` neither storing a pair nor engaged in an attempt
+    pub fn idle_slots(&self, node: &QuantumNode, current_time: f64) -> usize {
+        node.free_memory()
+            .saturating_sub(self.engaged_slots(node.id, current_time))
+    }
+
+    /// Whether the herald of `pair` has arrived, so it may be consumed
+    pub fn is_heralded(&self, pair: &StoredPair, current_time: f64) -> bool {
+        pair.creation_time + self.herald_delay_ms <= current_time
+    }
+
+    /// Heralded pair with `partner_id` with the highest fidelity at `current_time`
+    pub fn best_consumable_pair(
+        &self,
+        node: &QuantumNode,
+        partner_id: usize,
+        current_time: f64,
+    ) -> Option<PairHandle> {
+        let mut best: Option<(PairHandle, f64)> = None;
+        for pair in node.stored_pairs() {
+            if pair.partner_node_id != partner_id || !self.is_heralded(pair, current_time) {
+                continue;
+            }
+            let fidelity = pair.fidelity_at(current_time);
+            if best.is_none_or(|(_, best_fidelity)| fidelity > best_fidelity) {
+                best = Some((pair.handle(), fidelity));
+            }
+        }
+        best.map(|(handle, _)| handle)
+    }
+
+    /// Attempt generation between `node_a` and `node_b` under the duty cycle
+    ///
+    /// Returns Ok(None) without attempting if either node has no idle slot.
+    /// Otherwise both slots are engaged until the herald arrives; a
+    /// successful attempt leaves its pair stored in them.
+    #[allow(clippy::too_many_arguments)]
+    pub fn attempt(
+        &mut self,
+        protocol: &impl GenerationProtocol,
+        topology: &mut NetworkTopology,
+        node_a: usize,
+        node_b: usize,
+        current_time: f64,
+        coherence_time_ms: f64,
+        rng: &mut impl Rng,
+    ) -> Result<Option<AttemptOutcome>, String> {
+        for node_id in [node_a, node_b] {
+            if let Some(ends) = self.engaged.get_mut(&node_id) {
+                ends.retain(|&end| end > current_time);
+            }
+        }
+        let channel = topology
+            .find_channel(node_a, node_b)
+            .map(|(_, channel)| channel.clone())
+            .ok_or_else(|| format!("No channel between node {} and node {}", node_a, node_b))?;
+        for node_id in [node_a, node_b] {
+            if topology.get_node(node_id).is_none() {
+                return Err(format!("No node with id {}", node_id));
+            }
+        }
+        let idle = |node_id| self.idle_slots(topology.get_node(node_id).unwrap(), current_time);
+        if idle(node_a) == 0 || idle(node_b) == 0 {
+            self.blocked_attempts += 1;
+            return Ok(None);
+        }
+
+        if self.attempt_dephasing > 0.0 {
+            self.dephase_stored_pairs(topology, node_a);
+            self.dephase_stored_pairs(topology, node_b);
+        }
+
+        let (a, b) = topology.get_node_pair_mut(node_a, node_b).unwrap();
+        let outcome = protocol.attempt(a, b, &channel, current_time, coherence_time_ms, rng)?;
+        // A stored pair already occupies its slot; only a failed attempt
+        // holds an otherwise empty slot until the herald says so
+        if outcome == AttemptOutcome::Failure {
+            let end = current_time + self.herald_delay_ms;
+            for node_id in [node_a, node_b] {
+                self.engaged.entry(node_id).or_default().push(end);
+            }
+        }
+        Ok(Some(outcome))
+    }
+
+    /// Phase-flip each qubit stored at `node_id`, updating both records of its pair
+    fn dephase_stored_pairs(&self, topology: &mut NetworkTopology, node_id: usize) {
+        let Some(node) = topology.get_node(node_id) else {
+            return;
+        };
+        let pairs: Vec<(usize, PairHandle)> = node
+            .stored_pairs()
+            .iter()
+            .map(|pair| (pair.partner_node_id, pair.handle()))
+            .collect();
+        for (partner, handle) in pairs {
+            for holder in [node_id, partner] {
+                let record = topology
+                    .get_node_mut(holder)
+                    .and_then(|node| node.pair_mut(handle));
+                if let Some(pair) = record {
+                    pair.fidelity = dephased_fidelity(pair.fidelity, self.attempt_dephasing);
+                }
+            }
+        }
+    }
+}
+
+/// Fidelity of a Werner pair after a phase flip with probability `p` on one qubit
+/// The flip swaps |Φ+⟩ with |Φ-⟩, which holds (1 - F)/3 of a Werner state
+fn dephased_fidelity(fidelity: f64, p: f64) -> f64 {
+    fidelity * (1.0 - p) + (1.0 - fidelity) * p / 3.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::QuantumChannel;
+    use crate::testing::FakeGenerationProtocol;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn single_slot_link() -> NetworkTopology {
+        let mut topology = NetworkTopology::new_custom();
+        topology.add_node(QuantumNode::new(0, 1)).unwrap();
+        topology.add_node(QuantumNode::new(1, 1)).unwrap();
+        topology
+            .add_channel(QuantumChannel::new(0, 1, 10.0, 0.2))
+            .unwrap();
+        topology
+    }
+
+    #[test]
+    fn test_single_slot_halts_while_pair_is_stored() {
+        let protocol = FakeGenerationProtocol::scripted(
+            vec![AttemptOutcome::Failure, AttemptOutcome::Success],
+            0.95,
+        );
+        let mut topology = single_slot_link();
+        let mut model = MemoryOccupancyModel::new(0.5);
+        // The scripted protocol never draws
+        let mut rng = StdRng::seed_from_u64(0);
+
+        // A failed attempt engages the slot until its herald at t=0.5
+        assert_eq!(
+            model.attempt(&protocol, &mut topology, 0, 1, 0.0, 100.0, &mut rng),
+            Ok(Some(AttemptOutcome::Failure))
+        );
+        assert_eq!(model.engaged_slots(0, 0.25), 1);
+        assert_eq!(
+            model.attempt(&protocol, &mut topology, 0, 1, 0.25, 100.0, &mut rng),
+            Ok(None)
+        );
+        assert_eq!(
+            model.attempt(&protocol, &mut topology, 0, 1, 1.0, 100.0, &mut rng),
+            Ok(Some(AttemptOutcome::Success))
+        );
+
+        // The stored pair blocks every attempt, and is not consumable before its herald
+        let node = topology.get_node(0).unwrap();
+        assert_eq!(model.best_consumable_pair(node, 1, 1.25), None);
+        assert_eq!(
+            model.best_consumable_pair(node, 1, 1.5),
+            Some(node.stored_pairs()[0].handle())
+        );
+        for time in [2.0, 3.0, 4.0] {
+            assert_eq!(
+                model.attempt(&protocol, &mut topology, 0, 1, time, 100.0, &mut rng),
+                Ok(None)
+            );
+        }
+        assert_eq!(protocol.attempts(), 2);
+        assert_eq!(model.blocked_attempts, 4);
+
+        // Consuming the pair frees the slot and generation resumes
+        topology.get_node_mut(0).unwrap().remove_pair_with(1, 5.0);
+        topology.get_node_mut(1).unwrap().remove_pair_with(0, 5.0);
+        assert_eq!(
+            model.attempt(&protocol, &mut topology, 0, 1, 5.0, 100.0, &mut rng),
+            Ok(Some(AttemptOutcome::Failure))
+        );
+        assert_eq!(protocol.attempts(), 3);
+    }
+
+    /// Stored and current fidelity of the 0-1 pair when consumed at t=10
+    /// after ten attempts on link 1-2
+    fn consumed_fidelity(model: MemoryOccupancyModel) -> (f64, f64) {
+        let protocol = FakeGenerationProtocol::scripted(vec![AttemptOutcome::Failure], 0.95);
+        let mut topology = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        let pair_id = crate::network::next_pair_id();
+        let pair = |partner| {
+            let mut pair = StoredPair::new(
+                partner,
+                crate::quantum::TwoQubitState::new_bell_phi_plus(),
+                0.0,
+                1000.0,
+            )
+            .with_pair_id(pair_id);
+            pair.fidelity = 0.95;
+            pair
+        };
+        topology
+            .get_node_mut(0)
+            .unwrap()
+            .store_pair(pair(1))
+            .unwrap();
+        topology
+            .get_node_mut(1)
+            .unwrap()
+            .store_pair(pair(0))
+            .unwrap();
+
+        let mut model = model;
+        let mut rng = StdRng::seed_from_u64(0);
+        for step in 0..10 {
+            let outcome = model.attempt(
+                &protocol,
+                &mut topology,
+                1,
+                2,
+                step as f64,
+                1000.0,
+                &mut rng,
+            );
+            assert_eq!(outcome, Ok(Some(AttemptOutcome::Failure)));
+        }
+        let node = topology.get_node(0).unwrap();
+        let handle = model.best_consumable_pair(node, 1, 10.0).unwrap();
+        // Both records of the pair stay in step
+        let partner_fidelity = topology.get_node(1).unwrap().pair(handle).unwrap().fidelity;
+        let pair = node.pair(handle).unwrap();
+        assert_eq!(pair.fidelity, partner_fidelity);
+        (pair.fidelity, pair.fidelity_at(10.0))
+    }
+
+    #[test]
+    fn test_attempt_dephasing_lowers_waiting_pair_fidelity() {
+        let (stored, plain) = consumed_fidelity(MemoryOccupancyModel::new(0.5));
+        assert_eq!(stored, 0.95);
+        let (stored, dephased) =
+            consumed_fidelity(MemoryOccupancyModel::new(0.5).with_attempt_dephasing(0.01));
+        assert!(plain - dephased > 0.05, "{} vs {}", plain, dephased);
+
+        // Ten 1% flips of the repeater's qubit: F - 1/4 shrinks by (1 - 4p/3)^10
+        let expected = 0.25 + 0.7 * (1.0 - 0.04 / 3.0_f64).powi(10);
+        assert!((stored - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,597 @@
+use super::generation::{AttemptOutcome, GenerationProtocol};
+use crate::analysis::binary_entropy;
+use crate::network::{GenerationStats, QuantumChannel, QuantumNode};
+use crate::quantum::noise::PauliErrorRates;
+pub use crate::quantum::Basis;
+use crate::simulation::unseeded_rng;
+use rand::Rng;
+
+/// How each party picks its measurement basis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BasisStrategy {
+    /// Z or X uniformly, independently per party
+    Random,
+    /// Always the same basis
+    Fixed(Basis),
+    /// Z with the given probability (efficient BB84)
+    BiasedZ(f64),
+}
+
+impl BasisStrategy {
+    /// Draw a basis for one party
+    pub fn choose(&self, rng: &mut impl Rng) -> Basis {
+        match *self {
+            BasisStrategy::Random => Basis::random(rng),
+            BasisStrategy::Fixed(basis) => basis,
+            BasisStrategy::BiasedZ(p_z) => {
+                if rng.random::<f64>() < p_z {
+                    Basis::Z
+                } else {
+                    Basis::X
+                }
+            }
+        }
+    }
+}
+
+/// Receives outcomes of pairs measured by both parties
+pub trait MeasurementConsumer {
+    fn on_measured(&mut self, basis_a: Basis, basis_b: Basis, outcome_a: bool, outcome_b: bool);
+
+    /// Time-aware variant, for consumers with time-dependent behaviour
+    fn on_measured_at(
+        &mut self,
+        _time: f64,
+        basis_a: Basis,
+        basis_b: Basis,
+        outcome_a: bool,
+        outcome_b: bool,
+    ) {
+        self.on_measured(basis_a, basis_b, outcome_a, outcome_b);
+    }
+}
+
+/// Outcome an attacker forces on a blinded detector
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForcedOutcome {
+    /// The sender's bit, as after a correct-basis intercept
+    InterceptedBit,
+    /// A uniformly random bit
+    RandomBit,
+    /// Always the same bit
+    Fixed(bool),
+}
+
+/// Window [start, end) during which the receiver's detector is attacker-controlled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DetectorOverride {
+    pub start: f64,
+    pub end: f64,
+    pub forced: ForcedOutcome,
+}
+
+/// Receiver detector with a schedule of attacker-controlled windows
+///
+/// Inside a window the detector always clicks with the forced bit,
+/// regardless of the quantum state
+#[derive(Debug, Clone, Default)]
+pub struct BlindableDetector {
+    pub overrides: Vec<DetectorOverride>,
+    /// Rounds whose outcome was forced
+    pub overridden_rounds: usize,
+}
+
+impl BlindableDetector {
+    pub fn new(overrides: Vec<DetectorOverride>) -> Self {
+        BlindableDetector {
+            overrides,
+            overridden_rounds: 0,
+        }
+    }
+
+    /// Receiver outcome at `time` given the honest outcomes
+    pub fn observe(&mut self, time: f64, outcome_a: bool, outcome_b: bool) -> bool {
+        self.observe_forced(time, outcome_a, outcome_b, || {
+            unseeded_rng("BlindableDetector").random::<bool>()
+        })
+    }
+
+    /// `observe` drawing random forced bits from the caller's RNG
+    pub fn observe_with_rng(
+        &mut self,
+        time: f64,
+        outcome_a: bool,
+        outcome_b: bool,
+        rng: &mut impl Rng,
+    ) -> bool {
+        self.observe_forced(time, outcome_a, outcome_b, || rng.random::<bool>())
+    }
+
+    /// Receiver outcome; `random_bit` is only drawn in a `RandomBit` window
+    fn observe_forced(
+        &mut self,
+        time: f64,
+        outcome_a: bool,
+        outcome_b: bool,
+        random_bit: impl FnOnce() -> bool,
+    ) -> bool {
+        let window = self
+            .overrides
+            .iter()
+            .find(|window| window.start <= time && time < window.end);
+        let Some(window) = window else {
+            return outcome_b;
+        };
+
+        self.overridden_rounds += 1;
+        match window.forced {
+            ForcedOutcome::InterceptedBit => outcome_a,
+            ForcedOutcome::RandomBit => random_bit(),
+            ForcedOutcome::Fixed(bit) => bit,
+        }
+    }
+}
+
+/// Whether a session would pass the standard QBER check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecurityReport {
+    pub qber_z: f64,
+    pub qber_x: f64,
+    /// Abort threshold applied to both bases
+    pub qber_threshold: f64,
+    /// True if either QBER exceeds the threshold
+    pub aborted: bool,
+    /// Rounds forced by the attacker
+    pub overridden_rounds: usize,
+    /// Asymptotic secret fraction 1 - h(e_x) - h(e_z) (0 if aborted)
+    pub secret_fraction: f64,
+}
+
+impl SecurityReport {
+    /// The attacker forced outcomes and the QBER check did not notice
+    pub fn attack_undetected(&self) -> bool {
+        self.overridden_rounds > 0 && !self.aborted
+    }
+}
+
+/// Sample outcomes of measuring a noisy |Φ+⟩ pair
+///
+/// Matching bases agree up to the basis-dependent flip probability;
+/// mismatched bases give independent uniform outcomes. Panics on a custom basis
+pub fn sample_outcomes(
+    errors: &PauliErrorRates,
+    basis_a: Basis,
+    basis_b: Basis,
+    rng: &mut impl Rng,
+) -> (bool, bool) {
+    let outcome_a = rng.random::<bool>();
+    if basis_a != basis_b {
+        return (outcome_a, rng.random::<bool>());
+    }
+    let flip_probability = match basis_a {
+        Basis::Z => errors.bit_flip_probability(),
+        Basis::X => errors.phase_flip_probability(),
+        // |Φ+⟩ is anticorrelated in Y; B's outcome is reported inverted
+        Basis::Y => errors.p_x + errors.p_z,
+        Basis::Custom { .. } => panic!("Outcome sampling covers the Pauli bases only"),
+    };
+    (
+        outcome_a,
+        outcome_a ^ (rng.random::<f64>() < flip_probability),
+    )
+}
+
+/// Pauli errors on the best pair between two nodes at `current_time`
+/// Combines the Bell-diagonal part of the pair's initial state with memory
+/// noise on both halves
+pub fn pair_errors_at(
+    node_a: &QuantumNode,
+    node_b: &QuantumNode,
+    current_time: f64,
+) -> Option<PauliErrorRates> {
+    let handle_a = node_a.best_pair_with(node_b.id, current_time)?;
+    let pair_a = node_a.pair(handle_a)?;
+    let pair_b = node_b.pair(handle_a)?;
+
+    Some(
+        PauliErrorRates::from_bell_diagonal(&pair_a.initial_density_matrix())
+            .then(&pair_a.memory_errors_at(current_time))
+            .then(&pair_b.memory_errors_at(current_time)),
+    )
+}
+
+/// Consume the best pair between two nodes, measuring both halves in `basis`
+///
+/// Returns (outcome_a, outcome_b). Outcomes of |Φ+⟩ agree in both bases;
+/// they disagree with the basis-dependent flip probability of the pair.
+pub fn consume_and_measure(
+    node_a: &mut QuantumNode,
+    node_b: &mut QuantumNode,
+    basis: Basis,
+    current_time: f64,
+    rng: &mut impl Rng,
+) -> Result<(bool, bool), String> {
+    let errors = pair_errors_at(node_a, node_b, current_time)
+        .ok_or_else(|| format!("No pair between node {} and node {}", node_a.id, node_b.id))?;
+
+    let handle_a = node_a.best_pair_with(node_b.id, current_time).unwrap();
+    let pair_a = node_a.remove_pair_at(handle_a, current_time).unwrap();
+    node_b.remove_pair_by_id(pair_a.pair_id, current_time);
+
+    Ok(sample_outcomes(&errors, basis, basis, rng))
+}
+
+/// Link mode where heralded pairs are measured at once instead of stored
+///
+/// Node memory is bypassed entirely: no capacity limit and no decoherence.
+/// Each heralded pair counts as a delivery with zero storage time.
+#[derive(Debug)]
+pub struct MeasureOnHerald {
+    pub basis_strategy: BasisStrategy,
+    pub stats: GenerationStats,
+}
+
+impl MeasureOnHerald {
+    pub fn new(basis_strategy: BasisStrategy) -> Self {
+        MeasureOnHerald {
+            basis_strategy,
+            stats: GenerationStats::new(),
+        }
+    }
+
+    /// One generation attempt; a heralded pair is measured and handed to `consumer`
+    pub fn attempt(
+        &mut self,
+        protocol: &impl GenerationProtocol,
+        channel: &QuantumChannel,
+        current_time: f64,
+        consumer: &mut impl MeasurementConsumer,
+        rng: &mut impl Rng,
+    ) -> AttemptOutcome {
+        self.stats.attempts += 1;
+        let Some(fidelity) = protocol.herald(channel, current_time, rng) else {
+            self.stats.channel_failures += 1;
+            return AttemptOutcome::Failure;
+        };
+
+        self.stats.successes += 1;
+        self.stats.deliveries += 1;
+
+        let basis_a = self.basis_strategy.choose(rng);
+        let basis_b = self.basis_strategy.choose(rng);
+        let (outcome_a, outcome_b) =
+            sample_outcomes(&PauliErrorRates::werner(fidelity), basis_a, basis_b, rng);
+        consumer.on_measured_at(current_time, basis_a, basis_b, outcome_a, outcome_b);
+        AttemptOutcome::Success
+    }
+}
+
+/// BBM92 session statistics, with QBER tracked per basis
+#[derive(Debug, Clone, Default)]
+pub struct Bbm92Session {
+    pub z_rounds: usize,
+    pub z_errors: usize,
+    pub x_rounds: usize,
+    pub x_errors: usize,
+    /// Rounds where the two bases differed (removed by sifting)
+    pub discarded_rounds: usize,
+    /// Receiver detector; may be blinded during scheduled windows
+    pub detector: BlindableDetector,
+}
+
+impl Bbm92Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consume one pair, each side choosing a random basis
+    pub fn run_round(
+        &mut self,
+        node_a: &mut QuantumNode,
+        node_b: &mut QuantumNode,
+        current_time: f64,
+        rng: &mut impl Rng,
+    ) -> Result<(), String> {
+        let basis_a = Basis::random(rng);
+        let basis_b = Basis::random(rng);
+        let (outcome_a, outcome_b) =
+            consume_and_measure(node_a, node_b, basis_a, current_time, rng)?;
+        self.on_measured_at(current_time, basis_a, basis_b, outcome_a, outcome_b);
+        Ok(())
+    }
+
+    /// Record a sifted round measured in `basis`
+    pub fn record(&mut self, basis: Basis, outcome_a: bool, outcome_b: bool) {
+        let error = outcome_a != outcome_b;
+        match basis {
+            Basis::Z => {
+                self.z_rounds += 1;
+                self.z_errors += error as usize;
+            }
+            Basis::X => {
+                self.x_rounds += 1;
+                self.x_errors += error as usize;
+            }
+            Basis::Y | Basis::Custom { .. } => panic!("BBM92 sifts only Z and X rounds"),
+        }
+    }
+
+    /// Number of rounds surviving sifting
+    pub fn sifted_rounds(&self) -> usize {
+        self.z_rounds + self.x_rounds
+    }
+
+    /// Error rate of Z-basis rounds (0 if none)
+    pub fn qber_z(&self) -> f64 {
+        if self.z_rounds == 0 {
+            0.0
+        } else {
+            self.z_errors as f64 / self.z_rounds as f64
+        }
+    }
+
+    /// Error rate of X-basis rounds (0 if none)
+    pub fn qber_x(&self) -> f64 {
+        if self.x_rounds == 0 {
+            0.0
+        } else {
+            self.x_errors as f64 / self.x_rounds as f64
+        }
+    }
+
+    /// Apply a QBER abort threshold (11% is the usual BBM92 bound)
+    pub fn security_report(&self, qber_threshold: f64) -> SecurityReport {
+        let (qber_z, qber_x) = (self.qber_z(), self.qber_x());
+        let aborted = qber_z > qber_threshold || qber_x > qber_threshold;
+        let secret_fraction = if aborted {
+            0.0
+        } else {
+            (1.0 - binary_entropy(qber_x) - binary_entropy(qber_z)).max(0.0)
+        };
+        SecurityReport {
+            qber_z,
+            qber_x,
+            qber_threshold,
+            aborted,
+            overridden_rounds: self.detector.overridden_rounds,
+            secret_fraction,
+        }
+    }
+}
+
+impl MeasurementConsumer for Bbm92Session {
+    fn on_measured(&mut self, basis_a: Basis, basis_b: Basis, outcome_a: bool, outcome_b: bool) {
+        if basis_a == basis_b {
+            self.record(basis_a, outcome_a, outcome_b);
+        } else {
+            self.discarded_rounds += 1;
+        }
+    }
+
+    fn on_measured_at(
+        &mut self,
+        time: f64,
+        basis_a: Basis,
+        basis_b: Basis,
+        outcome_a: bool,
+        outcome_b: bool,
+    ) {
+        let outcome_b = self.detector.observe(time, outcome_a, outcome_b);
+        self.on_measured(basis_a, basis_b, outcome_a, outcome_b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::StoredPair;
+    use crate::protocols::barrett_kok::BarrettKokProtocol;
+    use crate::protocols::generation::LossOnlyProtocol;
+    use crate::quantum::{apply_pauli_channel, DensityMatrix, PairHalf, TwoQubitState};
+    use rand::SeedableRng;
+
+    /// Store one pair with pure dephasing memories at both nodes
+    fn store_dephasing_pair(node_a: &mut QuantumNode, node_b: &mut QuantumNode, t2_ms: f64) {
+        let bell_state = TwoQubitState::new_bell_phi_plus();
+        let pair_a = StoredPair::new(node_b.id, bell_state.clone(), 0.0, 1000.0)
+            .with_memory_times(f64::INFINITY, t2_ms);
+        let pair_b = StoredPair::new(node_a.id, bell_state, 0.0, 1000.0)
+            .with_pair_id(pair_a.pair_id)
+            .with_memory_times(f64::INFINITY, t2_ms);
+        node_a.store_pair(pair_a).unwrap();
+        node_b.store_pair(pair_b).unwrap();
+    }
+
+    fn session_after_storage(storage_ms: f64, rounds: usize) -> Bbm92Session {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let mut node_a = QuantumNode::new(0, 1);
+        let mut node_b = QuantumNode::new(1, 1);
+        let mut session = Bbm92Session::new();
+        for _ in 0..rounds {
+            store_dephasing_pair(&mut node_a, &mut node_b, 10.0);
+            session
+                .run_round(&mut node_a, &mut node_b, storage_ms, &mut rng)
+                .unwrap();
+        }
+        session
+    }
+
+    #[test]
+    fn test_pure_dephasing_only_affects_x_basis() {
+        let t2_ms = 10.0;
+        let storage_ms = 5.0;
+        let session = session_after_storage(storage_ms, 8000);
+
+        assert_eq!(session.qber_z(), 0.0);
+        assert!(session.x_rounds > 1000);
+
+        // Each half flips phase with q = (1 - e^(-t/T2))/2; the pair with 2q(1-q)
+        let q = (1.0 - (-storage_ms / t2_ms).exp()) / 2.0;
+        let expected = 2.0 * q * (1.0 - q);
+        assert!(
+            (session.qber_x() - expected).abs() < 0.03,
+            "QBER_X {} vs analytic {}",
+            session.qber_x(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_x_basis_error_grows_with_storage() {
+        let short = session_after_storage(1.0, 4000);
+        let long = session_after_storage(20.0, 4000);
+        assert_eq!(long.qber_z(), 0.0);
+        assert!(long.qber_x() > short.qber_x() + 0.1);
+    }
+
+    #[test]
+    fn test_stored_dephased_state_only_flips_x_outcomes() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let mut node_a = QuantumNode::new(0, 1);
+        let mut node_b = QuantumNode::new(1, 1);
+        let mut rho = DensityMatrix::from_pure(&TwoQubitState::new_bell_phi_plus());
+        apply_pauli_channel(
+            &mut rho,
+            PairHalf::B,
+            &PauliErrorRates {
+                p_x: 0.0,
+                p_y: 0.0,
+                p_z: 0.2,
+            },
+        );
+        let mut errors = [0, 0];
+        for (round, basis) in [Basis::Z, Basis::X]
+            .into_iter()
+            .cycle()
+            .take(4000)
+            .enumerate()
+        {
+            let bell_state = TwoQubitState::new_bell_phi_plus();
+            let pair_a = StoredPair::new(1, bell_state.clone(), 0.0, 1000.0)
+                .with_density_matrix(rho.clone());
+            let pair_b = StoredPair::new(0, bell_state, 0.0, 1000.0)
+                .with_pair_id(pair_a.pair_id)
+                .with_density_matrix(rho.clone());
+            node_a.store_pair(pair_a).unwrap();
+            node_b.store_pair(pair_b).unwrap();
+            let (a, b) =
+                consume_and_measure(&mut node_a, &mut node_b, basis, 0.0, &mut rng).unwrap();
+            errors[round % 2] += usize::from(a != b);
+        }
+
+        // A Werner pair of the same fidelity would flip Z outcomes too
+        assert_eq!(errors[0], 0);
+        assert!(
+            (errors[1] as f64 / 2000.0 - 0.2).abs() < 0.03,
+            "{}",
+            errors[1]
+        );
+    }
+
+    #[test]
+    fn test_consume_without_pair_fails() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut node_a = QuantumNode::new(0, 1);
+        let mut node_b = QuantumNode::new(1, 1);
+        assert!(consume_and_measure(&mut node_a, &mut node_b, Basis::Z, 0.0, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_measure_on_herald_ignores_memory_capacity() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let mut node_a = QuantumNode::new(0, 0);
+        let mut node_b = QuantumNode::new(1, 0);
+        let channel = QuantumChannel::new(0, 1, 0.0, 0.2);
+
+        // Storing protocols cannot run without memory
+        assert!(LossOnlyProtocol
+            .attempt(&mut node_a, &mut node_b, &channel, 0.0, 100.0, &mut rng)
+            .is_err());
+
+        let mut mode = MeasureOnHerald::new(BasisStrategy::Random);
+        let mut session = Bbm92Session::new();
+        for slot in 0..100 {
+            mode.attempt(
+                &LossOnlyProtocol,
+                &channel,
+                slot as f64,
+                &mut session,
+                &mut rng,
+            );
+        }
+
+        assert_eq!(mode.stats.deliveries, 100);
+        assert_eq!(mode.stats.mean_storage_time_ms(), 0.0);
+        assert!(session.sifted_rounds() > 0);
+        assert_eq!(session.qber_z() + session.qber_x(), 0.0);
+        assert_eq!(node_a.num_stored_pairs(), 0);
+    }
+
+    #[test]
+    fn test_measure_on_herald_qber_matches_heralded_fidelity() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        let protocol = BarrettKokProtocol::sequence_parameters();
+        let channel = QuantumChannel::new(0, 1, 0.0, 0.2);
+
+        let mut mode = MeasureOnHerald::new(BasisStrategy::Random);
+        let mut session = Bbm92Session::new();
+        for slot in 0..20000 {
+            mode.attempt(&protocol, &channel, slot as f64, &mut session, &mut rng);
+        }
+
+        // Werner pair: each basis flips with 2(1-F)/3, with no storage term
+        let expected = 2.0 * (1.0 - protocol.initial_fidelity) / 3.0;
+        assert!(mode.stats.deliveries > 1000);
+        assert!((session.qber_z() - expected).abs() < 0.015);
+        assert!((session.qber_x() - expected).abs() < 0.015);
+    }
+
+    /// Perfect pairs measured on herald, one per ms, with the receiver
+    /// blinded during the first ms of every 10
+    fn blinded_session(forced: ForcedOutcome) -> Bbm92Session {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(21);
+        let channel = QuantumChannel::new(0, 1, 0.0, 0.2);
+        let overrides = (0..2000)
+            .map(|k| DetectorOverride {
+                start: k as f64 * 10.0,
+                end: k as f64 * 10.0 + 1.0,
+                forced,
+            })
+            .collect();
+
+        let mut mode = MeasureOnHerald::new(BasisStrategy::Random);
+        let mut session = Bbm92Session::new();
+        session.detector = BlindableDetector::new(overrides);
+        for slot in 0..20000 {
+            mode.attempt(
+                &LossOnlyProtocol,
+                &channel,
+                slot as f64,
+                &mut session,
+                &mut rng,
+            );
+        }
+        session
+    }
+
+    #[test]
+    fn test_correct_basis_forcing_goes_undetected() {
+        let session = blinded_session(ForcedOutcome::InterceptedBit);
+        let report = session.security_report(0.11);
+
+        assert_eq!(report.overridden_rounds, 2000);
+        assert_eq!(report.qber_z + report.qber_x, 0.0);
+        assert!(report.attack_undetected());
+    }
+
+    #[test]
+    fn test_random_bit_forcing_raises_qber() {
+        let session = blinded_session(ForcedOutcome::RandomBit);
+        let report = session.security_report(0.11);
+
+        // 10% of sifted rounds are coin flips: QBER ≈ 0.1 × 1/2
+        assert!((report.qber_z - 0.05).abs() < 0.015, "{:?}", report);
+        assert!((report.qber_x - 0.05).abs() < 0.015, "{:?}", report);
+        assert!(report.secret_fraction < 0.6);
+    }
+}
@@ -0,0 +1,427 @@
+use crate::network::node::{PairHandle, StoredPair};
+use crate::network::NetworkTopology;
+use crate::quantum::noise::PauliErrorRates;
+use crate::quantum::{
+    apply_pauli_channel, two_qubit_depolarized_fidelity, DensityMatrix, PairHalf, PauliFrame,
+    TwoQubitState,
+};
+use crate::simulation::unseeded_rng;
+use rand::Rng;
+
+/// Fidelity of the pair produced by swapping two Werner pairs
+/// F = F1*F2 + (1-F1)(1-F2)/3
+pub fn swapped_fidelity(fidelity_a: f64, fidelity_b: f64) -> f64 {
+    fidelity_a * fidelity_b + (1.0 - fidelity_a) * (1.0 - fidelity_b) / 3.0
+}
+
+/// Bell-diagonal state left by swapping pairs in states `rho_a` and `rho_b`
+///
+/// Each pair acts as Pauli errors on |Φ+⟩ (`PauliErrorRates::from_bell_diagonal`);
+/// the swap composes them, and the BSM's two-qubit gate adds each of the 15
+/// non-identity Paulis with probability `gate_error`/15, as in
+/// `two_qubit_depolarized_fidelity`. For Werner pairs the fidelity is
+/// `swapped_fidelity`.
+pub fn swapped_density_matrix(
+    rho_a: &DensityMatrix,
+    rho_b: &DensityMatrix,
+    gate_error: f64,
+) -> DensityMatrix {
+    let errors = PauliErrorRates::from_bell_diagonal(rho_a)
+        .then(&PauliErrorRates::from_bell_diagonal(rho_b))
+        .then(&PauliErrorRates::depolarizing(16.0 * gate_error / 15.0));
+    let mut rho = DensityMatrix::from_pure(&TwoQubitState::new_bell_phi_plus());
+    apply_pauli_channel(&mut rho, PairHalf::B, &errors);
+    rho
+}
+
+/// Perform entanglement swapping at a repeater node
+/// Errors at end nodes (see `NodeRole::can_swap`)
+///
+/// Consumes the repeater's pairs with `left_id` and `right_id` (and the matching
+/// halves stored at those nodes) and stores a new pair between `left_id` and
+/// `right_id`. Returns the fidelity of the new pair.
+///
+/// The Bell-state measurement outcome is uniformly random; the new pair's
+/// `pauli_frame` composes it with the frames of the consumed pairs.
+pub fn perform_entanglement_swap(
+    topology: &mut NetworkTopology,
+    repeater_id: usize,
+    left_id: usize,
+    right_id: usize,
+    current_time: f64,
+) -> Result<f64, String> {
+    perform_entanglement_swap_with_rng(
+        topology,
+        repeater_id,
+        left_id,
+        right_id,
+        current_time,
+        &mut unseeded_rng("perform_entanglement_swap"),
+    )
+}
+
+/// `perform_entanglement_swap` drawing the measurement outcome from the caller's RNG
+pub fn perform_entanglement_swap_with_rng(
+    topology: &mut NetworkTopology,
+    repeater_id: usize,
+    left_id: usize,
+    right_id: usize,
+    current_time: f64,
+    rng: &mut impl Rng,
+) -> Result<f64, String> {
+    perform_entanglement_swap_with_outcome(
+        topology,
+        repeater_id,
+        left_id,
+        right_id,
+        current_time,
+        PauliFrame::random(rng),
+    )
+}
+
+/// `perform_entanglement_swap_with_rng` telling the end nodes' slot strategies
+/// how long their new halves are expected to wait
+/// (see `expected_wait_from_path_position`)
+///
+/// Returns the handle of the new pair, the same at both end nodes.
+pub fn perform_entanglement_swap_with_hints(
+    topology: &mut NetworkTopology,
+    repeater_id: usize,
+    left_id: usize,
+    right_id: usize,
+    current_time: f64,
+    expected_waits_ms: [Option<f64>; 2],
+    rng: &mut impl Rng,
+) -> Result<PairHandle, String> {
+    swap(
+        topology,
+        repeater_id,
+        left_id,
+        right_id,
+        current_time,
+        PauliFrame::random(rng),
+        expected_waits_ms,
+    )
+    .map(|(_, handle)| handle)
+}
+
+/// `perform_entanglement_swap` with a given Bell-state measurement outcome,
+/// expressed as the Pauli correction it calls for
+pub fn perform_entanglement_swap_with_outcome(
+    topology: &mut NetworkTopology,
+    repeater_id: usize,
+    left_id: usize,
+    right_id: usize,
+    current_time: f64,
+    bsm_outcome: PauliFrame,
+) -> Result<f64, String> {
+    swap(
+        topology,
+        repeater_id,
+        left_id,
+        right_id,
+        current_time,
+        bsm_outcome,
+        [None, None],
+    )
+    .map(|(fidelity, _)| fidelity)
+}
+
+fn swap(
+    topology: &mut NetworkTopology,
+    repeater_id: usize,
+    left_id: usize,
+    right_id: usize,
+    current_time: f64,
+    bsm_outcome: PauliFrame,
+    [wait_left_ms, wait_right_ms]: [Option<f64>; 2],
+) -> Result<(f64, PairHandle), String> {
+    let repeater = topology
+        .get_node(repeater_id)
+        .ok_or(format!("Node {} does not exist", repeater_id))?;
+    if !repeater.role.can_swap() {
+        return Err(format!(
+            "Node {} is {:?} and cannot swap",
+            repeater_id, repeater.role
+        ));
+    }
+    let handle_left = repeater
+        .best_pair_with(left_id, current_time)
+        .ok_or(format!(
+            "Node {} has no pair with node {}",
+            repeater_id, left_id
+        ))?;
+    let handle_right = repeater
+        .best_pair_with(right_id, current_time)
+        .ok_or(format!(
+            "Node {} has no pair with node {}",
+            repeater_id, right_id
+        ))?;
+
+    let (left, right) = topology
+        .get_node_pair_mut(left_id, right_id)
+        .ok_or(format!(
+            "Invalid swap endpoints {} and {}",
+            left_id, right_id
+        ))?;
+    // Consume the endpoints' halves of exactly the pairs chosen above
+    if left.pair(handle_left).is_none() || right.pair(handle_right).is_none() {
+        return Err(format!(
+            "Pair halves at nodes {} and {} do not match node {}",
+            left_id, right_id, repeater_id
+        ));
+    }
+    left.remove_pair_at(handle_left, current_time);
+    right.remove_pair_at(handle_right, current_time);
+
+    let repeater = topology.get_node_mut(repeater_id).unwrap();
+    let pair_left = repeater.remove_pair_at(handle_left, current_time).unwrap();
+    let pair_right = repeater.remove_pair_at(handle_right, current_time).unwrap();
+
+    // The BSM's two-qubit gate acts as a depolarizing channel on the result
+    let fidelity = two_qubit_depolarized_fidelity(
+        swapped_fidelity(
+            pair_left.fidelity_at(current_time),
+            pair_right.fidelity_at(current_time),
+        ),
+        repeater.gate_error,
+    );
+    let coherence_time_ms = pair_left
+        .coherence_time_ms
+        .min(pair_right.coherence_time_ms);
+
+    // Freed slots on the endpoints guarantee room for the new halves
+    let density_matrix = swapped_density_matrix(
+        &pair_left.density_matrix_at(current_time),
+        &pair_right.density_matrix_at(current_time),
+        repeater.gate_error,
+    );
+    let mut new_left =
+        StoredPair::new_with_fidelity(right_id, fidelity, current_time, coherence_time_ms);
+    new_left.density_matrix = Some(density_matrix.clone());
+    let mut new_right =
+        StoredPair::new_with_fidelity(left_id, fidelity, current_time, coherence_time_ms)
+            .with_pair_id(new_left.pair_id);
+    new_right.density_matrix = Some(density_matrix);
+    let pauli_frame = pair_left
+        .pauli_frame
+        .then(&pair_right.pauli_frame)
+        .then(&bsm_outcome);
+    new_left.pauli_frame = pauli_frame;
+    new_right.pauli_frame = pauli_frame;
+    let handle = new_left.handle();
+
+    let (left, right) = topology.get_node_pair_mut(left_id, right_id).unwrap();
+    left.store_pair_with_hint(new_left, wait_left_ms)?;
+    right.store_pair_with_hint(new_right, wait_right_ms)?;
+
+    Ok((fidelity, handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::attempt_entanglement_generation;
+    use crate::quantum::Basis;
+
+    #[test]
+    fn test_swapped_fidelity() {
+        assert!((swapped_fidelity(1.0, 1.0) - 1.0).abs() < 1e-10);
+        assert!((swapped_fidelity(0.9, 0.9) - (0.81 + 0.01 / 3.0)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_swap_on_three_node_chain() {
+        let mut network = NetworkTopology::new_linear(3, 2, 0.0, 0.0);
+
+        for (a, b) in [(0, 1), (1, 2)] {
+            let channel = network.find_channel(a, b).unwrap().1.clone();
+            let (node_a, node_b) = network.get_node_pair_mut(a, b).unwrap();
+            assert!(attempt_entanglement_generation(node_a, node_b, &channel, 0.0, 100.0).unwrap());
+        }
+
+        let fidelity = perform_entanglement_swap(&mut network, 1, 0, 2, 0.0).unwrap();
+        assert!((fidelity - 1.0).abs() < 1e-10);
+
+        assert_eq!(network.get_node(1).unwrap().num_stored_pairs(), 0);
+        assert!(network.get_node(0).unwrap().find_pair_with(2).is_some());
+        assert!(network.get_node(2).unwrap().find_pair_with(0).is_some());
+    }
+
+    #[test]
+    fn test_swap_accrues_slot_busy_time() {
+        let mut network = NetworkTopology::new_custom();
+        for id in 0..3 {
+            network
+                .add_node(crate::network::QuantumNode::with_slots(id, &[100.0, 100.0]))
+                .unwrap();
+        }
+        for (a, b) in [(0, 1), (1, 2)] {
+            network
+                .add_channel(crate::network::QuantumChannel::new(a, b, 0.0, 0.0))
+                .unwrap();
+            let channel = network.find_channel(a, b).unwrap().1.clone();
+            let (node_a, node_b) = network.get_node_pair_mut(a, b).unwrap();
+            assert!(attempt_entanglement_generation(node_a, node_b, &channel, 0.0, 100.0).unwrap());
+        }
+
+        perform_entanglement_swap(&mut network, 1, 0, 2, 5.0).unwrap();
+
+        let busy: Vec<f64> = network
+            .get_node(1)
+            .unwrap()
+            .slot_statistics(5.0)
+            .iter()
+            .map(|slot| slot.busy_time_ms)
+            .collect();
+        assert_eq!(busy, vec![5.0, 5.0]);
+    }
+
+    #[test]
+    fn test_swap_with_gate_error() {
+        let mut network = NetworkTopology::new_linear(3, 2, 0.0, 0.0);
+        network.get_node_mut(1).unwrap().gate_error = 0.1;
+
+        for (a, b) in [(0, 1), (1, 2)] {
+            let channel = network.find_channel(a, b).unwrap().1.clone();
+            let (node_a, node_b) = network.get_node_pair_mut(a, b).unwrap();
+            attempt_entanglement_generation(node_a, node_b, &channel, 0.0, 100.0).unwrap();
+        }
+
+        let fidelity = perform_entanglement_swap(&mut network, 1, 0, 2, 0.0).unwrap();
+        assert!((fidelity - (1.0 - 0.8 * 0.1)).abs() < 1e-10);
+
+        // Both new halves carry a state that agrees with the scalar
+        let phi_plus = TwoQubitState::new_bell_phi_plus();
+        for (node, partner) in [(0, 2), (2, 0)] {
+            let node = network.get_node(node).unwrap();
+            let pair = node.pair(node.find_pair_with(partner).unwrap()).unwrap();
+            assert!((pair.state.fidelity(&phi_plus) - fidelity).abs() < 1e-10);
+            let rho = pair.density_matrix.as_ref().unwrap();
+            assert!((rho.fidelity_with_pure(&phi_plus) - fidelity).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_swap_carries_memory_dephasing_into_density_matrix() {
+        let mut network = NetworkTopology::new_linear(3, 2, 0.0, 0.0);
+        for (a, b) in [(0, 1), (1, 2)] {
+            let channel = network.find_channel(a, b).unwrap().1.clone();
+            let (node_a, node_b) = network.get_node_pair_mut(a, b).unwrap();
+            attempt_entanglement_generation(node_a, node_b, &channel, 0.0, 100.0).unwrap();
+        }
+
+        perform_entanglement_swap(&mut network, 1, 0, 2, 10.0).unwrap();
+
+        // Four halves dephased for 10 ms with T2 = 100 ms; no bit flips
+        let half = PauliErrorRates::from_t1_t2(10.0, f64::INFINITY, 100.0);
+        let expected = half.then(&half).then(&half).then(&half);
+        let source = network.get_node(0).unwrap();
+        let pair = source.pair(source.find_pair_with(2).unwrap()).unwrap();
+        let errors = PauliErrorRates::from_bell_diagonal(pair.density_matrix.as_ref().unwrap());
+        assert!(errors.p_x.abs() < 1e-12 && errors.p_y.abs() < 1e-12);
+        assert!((errors.p_z - expected.p_z).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_scripted_swaps_compose_into_correcting_frame() {
+        let mut network = NetworkTopology::new_linear(5, 2, 0.0, 0.0);
+        for a in 0..4 {
+            let channel = network.find_channel(a, a + 1).unwrap().1.clone();
+            let (node_a, node_b) = network.get_node_pair_mut(a, a + 1).unwrap();
+            assert!(attempt_entanglement_generation(node_a, node_b, &channel, 0.0, 100.0).unwrap());
+        }
+
+        let outcomes = [
+            PauliFrame::new(true, false),
+            PauliFrame::new(true, true),
+            PauliFrame::new(true, false),
+        ];
+        for (repeater, outcome) in (1..4).zip(outcomes) {
+            perform_entanglement_swap_with_outcome(
+                &mut network,
+                repeater,
+                0,
+                repeater + 1,
+                0.0,
+                outcome,
+            )
+            .unwrap();
+        }
+
+        let expected = PauliFrame::new(true, true);
+        let source = network.get_node(0).unwrap();
+        let handle = source.find_pair_with(4).unwrap();
+        let frame = source.pair(handle).unwrap().pauli_frame;
+        let destination = network.get_node(4).unwrap();
+        assert_eq!(frame, expected);
+        assert_eq!(destination.pair(handle).unwrap().pauli_frame, expected);
+
+        // Uncorrected, the physical pair is anticorrelated in Z; reading the
+        // far end through the frame restores perfect ZZ correlation
+        let mut physical = TwoQubitState::new_bell_phi_plus();
+        frame.apply_to(&mut physical, PairHalf::B);
+        for (index, amplitude) in physical.state.iter().enumerate() {
+            if amplitude.norm_sqr() < 1e-12 {
+                continue;
+            }
+            let (a, raw_b) = (index >= 2, index % 2 == 1);
+            assert_ne!(a, raw_b);
+            assert_eq!(a, frame.reinterpret_outcome(Basis::Z, raw_b));
+        }
+    }
+
+    #[test]
+    fn test_swap_without_pairs_fails() {
+        let mut network = NetworkTopology::new_linear(3, 2, 0.0, 0.0);
+        assert!(perform_entanglement_swap(&mut network, 1, 0, 2, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_end_nodes_refuse_to_swap() {
+        use crate::network::NodeRole;
+        let mut network = NetworkTopology::new_linear(3, 2, 0.0, 0.0);
+        for (a, b) in [(0, 1), (1, 2)] {
+            let channel = network.find_channel(a, b).unwrap().1.clone();
+            let (node_a, node_b) = network.get_node_pair_mut(a, b).unwrap();
+            attempt_entanglement_generation(node_a, node_b, &channel, 0.0, 100.0).unwrap();
+        }
+        network.get_node_mut(1).unwrap().role = NodeRole::EndNode;
+
+        let error = perform_entanglement_swap(&mut network, 1, 0, 2, 0.0).unwrap_err();
+        assert_eq!(error, "Node 1 is EndNode and cannot swap");
+        // Nothing was consumed
+        assert_eq!(network.get_node(1).unwrap().num_stored_pairs(), 2);
+    }
+
+    #[test]
+    fn test_swap_consumes_matching_halves() {
+        let mut network = NetworkTopology::new_linear(3, 4, 0.0, 0.0);
+        let left_pair = StoredPair::new_with_fidelity(1, 0.9, 0.0, 100.0);
+        let repeater_half =
+            StoredPair::new_with_fidelity(0, 0.9, 0.0, 100.0).with_pair_id(left_pair.pair_id);
+        // An unrelated better pair between 0 and 1 whose other half is gone
+        let stray = StoredPair::new_with_fidelity(1, 1.0, 0.0, 100.0);
+        let stray_id = stray.pair_id;
+        network.get_node_mut(0).unwrap().store_pair(stray).unwrap();
+        network
+            .get_node_mut(0)
+            .unwrap()
+            .store_pair(left_pair)
+            .unwrap();
+        network
+            .get_node_mut(1)
+            .unwrap()
+            .store_pair(repeater_half)
+            .unwrap();
+        let channel = network.find_channel(1, 2).unwrap().1.clone();
+        let (node_b, node_c) = network.get_node_pair_mut(1, 2).unwrap();
+        attempt_entanglement_generation(node_b, node_c, &channel, 0.0, 100.0).unwrap();
+
+        perform_entanglement_swap(&mut network, 1, 0, 2, 0.0).unwrap();
+        let left = network.get_node(0).unwrap();
+        assert!(left.find_pair_by_id(stray_id).is_some());
+        let new_pair = left.find_pair_with(2).unwrap();
+        assert!(network.get_node(2).unwrap().pair(new_pair).is_some());
+    }
+}
@@ -1,6 +1,9 @@
-use super::state::Qubit;
+use super::noise::apply_pauli_pair;
+use super::state::{Qubit, ThreeQubitState, TwoQubitState};
+use ndarray::linalg::kron;
 use ndarray::Array2;
 use num_complex::Complex64;
+use rand::Rng;
 
 /// Pauli-X gate (NOT gate)
 /// Matrix: [[0, 1],
@@ -54,20 +57,88 @@ pub fn hadamard(qubit: &mut Qubit) {
 /// Identity gate (does nothing - useful for testing)
 /// Matrix: [[1, 0],
 ///          [0, 1]]
-pub fn identity(qubit: &mut Qubit) {
+pub fn identity(_qubit: &mut Qubit) {
     // Do nothing - state remains unchanged
     // Useful for: testing, placeholder in circuits, explicit "wait"
 }
 
+/// Rotation about the X axis by `theta`
+/// Matrix: [[cos θ/2, -i sin θ/2],
+///          [-i sin θ/2, cos θ/2]]
+pub fn rx(qubit: &mut Qubit, theta: f64) {
+    apply_gate(qubit, &get_rx_matrix(theta));
+}
+
+/// Rotation about the Y axis by `theta`
+/// Matrix: [[cos θ/2, -sin θ/2],
+///          [sin θ/2,  cos θ/2]]
+pub fn ry(qubit: &mut Qubit, theta: f64) {
+    apply_gate(qubit, &get_ry_matrix(theta));
+}
+
+/// Rotation about the Z axis by `theta`
+/// Matrix: [[e^(-iθ/2), 0],
+///          [0, e^(iθ/2)]]
+pub fn rz(qubit: &mut Qubit, theta: f64) {
+    apply_gate(qubit, &get_rz_matrix(theta));
+}
+
+/// Phase gate S (√Z)
+/// Effect: |1⟩ → i|1⟩
+pub fn phase_s(qubit: &mut Qubit) {
+    qubit.state[1] *= Complex64::new(0.0, 1.0);
+}
+
+/// Inverse phase gate S†
+/// Effect: |1⟩ → -i|1⟩
+pub fn phase_s_dagger(qubit: &mut Qubit) {
+    qubit.state[1] *= Complex64::new(0.0, -1.0);
+}
+
+/// π/8 gate T (√S)
+/// Effect: |1⟩ → e^(iπ/4)|1⟩
+pub fn phase_t(qubit: &mut Qubit) {
+    qubit.state[1] *= Complex64::from_polar(1.0, std::f64::consts::FRAC_PI_4);
+}
+
 /// Generic single-qubit gate application
 /// Applies a 2x2 unitary matrix to the qubit state
 pub fn apply_gate(qubit: &mut Qubit, gate_matrix: &Array2<Complex64>) {
     assert_eq!(gate_matrix.shape(), &[2, 2], "Gate must be 2x2 matrix");
+    debug_assert_unitary(gate_matrix);
 
     let new_state = gate_matrix.dot(&qubit.state);
     qubit.state = new_state;
 }
 
+/// Apply a 2x2 gate given as rows, without constructing an ndarray matrix
+/// rows[i][j] is the matrix element in row i, column j
+pub fn apply_gate_from_rows(qubit: &mut Qubit, rows: &[[Complex64; 2]; 2]) {
+    if cfg!(debug_assertions) {
+        debug_assert_unitary(&ndarray::arr2(rows));
+    }
+    let (alpha, beta) = qubit.amplitudes();
+    qubit.state = ndarray::array![
+        rows[0][0] * alpha + rows[0][1] * beta,
+        rows[1][0] * alpha + rows[1][1] * beta,
+    ];
+}
+
+/// Helper function to create Hadamard matrix
+pub fn get_hadamard_matrix() -> Array2<Complex64> {
+    let factor = 1.0 / (2.0_f64).sqrt();
+    Array2::from_shape_vec(
+        (2, 2),
+        vec![
+            Complex64::new(factor, 0.0),
+            Complex64::new(factor, 0.0),
+            Complex64::new(factor, 0.0),
+            Complex64::new(-factor, 0.0),
+        ],
+    )
+    .unwrap()
+}
+
 /// Helper function to create Pauli-X matrix (for testing/verification)
 pub fn get_pauli_x_matrix() -> Array2<Complex64> {
     Array2::from_shape_vec(
@@ -110,6 +181,535 @@ pub fn get_pauli_z_matrix() -> Array2<Complex64> {
     .unwrap()
 }
 
+/// Generic two-qubit gate application
+/// Applies a 4x4 unitary matrix to the state vector [|00⟩, |01⟩, |10⟩, |11⟩]
+pub fn apply_two_qubit_gate(state: &mut TwoQubitState, gate_matrix: &Array2<Complex64>) {
+    assert_eq!(gate_matrix.shape(), &[4, 4], "Gate must be 4x4 matrix");
+    debug_assert_unitary(gate_matrix);
+
+    let new_state = gate_matrix.dot(&state.state);
+    state.state = new_state;
+}
+
+/// CNOT gate, qubit A controls qubit B
+/// Effect: |10⟩ ↔ |11⟩
+pub fn cnot(state: &mut TwoQubitState) {
+    apply_two_qubit_gate(state, &get_cnot_matrix());
+}
+
+/// Controlled-Z gate (symmetric in the two qubits)
+/// Effect: |11⟩ → -|11⟩
+pub fn cz(state: &mut TwoQubitState) {
+    apply_two_qubit_gate(state, &get_cz_matrix());
+}
+
+/// SWAP gate (exchanges the two qubits)
+/// Effect: |01⟩ ↔ |10⟩
+pub fn swap(state: &mut TwoQubitState) {
+    apply_two_qubit_gate(state, &get_swap_matrix());
+}
+
+/// Apply a 2x2 gate to qubit A of a pair (G⊗I)
+pub fn apply_gate_to_qubit_a(state: &mut TwoQubitState, gate_matrix: &Array2<Complex64>) {
+    assert_eq!(gate_matrix.shape(), &[2, 2], "Gate must be 2x2 matrix");
+    apply_two_qubit_gate(state, &kron(gate_matrix, &Array2::eye(2)));
+}
+
+/// Apply a 2x2 gate to qubit B of a pair (I⊗G)
+pub fn apply_gate_to_qubit_b(state: &mut TwoQubitState, gate_matrix: &Array2<Complex64>) {
+    assert_eq!(gate_matrix.shape(), &[2, 2], "Gate must be 2x2 matrix");
+    apply_two_qubit_gate(state, &kron(&Array2::eye(2), gate_matrix));
+}
+
+/// Apply a 2x2 gate to qubit `position` (0, 1 or 2) of a three-qubit state
+pub fn apply_gate_to_three_qubit(
+    state: &mut ThreeQubitState,
+    position: usize,
+    gate_matrix: &Array2<Complex64>,
+) {
+    assert_eq!(gate_matrix.shape(), &[2, 2], "Gate must be 2x2 matrix");
+    assert!(position < 3, "Qubit position must be 0-2, got {}", position);
+    let identity = Array2::eye(2);
+    let factors: Vec<&Array2<Complex64>> = (0..3)
+        .map(|index| {
+            if index == position {
+                gate_matrix
+            } else {
+                &identity
+            }
+        })
+        .collect();
+    let full = kron(&kron(factors[0], factors[1]), factors[2]);
+    state.state = full.dot(&state.state);
+}
+
+/// Pauli-X on qubit A of a pair
+pub fn pauli_x_on_a(state: &mut TwoQubitState) {
+    apply_gate_to_qubit_a(state, &get_pauli_x_matrix());
+}
+
+/// Pauli-X on qubit B of a pair
+pub fn pauli_x_on_b(state: &mut TwoQubitState) {
+    apply_gate_to_qubit_b(state, &get_pauli_x_matrix());
+}
+
+/// Pauli-Y on qubit A of a pair
+pub fn pauli_y_on_a(state: &mut TwoQubitState) {
+    apply_gate_to_qubit_a(state, &get_pauli_y_matrix());
+}
+
+/// Pauli-Y on qubit B of a pair
+pub fn pauli_y_on_b(state: &mut TwoQubitState) {
+    apply_gate_to_qubit_b(state, &get_pauli_y_matrix());
+}
+
+/// Pauli-Z on qubit A of a pair
+pub fn pauli_z_on_a(state: &mut TwoQubitState) {
+    apply_gate_to_qubit_a(state, &get_pauli_z_matrix());
+}
+
+/// Pauli-Z on qubit B of a pair
+pub fn pauli_z_on_b(state: &mut TwoQubitState) {
+    apply_gate_to_qubit_b(state, &get_pauli_z_matrix());
+}
+
+/// Hadamard on qubit A of a pair
+pub fn hadamard_on_a(state: &mut TwoQubitState) {
+    apply_gate_to_qubit_a(state, &get_hadamard_matrix());
+}
+
+/// Hadamard on qubit B of a pair
+pub fn hadamard_on_b(state: &mut TwoQubitState) {
+    apply_gate_to_qubit_b(state, &get_hadamard_matrix());
+}
+
+/// 4x4 matrix whose entry in row i, column j is 1 if j == column_of[i]
+fn permutation_matrix(column_of: [usize; 4]) -> Array2<Complex64> {
+    Array2::from_shape_fn((4, 4), |(row, column)| {
+        if column_of[row] == column {
+            Complex64::new(1.0, 0.0)
+        } else {
+            Complex64::new(0.0, 0.0)
+        }
+    })
+}
+
+/// Helper function to create the CNOT matrix (qubit A controls)
+pub fn get_cnot_matrix() -> Array2<Complex64> {
+    permutation_matrix([0, 1, 3, 2])
+}
+
+/// Helper function to create the CZ matrix
+pub fn get_cz_matrix() -> Array2<Complex64> {
+    let mut matrix = permutation_matrix([0, 1, 2, 3]);
+    matrix[[3, 3]] = Complex64::new(-1.0, 0.0);
+    matrix
+}
+
+/// Helper function to create the SWAP matrix
+pub fn get_swap_matrix() -> Array2<Complex64> {
+    permutation_matrix([0, 2, 1, 3])
+}
+
+/// Tolerance on ‖U†U - I‖ used by the gate-application checks
+pub const UNITARITY_TOLERANCE: f64 = 1e-10;
+
+/// Frobenius norm of U†U - I; zero for a unitary matrix
+pub fn unitarity_deviation(matrix: &Array2<Complex64>) -> f64 {
+    let product = matrix.t().mapv(|x| x.conj()).dot(matrix);
+    product
+        .indexed_iter()
+        .map(|((row, column), value)| {
+            let expected = if row == column { 1.0 } else { 0.0 };
+            (value - Complex64::new(expected, 0.0)).norm_sqr()
+        })
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// U†U = I within `tol` (Frobenius norm of the difference)
+pub fn is_unitary(matrix: &Array2<Complex64>, tol: f64) -> bool {
+    matrix.is_square() && unitarity_deviation(matrix) <= tol
+}
+
+/// Process fidelity |tr(A†B)|²/d² of two d×d unitaries
+///
+/// 1 when they agree up to global phase, 0 when orthogonal (e.g. two
+/// different Paulis)
+pub fn process_fidelity(a: &Array2<Complex64>, b: &Array2<Complex64>) -> f64 {
+    assert_eq!(a.shape(), b.shape(), "Matrices must have the same shape");
+    let dimension = a.nrows() as f64;
+    let overlap: Complex64 = a.iter().zip(b.iter()).map(|(x, y)| x.conj() * y).sum();
+    overlap.norm_sqr() / (dimension * dimension)
+}
+
+fn debug_assert_unitary(matrix: &Array2<Complex64>) {
+    debug_assert!(
+        is_unitary(matrix, UNITARITY_TOLERANCE),
+        "Gate matrix is not unitary: ‖U†U - I‖ = {:.3e}",
+        unitarity_deviation(matrix)
+    );
+}
+
+/// Helper function to create the Rx(θ) matrix
+pub fn get_rx_matrix(theta: f64) -> Array2<Complex64> {
+    let (sin, cos) = (theta / 2.0).sin_cos();
+    ndarray::array![
+        [Complex64::new(cos, 0.0), Complex64::new(0.0, -sin)],
+        [Complex64::new(0.0, -sin), Complex64::new(cos, 0.0)],
+    ]
+}
+
+/// Helper function to create the Ry(θ) matrix
+pub fn get_ry_matrix(theta: f64) -> Array2<Complex64> {
+    let (sin, cos) = (theta / 2.0).sin_cos();
+    ndarray::array![
+        [Complex64::new(cos, 0.0), Complex64::new(-sin, 0.0)],
+        [Complex64::new(sin, 0.0), Complex64::new(cos, 0.0)],
+    ]
+}
+
+/// Helper function to create the Rz(θ) matrix
+pub fn get_rz_matrix(theta: f64) -> Array2<Complex64> {
+    ndarray::array![
+        [
+            Complex64::from_polar(1.0, -theta / 2.0),
+            Complex64::new(0.0, 0.0)
+        ],
+        [
+            Complex64::new(0.0, 0.0),
+            Complex64::from_polar(1.0, theta / 2.0)
+        ],
+    ]
+}
+
+/// Helper function to create the S matrix
+pub fn get_s_matrix() -> Array2<Complex64> {
+    get_phase_matrix(std::f64::consts::FRAC_PI_2)
+}
+
+/// Helper function to create the S† matrix
+pub fn get_s_dagger_matrix() -> Array2<Complex64> {
+    get_phase_matrix(-std::f64::consts::FRAC_PI_2)
+}
+
+/// Helper function to create the T matrix
+pub fn get_t_matrix() -> Array2<Complex64> {
+    get_phase_matrix(std::f64::consts::FRAC_PI_4)
+}
+
+/// diag(1, e^(iφ))
+fn get_phase_matrix(phi: f64) -> Array2<Complex64> {
+    ndarray::array![
+        [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)],
+        [Complex64::new(0.0, 0.0), Complex64::from_polar(1.0, phi)],
+    ]
+}
+
+/// Haar-random 2x2 unitary
+///
+/// A Haar-random first column (α, β) fixes [[α, -β*], [β, α*]] in SU(2);
+/// a uniform global phase extends it to U(2)
+pub fn random_unitary_2x2(rng: &mut impl Rng) -> Array2<Complex64> {
+    let (alpha, beta) = Qubit::new_random_with_rng(rng).amplitudes();
+    let phase = Complex64::from_polar(1.0, rng.random::<f64>() * 2.0 * std::f64::consts::PI);
+    ndarray::array![[alpha, -beta.conj()], [beta, alpha.conj()]].mapv(|x| x * phase)
+}
+
+/// Uniformly random Pauli: I, X, Y or Z
+pub fn random_pauli(rng: &mut impl Rng) -> Array2<Complex64> {
+    match rng.random_range(0..4) {
+        0 => Array2::eye(2),
+        1 => get_pauli_x_matrix(),
+        2 => get_pauli_y_matrix(),
+        _ => get_pauli_z_matrix(),
+    }
+}
+
+/// The 24 single-qubit Cliffords, one representative per global phase
+///
+/// Generated by closing {H, S} under multiplication, so the order is fixed
+/// and starts with the identity
+pub fn clifford_group_1q() -> Vec<Array2<Complex64>> {
+    let generators = [get_hadamard_matrix(), get_s_matrix()];
+    let mut group: Vec<Array2<Complex64>> = vec![Array2::eye(2)];
+    let mut next = 0;
+    while next < group.len() {
+        for generator in &generators {
+            let product = generator.dot(&group[next]);
+            if !group
+                .iter()
+                .any(|element| equal_up_to_phase(element, &product))
+            {
+                group.push(product);
+            }
+        }
+        next += 1;
+    }
+    debug_assert_eq!(group.len(), 24);
+    group
+}
+
+/// Uniformly random element of the single-qubit Clifford group
+pub fn random_clifford_1q(rng: &mut impl Rng) -> Array2<Complex64> {
+    let mut group = clifford_group_1q();
+    group.swap_remove(rng.random_range(0..group.len()))
+}
+
+/// Apply `gate`, then with probability `error_rate` a uniformly random
+/// Pauli (I, X, Y or Z)
+///
+/// Averaged over trajectories this is the depolarizing channel, so the
+/// mean fidelity with the ideal output is 1 - error_rate/2
+pub fn apply_noisy_gate(
+    qubit: &mut Qubit,
+    gate: &Array2<Complex64>,
+    error_rate: f64,
+    rng: &mut impl Rng,
+) {
+    apply_gate(qubit, gate);
+    if rng.random::<f64>() < error_rate {
+        apply_gate(qubit, &random_pauli(rng));
+    }
+}
+
+/// Apply the 4x4 `gate`, then with probability `error_rate` a uniformly
+/// random two-qubit Pauli (one of the 16 products, including I⊗I)
+pub fn apply_noisy_two_qubit_gate(
+    state: &mut TwoQubitState,
+    gate: &Array2<Complex64>,
+    error_rate: f64,
+    rng: &mut impl Rng,
+) {
+    apply_two_qubit_gate(state, gate);
+    if rng.random::<f64>() < error_rate {
+        let (pauli_a, pauli_b) = (rng.random_range(0..4), rng.random_range(0..4));
+        apply_pauli_pair(state, pauli_a, pauli_b);
+    }
+}
+
+/// Per-gate error probabilities of a node's gate set
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GateErrorConfig {
+    /// Depolarizing probability after each single-qubit gate
+    pub single_qubit_error: f64,
+
+    /// Depolarizing probability after each two-qubit gate
+    pub two_qubit_error: f64,
+}
+
+impl GateErrorConfig {
+    /// Perfect gates
+    pub fn ideal() -> Self {
+        Self::default()
+    }
+
+    /// `apply_noisy_gate` with this config's single-qubit error
+    pub fn apply_gate(&self, qubit: &mut Qubit, gate: &Array2<Complex64>, rng: &mut impl Rng) {
+        apply_noisy_gate(qubit, gate, self.single_qubit_error, rng);
+    }
+
+    /// `apply_noisy_two_qubit_gate` with this config's two-qubit error
+    pub fn apply_two_qubit_gate(
+        &self,
+        state: &mut TwoQubitState,
+        gate: &Array2<Complex64>,
+        rng: &mut impl Rng,
+    ) {
+        apply_noisy_two_qubit_gate(state, gate, self.two_qubit_error, rng);
+    }
+
+    /// Check both error rates are probabilities in [0, 1]
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("Single-qubit gate error", self.single_qubit_error),
+            ("Two-qubit gate error", self.two_qubit_error),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} must be in [0, 1], got {}", name, value));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// |Tr(A†B)| = 2: the unitaries differ only by a global phase
+fn equal_up_to_phase(a: &Array2<Complex64>, b: &Array2<Complex64>) -> bool {
+    let overlap: Complex64 = a.iter().zip(b.iter()).map(|(x, y)| x.conj() * y).sum();
+    (overlap.norm() - 2.0).abs() < 1e-10
+}
+
+/// A named gate in a circuit
+#[derive(Debug, Clone)]
+pub struct CircuitGate {
+    pub name: String,
+    pub matrix: Array2<Complex64>,
+}
+
+/// Ordered sequence of single-qubit gates
+#[derive(Debug, Clone, Default)]
+pub struct Circuit {
+    pub gates: Vec<CircuitGate>,
+}
+
+impl Circuit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an arbitrary 2x2 gate
+    pub fn push(mut self, name: &str, matrix: Array2<Complex64>) -> Self {
+        assert_eq!(matrix.shape(), &[2, 2], "Gate must be 2x2 matrix");
+        self.gates.push(CircuitGate {
+            name: name.to_string(),
+            matrix,
+        });
+        self
+    }
+
+    /// Append a Hadamard gate
+    pub fn h(self) -> Self {
+        self.push("H", get_hadamard_matrix())
+    }
+
+    /// Append a Pauli-X gate
+    pub fn x(self) -> Self {
+        self.push("X", get_pauli_x_matrix())
+    }
+
+    /// Append a Pauli-Y gate
+    pub fn y(self) -> Self {
+        self.push("Y", get_pauli_y_matrix())
+    }
+
+    /// Append a Pauli-Z gate
+    pub fn z(self) -> Self {
+        self.push("Z", get_pauli_z_matrix())
+    }
+
+    /// Append an S gate
+    pub fn s(self) -> Self {
+        self.push("S", get_s_matrix())
+    }
+
+    /// Append a T gate
+    pub fn t(self) -> Self {
+        self.push("T", get_t_matrix())
+    }
+
+    /// Append an X rotation by `theta`
+    pub fn rx(self, theta: f64) -> Self {
+        self.push("Rx", get_rx_matrix(theta))
+    }
+
+    /// Append a Y rotation by `theta`
+    pub fn ry(self, theta: f64) -> Self {
+        self.push("Ry", get_ry_matrix(theta))
+    }
+
+    /// Append a Z rotation by `theta`
+    pub fn rz(self, theta: f64) -> Self {
+        self.push("Rz", get_rz_matrix(theta))
+    }
+
+    /// Number of gates in the circuit
+    pub fn len(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// Check if the circuit has no gates
+    pub fn is_empty(&self) -> bool {
+        self.gates.is_empty()
+    }
+
+    /// Whether every gate is unitary (U†U = I within 1e-10)
+    pub fn is_unitary(&self) -> bool {
+        self.gates
+            .iter()
+            .all(|gate| is_unitary(&gate.matrix, UNITARITY_TOLERANCE))
+    }
+
+    /// Circuit undoing this one: the adjoints of the gates in reverse order
+    pub fn inverse(&self) -> Self {
+        Circuit {
+            gates: self
+                .gates
+                .iter()
+                .rev()
+                .map(|gate| CircuitGate {
+                    name: format!("{}†", gate.name),
+                    matrix: gate.matrix.t().mapv(|x| x.conj()),
+                })
+                .collect(),
+        }
+    }
+
+    /// Multiply the gates into one 2x2 unitary
+    /// Applying the result costs one matrix-vector product however long the circuit
+    pub fn compile(&self) -> CompiledCircuit {
+        let matrix = self
+            .gates
+            .iter()
+            .fold(Array2::eye(2), |product, gate| gate.matrix.dot(&product));
+        debug_assert_unitary(&matrix);
+        CompiledCircuit {
+            rows: [
+                [matrix[[0, 0]], matrix[[0, 1]]],
+                [matrix[[1, 0]], matrix[[1, 1]]],
+            ],
+        }
+    }
+
+    /// Apply every gate in order
+    pub fn apply(&self, qubit: &mut Qubit) {
+        self.apply_with(qubit, |_, _| {});
+    }
+
+    /// Apply every gate in order, calling `observer` with the gate name and state after each one
+    pub fn apply_with<F>(&self, qubit: &mut Qubit, mut observer: F)
+    where
+        F: FnMut(&str, &Qubit),
+    {
+        for gate in &self.gates {
+            apply_gate(qubit, &gate.matrix);
+            observer(&gate.name, qubit);
+        }
+    }
+}
+
+/// A circuit fused into a single 2x2 unitary by `Circuit::compile`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompiledCircuit {
+    /// rows[i][j] is the matrix element in row i, column j
+    pub rows: [[Complex64; 2]; 2],
+}
+
+impl CompiledCircuit {
+    /// The fused unitary as a matrix
+    pub fn matrix(&self) -> Array2<Complex64> {
+        Array2::from_shape_fn((2, 2), |(row, column)| self.rows[row][column])
+    }
+
+    /// U†U = I within 1e-10
+    pub fn is_unitary(&self) -> bool {
+        is_unitary(&self.matrix(), UNITARITY_TOLERANCE)
+    }
+
+    /// The adjoint U†
+    pub fn inverse(&self) -> Self {
+        let [[a, b], [c, d]] = self.rows;
+        CompiledCircuit {
+            rows: [[a.conj(), c.conj()], [b.conj(), d.conj()]],
+        }
+    }
+
+    /// Apply the fused unitary
+    pub fn apply(&self, qubit: &mut Qubit) {
+        apply_gate_from_rows(qubit, &self.rows);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,14 +745,13 @@ mod tests {
     #[test]
     fn test_pauli_x_twice_is_identity() {
         let mut qubit = Qubit::new_zero();
-        let original_state = qubit.state.clone();
+        let original = qubit.clone();
 
         pauli_x(&mut qubit);
         pauli_x(&mut qubit);
 
         // X² = I (applying X twice returns to original state)
-        assert!((qubit.state[0] - original_state[0]).norm() < 1e-10);
-        assert!((qubit.state[1] - original_state[1]).norm() < 1e-10);
+        assert!(qubit.approx_eq(&original, 1e-10));
     }
 
     #[test]
@@ -176,13 +775,11 @@ mod tests {
     #[test]
     fn test_pauli_z_on_zero() {
         let mut qubit = Qubit::new_zero();
-        let original_state = qubit.state.clone();
 
         pauli_z(&mut qubit);
 
         // Z|0⟩ = |0⟩ (unchanged)
-        assert!((qubit.state[0] - original_state[0]).norm() < 1e-10);
-        assert!((qubit.state[1] - original_state[1]).norm() < 1e-10);
+        assert!(qubit.approx_eq(&Qubit::new_zero(), 1e-10));
     }
 
     #[test]
@@ -224,14 +821,12 @@ mod tests {
     #[test]
     fn test_hadamard_twice_is_identity() {
         let mut qubit = Qubit::new_zero();
-        let original_state = qubit.state.clone();
 
         hadamard(&mut qubit);
         hadamard(&mut qubit);
 
         // H² = I
-        assert!((qubit.state[0] - original_state[0]).norm() < 1e-10);
-        assert!((qubit.state[1] - original_state[1]).norm() < 1e-10);
+        assert!(qubit.approx_eq(&Qubit::new_zero(), 1e-10));
     }
 
     #[test]
@@ -256,6 +851,316 @@ mod tests {
         assert!((qubit.prob_one() - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_gate_from_rows_matches_ndarray_path() {
+        let i = Complex64::new(0.0, 1.0);
+        let rows = [
+            [Complex64::new(0.6, 0.0), Complex64::new(0.0, 0.8)],
+            [Complex64::new(0.0, 0.8), Complex64::new(0.6, 0.0)],
+        ];
+        let matrix =
+            Array2::from_shape_vec((2, 2), vec![rows[0][0], rows[0][1], rows[1][0], rows[1][1]])
+                .unwrap();
+
+        let mut via_rows = Qubit::from_amplitudes(Complex64::new(0.6, 0.0), 0.8 * i);
+        let mut via_ndarray = via_rows.clone();
+
+        apply_gate_from_rows(&mut via_rows, &rows);
+        apply_gate(&mut via_ndarray, &matrix);
+
+        assert!(via_rows.approx_eq(&via_ndarray, 1e-12));
+    }
+
+    #[test]
+    fn test_circuit_hzh_is_x() {
+        let circuit = Circuit::new().h().z().h();
+        assert_eq!(circuit.len(), 3);
+
+        let mut qubit = Qubit::new_zero();
+        circuit.apply(&mut qubit);
+
+        // HZH|0⟩ = |1⟩
+        assert!((qubit.prob_one() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_random_unitaries_are_unitary() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(778);
+        for _ in 0..200 {
+            let unitary = random_unitary_2x2(&mut rng);
+            assert!(is_unitary(&unitary, UNITARITY_TOLERANCE));
+            let mut qubit = Qubit::new_random_with_rng(&mut rng);
+            apply_gate(&mut qubit, &unitary);
+            assert!(qubit.is_normalized());
+
+            assert!(is_unitary(&random_pauli(&mut rng), UNITARITY_TOLERANCE));
+            assert!(is_unitary(
+                &random_clifford_1q(&mut rng),
+                UNITARITY_TOLERANCE
+            ));
+        }
+
+        // Haar: |0⟩ lands anywhere, so P(0) after U averages 1/2
+        let mean_prob_zero: f64 = (0..4000)
+            .map(|_| {
+                let mut qubit = Qubit::new_zero();
+                apply_gate(&mut qubit, &random_unitary_2x2(&mut rng));
+                qubit.prob_zero()
+            })
+            .sum::<f64>()
+            / 4000.0;
+        assert!((mean_prob_zero - 0.5).abs() < 0.02, "{}", mean_prob_zero);
+    }
+
+    #[test]
+    fn test_clifford_sampling_covers_group() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(24);
+        let group = clifford_group_1q();
+        assert_eq!(group.len(), 24);
+
+        let mut hits = [0usize; 24];
+        for _ in 0..2400 {
+            let clifford = random_clifford_1q(&mut rng);
+            let index = group
+                .iter()
+                .position(|element| equal_up_to_phase(element, &clifford))
+                .unwrap();
+            hits[index] += 1;
+        }
+        assert!(hits.iter().all(|&count| count > 50), "{:?}", hits);
+
+        // Cliffords map Paulis to Paulis
+        let paulis = [
+            get_pauli_x_matrix(),
+            get_pauli_y_matrix(),
+            get_pauli_z_matrix(),
+        ];
+        for clifford in &group {
+            let adjoint = clifford.t().mapv(|x| x.conj());
+            for pauli in &paulis {
+                let conjugated = clifford.dot(pauli).dot(&adjoint);
+                assert!(paulis.iter().any(|p| equal_up_to_phase(p, &conjugated)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compiled_circuit_matches_sequential() {
+        let compiled = Circuit::new().h().z().h().compile();
+        let mut qubit = Qubit::new_zero();
+        compiled.apply(&mut qubit);
+        assert!(qubit.approx_eq(&Qubit::new_one(), 1e-12));
+
+        let circuit = Circuit::new().h().t().rx(0.3).s().ry(-1.2).rz(2.5).y();
+        assert!(circuit.is_unitary());
+        let compiled = circuit.compile();
+        assert!(compiled.is_unitary());
+        let mut sequential = Qubit::from_bloch_angles(0.9, 2.1);
+        let mut fused = sequential.clone();
+        circuit.apply(&mut sequential);
+        compiled.apply(&mut fused);
+        assert!((&sequential.state - &fused.state)
+            .iter()
+            .all(|x| x.norm() < 1e-12));
+
+        // Both inverses undo the circuit
+        let mut undone = sequential.clone();
+        circuit.inverse().apply(&mut undone);
+        assert!(undone.approx_eq(&Qubit::from_bloch_angles(0.9, 2.1), 1e-12));
+        compiled.inverse().apply(&mut sequential);
+        assert!(sequential.approx_eq(&Qubit::from_bloch_angles(0.9, 2.1), 1e-12));
+        assert_eq!(circuit.inverse().gates[0].name, "Y†");
+
+        let broken = Circuit::new().push("2X", get_pauli_x_matrix().mapv(|x| x * 2.0));
+        assert!(!broken.is_unitary());
+    }
+
+    #[test]
+    fn test_h_then_cnot_makes_bell_pair() {
+        let h_on_a = kron(&get_hadamard_matrix(), &Array2::eye(2));
+        let mut state = TwoQubitState::new_zero_zero();
+
+        apply_two_qubit_gate(&mut state, &h_on_a);
+        cnot(&mut state);
+
+        assert!(state.is_normalized());
+        assert!(state.approx_eq(&TwoQubitState::new_bell_phi_plus(), 1e-12));
+    }
+
+    #[test]
+    fn test_two_qubit_gates_on_basis_states() {
+        let basis = |index: usize| TwoQubitState {
+            state: ndarray::Array1::from_shape_fn(4, |i| {
+                Complex64::new(if i == index { 1.0 } else { 0.0 }, 0.0)
+            }),
+        };
+        // Basis index each input index is mapped to
+        let gates: [(fn(&mut TwoQubitState), _); 3] = [
+            (cnot, [0, 1, 3, 2]),
+            (cz, [0, 1, 2, 3]),
+            (swap, [0, 2, 1, 3]),
+        ];
+        for (gate, maps_to) in gates {
+            for (input, output) in maps_to.into_iter().enumerate() {
+                let mut state = basis(input);
+                gate(&mut state);
+                assert!(state.approx_eq(&basis(output), 1e-12));
+            }
+        }
+
+        // CZ only changes the phase of |11⟩
+        let mut state = basis(3);
+        cz(&mut state);
+        assert!((state.state[3].re + 1.0).abs() < 1e-12);
+
+        // CNOT twice is the identity
+        let mut state = TwoQubitState::new_bell_phi_plus();
+        cnot(&mut state);
+        cnot(&mut state);
+        assert!(state.approx_eq(&TwoQubitState::new_bell_phi_plus(), 1e-12));
+    }
+
+    #[test]
+    fn test_x_on_b_maps_phi_plus_to_psi_plus() {
+        let psi_plus = TwoQubitState::new_bell_psi_plus();
+        let mut state = TwoQubitState::new_bell_phi_plus();
+        pauli_x_on_b(&mut state);
+        assert!(state.approx_eq(&psi_plus, 1e-12));
+
+        // X on either half of |Φ+⟩ gives the same state
+        let mut on_a = TwoQubitState::new_bell_phi_plus();
+        pauli_x_on_a(&mut on_a);
+        assert!(on_a.approx_eq(&psi_plus, 1e-12));
+    }
+
+    #[test]
+    fn test_single_qubit_gate_acts_on_one_half_only() {
+        // H on B of |00⟩ is |0+⟩; H on A is |+0⟩
+        let (zero, plus) = (Qubit::new_zero(), Qubit::new_plus());
+        let mut on_b = TwoQubitState::new_zero_zero();
+        hadamard_on_b(&mut on_b);
+        assert!(on_b.approx_eq(&TwoQubitState::from_qubits(&zero, &plus), 1e-12));
+        let mut on_a = TwoQubitState::new_zero_zero();
+        hadamard_on_a(&mut on_a);
+        assert!(on_a.approx_eq(&TwoQubitState::from_qubits(&plus, &zero), 1e-12));
+
+        // Matches the Pauli-pair implementation used by the noise models
+        for (gate_a, gate_b, pauli) in [
+            (
+                pauli_y_on_a as fn(&mut TwoQubitState),
+                pauli_y_on_b as fn(&mut TwoQubitState),
+                2,
+            ),
+            (pauli_z_on_a, pauli_z_on_b, 3),
+        ] {
+            let mut expected = TwoQubitState::new_bell_phi_plus();
+            hadamard_on_a(&mut expected);
+            let mut actual = expected.clone();
+            crate::quantum::noise::apply_pauli_pair(&mut expected, pauli, pauli);
+            gate_a(&mut actual);
+            gate_b(&mut actual);
+            for (x, y) in actual.state.iter().zip(&expected.state) {
+                assert!((x - y).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Gate must be 4x4 matrix")]
+    fn test_two_qubit_gate_rejects_wrong_shape() {
+        apply_two_qubit_gate(&mut TwoQubitState::new_zero_zero(), &get_pauli_x_matrix());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not unitary")]
+    fn test_two_qubit_gate_rejects_non_unitary() {
+        let mut matrix = get_cnot_matrix();
+        matrix[[0, 0]] = Complex64::new(2.0, 0.0);
+        apply_two_qubit_gate(&mut TwoQubitState::new_zero_zero(), &matrix);
+    }
+
+    /// a = e^(iφ) b for some global phase φ
+    fn equal_up_to_global_phase(a: &Array2<Complex64>, b: &Array2<Complex64>) -> bool {
+        let (index, pivot) = b
+            .indexed_iter()
+            .max_by(|x, y| x.1.norm().total_cmp(&y.1.norm()))
+            .unwrap();
+        let phase = a[index] / pivot;
+        (phase.norm() - 1.0).abs() < 1e-10
+            && a.iter().zip(b).all(|(x, y)| (x - phase * y).norm() < 1e-10)
+    }
+
+    #[test]
+    fn test_rotations_by_pi_are_paulis() {
+        use std::f64::consts::PI;
+        assert!(equal_up_to_global_phase(
+            &get_rx_matrix(PI),
+            &get_pauli_x_matrix()
+        ));
+        assert!(equal_up_to_global_phase(
+            &get_ry_matrix(PI),
+            &get_pauli_y_matrix()
+        ));
+        assert!(equal_up_to_global_phase(
+            &get_rz_matrix(PI),
+            &get_pauli_z_matrix()
+        ));
+        assert!(!equal_up_to_global_phase(
+            &get_rx_matrix(PI / 2.0),
+            &get_pauli_x_matrix()
+        ));
+
+        // Rx(π) acts like X on states, up to a global phase of -i
+        let mut rotated = Qubit::from_bloch_angles(1.1, 0.4);
+        let mut flipped = rotated.clone();
+        rx(&mut rotated, PI);
+        pauli_x(&mut flipped);
+        assert!(rotated.approx_eq(&flipped, 1e-10));
+        assert!((rotated.state[0] - flipped.state[0]).norm() > 0.1);
+
+        // Ry(π/2)|0⟩ = |+⟩
+        let mut qubit = Qubit::new_zero();
+        ry(&mut qubit, PI / 2.0);
+        assert!(qubit.approx_eq(&Qubit::new_plus(), 1e-10));
+        // Rz leaves populations alone
+        rz(&mut qubit, 0.7);
+        rx(&mut qubit, 0.0);
+        assert!((qubit.prob_zero() - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_phase_gates() {
+        let s = get_s_matrix();
+        let t = get_t_matrix();
+        assert!(equal_up_to_global_phase(&s.dot(&s), &get_pauli_z_matrix()));
+        assert!(equal_up_to_global_phase(&t.dot(&t), &s));
+        assert!(equal_up_to_global_phase(
+            &s.dot(&get_s_dagger_matrix()),
+            &Array2::eye(2)
+        ));
+
+        // The in-place gates match their matrices
+        for (gate, matrix) in [
+            (phase_s as fn(&mut Qubit), s),
+            (phase_s_dagger, get_s_dagger_matrix()),
+            (phase_t, t),
+        ] {
+            let mut in_place = Qubit::new_plus();
+            let mut via_matrix = Qubit::new_plus();
+            gate(&mut in_place);
+            apply_gate(&mut via_matrix, &matrix);
+            assert!(in_place.approx_eq(&via_matrix, 1e-12));
+        }
+
+        // S|+⟩ = |+i⟩
+        let mut qubit = Qubit::new_plus();
+        phase_s(&mut qubit);
+        assert!(qubit.approx_eq(&Qubit::new_iplus(), 1e-10));
+    }
+
     #[test]
     fn test_pauli_matrices_are_unitary() {
         let matrices = vec![
@@ -276,4 +1181,109 @@ mod tests {
             assert!(product[[1, 0]].norm() < 1e-10);
         }
     }
+
+    #[test]
+    fn test_noisy_identity_matches_depolarizing_fidelity() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(783);
+        let p = 0.2;
+        let trials = 20_000;
+        let identity = Array2::eye(2);
+
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let ideal = Qubit::new_random_with_rng(&mut rng);
+            let mut qubit = ideal.clone();
+            apply_noisy_gate(&mut qubit, &identity, p, &mut rng);
+            assert!(qubit.is_normalized());
+            total += qubit.fidelity(&ideal);
+        }
+        // Standard error ≈ √(p/2 · (1 - p/2) / trials) ≈ 0.002
+        let average = total / trials as f64;
+        assert!((average - (1.0 - p / 2.0)).abs() < 0.01, "{}", average);
+    }
+
+    #[test]
+    fn test_noisy_cnot_fidelity() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7830);
+        let config = GateErrorConfig {
+            single_qubit_error: 0.0,
+            two_qubit_error: 0.1,
+        };
+        assert!(config.validate().is_ok());
+        let trials = 20_000;
+
+        // CNOT on |+0⟩ gives |Φ+⟩, which is stabilized by 4 of the 16 Paulis
+        // (II, XX, YY and ZZ up to phase)
+        let plus_zero = TwoQubitState::from_qubits(&Qubit::new_plus(), &Qubit::new_zero());
+        let bell = TwoQubitState::new_bell_phi_plus();
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let mut state = plus_zero.clone();
+            config.apply_two_qubit_gate(&mut state, &get_cnot_matrix(), &mut rng);
+            total += state.fidelity(&bell);
+        }
+        let expected = 1.0 - 12.0 * config.two_qubit_error / 16.0;
+        assert!((total / trials as f64 - expected).abs() < 0.01);
+
+        // Ideal config applies the bare gate
+        let mut qubit = Qubit::new_zero();
+        GateErrorConfig::ideal().apply_gate(&mut qubit, &get_hadamard_matrix(), &mut rng);
+        assert!(qubit.approx_eq(&Qubit::new_plus(), 1e-12));
+        let invalid = GateErrorConfig {
+            two_qubit_error: 1.5,
+            ..GateErrorConfig::ideal()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_unitarity_check() {
+        for pauli in [
+            get_pauli_x_matrix(),
+            get_pauli_y_matrix(),
+            get_pauli_z_matrix(),
+        ] {
+            assert!(is_unitary(&pauli, UNITARITY_TOLERANCE));
+        }
+        assert!(is_unitary(&get_cnot_matrix(), UNITARITY_TOLERANCE));
+
+        // 1.01·X: U†U = 1.0201·I, off by 0.0201 on each diagonal entry
+        let scaled = get_pauli_x_matrix() * Complex64::new(1.01, 0.0);
+        assert!(!is_unitary(&scaled, UNITARITY_TOLERANCE));
+        assert!((unitarity_deviation(&scaled) - 0.0201 * 2.0_f64.sqrt()).abs() < 1e-12);
+        assert!(is_unitary(&scaled, 0.05));
+        assert!(!is_unitary(&Array2::zeros((2, 3)), 1.0));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not unitary")]
+    fn test_apply_gate_rejects_non_unitary() {
+        let scaled = get_hadamard_matrix() * Complex64::new(2.0, 0.0);
+        apply_gate(&mut Qubit::new_zero(), &scaled);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not unitary")]
+    fn test_apply_gate_from_rows_rejects_non_unitary() {
+        let one = Complex64::new(1.0, 0.0);
+        apply_gate_from_rows(&mut Qubit::new_zero(), &[[one, one], [one, one]]);
+    }
+
+    #[test]
+    fn test_process_fidelity() {
+        use std::f64::consts::PI;
+        // Rz(π) = -iZ: equal to Z up to global phase
+        assert!((process_fidelity(&get_rz_matrix(PI), &get_pauli_z_matrix()) - 1.0).abs() < 1e-12);
+        for theta in [0.0, 0.4, 1.3, 2.9] {
+            let fidelity = process_fidelity(&get_rz_matrix(theta), &get_pauli_z_matrix());
+            let expected = ((theta - PI) / 2.0).cos().powi(2);
+            assert!((fidelity - expected).abs() < 1e-12, "θ = {}", theta);
+        }
+        assert!(process_fidelity(&get_pauli_x_matrix(), &get_pauli_z_matrix()).abs() < 1e-12);
+        assert!((process_fidelity(&get_cnot_matrix(), &get_cnot_matrix()) - 1.0).abs() < 1e-12);
+    }
 }
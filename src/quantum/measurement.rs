@@ -1,13 +1,85 @@
-use super::state::Qubit;
+use super::gates::{apply_gate_to_qubit_a, apply_gate_to_qubit_b, get_s_dagger_matrix};
+use super::pauli_frame::PairHalf;
+use super::state::{BellState, Qubit, ThreeQubitState, TwoQubitState};
+use crate::simulation::unseeded_rng;
+use ndarray::{array, Array1};
 use num_complex::Complex64;
 use rand::Rng;
 
+/// Single-qubit measurement basis, as chosen by QKD parties
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Basis {
+    /// Computational basis; flipped by X and Y errors
+    Z,
+    /// Hadamard basis; flipped by Z and Y errors
+    X,
+    /// Circular basis; flipped by X and Z errors
+    Y,
+    /// Along the Bloch direction (sinθ cosφ, sinθ sinφ, cosθ)
+    Custom { theta: f64, phi: f64 },
+}
+
+impl Basis {
+    /// Uniformly random choice of Z or X, the BBM92 bases
+    pub fn random(rng: &mut impl Rng) -> Self {
+        if rng.random::<bool>() {
+            Basis::X
+        } else {
+            Basis::Z
+        }
+    }
+}
+
+/// Ideal measurement of `qubit` in `basis`
+/// Returns true for the -1 eigenstate (|1⟩, |-⟩, |-i⟩, or the antipode of the direction)
+pub fn measure(qubit: &mut Qubit, basis: Basis) -> bool {
+    measure_with_rng(qubit, basis, &mut unseeded_rng("measure"))
+}
+
+/// `measure` drawing from the caller's RNG
+pub fn measure_with_rng(qubit: &mut Qubit, basis: Basis, rng: &mut impl Rng) -> bool {
+    match basis {
+        Basis::Z => measure_z_with_rng(qubit, rng),
+        Basis::X => measure_x_with_rng(qubit, rng),
+        Basis::Y => measure_y_with_rng(qubit, rng),
+        Basis::Custom { theta, phi } => measure_in_basis_with_rng(qubit, theta, phi, rng),
+    }
+}
+
+/// Measure along the Bloch direction (sinθ cosφ, sinθ sinφ, cosθ)
+///
+/// Returns true for the eigenstate pointing away from the direction; the
+/// qubit collapses onto the measured eigenstate
+pub fn measure_in_basis(qubit: &mut Qubit, theta: f64, phi: f64) -> bool {
+    measure_in_basis_with_rng(qubit, theta, phi, &mut unseeded_rng("measure_in_basis"))
+}
+
+/// `measure_in_basis` drawing from the caller's RNG
+pub fn measure_in_basis_with_rng(
+    qubit: &mut Qubit,
+    theta: f64,
+    phi: f64,
+    rng: &mut impl Rng,
+) -> bool {
+    let aligned = Qubit::from_bloch_angles(theta, phi);
+    let result = rng.random::<f64>() >= qubit.fidelity(&aligned);
+    *qubit = if result {
+        Qubit::from_bloch_angles(std::f64::consts::PI - theta, phi + std::f64::consts::PI)
+    } else {
+        aligned
+    };
+    result
+}
+
 /// Perform ideal Z-basis measurement on a qubit
 /// Returns true for |1⟩, false for |0⟩
 pub fn measure_z(qubit: &mut Qubit) -> bool {
-    let prob_zero = qubit.prob_zero();
-    let mut rng = rand::rng();
+    measure_z_with_rng(qubit, &mut unseeded_rng("measure_z"))
+}
 
+/// `measure_z` drawing from the caller's RNG
+pub fn measure_z_with_rng(qubit: &mut Qubit, rng: &mut impl Rng) -> bool {
+    let prob_zero = qubit.prob_zero();
     let result = rng.random::<f64>() >= prob_zero;
 
     // Collapse to measured state
@@ -29,67 +101,779 @@ pub fn measure_z(qubit: &mut Qubit) -> bool {
 /// Models realistic detectors with:
 /// - Dark counts: false positives when no photon arrives
 /// - Detector efficiency: probability of actually detecting a photon
-/// - Measurement errors: bit flip errors in the classical result
+/// - Readout confusion: direction-dependent flips of the classical result
 pub fn measure_z_with_noise(
     qubit: &mut Qubit,
     detector_efficiency: f64,
     dark_count_rate: f64,
-    measurement_error_rate: f64,
+    confusion: ConfusionMatrix,
+) -> bool {
+    measure_z_with_config(
+        qubit,
+        &MeasurementConfig {
+            detector_efficiency,
+            dark_count_rate,
+            confusion,
+        },
+    )
+}
+
+/// `measure_z_with_noise` drawing from the caller's RNG
+pub fn measure_z_with_noise_and_rng(
+    qubit: &mut Qubit,
+    detector_efficiency: f64,
+    dark_count_rate: f64,
+    confusion: ConfusionMatrix,
+    rng: &mut impl Rng,
 ) -> bool {
-    let mut rng = rand::rng();
+    measure_z_with_config_and_rng(
+        qubit,
+        &MeasurementConfig {
+            detector_efficiency,
+            dark_count_rate,
+            confusion,
+        },
+        rng,
+    )
+}
+
+/// Z-basis measurement through the detector described by `config`
+pub fn measure_z_with_config(qubit: &mut Qubit, config: &MeasurementConfig) -> bool {
+    measure_z_with_config_and_rng(qubit, config, &mut unseeded_rng("measure_z_with_config"))
+}
+
+/// `measure_z_with_config` drawing from the caller's RNG
+pub fn measure_z_with_config_and_rng(
+    qubit: &mut Qubit,
+    config: &MeasurementConfig,
+    rng: &mut impl Rng,
+) -> bool {
+    debug_assert!(
+        config.validate().is_ok(),
+        "{}",
+        config.validate().unwrap_err()
+    );
 
     // First, ideal quantum measurement
-    let ideal_result = measure_z(qubit);
+    let ideal_result = measure_z_with_rng(qubit, rng);
 
     // Apply detector inefficiency
     let detected = if ideal_result {
         // Photon present - might not detect it
-        rng.random::<f64>() < detector_efficiency
+        rng.random::<f64>() < config.detector_efficiency
     } else {
         // No photon - might have dark count
-        rng.random::<f64>() < dark_count_rate
+        rng.random::<f64>() < config.dark_count_rate
     };
 
-    // Apply measurement error (bit flip)
-    let final_result = if rng.random::<f64>() < measurement_error_rate {
-        !detected // Flip the bit
-    } else {
-        detected
-    };
+    // Apply readout error
+    config.confusion.apply(detected, rng)
+}
 
-    final_result
+/// X-basis measurement with detector errors
+/// Rotates into the Z basis, then applies the `measure_z_with_noise` detector model
+pub fn measure_x_with_noise(
+    qubit: &mut Qubit,
+    detector_efficiency: f64,
+    dark_count_rate: f64,
+    confusion: ConfusionMatrix,
+) -> bool {
+    measure_x_with_config(
+        qubit,
+        &MeasurementConfig {
+            detector_efficiency,
+            dark_count_rate,
+            confusion,
+        },
+    )
+}
+
+/// Y-basis measurement with detector errors
+/// Rotates into the Z basis, then applies the `measure_z_with_noise` detector model
+pub fn measure_y_with_noise(
+    qubit: &mut Qubit,
+    detector_efficiency: f64,
+    dark_count_rate: f64,
+    confusion: ConfusionMatrix,
+) -> bool {
+    measure_y_with_config(
+        qubit,
+        &MeasurementConfig {
+            detector_efficiency,
+            dark_count_rate,
+            confusion,
+        },
+    )
+}
+
+/// X-basis measurement through the detector described by `config`
+pub fn measure_x_with_config(qubit: &mut Qubit, config: &MeasurementConfig) -> bool {
+    super::gates::hadamard(qubit);
+    measure_z_with_config(qubit, config)
+}
+
+/// Y-basis measurement through the detector described by `config`
+pub fn measure_y_with_config(qubit: &mut Qubit, config: &MeasurementConfig) -> bool {
+    super::gates::phase_s_dagger(qubit);
+    measure_x_with_config(qubit, config)
 }
 
 /// Perform X-basis measurement (measure in |+⟩, |-⟩ basis)
 pub fn measure_x(qubit: &mut Qubit) -> bool {
+    measure_x_with_rng(qubit, &mut unseeded_rng("measure_x"))
+}
+
+/// `measure_x` drawing from the caller's RNG
+pub fn measure_x_with_rng(qubit: &mut Qubit, rng: &mut impl Rng) -> bool {
     // Apply Hadamard to convert X-basis to Z-basis
     super::gates::hadamard(qubit);
 
     // Measure in Z-basis
-    let result = measure_z(qubit);
-
-    result
+    measure_z_with_rng(qubit, rng)
 }
 
 /// Perform Y-basis measurement
 pub fn measure_y(qubit: &mut Qubit) -> bool {
-    // S†H converts Y-basis to Z-basis
-    // For simplicity, we'll apply the transformation
+    measure_y_with_rng(qubit, &mut unseeded_rng("measure_y"))
+}
 
-    // Apply S† (phase gate conjugate transpose)
-    qubit.state[[1]] *= Complex64::new(0.0, -1.0);
+/// `measure_y` drawing from the caller's RNG
+pub fn measure_y_with_rng(qubit: &mut Qubit, rng: &mut impl Rng) -> bool {
+    // S†H converts Y-basis to Z-basis
+    super::gates::phase_s_dagger(qubit);
 
     // Apply Hadamard
     super::gates::hadamard(qubit);
 
     // Measure in Z-basis
-    let result = measure_z(qubit);
+    measure_z_with_rng(qubit, rng)
+}
 
+/// Born probabilities (P(false), P(true)) of measuring `qubit` in `basis`
+pub fn outcome_probabilities(qubit: &Qubit, basis: Basis) -> (f64, f64) {
+    let (alpha, beta) = (qubit.state[[0]], qubit.state[[1]]);
+    let p_zero = match basis {
+        Basis::Z => alpha.norm_sqr(),
+        // Overlap with |+⟩ and |+i⟩
+        Basis::X => (alpha + beta).norm_sqr() / 2.0,
+        Basis::Y => (alpha - Complex64::i() * beta).norm_sqr() / 2.0,
+        Basis::Custom { theta, phi } => qubit.fidelity(&Qubit::from_bloch_angles(theta, phi)),
+    };
+    let p_zero = p_zero.clamp(0.0, 1.0);
+    (p_zero, 1.0 - p_zero)
+}
+
+/// Counts (zeros, ones) of `n` ideal measurements of fresh copies of `qubit`
+///
+/// Draws from the Born distribution directly; `qubit` is neither cloned
+/// nor collapsed
+pub fn sample(qubit: &Qubit, basis: Basis, n: usize, rng: &mut impl Rng) -> (usize, usize) {
+    let (_, p_one) = outcome_probabilities(qubit, basis);
+    // One 32-bit draw per shot: 2^-32 resolution is far below shot noise,
+    // and half the cost of drawing an f64
+    let threshold = (p_one * 4_294_967_296.0) as u64;
+    let ones = (0..n)
+        .filter(|_| u64::from(rng.next_u32()) < threshold)
+        .count();
+    (n - ones, ones)
+}
+
+/// `sample` in the Z basis
+pub fn sample_z(qubit: &Qubit, n: usize, rng: &mut impl Rng) -> (usize, usize) {
+    sample(qubit, Basis::Z, n, rng)
+}
+
+/// `sample` in the X basis
+pub fn sample_x(qubit: &Qubit, n: usize, rng: &mut impl Rng) -> (usize, usize) {
+    sample(qubit, Basis::X, n, rng)
+}
+
+/// `sample` in the Y basis
+pub fn sample_y(qubit: &Qubit, n: usize, rng: &mut impl Rng) -> (usize, usize) {
+    sample(qubit, Basis::Y, n, rng)
+}
+
+/// Z-basis measurement of one half of a pair, drawing from the caller's RNG
+/// Returns true for |1⟩; the state collapses onto the outcome and is renormalised
+pub fn measure_qubit_with_rng(
+    state: &mut TwoQubitState,
+    half: PairHalf,
+    rng: &mut impl Rng,
+) -> bool {
+    // Index = 2*a + b
+    let bit = |index: usize| match half {
+        PairHalf::A => index / 2 == 1,
+        PairHalf::B => index % 2 == 1,
+    };
+    let prob_one: f64 = (0..4)
+        .filter(|&index| bit(index))
+        .map(|index| state.state[index].norm_sqr())
+        .sum();
+    let result = rng.random::<f64>() < prob_one;
+
+    let norm = if result { prob_one } else { 1.0 - prob_one }.sqrt();
+    for index in 0..4 {
+        if bit(index) == result {
+            state.state[index] /= norm;
+        } else {
+            state.state[index] = Complex64::new(0.0, 0.0);
+        }
+    }
     result
 }
 
+/// Z-basis measurement of qubit A of a pair
+pub fn measure_qubit_a(state: &mut TwoQubitState) -> bool {
+    measure_qubit_with_rng(state, PairHalf::A, &mut unseeded_rng("measure_qubit_a"))
+}
+
+/// Z-basis measurement of qubit B of a pair
+pub fn measure_qubit_b(state: &mut TwoQubitState) -> bool {
+    measure_qubit_with_rng(state, PairHalf::B, &mut unseeded_rng("measure_qubit_b"))
+}
+
+/// X-basis measurement of qubit A (Hadamard, then Z)
+pub fn measure_qubit_a_x(state: &mut TwoQubitState) -> bool {
+    super::gates::hadamard_on_a(state);
+    measure_qubit_a(state)
+}
+
+/// X-basis measurement of qubit B (Hadamard, then Z)
+pub fn measure_qubit_b_x(state: &mut TwoQubitState) -> bool {
+    super::gates::hadamard_on_b(state);
+    measure_qubit_b(state)
+}
+
+/// Y-basis measurement of qubit A (S†, Hadamard, then Z)
+pub fn measure_qubit_a_y(state: &mut TwoQubitState) -> bool {
+    apply_gate_to_qubit_a(state, &get_s_dagger_matrix());
+    measure_qubit_a_x(state)
+}
+
+/// Y-basis measurement of qubit B (S†, Hadamard, then Z)
+pub fn measure_qubit_b_y(state: &mut TwoQubitState) -> bool {
+    apply_gate_to_qubit_b(state, &get_s_dagger_matrix());
+    measure_qubit_b_x(state)
+}
+
+/// Measure one half of a pair along the Bloch direction (θ, φ), drawing from the caller's RNG
+///
+/// Rotates the direction onto Z, then measures; true is the eigenstate
+/// pointing away from the direction. The half is left in the rotated frame
+pub fn measure_qubit_in_basis_with_rng(
+    state: &mut TwoQubitState,
+    half: PairHalf,
+    theta: f64,
+    phi: f64,
+    rng: &mut impl Rng,
+) -> bool {
+    // Rows ⟨n+| and ⟨n-| take |n+⟩ to |0⟩ and |n-⟩ to |1⟩
+    let (cos, sin) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+    let phase = Complex64::from_polar(1.0, -phi);
+    let rotation = array![
+        [Complex64::new(cos, 0.0), phase * sin],
+        [Complex64::new(-sin, 0.0), phase * cos]
+    ];
+    match half {
+        PairHalf::A => apply_gate_to_qubit_a(state, &rotation),
+        PairHalf::B => apply_gate_to_qubit_b(state, &rotation),
+    }
+    measure_qubit_with_rng(state, half, rng)
+}
+
+/// CHSH value S of `pairs` pairs from `state_factory`
+///
+/// Each pair is measured at a random pair of analyser angles, Alice's from
+/// {0, π/4} and Bob's from {π/8, 3π/8}, in the X-Z plane (analyser angle α is
+/// Bloch polar angle 2α). S = E(a,b) - E(a,b') + E(a',b) + E(a',b'): 2√2
+/// for |Φ+⟩, while any local model stays within 2
+pub fn chsh_value(
+    pairs: usize,
+    state_factory: impl Fn() -> TwoQubitState,
+    rng: &mut impl Rng,
+) -> f64 {
+    use std::f64::consts::PI;
+    let alice = [0.0, PI / 4.0];
+    let bob = [PI / 8.0, 3.0 * PI / 8.0];
+    let mut products = [[0.0; 2]; 2];
+    let mut counts = [[0usize; 2]; 2];
+    for _ in 0..pairs {
+        let (i, j) = (rng.random_range(0..2), rng.random_range(0..2));
+        let mut state = state_factory();
+        let a = measure_qubit_in_basis_with_rng(&mut state, PairHalf::A, 2.0 * alice[i], 0.0, rng);
+        let b = measure_qubit_in_basis_with_rng(&mut state, PairHalf::B, 2.0 * bob[j], 0.0, rng);
+        products[i][j] += if a == b { 1.0 } else { -1.0 };
+        counts[i][j] += 1;
+    }
+    let correlator = |i: usize, j: usize| {
+        if counts[i][j] == 0 {
+            0.0
+        } else {
+            products[i][j] / counts[i][j] as f64
+        }
+    };
+    correlator(0, 0) - correlator(0, 1) + correlator(1, 0) + correlator(1, 1)
+}
+
+/// Measure qubit `position` of a three-qubit state in `basis`, drawing from the caller's RNG
+///
+/// Returns the outcome (true = |1⟩ or |-⟩) and the collapsed state of the
+/// other two qubits, in their original order
+pub fn measure_three_qubit_with_rng(
+    state: &ThreeQubitState,
+    position: usize,
+    basis: Basis,
+    rng: &mut impl Rng,
+) -> (bool, TwoQubitState) {
+    let mut state = state.clone();
+    if basis == Basis::X {
+        super::gates::apply_gate_to_three_qubit(
+            &mut state,
+            position,
+            &super::gates::get_hadamard_matrix(),
+        );
+    }
+    // Index = 4*q0 + 2*q1 + q2
+    let shift = 2 - position;
+    let bit = |index: usize| (index >> shift) & 1 == 1;
+    let prob_one: f64 = (0..8)
+        .filter(|&index| bit(index))
+        .map(|index| state.state[index].norm_sqr())
+        .sum();
+    let result = rng.random::<f64>() < prob_one;
+    let norm = if result { prob_one } else { 1.0 - prob_one }.sqrt();
+
+    let mut residual = Array1::from_elem(4, Complex64::new(0.0, 0.0));
+    for index in (0..8).filter(|&index| bit(index) == result) {
+        // Drop the measured bit: high bits shift down past it
+        let high = index >> (shift + 1);
+        let low = index & ((1 << shift) - 1);
+        residual[(high << shift) | low] = state.state[index] / norm;
+    }
+    (result, TwoQubitState { state: residual })
+}
+
+/// Measure qubit `position` of a three-qubit state in `basis`
+pub fn measure_three_qubit(
+    state: &ThreeQubitState,
+    position: usize,
+    basis: Basis,
+) -> (bool, TwoQubitState) {
+    measure_three_qubit_with_rng(
+        state,
+        position,
+        basis,
+        &mut unseeded_rng("measure_three_qubit"),
+    )
+}
+
+/// Bell-basis measurement of a pair, drawing from the caller's RNG
+/// The state collapses onto the returned Bell state, keeping its global phase
+pub fn measure_bell_with_rng(state: &mut TwoQubitState, rng: &mut impl Rng) -> BellState {
+    let projections = BellState::ALL.map(|bell| {
+        let basis = TwoQubitState::from_bell(bell);
+        let overlap: Complex64 = basis
+            .state
+            .iter()
+            .zip(&state.state)
+            .map(|(b, s)| b.conj() * s)
+            .sum();
+        (bell, basis, overlap)
+    });
+
+    let draw = rng.random::<f64>();
+    let mut cumulative = 0.0;
+    // Rounding can leave the total just below 1; fall back to the likeliest outcome
+    let (bell, basis, overlap) = projections
+        .iter()
+        .find(|(_, _, overlap)| {
+            cumulative += overlap.norm_sqr();
+            draw < cumulative
+        })
+        .unwrap_or_else(|| {
+            projections
+                .iter()
+                .max_by(|x, y| x.2.norm_sqr().total_cmp(&y.2.norm_sqr()))
+                .unwrap()
+        });
+    let phase = overlap / overlap.norm();
+    state.state = basis.state.mapv(|amplitude| phase * amplitude);
+    *bell
+}
+
+/// Bell-basis measurement of a pair
+pub fn measure_bell(state: &mut TwoQubitState) -> BellState {
+    measure_bell_with_rng(state, &mut unseeded_rng("measure_bell"))
+}
+
+/// Which Bell states a BSM setup can tell apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BsmResolution {
+    /// All four outcomes (e.g. CNOT + H + two Z measurements)
+    Complete,
+    /// Only |Ψ+⟩ and |Ψ-⟩ click distinguishably: a beam splitter and two
+    /// detectors, at most 50% efficient
+    LinearOptics,
+}
+
+impl BsmResolution {
+    pub fn distinguishes(&self, bell: BellState) -> bool {
+        match self {
+            BsmResolution::Complete => true,
+            BsmResolution::LinearOptics => {
+                matches!(bell, BellState::PsiPlus | BellState::PsiMinus)
+            }
+        }
+    }
+}
+
+/// Bell-state measurement with losses and limited resolution
+///
+/// Fails (None) with probability 1 - `success_probability`, leaving the
+/// state untouched, or when the projected outcome is one `resolution`
+/// cannot distinguish, in which case the state is still collapsed
+pub fn measure_bell_with_noise(
+    state: &mut TwoQubitState,
+    success_probability: f64,
+    resolution: BsmResolution,
+) -> Option<BellState> {
+    measure_bell_with_noise_and_rng(
+        state,
+        success_probability,
+        resolution,
+        &mut unseeded_rng("measure_bell_with_noise"),
+    )
+}
+
+/// `measure_bell_with_noise` drawing from the caller's RNG
+pub fn measure_bell_with_noise_and_rng(
+    state: &mut TwoQubitState,
+    success_probability: f64,
+    resolution: BsmResolution,
+    rng: &mut impl Rng,
+) -> Option<BellState> {
+    if rng.random::<f64>() >= success_probability {
+        return None;
+    }
+    let bell = measure_bell_with_rng(state, rng);
+    resolution.distinguishes(bell).then_some(bell)
+}
+
+/// Classical readout confusion of a detector
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfusionMatrix {
+    /// P(report 1 | true 0), e.g. driven by dark counts
+    pub p_report1_given0: f64,
+    /// P(report 0 | true 1), e.g. driven by loss
+    pub p_report0_given1: f64,
+}
+
+impl ConfusionMatrix {
+    /// Same flip probability in both directions
+    pub fn symmetric(error_rate: f64) -> Self {
+        ConfusionMatrix {
+            p_report1_given0: error_rate,
+            p_report0_given1: error_rate,
+        }
+    }
+
+    /// No readout error
+    pub fn ideal() -> Self {
+        Self::symmetric(0.0)
+    }
+
+    /// Reported outcome for a true outcome
+    pub fn apply(&self, outcome: bool, rng: &mut impl Rng) -> bool {
+        let flip_probability = if outcome {
+            self.p_report0_given1
+        } else {
+            self.p_report1_given0
+        };
+        outcome ^ (rng.random::<f64>() < flip_probability)
+    }
+
+    /// Readout-error mitigation: invert the confusion matrix on observed counts
+    ///
+    /// Negative estimates (from statistical noise) are clipped to zero and
+    /// the result rescaled to the observed total
+    pub fn correct_counts(&self, observed: OutcomeCounts) -> OutcomeCounts {
+        let (a, b) = (self.p_report1_given0, self.p_report0_given1);
+        let determinant = 1.0 - a - b;
+        assert!(
+            determinant.abs() > 1e-12,
+            "Confusion matrix is singular (p10 + p01 = 1)"
+        );
+
+        let zeros = (((1.0 - b) * observed.zeros - b * observed.ones) / determinant).max(0.0);
+        let ones = (((1.0 - a) * observed.ones - a * observed.zeros) / determinant).max(0.0);
+        let scale = if zeros + ones > 0.0 {
+            observed.total() / (zeros + ones)
+        } else {
+            0.0
+        };
+        OutcomeCounts {
+            zeros: zeros * scale,
+            ones: ones * scale,
+        }
+    }
+}
+
+/// Counts of 0 and 1 outcomes (fractional after mitigation)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OutcomeCounts {
+    pub zeros: f64,
+    pub ones: f64,
+}
+
+impl OutcomeCounts {
+    /// Tally a sequence of outcomes
+    pub fn from_outcomes(outcomes: impl IntoIterator<Item = bool>) -> Self {
+        let mut counts = OutcomeCounts::default();
+        for outcome in outcomes {
+            if outcome {
+                counts.ones += 1.0;
+            } else {
+                counts.zeros += 1.0;
+            }
+        }
+        counts
+    }
+
+    pub fn total(&self) -> f64 {
+        self.zeros + self.ones
+    }
+
+    /// Fraction of 1 outcomes (0 if empty)
+    pub fn p_one(&self) -> f64 {
+        if self.total() == 0.0 {
+            0.0
+        } else {
+            self.ones / self.total()
+        }
+    }
+}
+
+/// QKD bookkeeping: prepared vs measured bits, sifted by basis agreement
+#[derive(Debug, Clone, Default)]
+pub struct MeasurementRecord {
+    /// (basis, sifted rounds, errors) in the order bases were first seen
+    per_basis: Vec<(Basis, usize, usize)>,
+    /// Rounds where the two bases differed (removed by sifting)
+    discarded: usize,
+}
+
+impl MeasurementRecord {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a sifted round: both sides used `basis`
+    pub fn record(&mut self, basis: Basis, prepared: bool, measured: bool) {
+        let error = (prepared != measured) as usize;
+        match self.per_basis.iter_mut().find(|(b, _, _)| *b == basis) {
+            Some((_, rounds, errors)) => {
+                *rounds += 1;
+                *errors += error;
+            }
+            None => self.per_basis.push((basis, 1, error)),
+        }
+    }
+
+    /// Record a round whose bases differed; it counts only towards `total_count`
+    pub fn record_discarded(&mut self) {
+        self.discarded += 1;
+    }
+
+    /// Record a round, sifting it by comparing the two bases
+    pub fn record_round(
+        &mut self,
+        prepared_basis: Basis,
+        measured_basis: Basis,
+        prepared: bool,
+        measured: bool,
+    ) {
+        if prepared_basis == measured_basis {
+            self.record(prepared_basis, prepared, measured);
+        } else {
+            self.record_discarded();
+        }
+    }
+
+    /// Rounds surviving sifting
+    pub fn sifted_count(&self) -> usize {
+        self.per_basis.iter().map(|&(_, rounds, _)| rounds).sum()
+    }
+
+    /// All recorded rounds, sifted or not
+    pub fn total_count(&self) -> usize {
+        self.sifted_count() + self.discarded
+    }
+
+    /// Fraction of rounds surviving sifting (0 if empty)
+    pub fn sifting_fraction(&self) -> f64 {
+        ratio(self.sifted_count(), self.total_count())
+    }
+
+    /// Sifted rounds whose bits disagree
+    pub fn error_count(&self) -> usize {
+        self.per_basis.iter().map(|&(_, _, errors)| errors).sum()
+    }
+
+    /// Error rate over all sifted rounds (0 if none)
+    pub fn qber(&self) -> f64 {
+        ratio(self.error_count(), self.sifted_count())
+    }
+
+    /// Error rate of each basis, in the order the bases were first recorded
+    pub fn qber_per_basis(&self) -> Vec<(Basis, f64)> {
+        self.per_basis
+            .iter()
+            .map(|&(basis, rounds, errors)| (basis, ratio(errors, rounds)))
+            .collect()
+    }
+
+    /// Two-sided chi-square test (one degree of freedom) of the error count
+    /// against a binomial with rate `expected_qber`
+    ///
+    /// Returns false if the deviation is significant at `confidence`
+    /// (e.g. 0.99); an empty record is consistent with anything
+    pub fn is_consistent_with(&self, expected_qber: f64, confidence: f64) -> bool {
+        let n = self.sifted_count() as f64;
+        let expected = n * expected_qber;
+        let variance = expected * (1.0 - expected_qber);
+        let deviation = self.error_count() as f64 - expected;
+        if variance == 0.0 {
+            return deviation == 0.0;
+        }
+        let critical = normal_quantile(0.5 + confidence / 2.0);
+        deviation * deviation / variance <= critical * critical
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+/// Standard normal quantile (Abramowitz & Stegun 26.2.23, error < 4.5e-4)
+fn normal_quantile(p: f64) -> f64 {
+    let tail = p.min(1.0 - p);
+    let t = (-2.0 * tail.ln()).sqrt();
+    let z = t
+        - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+            / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t * t * t);
+    if p < 0.5 {
+        -z
+    } else {
+        z
+    }
+}
+
+/// Single-photon detector with finite efficiency, dark counts, dead time
+/// and timing jitter
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Detector {
+    /// Probability that an arriving photon produces a click (0.0 to 1.0)
+    pub efficiency: f64,
+
+    /// Rate of clicks without a photon, in Hz
+    pub dark_count_rate_hz: f64,
+
+    /// Time after a click during which the detector is blind, in ns
+    pub dead_time_ns: f64,
+
+    /// Standard deviation of the recorded click time, in ns
+    pub jitter_ns: f64,
+
+    /// Width of the window in which a dark count can fire per call, in ns
+    pub gate_ns: f64,
+
+    /// Arrival time of the last click, which starts the dead time
+    last_click_ns: Option<f64>,
+}
+
+impl Detector {
+    /// Detector with a 1 ns gate that has not clicked yet
+    pub fn new(
+        efficiency: f64,
+        dark_count_rate_hz: f64,
+        dead_time_ns: f64,
+        jitter_ns: f64,
+    ) -> Self {
+        Detector {
+            efficiency,
+            dark_count_rate_hz,
+            dead_time_ns,
+            jitter_ns,
+            gate_ns: 1.0,
+            last_click_ns: None,
+        }
+    }
+
+    /// Perfectly efficient, noiseless detector with no dead time
+    pub fn ideal() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Otherwise ideal detector with the given efficiency
+    pub fn with_efficiency(efficiency: f64) -> Self {
+        Self::new(efficiency, 0.0, 0.0, 0.0)
+    }
+
+    /// Probability of a dark count within one gate
+    pub fn dark_count_probability(&self) -> f64 {
+        1.0 - (-self.dark_count_rate_hz * self.gate_ns * 1e-9).exp()
+    }
+
+    /// Whether the detector is still blind at `time_ns`
+    pub fn is_dead(&self, time_ns: f64) -> bool {
+        self.last_click_ns
+            .is_some_and(|last| time_ns - last < self.dead_time_ns)
+    }
+
+    /// Forget the last click, ending any dead time
+    pub fn reset(&mut self) {
+        self.last_click_ns = None;
+    }
+
+    /// Register a gate at `arrival_time_ns`, with or without a photon
+    ///
+    /// Returns the recorded click time, smeared by Gaussian jitter, or None
+    /// if the detector is dead or neither the photon nor a dark count fires
+    pub fn detect(
+        &mut self,
+        photon_present: bool,
+        arrival_time_ns: f64,
+        rng: &mut impl Rng,
+    ) -> Option<f64> {
+        if self.is_dead(arrival_time_ns) {
+            return None;
+        }
+        let photon_click = photon_present && rng.random::<f64>() < self.efficiency;
+        if !photon_click && rng.random::<f64>() >= self.dark_count_probability() {
+            return None;
+        }
+        self.last_click_ns = Some(arrival_time_ns);
+        Some(arrival_time_ns + self.jitter_ns * standard_normal(rng))
+    }
+}
+
+/// Standard normal sample by the Box-Muller transform
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    // 1 - u lies in (0, 1], keeping the logarithm finite
+    let u1 = 1.0 - rng.random::<f64>();
+    let u2 = rng.random::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
 /// Configuration for realistic measurement parameters
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MeasurementConfig {
     /// Detector efficiency (0.0 to 1.0)
     /// Typical: 0.90-0.95 for good detectors
@@ -99,9 +883,9 @@ pub struct MeasurementConfig {
     /// Typical: 0.001-0.01 (0.1% to 1%)
     pub dark_count_rate: f64,
 
-    /// Classical bit flip error rate (0.0 to 1.0)
-    /// Typical: 0.001-0.02 (0.1% to 2%)
-    pub measurement_error_rate: f64,
+    /// Classical readout confusion
+    /// Typical flip rates: 0.001-0.02 (0.1% to 2%)
+    pub confusion: ConfusionMatrix,
 }
 
 impl MeasurementConfig {
@@ -110,7 +894,7 @@ impl MeasurementConfig {
         MeasurementConfig {
             detector_efficiency: 1.0,
             dark_count_rate: 0.0,
-            measurement_error_rate: 0.0,
+            confusion: ConfusionMatrix::ideal(),
         }
     }
 
@@ -119,16 +903,44 @@ impl MeasurementConfig {
         MeasurementConfig {
             detector_efficiency: 0.95,
             dark_count_rate: 0.01,
-            measurement_error_rate: 0.02,
+            confusion: ConfusionMatrix::symmetric(0.02),
         }
     }
 
+    /// Ideal detector with the given readout confusion
+    pub fn from_confusion(confusion: ConfusionMatrix) -> Self {
+        MeasurementConfig {
+            confusion,
+            ..Self::perfect()
+        }
+    }
+
+    /// Z-basis measurement using this configuration
+    pub fn measure_z(&self, qubit: &mut Qubit) -> bool {
+        measure_z_with_config(qubit, self)
+    }
+
+    /// Check every rate is a probability in [0, 1]
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("Detector efficiency", self.detector_efficiency),
+            ("Dark count rate", self.dark_count_rate),
+            ("P(report 1 | true 0)", self.confusion.p_report1_given0),
+            ("P(report 0 | true 1)", self.confusion.p_report0_given1),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} must be in [0, 1], got {}", name, value));
+            }
+        }
+        Ok(())
+    }
+
     /// High-quality measurement
     pub fn high_quality() -> Self {
         MeasurementConfig {
             detector_efficiency: 0.98,
             dark_count_rate: 0.001,
-            measurement_error_rate: 0.005,
+            confusion: ConfusionMatrix::symmetric(0.005),
         }
     }
 }
@@ -138,6 +950,357 @@ mod tests {
     use super::*;
     use crate::quantum::state::Qubit;
 
+    /// Measure both halves of fresh |Φ+⟩ pairs with `measure`, returning
+    /// how many of `trials` gave equal outcomes
+    fn agreeing_outcomes(
+        trials: usize,
+        measure: impl Fn(&mut TwoQubitState) -> (bool, bool),
+    ) -> usize {
+        (0..trials)
+            .filter(|_| {
+                let (a, b) = measure(&mut TwoQubitState::new_bell_phi_plus());
+                a == b
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_pair_halves_are_correlated() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(759);
+        let mut ones = 0;
+        for _ in 0..2000 {
+            let mut state = TwoQubitState::new_bell_phi_plus();
+            let a = measure_qubit_with_rng(&mut state, PairHalf::A, &mut rng);
+            // B is already determined by A's outcome
+            let b = measure_qubit_with_rng(&mut state, PairHalf::B, &mut rng);
+            assert_eq!(a, b);
+            ones += a as usize;
+
+            // Left in |00⟩ or |11⟩
+            assert!(state.is_normalized());
+            let index = if a { 3 } else { 0 };
+            assert!((state.state[index].norm() - 1.0).abs() < 1e-12);
+        }
+        assert!((900..1100).contains(&ones), "{}", ones);
+    }
+
+    #[test]
+    fn test_measure_dispatches_on_basis() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(774);
+        let trials = 4000;
+        let ones = |prepare: fn() -> Qubit, basis: Basis, rng: &mut rand::rngs::StdRng| {
+            (0..trials)
+                .filter(|_| measure_with_rng(&mut prepare(), basis, rng))
+                .count()
+        };
+
+        // Basis::Z reproduces measure_z: |0⟩ always 0, |+⟩ 50/50
+        assert_eq!(ones(Qubit::new_zero, Basis::Z, &mut rng), 0);
+        let plus_ones = ones(Qubit::new_plus, Basis::Z, &mut rng);
+        assert!((1800..2200).contains(&plus_ones), "{}", plus_ones);
+        assert_eq!(ones(Qubit::new_minus, Basis::X, &mut rng), trials);
+        assert_eq!(ones(Qubit::new_iplus, Basis::Y, &mut rng), 0);
+
+        // θ = 0 is Z; θ = π/2 on |0⟩ is 50/50
+        let along_z = Basis::Custom {
+            theta: 0.0,
+            phi: 0.0,
+        };
+        assert_eq!(ones(Qubit::new_one, along_z, &mut rng), trials);
+        let equator = Basis::Custom {
+            theta: std::f64::consts::FRAC_PI_2,
+            phi: 0.3,
+        };
+        let equator_ones = ones(Qubit::new_zero, equator, &mut rng);
+        assert!((1800..2200).contains(&equator_ones), "{}", equator_ones);
+    }
+
+    #[test]
+    fn test_measure_in_basis_collapses_to_eigenstate() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7740);
+        let (theta, phi) = (std::f64::consts::PI / 8.0, 0.7);
+        let direction = Qubit::from_bloch_angles(theta, phi);
+        for _ in 0..100 {
+            let mut qubit = Qubit::new_random_with_rng(&mut rng);
+            let result = measure_in_basis_with_rng(&mut qubit, theta, phi, &mut rng);
+            let overlap = qubit.fidelity(&direction);
+            assert!((overlap - if result { 0.0 } else { 1.0 }).abs() < 1e-12);
+            // Measuring again repeats the outcome
+            assert_eq!(
+                measure_in_basis_with_rng(&mut qubit, theta, phi, &mut rng),
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_pair_half_measurement_in_basis() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(775);
+        let (theta, phi) = (1.1, -0.4);
+        for _ in 0..200 {
+            // Both halves of |Φ+⟩ along (θ, φ) and its conjugate (θ, -φ) agree
+            let mut state = TwoQubitState::new_bell_phi_plus();
+            let a = measure_qubit_in_basis_with_rng(&mut state, PairHalf::A, theta, phi, &mut rng);
+            let b = measure_qubit_in_basis_with_rng(&mut state, PairHalf::B, theta, -phi, &mut rng);
+            assert_eq!(a, b);
+            assert!(state.is_normalized());
+        }
+    }
+
+    #[test]
+    fn test_chsh_value() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(2775);
+        let pairs = 16_000;
+        // Each correlator has standard error ~ 1/√(pairs/4) ≈ 0.016
+        let bell = chsh_value(pairs, TwoQubitState::new_bell_phi_plus, &mut rng);
+        assert!((bell - 2.0 * 2.0_f64.sqrt()).abs() < 0.1, "{}", bell);
+
+        // Werner trajectories of fidelity 0.7: |Φ+⟩ with a random Pauli on
+        // one half, which averages to the mixed Werner state (S = 2√2 · 0.6)
+        let errors = crate::quantum::PauliErrorRates::werner(0.7);
+        let trajectories = std::cell::RefCell::new(rand::rngs::StdRng::seed_from_u64(775));
+        let werner = || {
+            let mut state = TwoQubitState::new_bell_phi_plus();
+            let pauli = errors.sample(&mut *trajectories.borrow_mut());
+            crate::quantum::noise::apply_pauli_pair(&mut state, 0, pauli);
+            state
+        };
+        let noisy = chsh_value(pairs, werner, &mut rng);
+        assert!(
+            (noisy - 2.0 * 2.0_f64.sqrt() * 0.6).abs() < 0.1,
+            "{}",
+            noisy
+        );
+        assert!(noisy < 2.0, "{}", noisy);
+
+        let product = chsh_value(pairs, TwoQubitState::new_zero_zero, &mut rng);
+        assert!(product.abs() <= 2.1, "{}", product);
+    }
+
+    #[test]
+    fn test_perfect_config_never_errs() {
+        let config = MeasurementConfig::perfect();
+        for _ in 0..10_000 {
+            assert!(!measure_z_with_config(&mut Qubit::new_zero(), &config));
+            assert!(!measure_x_with_config(&mut Qubit::new_plus(), &config));
+            assert!(!measure_y_with_config(&mut Qubit::new_iplus(), &config));
+        }
+        assert!(measure_x_with_config(&mut Qubit::new_minus(), &config));
+        assert!(measure_y_with_config(&mut Qubit::new_iminus(), &config));
+    }
+
+    #[test]
+    fn test_measurement_config_validation() {
+        assert!(MeasurementConfig::perfect().validate().is_ok());
+        assert!(MeasurementConfig::realistic().validate().is_ok());
+        let invalid = MeasurementConfig {
+            dark_count_rate: 1.5,
+            ..MeasurementConfig::perfect()
+        };
+        assert!(invalid.validate().unwrap_err().contains("Dark count rate"));
+        let negative = MeasurementConfig::from_confusion(ConfusionMatrix::symmetric(-0.1));
+        assert!(negative.validate().is_err());
+    }
+
+    #[test]
+    fn test_seeded_single_qubit_measurements_repeat() {
+        use rand::SeedableRng;
+        let outcomes = |seed: u64| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let confusion = ConfusionMatrix::symmetric(0.1);
+            (0..200)
+                .map(|_| {
+                    let mut plus = Qubit::new_plus();
+                    let mut iplus = Qubit::new_iplus();
+                    (
+                        measure_z_with_noise_and_rng(&mut plus, 0.9, 0.05, confusion, &mut rng),
+                        measure_x_with_rng(&mut Qubit::new_zero(), &mut rng),
+                        measure_y_with_rng(&mut iplus, &mut rng),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let first = outcomes(42);
+        assert_eq!(first, outcomes(42));
+        // |+i⟩ is the +1 eigenstate of Y: always reads 0
+        assert!(first.iter().all(|&(_, _, y)| !y));
+    }
+
+    /// Outcome counts of `trials` Bell measurements of `prepare()`, in `BellState::ALL` order
+    fn bell_counts(trials: usize, prepare: impl Fn() -> TwoQubitState) -> [usize; 4] {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(760);
+        let mut counts = [0; 4];
+        for _ in 0..trials {
+            let mut state = prepare();
+            let bell = measure_bell_with_rng(&mut state, &mut rng);
+            assert!(state.approx_eq(&TwoQubitState::from_bell(bell), 1e-12));
+            counts[BellState::ALL.iter().position(|&b| b == bell).unwrap()] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_bell_measurement_distribution() {
+        // Bell states are eigenstates of the measurement
+        for (index, bell) in BellState::ALL.into_iter().enumerate() {
+            let counts = bell_counts(100, || TwoQubitState::from_bell(bell));
+            assert_eq!(counts[index], 100, "{:?}", bell);
+        }
+
+        // |00⟩ = (|Φ+⟩ + |Φ-⟩)/√2
+        let counts = bell_counts(4000, TwoQubitState::new_zero_zero);
+        assert_eq!(counts[2] + counts[3], 0);
+        assert!((1850..2150).contains(&counts[0]), "{:?}", counts);
+
+        // |+0⟩ spreads evenly over all four
+        let counts = bell_counts(4000, || {
+            TwoQubitState::from_qubits(&Qubit::new_plus(), &Qubit::new_zero())
+        });
+        for count in counts {
+            assert!((900..1100).contains(&count), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_measuring_ghz_qubit() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(765);
+        let ghz = ThreeQubitState::new_ghz();
+        let zero_zero = TwoQubitState::new_zero_zero();
+        let one_one = TwoQubitState::from_qubits(&Qubit::new_one(), &Qubit::new_one());
+
+        let mut ones = 0;
+        for trial in 0..600 {
+            let position = trial % 3;
+            let (result, residual) =
+                measure_three_qubit_with_rng(&ghz, position, Basis::Z, &mut rng);
+            let expected = if result { &one_one } else { &zero_zero };
+            assert!(residual.approx_eq(expected, 1e-12));
+            ones += result as usize;
+        }
+        assert!((250..350).contains(&ones), "{}", ones);
+
+        // An X measurement leaves a Bell pair: |Φ+⟩ on |+⟩, |Φ-⟩ on |-⟩
+        for position in 0..3 {
+            let (result, residual) =
+                measure_three_qubit_with_rng(&ghz, position, Basis::X, &mut rng);
+            assert!(residual.is_normalized());
+            assert!(!residual.is_product_state());
+            let bell = if result {
+                BellState::PhiMinus
+            } else {
+                BellState::PhiPlus
+            };
+            assert!(residual.approx_eq(&TwoQubitState::from_bell(bell), 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_measuring_w_qubit_keeps_order() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        // Measuring qubit 1 of |100⟩ leaves |10⟩ on qubits 0 and 2
+        let mut state = ThreeQubitState::new_zero_zero_zero();
+        crate::quantum::gates::apply_gate_to_three_qubit(
+            &mut state,
+            0,
+            &crate::quantum::gates::get_pauli_x_matrix(),
+        );
+        let (result, residual) = measure_three_qubit_with_rng(&state, 1, Basis::Z, &mut rng);
+        assert!(!result);
+        let one_zero = TwoQubitState::from_qubits(&Qubit::new_one(), &Qubit::new_zero());
+        assert!(residual.approx_eq(&one_zero, 1e-12));
+
+        // Measuring |0⟩ on one qubit of W leaves |Ψ+⟩ on the others
+        let w = ThreeQubitState::new_w();
+        loop {
+            let (result, residual) = measure_three_qubit_with_rng(&w, 2, Basis::Z, &mut rng);
+            if !result {
+                let psi_plus = TwoQubitState::new_bell_psi_plus();
+                assert!(residual.approx_eq(&psi_plus, 1e-12));
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_noisy_bell_measurement() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut psi = TwoQubitState::new_bell_psi_minus();
+        assert_eq!(
+            measure_bell_with_noise_and_rng(&mut psi, 1.0, BsmResolution::LinearOptics, &mut rng),
+            Some(BellState::PsiMinus)
+        );
+        // Linear optics cannot tell |Φ±⟩ apart from failure
+        let mut phi = TwoQubitState::new_bell_phi_plus();
+        assert_eq!(
+            measure_bell_with_noise_and_rng(&mut phi, 1.0, BsmResolution::LinearOptics, &mut rng),
+            None
+        );
+        assert_eq!(
+            measure_bell_with_noise_and_rng(&mut phi, 1.0, BsmResolution::Complete, &mut rng),
+            Some(BellState::PhiPlus)
+        );
+
+        // Lost attempts leave the state alone
+        let successes = (0..2000)
+            .filter(|_| {
+                let mut state = TwoQubitState::new_bell_psi_plus();
+                let outcome = measure_bell_with_noise_and_rng(
+                    &mut state,
+                    0.3,
+                    BsmResolution::Complete,
+                    &mut rng,
+                );
+                assert!(state.approx_eq(&TwoQubitState::new_bell_psi_plus(), 1e-12));
+                outcome.is_some()
+            })
+            .count();
+        assert!((500..700).contains(&successes), "{}", successes);
+    }
+
+    #[test]
+    fn test_partial_measurement_collapses_one_half() {
+        // |+⟩⊗|1⟩: measuring A leaves B alone
+        let mut state = TwoQubitState::from_qubits(&Qubit::new_plus(), &Qubit::new_one());
+        let a = measure_qubit_a(&mut state);
+        let expected = if a {
+            Qubit::new_one()
+        } else {
+            Qubit::new_zero()
+        };
+        assert!(state.approx_eq(
+            &TwoQubitState::from_qubits(&expected, &Qubit::new_one()),
+            1e-12
+        ));
+        assert!(measure_qubit_b(&mut state));
+    }
+
+    #[test]
+    fn test_rotated_basis_correlations_of_phi_plus() {
+        // ⟨XX⟩ = 1, ⟨YY⟩ = -1 for |Φ+⟩
+        let x = agreeing_outcomes(200, |state| {
+            (measure_qubit_a_x(state), measure_qubit_b_x(state))
+        });
+        assert_eq!(x, 200);
+        let y = agreeing_outcomes(200, |state| {
+            (measure_qubit_a_y(state), measure_qubit_b_y(state))
+        });
+        assert_eq!(y, 0);
+        // Mismatched bases are uncorrelated
+        let xz = agreeing_outcomes(2000, |state| {
+            (measure_qubit_a_x(state), measure_qubit_b(state))
+        });
+        assert!((850..1150).contains(&xz), "{}", xz);
+    }
+
     #[test]
     fn test_measure_zero_state() {
         let mut qubit = Qubit::new_zero();
@@ -166,23 +1329,69 @@ mod tests {
 
     #[test]
     fn test_measure_superposition() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(781);
         // Measure |+⟩ = (|0⟩ + |1⟩)/√2 many times
         let num_trials = 10000;
-        let mut num_ones = 0;
-
-        for _ in 0..num_trials {
-            let mut qubit = Qubit::new_plus();
-            if measure_z(&mut qubit) {
-                num_ones += 1;
-            }
-        }
+        let (_, num_ones) = sample_z(&Qubit::new_plus(), num_trials, &mut rng);
 
         // Should get roughly 50% ones
         let ratio = num_ones as f64 / num_trials as f64;
-        println!("{}", (ratio - 0.5).abs());
         assert!((ratio - 0.5).abs() < 0.05); // Within 5%
     }
 
+    #[test]
+    fn test_outcome_probabilities() {
+        let eigenstates = [
+            (Qubit::new_zero(), Basis::Z),
+            (Qubit::new_plus(), Basis::X),
+            (Qubit::new_iplus(), Basis::Y),
+        ];
+        for (qubit, basis) in eigenstates {
+            let (p0, p1) = outcome_probabilities(&qubit, basis);
+            assert!((p0 - 1.0).abs() < 1e-12 && p1.abs() < 1e-12, "{:?}", basis);
+        }
+        let (p0, p1) = outcome_probabilities(&Qubit::new_iminus(), Basis::Y);
+        assert!(p0.abs() < 1e-12 && (p1 - 1.0).abs() < 1e-12);
+
+        // Custom agrees with the named bases on a random state
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7810);
+        let qubit = Qubit::new_random_with_rng(&mut rng);
+        let custom = |theta: f64, phi: f64| Basis::Custom { theta, phi };
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        for (named, equivalent) in [
+            (Basis::Z, custom(0.0, 0.0)),
+            (Basis::X, custom(half_pi, 0.0)),
+            (Basis::Y, custom(half_pi, half_pi)),
+        ] {
+            let (a, _) = outcome_probabilities(&qubit, named);
+            let (b, _) = outcome_probabilities(&qubit, equivalent);
+            assert!((a - b).abs() < 1e-12, "{:?}", named);
+        }
+    }
+
+    #[test]
+    fn test_sampling_matches_born_rule() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7811);
+        let qubit = Qubit::from_bloch_angles(1.0, 0.4);
+        let snapshot = qubit.clone();
+        let n = 40_000;
+        for (basis, counts) in [
+            (Basis::Z, sample_z(&qubit, n, &mut rng)),
+            (Basis::X, sample_x(&qubit, n, &mut rng)),
+            (Basis::Y, sample_y(&qubit, n, &mut rng)),
+        ] {
+            assert_eq!(counts.0 + counts.1, n);
+            let (_, p1) = outcome_probabilities(&qubit, basis);
+            let observed = counts.1 as f64 / n as f64;
+            assert!((observed - p1).abs() < 0.01, "{:?}: {}", basis, observed);
+        }
+        // Sampling leaves the state untouched
+        assert_eq!(qubit.state, snapshot.state);
+    }
+
     #[test]
     fn test_perfect_measurement() {
         let config = MeasurementConfig::perfect();
@@ -193,7 +1402,7 @@ mod tests {
             &mut qubit,
             config.detector_efficiency,
             config.dark_count_rate,
-            config.measurement_error_rate,
+            config.confusion,
         );
 
         // Perfect measurement should give correct result
@@ -213,7 +1422,7 @@ mod tests {
                 &mut qubit,
                 config.detector_efficiency,
                 config.dark_count_rate,
-                config.measurement_error_rate,
+                config.confusion,
             );
 
             if result {
@@ -224,21 +1433,52 @@ mod tests {
 
         let error_rate = errors as f64 / num_trials as f64;
 
-        // Error rate should be approximately dark_count_rate + measurement_error_rate
+        // Error rate should be approximately dark_count_rate + readout flip rate
         // But interaction is complex, so just check it's reasonable
         assert!(error_rate > 0.0);
         assert!(error_rate < 0.1); // Less than 10%
     }
 
+    #[test]
+    fn test_noisy_x_and_y_measurement_statistics() {
+        let config = MeasurementConfig::realistic();
+        let num_trials = 1000;
+        let error_rate = |measure: &dyn Fn() -> bool| {
+            (0..num_trials).filter(|_| measure()).count() as f64 / num_trials as f64
+        };
+        let noisy_x = || {
+            measure_x_with_noise(
+                &mut Qubit::new_plus(),
+                config.detector_efficiency,
+                config.dark_count_rate,
+                config.confusion,
+            )
+        };
+        let noisy_y = || {
+            measure_y_with_noise(
+                &mut Qubit::new_iplus(),
+                config.detector_efficiency,
+                config.dark_count_rate,
+                config.confusion,
+            )
+        };
+
+        // Same detector as the Z-basis test: dark counts plus readout flips
+        for rate in [error_rate(&noisy_x), error_rate(&noisy_y)] {
+            assert!(rate > 0.0);
+            assert!(rate < 0.1);
+        }
+    }
+
     #[test]
     fn test_x_basis_measurement() {
         // Measure |+⟩ in X-basis should always give |+⟩ (false)
         let mut qubit = Qubit::new_plus();
         let result = measure_x(&mut qubit);
 
-        // Result is probabilistic but state should be valid
-        // Just check the measurement doesn't panic
-        assert!(result == true || result == false);
+        // H|+⟩ = |0⟩, so the outcome is deterministic
+        assert!(!result);
+        assert!(qubit.is_normalized());
     }
 
     #[test]
@@ -253,4 +1493,116 @@ mod tests {
 
         assert_eq!(result1, result2);
     }
+
+    #[test]
+    fn test_asymmetric_confusion_statistics() {
+        let config = MeasurementConfig::from_confusion(ConfusionMatrix {
+            p_report1_given0: 0.02,
+            p_report0_given1: 0.15,
+        });
+        let trials = 20000;
+
+        let zeros_reported_one = (0..trials)
+            .filter(|_| config.measure_z(&mut Qubit::new_zero()))
+            .count();
+        let ones_reported_zero = (0..trials)
+            .filter(|_| !config.measure_z(&mut Qubit::new_one()))
+            .count();
+
+        assert!((zeros_reported_one as f64 / trials as f64 - 0.02).abs() < 0.005);
+        assert!((ones_reported_zero as f64 / trials as f64 - 0.15).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_count_correction_recovers_probabilities() {
+        let confusion = ConfusionMatrix {
+            p_report1_given0: 0.05,
+            p_report0_given1: 0.2,
+        };
+        let config = MeasurementConfig::from_confusion(confusion);
+
+        // |ψ⟩ with P(1) = 0.3
+        let theta = 2.0 * 0.3_f64.sqrt().asin();
+        let observed = OutcomeCounts::from_outcomes(
+            (0..40000).map(|_| config.measure_z(&mut Qubit::from_bloch(theta, 0.0))),
+        );
+        // Raw: 0.7·0.05 + 0.3·0.8 = 0.275
+        assert!((observed.p_one() - 0.275).abs() < 0.01);
+
+        let corrected = confusion.correct_counts(observed);
+        assert!((corrected.p_one() - 0.3).abs() < 0.015);
+        assert!((corrected.total() - observed.total()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_detector_dead_time_drops_second_photon() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut detector = Detector::new(1.0, 0.0, 50.0, 0.0);
+
+        assert_eq!(detector.detect(true, 100.0, &mut rng), Some(100.0));
+        assert_eq!(detector.detect(true, 120.0, &mut rng), None);
+        assert_eq!(detector.detect(true, 160.0, &mut rng), Some(160.0));
+    }
+
+    #[test]
+    fn test_detector_jitter_spreads_click_times() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut detector = Detector::new(1.0, 0.0, 0.0, 0.5);
+        let offsets: Vec<f64> = (0..10_000)
+            .map(|_| detector.detect(true, 0.0, &mut rng).unwrap())
+            .collect();
+        let mean = offsets.iter().sum::<f64>() / offsets.len() as f64;
+        let variance =
+            offsets.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / offsets.len() as f64;
+        assert!(mean.abs() < 0.02);
+        assert!((variance.sqrt() - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_measurement_record_sifting_and_qber() {
+        let mut record = MeasurementRecord::new();
+        // 4 Z rounds with 1 error, 2 X rounds with 1 error, 2 mismatched
+        for (prepared, measured) in [(false, false), (true, true), (true, false), (false, false)] {
+            record.record(Basis::Z, prepared, measured);
+        }
+        record.record_round(Basis::X, Basis::X, true, true);
+        record.record_round(Basis::X, Basis::X, false, true);
+        record.record_round(Basis::Z, Basis::X, true, false);
+        record.record_round(Basis::X, Basis::Z, false, false);
+
+        assert_eq!(record.sifted_count(), 6);
+        assert_eq!(record.total_count(), 8);
+        assert_eq!(record.sifting_fraction(), 0.75);
+        assert!((record.qber() - 2.0 / 6.0).abs() < 1e-12);
+        assert_eq!(
+            record.qber_per_basis(),
+            vec![(Basis::Z, 0.25), (Basis::X, 0.5)]
+        );
+
+        let empty = MeasurementRecord::new();
+        assert_eq!(empty.qber(), 0.0);
+        assert_eq!(empty.sifting_fraction(), 0.0);
+        assert!(empty.is_consistent_with(0.1, 0.99));
+    }
+
+    #[test]
+    fn test_measurement_record_consistency_check() {
+        // 1000 sifted rounds with exactly 50 errors
+        let mut record = MeasurementRecord::new();
+        for i in 0..1000 {
+            record.record(Basis::Z, false, i % 20 == 0);
+        }
+        assert!(record.is_consistent_with(0.05, 0.99));
+        // σ ≈ 6.2 errors at 4%: 50 is 1.6σ off; at 11% it is 6σ off
+        assert!(record.is_consistent_with(0.04, 0.95));
+        assert!(!record.is_consistent_with(0.11, 0.99));
+        assert!(!record.is_consistent_with(0.0, 0.99));
+
+        let mut perfect = MeasurementRecord::new();
+        perfect.record(Basis::X, true, true);
+        assert!(perfect.is_consistent_with(0.0, 0.99));
+        assert!((normal_quantile(0.975) - 1.96).abs() < 1e-3);
+    }
 }
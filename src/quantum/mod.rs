@@ -1,9 +1,37 @@
 pub mod gates;
 pub mod measurement;
 pub mod noise;
+pub mod pauli_frame;
 pub mod state;
 
-pub use gates::{hadamard, identity, pauli_x, pauli_y, pauli_z};
-pub use measurement::{measure_x, measure_y, measure_z, measure_z_with_noise, MeasurementConfig};
-pub use noise::fidelity_after_decoherence;
-pub use state::{Qubit, TwoQubitState};
+pub use gates::{
+    apply_gate_to_qubit_a, apply_gate_to_qubit_b, apply_gate_to_three_qubit, apply_noisy_gate,
+    apply_noisy_two_qubit_gate, apply_two_qubit_gate, clifford_group_1q, cnot, cz, hadamard,
+    identity, is_unitary, pauli_x, pauli_y, pauli_z, process_fidelity, random_clifford_1q,
+    random_pauli, random_unitary_2x2, swap, Circuit, CompiledCircuit, GateErrorConfig,
+};
+pub use measurement::{
+    chsh_value, measure, measure_bell, measure_bell_with_noise, measure_bell_with_noise_and_rng,
+    measure_bell_with_rng, measure_in_basis, measure_in_basis_with_rng, measure_qubit_a,
+    measure_qubit_a_x, measure_qubit_a_y, measure_qubit_b, measure_qubit_b_x, measure_qubit_b_y,
+    measure_qubit_with_rng, measure_three_qubit, measure_three_qubit_with_rng, measure_with_rng,
+    measure_x, measure_x_with_config, measure_x_with_noise, measure_x_with_rng, measure_y,
+    measure_y_with_config, measure_y_with_noise, measure_y_with_rng, measure_z,
+    measure_z_with_config, measure_z_with_config_and_rng, measure_z_with_noise,
+    measure_z_with_noise_and_rng, measure_z_with_rng, outcome_probabilities, sample, sample_x,
+    sample_y, sample_z, Basis, BsmResolution, ConfusionMatrix, Detector, MeasurementConfig,
+    OutcomeCounts,
+};
+pub use noise::{
+    apply_amplitude_damping, apply_depolarizing, apply_kraus, apply_pauli_channel,
+    apply_pauli_twirl, apply_phase_damping, apply_single_qubit_depolarizing,
+    apply_single_qubit_depolarizing_to, apply_two_qubit_depolarizing,
+    decoherence_parameters_from_times, fidelity_after_decoherence, fidelity_after_depolarization,
+    two_qubit_depolarized_fidelity, ExponentialDecay, GaussianDephasing, NoiseModel,
+    PauliErrorRates, T1T2,
+};
+pub use pauli_frame::{PairHalf, PauliFrame};
+pub use state::{
+    von_neumann_entropy, BellState, DensityMatrix, QuantumRegister, Qubit, ThreeQubitState,
+    TwoQubitState, MAX_REGISTER_QUBITS,
+};
@@ -1,5 +1,10 @@
-use super::state::TwoQubitState;
+use super::gates::{get_pauli_x_matrix, get_pauli_y_matrix, get_pauli_z_matrix};
+use super::pauli_frame::PairHalf;
+use super::state::{BellState, DensityMatrix, TwoQubitState};
+use ndarray::linalg::kron;
+use ndarray::Array2;
 use num_complex::Complex64;
+use rand::Rng;
 
 /// Calculate fidelity after decoherence
 ///
@@ -10,15 +15,394 @@ pub fn fidelity_after_decoherence(
     elapsed_time_ms: f64,
     coherence_time_ms: f64,
 ) -> f64 {
+    debug_assert!(
+        elapsed_time_ms >= 0.0,
+        "Negative elapsed time {} ms",
+        elapsed_time_ms
+    );
     let decay_factor = (-elapsed_time_ms / coherence_time_ms).exp();
 
     // Fidelity decays as: F(t) = F_0 * e^(-t/T_coh)
     initial_fidelity * decay_factor
 }
 
+/// Law by which a stored pair's fidelity decays in memory
+pub trait NoiseModel {
+    /// Fidelity of a pair that started at `initial` after `elapsed_ms` in memory
+    fn fidelity(&self, initial: f64, elapsed_ms: f64) -> f64;
+}
+
+/// F(t) = F0 e^(-t/T), the `fidelity_after_decoherence` law
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExponentialDecay {
+    pub coherence_time_ms: f64,
+}
+
+impl NoiseModel for ExponentialDecay {
+    fn fidelity(&self, initial: f64, elapsed_ms: f64) -> f64 {
+        fidelity_after_decoherence(initial, elapsed_ms, self.coherence_time_ms)
+    }
+}
+
+/// Dephasing from quasi-static noise: coherence decays as e^(-(t/T2*)²)
+///
+/// Dephasing only mixes |Φ+⟩ with |Φ-⟩. For a Werner pair, whose |Φ-⟩
+/// weight is (1 - F0)/3, the coherence term shrinks:
+/// F(t) = (2F0 + 1)/6 + (4F0 - 1)/6 · e^(-(t/T2*)²)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GaussianDephasing {
+    pub t2_star_ms: f64,
+}
+
+impl NoiseModel for GaussianDephasing {
+    fn fidelity(&self, initial: f64, elapsed_ms: f64) -> f64 {
+        let decay = (-(elapsed_ms / self.t2_star_ms).powi(2)).exp();
+        dephased_werner_fidelity(initial, decay)
+    }
+}
+
+/// Fidelity of a Werner pair once its |00⟩⟨11| coherence is scaled by `decay`
+fn dephased_werner_fidelity(initial: f64, decay: f64) -> f64 {
+    (2.0 * initial + 1.0) / 6.0 + (4.0 * initial - 1.0) / 6.0 * decay
+}
+
+/// Amplitude damping (T1) plus dephasing (T2) on both halves of a Werner pair
+///
+/// Evaluated on the density matrix with `decoherence_parameters_from_times`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct T1T2 {
+    pub t1_ms: f64,
+    pub t2_ms: f64,
+}
+
+impl NoiseModel for T1T2 {
+    fn fidelity(&self, initial: f64, elapsed_ms: f64) -> f64 {
+        let (gamma, lambda) = decoherence_parameters_from_times(elapsed_ms, self.t1_ms, self.t2_ms);
+        // Werner parameter p with F = (3p + 1)/4
+        let mut rho = DensityMatrix::werner((4.0 * initial - 1.0) / 3.0);
+        for half in [PairHalf::A, PairHalf::B] {
+            apply_amplitude_damping(&mut rho, half, gamma);
+            apply_phase_damping(&mut rho, half, lambda);
+        }
+        rho.fidelity_with_pure(&TwoQubitState::new_bell_phi_plus())
+    }
+}
+
+/// Pair fidelity after one photon passes a depolarizing channel
+///
+/// With probability p the photon suffers a random Pauli error, which maps
+/// |Φ+⟩ to one of the three other Bell states: F → F(1-p) + (1-F)p/3
+pub fn fidelity_after_depolarization(fidelity: f64, depolarization_prob: f64) -> f64 {
+    fidelity * (1.0 - depolarization_prob) + (1.0 - fidelity) * depolarization_prob / 3.0
+}
+
+/// Apply Pauli `pauli_a` to qubit A and `pauli_b` to qubit B (0=I, 1=X, 2=Y, 3=Z)
+/// Basis ordering is |q_a q_b⟩: index = 2*a + b
+pub fn apply_pauli_pair(state: &mut TwoQubitState, pauli_a: usize, pauli_b: usize) {
+    let apply = |amplitudes: [Complex64; 2], pauli: usize| -> [Complex64; 2] {
+        let [zero, one] = amplitudes;
+        let i = Complex64::new(0.0, 1.0);
+        match pauli {
+            0 => [zero, one],
+            1 => [one, zero],
+            2 => [-i * one, i * zero],
+            3 => [zero, -one],
+            _ => panic!("Pauli index must be 0-3, got {}", pauli),
+        }
+    };
+
+    // Qubit A acts on pairs (|0b⟩, |1b⟩)
+    for b in 0..2 {
+        let [new_0, new_1] = apply([state.state[b], state.state[2 + b]], pauli_a);
+        state.state[b] = new_0;
+        state.state[2 + b] = new_1;
+    }
+    // Qubit B acts on pairs (|a0⟩, |a1⟩)
+    for a in 0..2 {
+        let [new_0, new_1] = apply([state.state[2 * a], state.state[2 * a + 1]], pauli_b);
+        state.state[2 * a] = new_0;
+        state.state[2 * a + 1] = new_1;
+    }
+}
+
+/// Two-qubit depolarizing channel (single trajectory)
+///
+/// With probability p, applies one of the 15 non-identity two-qubit Paulis
+/// chosen uniformly at random
+pub fn apply_two_qubit_depolarizing(state: &mut TwoQubitState, p: f64, rng: &mut impl Rng) {
+    if rng.random::<f64>() >= p {
+        return;
+    }
+    // 1..16 skips I⊗I
+    let pauli = rng.random_range(1..16);
+    apply_pauli_pair(state, pauli / 4, pauli % 4);
+}
+
+/// Single-qubit depolarizing channel on one half (single trajectory)
+///
+/// With probability p the qubit is replaced by the maximally mixed state,
+/// sampled as a uniformly random I, X, Y or Z. Averaged over trajectories
+/// |Φ+⟩ keeps fidelity 1 - 3p/4.
+pub fn apply_single_qubit_depolarizing(
+    state: &mut TwoQubitState,
+    half: PairHalf,
+    p: f64,
+    rng: &mut impl Rng,
+) {
+    if rng.random::<f64>() >= p {
+        return;
+    }
+    let pauli = rng.random_range(0..4);
+    match half {
+        PairHalf::A => apply_pauli_pair(state, pauli, 0),
+        PairHalf::B => apply_pauli_pair(state, 0, pauli),
+    }
+}
+
+/// Independent Pauli channel on each half of a pair (single trajectory)
+///
+/// Each qubit gets X, Y or Z with probability `px`, `py`, `pz`, else I.
+/// Averaged over trajectories |Φ+⟩ keeps fidelity p_I² + px² + py² + pz²,
+/// since the same Pauli on both halves leaves it unchanged
+pub fn apply_pauli_twirl(state: &mut TwoQubitState, px: f64, py: f64, pz: f64, rng: &mut impl Rng) {
+    let rates = PauliErrorRates {
+        p_x: px,
+        p_y: py,
+        p_z: pz,
+    };
+    let pauli_a = rates.sample(rng);
+    let pauli_b = rates.sample(rng);
+    apply_pauli_pair(state, pauli_a, pauli_b);
+}
+
+/// Two-qubit depolarizing channel on a density matrix
+/// ρ → (1-p)ρ + p I/4
+pub fn apply_depolarizing(rho: &mut DensityMatrix, p: f64) {
+    let mixed = Array2::<Complex64>::eye(4).mapv(|x| x * p * rho.trace() / 4.0);
+    rho.matrix = rho.matrix.mapv(|x| x * (1.0 - p)) + mixed;
+}
+
+/// Single-qubit depolarizing channel on one half of a density matrix
+/// ρ → (1-p)ρ + p (I/2 ⊗ Tr_half ρ)
+pub fn apply_single_qubit_depolarizing_to(rho: &mut DensityMatrix, half: PairHalf, p: f64) {
+    apply_pauli_channel(rho, half, &PauliErrorRates::depolarizing(p));
+}
+
+/// Pauli channel on one half of a density matrix
+/// ρ → p_I ρ + p_X XρX + p_Y YρY + p_Z ZρZ
+pub fn apply_pauli_channel(rho: &mut DensityMatrix, half: PairHalf, rates: &PauliErrorRates) {
+    let on_half = |pauli: Array2<Complex64>| match half {
+        PairHalf::A => kron(&pauli, &Array2::eye(2)),
+        PairHalf::B => kron(&Array2::eye(2), &pauli),
+    };
+    let mut matrix = rho.matrix.mapv(|x| x * rates.p_identity());
+    for (pauli, probability) in [
+        (get_pauli_x_matrix(), rates.p_x),
+        (get_pauli_y_matrix(), rates.p_y),
+        (get_pauli_z_matrix(), rates.p_z),
+    ] {
+        // Paulis are Hermitian, so PρP† = PρP
+        let pauli = on_half(pauli);
+        matrix = matrix + pauli.dot(&rho.matrix).dot(&pauli).mapv(|x| x * probability);
+    }
+    rho.matrix = matrix;
+}
+
+/// Apply single-qubit Kraus operators to one half: ρ → Σ K ρ K†
+pub fn apply_kraus(rho: &mut DensityMatrix, half: PairHalf, kraus: &[Array2<Complex64>]) {
+    let mut matrix = Array2::zeros((4, 4));
+    for operator in kraus {
+        let operator = match half {
+            PairHalf::A => kron(operator, &Array2::eye(2)),
+            PairHalf::B => kron(&Array2::eye(2), operator),
+        };
+        let adjoint = operator.t().mapv(|x| x.conj());
+        matrix = matrix + operator.dot(&rho.matrix).dot(&adjoint);
+    }
+    rho.matrix = matrix;
+}
+
+/// Amplitude damping (T1) on one half: |1⟩ decays to |0⟩ with probability γ
+/// K0 = [[1, 0], [0, √(1-γ)]], K1 = [[0, √γ], [0, 0]]
+pub fn apply_amplitude_damping(rho: &mut DensityMatrix, half: PairHalf, gamma: f64) {
+    let zero = Complex64::new(0.0, 0.0);
+    let one = Complex64::new(1.0, 0.0);
+    let kraus = [
+        ndarray::array![
+            [one, zero],
+            [zero, Complex64::new((1.0 - gamma).sqrt(), 0.0)]
+        ],
+        ndarray::array![[zero, Complex64::new(gamma.sqrt(), 0.0)], [zero, zero]],
+    ];
+    apply_kraus(rho, half, &kraus);
+}
+
+/// Phase damping (pure dephasing) on one half: coherences shrink by √(1-λ)
+/// K0 = [[1, 0], [0, √(1-λ)]], K1 = [[0, 0], [0, √λ]]
+pub fn apply_phase_damping(rho: &mut DensityMatrix, half: PairHalf, lambda: f64) {
+    let zero = Complex64::new(0.0, 0.0);
+    let one = Complex64::new(1.0, 0.0);
+    let kraus = [
+        ndarray::array![
+            [one, zero],
+            [zero, Complex64::new((1.0 - lambda).sqrt(), 0.0)]
+        ],
+        ndarray::array![[zero, zero], [zero, Complex64::new(lambda.sqrt(), 0.0)]],
+    ];
+    apply_kraus(rho, half, &kraus);
+}
+
+/// (γ, λ) for amplitude then phase damping after `elapsed_ms` of storage
+///
+/// γ = 1 - e^(-t/T1). Amplitude damping alone shrinks coherences by
+/// e^(-t/2T1), so dephasing supplies the rest of e^(-t/T2):
+/// λ = 1 - e^(-2t/T2 + t/T1), clamped to 0 when T2 > 2T1.
+/// Use f64::INFINITY to disable either process.
+pub fn decoherence_parameters_from_times(elapsed_ms: f64, t1_ms: f64, t2_ms: f64) -> (f64, f64) {
+    debug_assert!(elapsed_ms >= 0.0, "Negative elapsed time {} ms", elapsed_ms);
+    let gamma = 1.0 - (-elapsed_ms / t1_ms).exp();
+    let lambda = 1.0 - (-2.0 * elapsed_ms / t2_ms + elapsed_ms / t1_ms).exp();
+    (gamma, lambda.clamp(0.0, 1.0))
+}
+
+/// Fidelity map of the two-qubit depolarizing channel for Werner pairs
+///
+/// 3 of the 15 Paulis leave |Φ+⟩ unchanged and 4 map each other Bell state
+/// onto it: F → (1-p)F + p(F/5 + 4(1-F)/15)
+pub fn two_qubit_depolarized_fidelity(fidelity: f64, p: f64) -> f64 {
+    (1.0 - p) * fidelity + p * (fidelity / 5.0 + 4.0 * (1.0 - fidelity) / 15.0)
+}
+
+/// Single-qubit Pauli channel: probabilities of X, Y and Z errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PauliErrorRates {
+    pub p_x: f64,
+    pub p_y: f64,
+    pub p_z: f64,
+}
+
+impl PauliErrorRates {
+    /// No error
+    pub fn ideal() -> Self {
+        PauliErrorRates {
+            p_x: 0.0,
+            p_y: 0.0,
+            p_z: 0.0,
+        }
+    }
+
+    /// Depolarizing channel: replaced by I/2 with probability p,
+    /// i.e. each Pauli with probability p/4
+    pub fn depolarizing(p: f64) -> Self {
+        PauliErrorRates {
+            p_x: p / 4.0,
+            p_y: p / 4.0,
+            p_z: p / 4.0,
+        }
+    }
+
+    /// Errors on half B that twirl |Φ+⟩ into the Bell-diagonal part of `rho`
+    /// X, Y and Z take |Φ+⟩ to |Ψ+⟩, |Ψ-⟩ and |Φ-⟩ up to phase
+    pub fn from_bell_diagonal(rho: &DensityMatrix) -> Self {
+        let weight = |bell| rho.fidelity_with_pure(&TwoQubitState::from_bell(bell));
+        PauliErrorRates {
+            p_x: weight(BellState::PsiPlus),
+            p_y: weight(BellState::PsiMinus),
+            p_z: weight(BellState::PhiMinus),
+        }
+    }
+
+    /// Equivalent error on one half of a Werner pair with fidelity F
+    /// Each non-identity Pauli occurs with probability (1-F)/3
+    pub fn werner(fidelity: f64) -> Self {
+        let p = (1.0 - fidelity) / 3.0;
+        PauliErrorRates {
+            p_x: p,
+            p_y: p,
+            p_z: p,
+        }
+    }
+
+    /// Pauli-twirled memory noise after `elapsed_ms` of storage
+    ///
+    /// Amplitude damping (T1) gives p_x = p_y = (1 - e^(-t/T1))/4;
+    /// the remaining coherence loss (T2) is dephasing:
+    /// p_z = (1 - e^(-t/T2))/2 - p_x. Use f64::INFINITY to disable either.
+    pub fn from_t1_t2(elapsed_ms: f64, t1_ms: f64, t2_ms: f64) -> Self {
+        debug_assert!(elapsed_ms >= 0.0, "Negative elapsed time {} ms", elapsed_ms);
+        let relaxation = 1.0 - (-elapsed_ms / t1_ms).exp();
+        let coherence_loss = 1.0 - (-elapsed_ms / t2_ms).exp();
+        let p_xy = relaxation / 4.0;
+        PauliErrorRates {
+            p_x: p_xy,
+            p_y: p_xy,
+            p_z: (coherence_loss / 2.0 - p_xy).max(0.0),
+        }
+    }
+
+    /// Probability of no error
+    pub fn p_identity(&self) -> f64 {
+        1.0 - self.p_x - self.p_y - self.p_z
+    }
+
+    /// Channel equivalent to applying `self` then `other`
+    /// (Pauli products up to phase: XZ ~ Y, XY ~ Z, YZ ~ X)
+    pub fn then(&self, other: &PauliErrorRates) -> PauliErrorRates {
+        let (a_i, b_i) = (self.p_identity(), other.p_identity());
+        PauliErrorRates {
+            p_x: a_i * other.p_x + self.p_x * b_i + self.p_y * other.p_z + self.p_z * other.p_y,
+            p_y: a_i * other.p_y + self.p_y * b_i + self.p_x * other.p_z + self.p_z * other.p_x,
+            p_z: a_i * other.p_z + self.p_z * b_i + self.p_x * other.p_y + self.p_y * other.p_x,
+        }
+    }
+
+    /// Channel equivalent to applying `self` `times` times; `times` may be fractional
+    ///
+    /// Powers the Pauli eigenvalues λ_X = 1 - 2(p_y + p_z) etc., clamped at 0,
+    /// so `self` should be a per-step rate well below 1/2
+    pub fn repeated(&self, times: f64) -> PauliErrorRates {
+        let decay = |flips: f64| (1.0 - 2.0 * flips).max(0.0).powf(times);
+        let (lambda_x, lambda_y, lambda_z) = (
+            decay(self.p_y + self.p_z),
+            decay(self.p_x + self.p_z),
+            decay(self.p_x + self.p_y),
+        );
+        PauliErrorRates {
+            p_x: (1.0 + lambda_x - lambda_y - lambda_z) / 4.0,
+            p_y: (1.0 - lambda_x + lambda_y - lambda_z) / 4.0,
+            p_z: (1.0 - lambda_x - lambda_y + lambda_z) / 4.0,
+        }
+    }
+
+    /// Draw a Pauli index (0=I, 1=X, 2=Y, 3=Z) with these probabilities
+    pub fn sample(&self, rng: &mut impl Rng) -> usize {
+        let draw = rng.random::<f64>();
+        if draw < self.p_x {
+            1
+        } else if draw < self.p_x + self.p_y {
+            2
+        } else if draw < self.p_x + self.p_y + self.p_z {
+            3
+        } else {
+            0
+        }
+    }
+
+    /// Probability that a Z-basis outcome flips (X or Y error)
+    pub fn bit_flip_probability(&self) -> f64 {
+        self.p_x + self.p_y
+    }
+
+    /// Probability that an X-basis outcome flips (Z or Y error)
+    pub fn phase_flip_probability(&self) -> f64 {
+        self.p_z + self.p_y
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::quantum::Qubit;
 
     #[test]
     fn test_fidelity_calculation() {
@@ -45,4 +429,293 @@ mod tests {
         assert!((fidelity - expected).abs() < 1e-10);
         assert!(fidelity < 0.01);
     }
+
+    #[test]
+    fn test_depolarization_fidelity_map() {
+        assert!((fidelity_after_depolarization(0.9, 0.0) - 0.9).abs() < 1e-12);
+        // Fully depolarizing one photon of a perfect pair leaves F = 0
+        assert!(fidelity_after_depolarization(1.0, 1.0).abs() < 1e-12);
+        assert!((fidelity_after_depolarization(1.0, 0.1) - 0.9).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_single_qubit_depolarizing_trajectories_average_to_channel() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(762);
+        let bell = TwoQubitState::new_bell_phi_plus();
+        let p = 0.2;
+        let trials = 20_000;
+
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let mut state = bell.clone();
+            apply_single_qubit_depolarizing(&mut state, PairHalf::B, p, &mut rng);
+            total += state.fidelity(&bell);
+        }
+        let expected = 1.0 - 3.0 * p / 4.0;
+        assert!((total / trials as f64 - expected).abs() < 0.01);
+
+        for half in [PairHalf::A, PairHalf::B] {
+            let mut rho = DensityMatrix::from_pure(&bell);
+            apply_single_qubit_depolarizing_to(&mut rho, half, p);
+            assert!((rho.fidelity_with_pure(&bell) - expected).abs() < 1e-12);
+            assert!((rho.trace() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_two_qubit_depolarizing_density_matrix_is_werner() {
+        let bell = TwoQubitState::new_bell_phi_plus();
+        let mut rho = DensityMatrix::from_pure(&bell);
+        apply_depolarizing(&mut rho, 0.4);
+        let werner = DensityMatrix::werner(0.6);
+        assert!((&rho.matrix - &werner.matrix)
+            .iter()
+            .all(|x| x.norm() < 1e-12));
+
+        // The Pauli-error convention of `fidelity_after_depolarization`
+        let mut rho = DensityMatrix::from_pure(&bell);
+        apply_pauli_channel(&mut rho, PairHalf::A, &PauliErrorRates::werner(0.9));
+        let expected = fidelity_after_depolarization(1.0, 0.1);
+        assert!((rho.fidelity_with_pure(&bell) - expected).abs() < 1e-12);
+    }
+
+    /// ⟨O⟩ on half B of ρ
+    fn expectation_on_b(rho: &DensityMatrix, observable: Array2<Complex64>) -> f64 {
+        let observable = kron(&Array2::eye(2), &observable);
+        observable
+            .dot(&rho.matrix)
+            .diag()
+            .iter()
+            .sum::<Complex64>()
+            .re
+    }
+
+    #[test]
+    fn test_amplitude_damping_relaxes_toward_zero() {
+        let excited = TwoQubitState::from_qubits(&Qubit::new_zero(), &Qubit::new_one());
+        let mut rho = DensityMatrix::from_pure(&excited);
+        apply_amplitude_damping(&mut rho, PairHalf::B, 0.3);
+        // P(|1⟩) = 1 - γ, read as (1 - ⟨Z⟩)/2
+        let p_one = (1.0 - expectation_on_b(&rho, get_pauli_z_matrix())) / 2.0;
+        assert!((p_one - 0.7).abs() < 1e-12);
+        assert!((rho.trace() - 1.0).abs() < 1e-12);
+
+        apply_amplitude_damping(&mut rho, PairHalf::B, 1.0);
+        assert!((expectation_on_b(&rho, get_pauli_z_matrix()) - 1.0).abs() < 1e-12);
+        // Half A is untouched
+        assert!((rho.purity() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_phase_damping_keeps_populations_and_kills_coherence() {
+        let plus = TwoQubitState::from_qubits(&Qubit::new_zero(), &Qubit::new_plus());
+        for lambda in [0.0, 0.36, 1.0] {
+            let mut rho = DensityMatrix::from_pure(&plus);
+            apply_phase_damping(&mut rho, PairHalf::B, lambda);
+            assert!(expectation_on_b(&rho, get_pauli_z_matrix()).abs() < 1e-12);
+            let expected_x = (1.0 - lambda).sqrt();
+            assert!((expectation_on_b(&rho, get_pauli_x_matrix()) - expected_x).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_decoherence_parameters_from_times() {
+        let (gamma, lambda) = decoherence_parameters_from_times(10.0, 10.0, 20.0);
+        assert!((gamma - (1.0 - (-1.0_f64).exp())).abs() < 1e-12);
+        // T2 = 2 T1: relaxation alone accounts for the dephasing
+        assert!(lambda.abs() < 1e-12);
+
+        let (gamma, lambda) = decoherence_parameters_from_times(5.0, f64::INFINITY, 10.0);
+        assert_eq!(gamma, 0.0);
+        assert!((lambda - (1.0 - (-1.0_f64).exp())).abs() < 1e-12);
+
+        // Coherence of |+⟩ decays as e^(-t/T2) overall
+        let (t, t1, t2) = (3.0, 8.0, 5.0);
+        let (gamma, lambda) = decoherence_parameters_from_times(t, t1, t2);
+        let plus = TwoQubitState::from_qubits(&Qubit::new_zero(), &Qubit::new_plus());
+        let mut rho = DensityMatrix::from_pure(&plus);
+        apply_amplitude_damping(&mut rho, PairHalf::B, gamma);
+        apply_phase_damping(&mut rho, PairHalf::B, lambda);
+        let coherence = expectation_on_b(&rho, get_pauli_x_matrix());
+        assert!((coherence - (-t / t2).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_noise_models_at_one_time_constant() {
+        let (initial, t) = (0.95, 10.0);
+        let models: [(&dyn NoiseModel, f64); 3] = [
+            (
+                &ExponentialDecay {
+                    coherence_time_ms: t,
+                },
+                initial * (-1.0_f64).exp(),
+            ),
+            (
+                &GaussianDephasing { t2_star_ms: t },
+                dephased_werner_fidelity(initial, (-1.0_f64).exp()),
+            ),
+            // Pure dephasing on both halves: coherence e^(-2), populations kept
+            (
+                &T1T2 {
+                    t1_ms: f64::INFINITY,
+                    t2_ms: t,
+                },
+                dephased_werner_fidelity(initial, (-2.0_f64).exp()),
+            ),
+        ];
+        for (model, expected) in models {
+            assert!((model.fidelity(initial, 0.0) - initial).abs() < 1e-12);
+            assert!((model.fidelity(initial, t) - expected).abs() < 1e-12);
+        }
+
+        // Same-pair comparison: exponential decay is harshest, since it ignores
+        // that dephasing alone cannot push fidelity below (2F0 + 1)/6
+        let exponential = models[0].0.fidelity(initial, t);
+        let gaussian = models[1].0.fidelity(initial, t);
+        let t1_t2 = T1T2 { t1_ms: t, t2_ms: t }.fidelity(initial, t);
+        assert!(exponential < t1_t2 && t1_t2 < gaussian);
+    }
+
+    #[test]
+    fn test_pauli_pair_on_bell_state() {
+        // X⊗X|Φ+⟩ = |Φ+⟩, X⊗I|Φ+⟩ = |Ψ+⟩ (orthogonal)
+        let bell = TwoQubitState::new_bell_phi_plus();
+
+        let mut state = bell.clone();
+        apply_pauli_pair(&mut state, 1, 1);
+        assert!((state.fidelity(&bell) - 1.0).abs() < 1e-10);
+
+        let mut state = bell.clone();
+        apply_pauli_pair(&mut state, 1, 0);
+        assert!(state.fidelity(&bell).abs() < 1e-10);
+        assert!(state.is_normalized());
+    }
+
+    #[test]
+    fn test_two_qubit_depolarizing_zero_is_identity() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let bell = TwoQubitState::new_bell_phi_plus();
+
+        let mut state = bell.clone();
+        for _ in 0..100 {
+            apply_two_qubit_depolarizing(&mut state, 0.0, &mut rng);
+        }
+        assert!((state.fidelity(&bell) - 1.0).abs() < 1e-10);
+        assert!((two_qubit_depolarized_fidelity(0.9, 0.0) - 0.9).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_two_qubit_depolarizing_matches_analytic_map() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let bell = TwoQubitState::new_bell_phi_plus();
+        let p = 0.05;
+        let trials = 20_000;
+
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let mut state = bell.clone();
+            apply_two_qubit_depolarizing(&mut state, p, &mut rng);
+            total += state.fidelity(&bell);
+        }
+        let average = total / trials as f64;
+
+        // Expected 1 - 4p/5 = 0.96
+        let expected = two_qubit_depolarized_fidelity(1.0, p);
+        assert!((average - expected).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_pure_dephasing_only_flips_phase() {
+        let errors = PauliErrorRates::from_t1_t2(10.0, f64::INFINITY, 10.0);
+        assert_eq!(errors.bit_flip_probability(), 0.0);
+        let expected = (1.0 - (-1.0_f64).exp()) / 2.0;
+        assert!((errors.phase_flip_probability() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pauli_composition() {
+        let a = PauliErrorRates::from_t1_t2(5.0, f64::INFINITY, 10.0);
+        let combined = a.then(&a);
+        // Two independent phase flips cancel when both occur
+        let q = a.p_z;
+        assert!((combined.p_z - 2.0 * q * (1.0 - q)).abs() < 1e-12);
+        assert!((PauliErrorRates::ideal().then(&a).p_z - a.p_z).abs() < 1e-12);
+
+        // Werner noise is basis-symmetric
+        let werner = PauliErrorRates::werner(0.91);
+        assert!((werner.bit_flip_probability() - 0.06).abs() < 1e-12);
+        assert!((werner.phase_flip_probability() - 0.06).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bell_diagonal_errors_round_trip() {
+        let rates = PauliErrorRates {
+            p_x: 0.01,
+            p_y: 0.02,
+            p_z: 0.05,
+        };
+        let mut rho = DensityMatrix::from_pure(&TwoQubitState::new_bell_phi_plus());
+        apply_pauli_channel(&mut rho, PairHalf::A, &rates);
+        let read = PauliErrorRates::from_bell_diagonal(&rho);
+        assert!((read.p_x - 0.01).abs() < 1e-12);
+        assert!((read.p_y - 0.02).abs() < 1e-12);
+        assert!((read.p_z - 0.05).abs() < 1e-12);
+
+        let werner = PauliErrorRates::from_bell_diagonal(&DensityMatrix::werner_with_fidelity(0.7));
+        assert!((werner.p_identity() - 0.7).abs() < 1e-12);
+        assert!((werner.p_y - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_repeated_pauli_channel() {
+        let rates = PauliErrorRates {
+            p_x: 0.01,
+            p_y: 0.02,
+            p_z: 0.05,
+        };
+        let twice = rates.repeated(2.0);
+        let composed = rates.then(&rates);
+        assert!((twice.p_x - composed.p_x).abs() < 1e-12);
+        assert!((twice.p_y - composed.p_y).abs() < 1e-12);
+        assert!((twice.p_z - composed.p_z).abs() < 1e-12);
+
+        let once = rates.repeated(1.0);
+        assert!((once.p_z - rates.p_z).abs() < 1e-12);
+        assert!(rates.repeated(0.0).p_identity() > 1.0 - 1e-12);
+        // Long storage tends to the fully depolarized qubit
+        assert!((rates.repeated(1e4).p_identity() - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pauli_twirl_trajectories_average_to_channel() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(779);
+        let bell = TwoQubitState::new_bell_phi_plus();
+        let (px, py, pz) = (0.03, 0.05, 0.08);
+        let trials = 10_000;
+
+        let mut total = 0.0;
+        for _ in 0..trials {
+            let mut state = bell.clone();
+            apply_pauli_twirl(&mut state, px, py, pz, &mut rng);
+            assert!(state.is_normalized());
+            total += state.fidelity(&bell);
+        }
+        let rates = PauliErrorRates {
+            p_x: px,
+            p_y: py,
+            p_z: pz,
+        };
+        let expected = rates.then(&rates).p_identity();
+        assert!((total / trials as f64 - expected).abs() < 0.01);
+
+        // The same channel on both halves of the density matrix agrees exactly
+        let mut rho = DensityMatrix::from_pure(&bell);
+        apply_pauli_channel(&mut rho, PairHalf::A, &rates);
+        apply_pauli_channel(&mut rho, PairHalf::B, &rates);
+        assert!((rho.fidelity_with_pure(&bell) - expected).abs() < 1e-12);
+    }
 }
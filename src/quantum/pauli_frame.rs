@@ -0,0 +1,191 @@
+use super::measurement::Basis;
+use super::noise::apply_pauli_pair;
+use super::state::TwoQubitState;
+use rand::Rng;
+
+/// One qubit of a `TwoQubitState`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairHalf {
+    A,
+    B,
+}
+
+/// Pauli correction X^x Z^z owed to one half of a |Φ+⟩ pair, up to global phase
+///
+/// A frame on either half of |Φ+⟩ is equivalent up to phase, so a pair
+/// carries one frame whichever end applies it. Frames compose by XOR and
+/// are their own inverse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauliFrame {
+    /// Bit-flip component
+    pub x: bool,
+    /// Phase-flip component
+    pub z: bool,
+}
+
+impl PauliFrame {
+    pub const IDENTITY: PauliFrame = PauliFrame { x: false, z: false };
+
+    pub fn new(x: bool, z: bool) -> Self {
+        PauliFrame { x, z }
+    }
+
+    /// Uniformly random frame, e.g. the outcome of an ideal Bell-state measurement
+    pub fn random(rng: &mut impl Rng) -> Self {
+        PauliFrame::new(rng.random(), rng.random())
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == PauliFrame::IDENTITY
+    }
+
+    /// Apply `self`, then `other`
+    pub fn then(&self, other: &PauliFrame) -> PauliFrame {
+        PauliFrame::new(self.x ^ other.x, self.z ^ other.z)
+    }
+
+    /// Paulis square to the identity (up to phase)
+    pub fn inverse(&self) -> PauliFrame {
+        *self
+    }
+
+    /// Pauli index used by `apply_pauli_pair` (0=I, 1=X, 2=Y, 3=Z)
+    fn pauli_index(&self) -> usize {
+        match (self.x, self.z) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (true, true) => 2,
+            (false, true) => 3,
+        }
+    }
+
+    /// Apply the correction to one half of `state`
+    pub fn apply_to(&self, state: &mut TwoQubitState, half: PairHalf) {
+        match half {
+            PairHalf::A => apply_pauli_pair(state, self.pauli_index(), 0),
+            PairHalf::B => apply_pauli_pair(state, 0, self.pauli_index()),
+        }
+    }
+
+    /// Outcome the corrected qubit would have given, from the raw outcome of
+    /// measuring the uncorrected one in `basis`
+    ///
+    /// Lets consumers fold the frame into measurement interpretation instead
+    /// of touching the state: X flips Z-basis outcomes, Z flips X-basis ones,
+    /// and either flips Y-basis ones. A custom direction is rotated rather
+    /// than flipped by a correction, so it needs an identity frame.
+    pub fn reinterpret_outcome(&self, basis: Basis, raw_outcome: bool) -> bool {
+        match basis {
+            Basis::Z => raw_outcome ^ self.x,
+            Basis::X => raw_outcome ^ self.z,
+            Basis::Y => raw_outcome ^ self.x ^ self.z,
+            Basis::Custom { .. } => {
+                assert!(
+                    self.is_identity(),
+                    "Pauli corrections do not map a custom basis onto itself"
+                );
+                raw_outcome
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_complex::Complex64;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Sample both halves of `state` measured in `basis`
+    fn measure_pair(state: &TwoQubitState, basis: Basis, rng: &mut impl Rng) -> (bool, bool) {
+        let amplitudes: Vec<Complex64> = match basis {
+            Basis::Z => state.state.to_vec(),
+            // H⊗H has entries (-1)^popcount(i & j) / 2
+            Basis::X => (0..4)
+                .map(|i: usize| {
+                    (0..4)
+                        .map(|j: usize| {
+                            let sign = if (i & j).count_ones().is_multiple_of(2) {
+                                0.5
+                            } else {
+                                -0.5
+                            };
+                            state.state[j] * sign
+                        })
+                        .sum()
+                })
+                .collect(),
+            Basis::Y | Basis::Custom { .. } => unreachable!("only Z and X are compared"),
+        };
+        let draw = rng.random::<f64>();
+        let mut cumulative = 0.0;
+        for (index, amplitude) in amplitudes.iter().enumerate() {
+            cumulative += amplitude.norm_sqr();
+            if draw < cumulative {
+                return (index >= 2, index % 2 == 1);
+            }
+        }
+        (true, true)
+    }
+
+    #[test]
+    fn test_composition_and_inverse() {
+        let x = PauliFrame::new(true, false);
+        let z = PauliFrame::new(false, true);
+        assert_eq!(x.then(&z), PauliFrame::new(true, true));
+        assert!(x.then(&x.inverse()).is_identity());
+
+        let mut state = TwoQubitState::new_bell_phi_plus();
+        let y = x.then(&z);
+        y.apply_to(&mut state, PairHalf::A);
+        y.inverse().apply_to(&mut state, PairHalf::A);
+        assert!((state.fidelity(&TwoQubitState::new_bell_phi_plus()) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_frame_on_either_half_is_equivalent() {
+        for frame in [
+            PauliFrame::new(true, false),
+            PauliFrame::new(false, true),
+            PauliFrame::new(true, true),
+        ] {
+            let mut on_a = TwoQubitState::new_bell_phi_plus();
+            let mut on_b = TwoQubitState::new_bell_phi_plus();
+            frame.apply_to(&mut on_a, PairHalf::A);
+            frame.apply_to(&mut on_b, PairHalf::B);
+            assert!((on_a.fidelity(&on_b) - 1.0).abs() < 1e-12);
+            // A nontrivial frame moves |Φ+⟩ to an orthogonal Bell state
+            assert!(on_a.fidelity(&TwoQubitState::new_bell_phi_plus()) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_eager_and_lazy_corrections_agree() {
+        for frame in [
+            PauliFrame::IDENTITY,
+            PauliFrame::new(true, false),
+            PauliFrame::new(false, true),
+            PauliFrame::new(true, true),
+        ] {
+            // The uncorrected physical state
+            let mut physical = TwoQubitState::new_bell_phi_plus();
+            frame.apply_to(&mut physical, PairHalf::B);
+            let mut corrected = physical.clone();
+            frame.inverse().apply_to(&mut corrected, PairHalf::B);
+
+            for basis in [Basis::Z, Basis::X] {
+                let mut eager_rng = StdRng::seed_from_u64(17);
+                let mut lazy_rng = StdRng::seed_from_u64(17);
+                for _ in 0..200 {
+                    let eager = measure_pair(&corrected, basis, &mut eager_rng);
+                    let (a, raw_b) = measure_pair(&physical, basis, &mut lazy_rng);
+                    let lazy = (a, frame.reinterpret_outcome(basis, raw_b));
+                    assert_eq!(eager, lazy);
+                    assert_eq!(eager.0, eager.1);
+                }
+            }
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use ndarray::{Array1, Array2};
 use num_complex::Complex64;
+use std::fmt;
 
 /// A single qubit state represented as a state vector
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Qubit {
     /// State vector: [α, β] for α|0⟩ + β|1⟩
+    #[cfg_attr(feature = "serde", serde(with = "amplitudes"))]
     pub state: Array1<Complex64>,
 }
 
@@ -79,20 +82,16 @@ impl Qubit {
 
     /// Create a qubit with random state (uniformly distributed on Bloch sphere)
     pub fn new_random() -> Self {
-        use rand::Rng;
-        let mut rng = rand::rng();
-
-        // Generate random complex amplitudes
-        let alpha_re = rng.random::<f64>() * 2.0 - 1.0; // [-1, 1]
-        let alpha_im = rng.random::<f64>() * 2.0 - 1.0;
-        let beta_re = rng.random::<f64>() * 2.0 - 1.0;
-        let beta_im = rng.random::<f64>() * 2.0 - 1.0;
-
-        let alpha = Complex64::new(alpha_re, alpha_im);
-        let beta = Complex64::new(beta_re, beta_im);
+        Self::new_random_with_rng(&mut crate::simulation::unseeded_rng("Qubit::new_random"))
+    }
 
-        // Normalize automatically
-        Qubit::new_custom(alpha, beta)
+    /// `new_random` drawing from the caller's RNG
+    ///
+    /// Haar-random: cos θ and φ are uniform, so P(0) = cos²(θ/2) is uniform on [0, 1]
+    pub fn new_random_with_rng(rng: &mut impl rand::Rng) -> Self {
+        let cos_theta = rng.random::<f64>() * 2.0 - 1.0;
+        let phi = rng.random::<f64>() * 2.0 * std::f64::consts::PI;
+        Qubit::from_bloch(cos_theta.acos(), phi)
     }
 
     /// Create a custom qubit state (will normalize automatically)
@@ -103,6 +102,66 @@ impl Qubit {
         }
     }
 
+    /// Create a qubit directly from its amplitudes α|0⟩ + β|1⟩
+    /// Amplitudes are taken as-is (use new_custom to normalize)
+    pub fn from_amplitudes(alpha: Complex64, beta: Complex64) -> Self {
+        Qubit {
+            state: Array1::from_vec(vec![alpha, beta]),
+        }
+    }
+
+    /// Get the amplitudes (α, β) without going through ndarray
+    pub fn amplitudes(&self) -> (Complex64, Complex64) {
+        (self.state[0], self.state[1])
+    }
+
+    /// Create a qubit from Bloch sphere angles (same as `from_bloch_angles`)
+    pub fn from_bloch(theta: f64, phi: f64) -> Self {
+        Self::from_bloch_angles(theta, phi)
+    }
+
+    /// Create a qubit from polar angle `theta` and azimuth `phi`
+    /// |ψ⟩ = cos(θ/2)|0⟩ + e^(iφ) sin(θ/2)|1⟩
+    pub fn from_bloch_angles(theta: f64, phi: f64) -> Self {
+        Qubit {
+            state: Array1::from_vec(vec![
+                Complex64::new((theta / 2.0).cos(), 0.0),
+                Complex64::from_polar((theta / 2.0).sin(), phi),
+            ]),
+        }
+    }
+
+    /// Bloch vector (⟨X⟩, ⟨Y⟩, ⟨Z⟩)
+    pub fn bloch_vector(&self) -> (f64, f64, f64) {
+        (
+            self.expectation_x(),
+            self.expectation_y(),
+            self.expectation_z(),
+        )
+    }
+
+    /// Bloch angles (θ, φ) with θ in [0, π] and φ in (-π, π]
+    /// φ is 0 at the poles, where it is undefined
+    pub fn bloch_angles(&self) -> (f64, f64) {
+        let (x, y, z) = self.bloch_vector();
+        (z.clamp(-1.0, 1.0).acos(), y.atan2(x))
+    }
+
+    /// ⟨X⟩ = 2 Re(α*β)
+    pub fn expectation_x(&self) -> f64 {
+        2.0 * (self.state[0].conj() * self.state[1]).re
+    }
+
+    /// ⟨Y⟩ = 2 Im(α*β)
+    pub fn expectation_y(&self) -> f64 {
+        2.0 * (self.state[0].conj() * self.state[1]).im
+    }
+
+    /// ⟨Z⟩ = |α|² - |β|²
+    pub fn expectation_z(&self) -> f64 {
+        self.prob_zero() - self.prob_one()
+    }
+
     /// Get probability of measuring |0⟩
     pub fn prob_zero(&self) -> f64 {
         self.state[0].norm_sqr()
@@ -118,12 +177,77 @@ impl Qubit {
         let norm = self.state[0].norm_sqr() + self.state[1].norm_sqr();
         (norm - 1.0).abs() < 1e-10
     }
+
+    /// Fidelity with another qubit state
+    /// F = |⟨ψ|φ⟩|²
+    pub fn fidelity(&self, other: &Qubit) -> f64 {
+        (self.state[0].conj() * other.state[0] + self.state[1].conj() * other.state[1]).norm_sqr()
+    }
+
+    /// 1 - fidelity: 0 for equal states, 1 for orthogonal ones
+    pub fn distance(&self, other: &Qubit) -> f64 {
+        1.0 - self.fidelity(other)
+    }
+
+    /// Same state up to a global phase: |⟨ψ|φ⟩|² ≥ 1 - tol
+    pub fn approx_eq(&self, other: &Qubit, tol: f64) -> bool {
+        self.fidelity(other) >= 1.0 - tol
+    }
+
+    /// arg(β) - arg(α) in (-π, π]; 0 if either amplitude vanishes
+    pub fn relative_phase(&self) -> f64 {
+        (self.state[0].conj() * self.state[1]).arg()
+    }
+
+    /// Remove the global phase: the first nonzero amplitude becomes real
+    /// and positive, so equal states have equal amplitudes
+    pub fn normalize_global_phase(&mut self) {
+        normalize_global_phase(&mut self.state);
+    }
+
+    /// Dirac notation with `precision` decimals, e.g. `0.707|0⟩ + 0.707|1⟩`
+    pub fn to_dirac_string(&self, precision: usize) -> String {
+        dirac_string(&self.state, 1, precision)
+    }
+}
+
+/// Dirac notation; `{:.N}` sets the precision (default 3)
+impl fmt::Display for Qubit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(DIRAC_PRECISION);
+        write!(f, "{}", self.to_dirac_string(precision))
+    }
+}
+
+/// The four maximally entangled Bell states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BellState {
+    /// (|00⟩ + |11⟩)/√2
+    PhiPlus,
+    /// (|00⟩ - |11⟩)/√2
+    PhiMinus,
+    /// (|01⟩ + |10⟩)/√2
+    PsiPlus,
+    /// (|01⟩ - |10⟩)/√2
+    PsiMinus,
+}
+
+impl BellState {
+    pub const ALL: [BellState; 4] = [
+        BellState::PhiPlus,
+        BellState::PhiMinus,
+        BellState::PsiPlus,
+        BellState::PsiMinus,
+    ];
 }
 
 /// Two-qubit state for entangled pairs
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TwoQubitState {
     /// State vector of size 4: [|00⟩, |01⟩, |10⟩, |11⟩]
+    #[cfg_attr(feature = "serde", serde(with = "amplitudes"))]
     pub state: Array1<Complex64>,
 }
 
@@ -153,6 +277,101 @@ impl TwoQubitState {
         }
     }
 
+    /// Create Bell state |Φ-⟩ = (|00⟩ - |11⟩)/√2
+    pub fn new_bell_phi_minus() -> Self {
+        Self::from_bell(BellState::PhiMinus)
+    }
+
+    /// Create Bell state |Ψ+⟩ = (|01⟩ + |10⟩)/√2
+    pub fn new_bell_psi_plus() -> Self {
+        Self::from_bell(BellState::PsiPlus)
+    }
+
+    /// Create Bell state |Ψ-⟩ = (|01⟩ - |10⟩)/√2
+    /// The state heralded by Barrett-Kok on detectors in different arms
+    pub fn new_bell_psi_minus() -> Self {
+        Self::from_bell(BellState::PsiMinus)
+    }
+
+    /// Pure state with fidelity `f` against |Φ+⟩
+    ///
+    /// √f|Φ+⟩ plus the remaining weight spread evenly over the other three
+    /// Bell states, the pure counterpart of a Werner state of fidelity `f`
+    pub fn werner_approx(f: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&f),
+            "fidelity must be in [0, 1], got {}",
+            f
+        );
+        let error = ((1.0 - f) / 3.0).sqrt();
+        let mut state = Self::new_bell_phi_plus().state.mapv(|x| x * f.sqrt());
+        for bell in [BellState::PhiMinus, BellState::PsiPlus, BellState::PsiMinus] {
+            state = state + Self::from_bell(bell).state.mapv(|x| x * error);
+        }
+        TwoQubitState { state }
+    }
+
+    /// Tensor product |a⟩⊗|b⟩ of two independent qubits
+    pub fn from_qubits(a: &Qubit, b: &Qubit) -> Self {
+        TwoQubitState {
+            state: Array1::from_shape_fn(4, |index| a.state[index / 2] * b.state[index % 2]),
+        }
+    }
+
+    /// Whether the state factors into two single-qubit states
+    ///
+    /// Reshaped to the 2x2 matrix [[c00, c01], [c10, c11]], a product state
+    /// has Schmidt rank 1, i.e. zero determinant
+    pub fn is_product_state(&self) -> bool {
+        let determinant = self.state[0] * self.state[3] - self.state[1] * self.state[2];
+        determinant.norm() < 1e-10
+    }
+
+    /// Concurrence of a pure state: C = 2|c00 c11 - c01 c10|
+    /// 0 for product states, 1 for Bell states
+    pub fn concurrence(&self) -> f64 {
+        2.0 * (self.state[0] * self.state[3] - self.state[1] * self.state[2]).norm()
+    }
+
+    /// Whether the concurrence exceeds `tol`
+    pub fn is_entangled(&self, tol: f64) -> bool {
+        self.concurrence() > tol
+    }
+
+    /// Reduced density matrix of qubit A: ρ_A = Tr_B |ψ⟩⟨ψ|
+    pub fn reduced_a(&self) -> Array2<Complex64> {
+        Array2::from_shape_fn((2, 2), |(a, a_prime)| {
+            (0..2)
+                .map(|b| self.state[2 * a + b] * self.state[2 * a_prime + b].conj())
+                .sum()
+        })
+    }
+
+    /// Reduced density matrix of qubit B: ρ_B = Tr_A |ψ⟩⟨ψ|
+    pub fn reduced_b(&self) -> Array2<Complex64> {
+        Array2::from_shape_fn((2, 2), |(b, b_prime)| {
+            (0..2)
+                .map(|a| self.state[2 * a + b] * self.state[2 * a + b_prime].conj())
+                .sum()
+        })
+    }
+
+    /// Create any of the four Bell states
+    pub fn from_bell(bell: BellState) -> Self {
+        let factor = 1.0 / (2.0_f64).sqrt();
+        // (index of the first term, index of the second term, sign of the second)
+        let (first, second, sign) = match bell {
+            BellState::PhiPlus => (0, 3, 1.0),
+            BellState::PhiMinus => (0, 3, -1.0),
+            BellState::PsiPlus => (1, 2, 1.0),
+            BellState::PsiMinus => (1, 2, -1.0),
+        };
+        let mut state = Array1::from_elem(4, Complex64::new(0.0, 0.0));
+        state[first] = Complex64::new(factor, 0.0);
+        state[second] = Complex64::new(sign * factor, 0.0);
+        TwoQubitState { state }
+    }
+
     /// Calculate fidelity with another two-qubit state
     /// F = |⟨ψ|φ⟩|²
     pub fn fidelity(&self, other: &TwoQubitState) -> f64 {
@@ -163,6 +382,151 @@ impl TwoQubitState {
         inner_product.norm_sqr()
     }
 
+    /// 1 - fidelity: 0 for equal states, 1 for orthogonal ones
+    pub fn distance(&self, other: &TwoQubitState) -> f64 {
+        1.0 - self.fidelity(other)
+    }
+
+    /// Same state up to a global phase: |⟨ψ|φ⟩|² ≥ 1 - tol
+    pub fn approx_eq(&self, other: &TwoQubitState, tol: f64) -> bool {
+        self.fidelity(other) >= 1.0 - tol
+    }
+
+    /// Remove the global phase: the first nonzero amplitude becomes real
+    /// and positive, so equal states have equal amplitudes
+    pub fn normalize_global_phase(&mut self) {
+        normalize_global_phase(&mut self.state);
+    }
+
+    /// Check if normalized
+    pub fn is_normalized(&self) -> bool {
+        let norm: f64 = self.state.iter().map(|c| c.norm_sqr()).sum();
+        (norm - 1.0).abs() < 1e-10
+    }
+
+    /// Dirac notation with `precision` decimals, e.g. `0.707|00⟩ + 0.707|11⟩`
+    pub fn to_dirac_string(&self, precision: usize) -> String {
+        dirac_string(&self.state, 2, precision)
+    }
+}
+
+/// Dirac notation; `{:.N}` sets the precision (default 3)
+impl fmt::Display for TwoQubitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(DIRAC_PRECISION);
+        write!(f, "{}", self.to_dirac_string(precision))
+    }
+}
+
+/// Amplitudes below this magnitude are treated as zero when fixing the phase
+const PHASE_REFERENCE_THRESHOLD: f64 = 1e-12;
+
+fn normalize_global_phase(amplitudes: &mut Array1<Complex64>) {
+    if let Some(reference) = amplitudes
+        .iter()
+        .find(|amplitude| amplitude.norm() > PHASE_REFERENCE_THRESHOLD)
+    {
+        let rotation = Complex64::from_polar(1.0, -reference.arg());
+        amplitudes.mapv_inplace(|amplitude| amplitude * rotation);
+    }
+}
+
+/// Decimals in the Display of states
+const DIRAC_PRECISION: usize = 3;
+
+/// Sum of the basis kets of `amplitudes` over `num_qubits`
+///
+/// Terms that round to zero at `precision` are left out; amplitudes are
+/// printed real or imaginary when the other part rounds to zero
+fn dirac_string(amplitudes: &Array1<Complex64>, num_qubits: usize, precision: usize) -> String {
+    let threshold = 0.5 * 10f64.powi(-(precision as i32));
+    let mut text = String::new();
+    for (index, amplitude) in amplitudes.iter().enumerate() {
+        let (re, im) = (amplitude.re, amplitude.im);
+        if re.abs() < threshold && im.abs() < threshold {
+            continue;
+        }
+        let (negative, coefficient) = if im.abs() < threshold {
+            (re < 0.0, format!("{:.*}", precision, re.abs()))
+        } else if re.abs() < threshold {
+            (im < 0.0, format!("{:.*}i", precision, im.abs()))
+        } else {
+            (
+                false,
+                format!("({:.*}{:+.*}i)", precision, re, precision, im),
+            )
+        };
+        let sign = match (text.is_empty(), negative) {
+            (true, false) => "",
+            (true, true) => "-",
+            (false, false) => " + ",
+            (false, true) => " - ",
+        };
+        text.push_str(&format!(
+            "{}{}|{:0width$b}⟩",
+            sign,
+            coefficient,
+            index,
+            width = num_qubits
+        ));
+    }
+    if text.is_empty() {
+        text.push('0');
+    }
+    text
+}
+
+/// Three-qubit state for multipartite distribution
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThreeQubitState {
+    /// State vector of size 8: [|000⟩, |001⟩, ..., |111⟩], index = 4*q0 + 2*q1 + q2
+    #[cfg_attr(feature = "serde", serde(with = "amplitudes"))]
+    pub state: Array1<Complex64>,
+}
+
+impl ThreeQubitState {
+    /// Create |000⟩ state
+    pub fn new_zero_zero_zero() -> Self {
+        let mut state = Array1::from_elem(8, Complex64::new(0.0, 0.0));
+        state[0] = Complex64::new(1.0, 0.0);
+        ThreeQubitState { state }
+    }
+
+    /// Create GHZ state (|000⟩ + |111⟩)/√2
+    pub fn new_ghz() -> Self {
+        let factor = 1.0 / (2.0_f64).sqrt();
+        let mut state = Array1::from_elem(8, Complex64::new(0.0, 0.0));
+        state[0] = Complex64::new(factor, 0.0);
+        state[7] = Complex64::new(factor, 0.0);
+        ThreeQubitState { state }
+    }
+
+    /// Create W state (|001⟩ + |010⟩ + |100⟩)/√3
+    pub fn new_w() -> Self {
+        let factor = 1.0 / (3.0_f64).sqrt();
+        let mut state = Array1::from_elem(8, Complex64::new(0.0, 0.0));
+        for index in [1, 2, 4] {
+            state[index] = Complex64::new(factor, 0.0);
+        }
+        ThreeQubitState { state }
+    }
+
+    /// F = |⟨ψ|φ⟩|²
+    pub fn fidelity(&self, other: &ThreeQubitState) -> f64 {
+        self.state
+            .iter()
+            .zip(&other.state)
+            .map(|(a, b)| a.conj() * b)
+            .sum::<Complex64>()
+            .norm_sqr()
+    }
+
+    /// Same state up to a global phase: |⟨ψ|φ⟩|² ≥ 1 - tol
+    pub fn approx_eq(&self, other: &ThreeQubitState, tol: f64) -> bool {
+        self.fidelity(other) >= 1.0 - tol
+    }
+
     /// Check if normalized
     pub fn is_normalized(&self) -> bool {
         let norm: f64 = self.state.iter().map(|c| c.norm_sqr()).sum();
@@ -170,6 +534,345 @@ impl TwoQubitState {
     }
 }
 
+/// Largest register `QuantumRegister::new_zeros` accepts (2^12 amplitudes)
+pub const MAX_REGISTER_QUBITS: usize = 12;
+
+/// Coherent register of n qubits, e.g. all memory qubits of a small node
+///
+/// Qubit 0 is the most significant bit of the basis index, as in
+/// `TwoQubitState` and `ThreeQubitState`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantumRegister {
+    pub num_qubits: usize,
+    /// State vector of size 2^n
+    #[cfg_attr(feature = "serde", serde(with = "amplitudes"))]
+    pub state: Array1<Complex64>,
+}
+
+impl QuantumRegister {
+    /// Create |0...0⟩ on `num_qubits` qubits
+    pub fn new_zeros(num_qubits: usize) -> Self {
+        assert!(
+            num_qubits <= MAX_REGISTER_QUBITS,
+            "Register of {} qubits exceeds the {}-qubit limit",
+            num_qubits,
+            MAX_REGISTER_QUBITS
+        );
+        let mut state = Array1::from_elem(1 << num_qubits, Complex64::new(0.0, 0.0));
+        state[0] = Complex64::new(1.0, 0.0);
+        QuantumRegister { num_qubits, state }
+    }
+
+    /// Bit mask of qubit `target` in the basis index
+    fn mask(&self, target: usize) -> usize {
+        assert!(
+            target < self.num_qubits,
+            "Qubit {} out of range for a {}-qubit register",
+            target,
+            self.num_qubits
+        );
+        1 << (self.num_qubits - 1 - target)
+    }
+
+    /// Apply a 2x2 gate to qubit `target`
+    pub fn apply_single_qubit_gate(&mut self, target: usize, gate: &Array2<Complex64>) {
+        self.apply_gate_where(target, gate, |_| true);
+    }
+
+    /// Apply a 2x2 gate to `target` on the branches where `control` is |1⟩
+    pub fn apply_controlled_gate(
+        &mut self,
+        control: usize,
+        target: usize,
+        gate: &Array2<Complex64>,
+    ) {
+        assert_ne!(control, target, "Control and target must differ");
+        let control_mask = self.mask(control);
+        self.apply_gate_where(target, gate, |index| index & control_mask != 0);
+    }
+
+    /// Apply `gate` to each (|..0..⟩, |..1..⟩) amplitude pair of `target`
+    /// whose index satisfies `condition`
+    fn apply_gate_where(
+        &mut self,
+        target: usize,
+        gate: &Array2<Complex64>,
+        condition: impl Fn(usize) -> bool,
+    ) {
+        assert_eq!(gate.shape(), &[2, 2], "Gate must be 2x2 matrix");
+        let mask = self.mask(target);
+        for zero in (0..self.state.len()).filter(|&index| index & mask == 0 && condition(index)) {
+            let one = zero | mask;
+            let (a, b) = (self.state[zero], self.state[one]);
+            self.state[zero] = gate[[0, 0]] * a + gate[[0, 1]] * b;
+            self.state[one] = gate[[1, 0]] * a + gate[[1, 1]] * b;
+        }
+    }
+
+    /// Z-basis measurement of qubit `target`, drawing from the caller's RNG
+    /// The register collapses; returns true for |1⟩
+    pub fn measure_with_rng(&mut self, target: usize, rng: &mut impl rand::Rng) -> bool {
+        let mask = self.mask(target);
+        let prob_one: f64 = self
+            .state
+            .indexed_iter()
+            .filter(|(index, _)| index & mask != 0)
+            .map(|(_, amplitude)| amplitude.norm_sqr())
+            .sum();
+        let result = rng.random::<f64>() < prob_one;
+        let norm = if result { prob_one } else { 1.0 - prob_one }.sqrt();
+        for (index, amplitude) in self.state.indexed_iter_mut() {
+            if (index & mask != 0) == result {
+                *amplitude /= norm;
+            } else {
+                *amplitude = Complex64::new(0.0, 0.0);
+            }
+        }
+        result
+    }
+
+    /// Z-basis measurement of qubit `target`
+    pub fn measure(&mut self, target: usize) -> bool {
+        self.measure_with_rng(
+            target,
+            &mut crate::simulation::unseeded_rng("QuantumRegister::measure"),
+        )
+    }
+
+    /// F = |⟨ψ|φ⟩|²
+    pub fn fidelity(&self, other: &QuantumRegister) -> f64 {
+        assert_eq!(
+            self.num_qubits, other.num_qubits,
+            "Registers must have the same size"
+        );
+        self.state
+            .iter()
+            .zip(&other.state)
+            .map(|(a, b)| a.conj() * b)
+            .sum::<Complex64>()
+            .norm_sqr()
+    }
+
+    /// Check if normalized
+    pub fn is_normalized(&self) -> bool {
+        let norm: f64 = self.state.iter().map(|c| c.norm_sqr()).sum();
+        (norm - 1.0).abs() < 1e-10
+    }
+}
+
+/// Mixed state of a pair: ρ as a 4x4 matrix in the |00⟩..|11⟩ basis
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DensityMatrix {
+    #[cfg_attr(feature = "serde", serde(with = "matrix"))]
+    pub matrix: Array2<Complex64>,
+}
+
+impl DensityMatrix {
+    /// |ψ⟩⟨ψ|
+    pub fn from_pure(state: &TwoQubitState) -> Self {
+        DensityMatrix {
+            matrix: Array2::from_shape_fn((4, 4), |(row, column)| {
+                state.state[row] * state.state[column].conj()
+            }),
+        }
+    }
+
+    /// Werner state p|Φ+⟩⟨Φ+| + (1 - p) I/4
+    pub fn werner(p: f64) -> Self {
+        let bell = Self::from_pure(&TwoQubitState::new_bell_phi_plus());
+        let noise = Array2::<Complex64>::eye(4).mapv(|x| x * (1.0 - p) / 4.0);
+        DensityMatrix {
+            matrix: bell.matrix.mapv(|x| x * p) + noise,
+        }
+    }
+
+    /// Werner state with fidelity `f` against |Φ+⟩: p = (4f - 1)/3
+    pub fn werner_with_fidelity(f: f64) -> Self {
+        Self::werner((4.0 * f - 1.0) / 3.0)
+    }
+
+    /// Tr(ρ), 1 for a physical state
+    pub fn trace(&self) -> f64 {
+        self.matrix.diag().iter().sum::<Complex64>().re
+    }
+
+    /// Tr(ρ²): 1 for pure states, 1/4 for the maximally mixed state
+    pub fn purity(&self) -> f64 {
+        self.matrix
+            .dot(&self.matrix)
+            .diag()
+            .iter()
+            .sum::<Complex64>()
+            .re
+    }
+
+    /// F = ⟨ψ|ρ|ψ⟩
+    pub fn fidelity_with_pure(&self, state: &TwoQubitState) -> f64 {
+        let conjugate = state.state.mapv(|x| x.conj());
+        conjugate.dot(&self.matrix.dot(&state.state)).re
+    }
+
+    /// Partial transpose on qubit B: ⟨a b|ρ^T_B|a' b'⟩ = ⟨a b'|ρ|a' b⟩
+    pub fn partial_transpose(&self) -> DensityMatrix {
+        DensityMatrix {
+            matrix: Array2::from_shape_fn((4, 4), |(row, column)| {
+                let (a, b) = (row / 2, row % 2);
+                let (a_prime, b_prime) = (column / 2, column % 2);
+                self.matrix[[2 * a + b_prime, 2 * a_prime + b]]
+            }),
+        }
+    }
+
+    /// Negativity: summed magnitude of the negative eigenvalues of ρ^T_B
+    /// 0 for separable states, 1/2 for Bell states; C/2 for pure states
+    pub fn negativity(&self) -> f64 {
+        hermitian_eigenvalues(&self.partial_transpose().matrix)
+            .into_iter()
+            .filter(|&eigenvalue| eigenvalue < 0.0)
+            .map(f64::abs)
+            .sum()
+    }
+
+    /// Whether the negativity exceeds `tol`
+    /// For two qubits a positive partial transpose means separable
+    pub fn is_entangled(&self, tol: f64) -> bool {
+        self.negativity() > tol
+    }
+
+    /// ρ → UρU†
+    pub fn apply_unitary(&mut self, unitary: &Array2<Complex64>) {
+        assert_eq!(unitary.dim(), (4, 4), "pair unitaries must be 4x4");
+        let adjoint = unitary.t().mapv(|x| x.conj());
+        self.matrix = unitary.dot(&self.matrix).dot(&adjoint);
+    }
+}
+
+/// Von Neumann entropy S(ρ) = -Tr ρ log2 ρ, in bits
+///
+/// Of a reduced state of a pure pair this is its entanglement entropy:
+/// 0 for product states, 1 for Bell states
+pub fn von_neumann_entropy(rho: &Array2<Complex64>) -> f64 {
+    hermitian_eigenvalues(rho)
+        .into_iter()
+        .filter(|&eigenvalue| eigenvalue > 1e-12)
+        .map(|eigenvalue| -eigenvalue * eigenvalue.log2())
+        .sum()
+}
+
+/// Eigenvalues of a Hermitian matrix, ascending
+///
+/// H = A + iB has the same spectrum, doubled, as the real symmetric
+/// [[A, -B], [B, A]], which is diagonalised by cyclic Jacobi rotations
+fn hermitian_eigenvalues(matrix: &Array2<Complex64>) -> Vec<f64> {
+    let n = matrix.nrows();
+    let mut a = Array2::from_shape_fn((2 * n, 2 * n), |(row, column)| {
+        let value = matrix[[row % n, column % n]];
+        match (row < n, column < n) {
+            (true, true) | (false, false) => value.re,
+            (true, false) => -value.im,
+            (false, true) => value.im,
+        }
+    });
+    let size = 2 * n;
+    for _sweep in 0..100 {
+        let off_diagonal: f64 = (0..size)
+            .flat_map(|p| (p + 1..size).map(move |q| (p, q)))
+            .map(|(p, q)| a[[p, q]].powi(2))
+            .sum();
+        if off_diagonal < 1e-24 {
+            break;
+        }
+        for p in 0..size {
+            for q in p + 1..size {
+                if a[[p, q]].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[[q, q]] - a[[p, p]]) / (2.0 * a[[p, q]]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..size {
+                    let (kp, kq) = (a[[k, p]], a[[k, q]]);
+                    a[[k, p]] = c * kp - s * kq;
+                    a[[k, q]] = s * kp + c * kq;
+                }
+                for k in 0..size {
+                    let (pk, qk) = (a[[p, k]], a[[q, k]]);
+                    a[[p, k]] = c * pk - s * qk;
+                    a[[q, k]] = s * pk + c * qk;
+                }
+            }
+        }
+    }
+    let mut eigenvalues: Vec<f64> = a.diag().to_vec();
+    eigenvalues.sort_by(f64::total_cmp);
+    // Each eigenvalue appears twice
+    eigenvalues.into_iter().step_by(2).collect()
+}
+
+/// State vectors as arrays of `[re, im]` pairs
+#[cfg(feature = "serde")]
+mod amplitudes {
+    use ndarray::Array1;
+    use num_complex::Complex64;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        state: &Array1<Complex64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let pairs: Vec<[f64; 2]> = state.iter().map(|c| [c.re, c.im]).collect();
+        pairs.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Array1<Complex64>, D::Error> {
+        let pairs = Vec::<[f64; 2]>::deserialize(deserializer)?;
+        Ok(pairs
+            .into_iter()
+            .map(|[re, im]| Complex64::new(re, im))
+            .collect())
+    }
+}
+
+/// Square matrices as rows of `[re, im]` pairs
+#[cfg(feature = "serde")]
+mod matrix {
+    use ndarray::Array2;
+    use num_complex::Complex64;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        matrix: &Array2<Complex64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let rows: Vec<Vec<[f64; 2]>> = matrix
+            .rows()
+            .into_iter()
+            .map(|row| row.iter().map(|c| [c.re, c.im]).collect())
+            .collect();
+        rows.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Array2<Complex64>, D::Error> {
+        let rows = Vec::<Vec<[f64; 2]>>::deserialize(deserializer)?;
+        let size = rows.len();
+        if rows.iter().any(|row| row.len() != size) {
+            return Err(D::Error::custom("matrix must be square"));
+        }
+        Ok(Array2::from_shape_fn((size, size), |(row, column)| {
+            let [re, im] = rows[row][column];
+            Complex64::new(re, im)
+        }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +899,138 @@ mod tests {
         assert!((bell.fidelity(&bell) - 1.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_bell_states_are_orthonormal() {
+        for (i, a) in BellState::ALL.into_iter().enumerate() {
+            let state_a = TwoQubitState::from_bell(a);
+            assert!(state_a.is_normalized());
+            for (j, b) in BellState::ALL.into_iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                let fidelity = state_a.fidelity(&TwoQubitState::from_bell(b));
+                assert!((fidelity - expected).abs() < 1e-12, "{:?} vs {:?}", a, b);
+            }
+        }
+
+        let psi_minus = TwoQubitState::new_bell_psi_minus();
+        assert!((psi_minus.state[1].re - 1.0 / 2.0_f64.sqrt()).abs() < 1e-12);
+        assert!((psi_minus.state[2].re + 1.0 / 2.0_f64.sqrt()).abs() < 1e-12);
+        let constructors = [
+            TwoQubitState::new_bell_phi_plus(),
+            TwoQubitState::new_bell_phi_minus(),
+            TwoQubitState::new_bell_psi_plus(),
+            psi_minus,
+        ];
+        for (state, bell) in constructors.iter().zip(BellState::ALL) {
+            assert_eq!(state.state, TwoQubitState::from_bell(bell).state);
+        }
+    }
+
+    #[test]
+    fn test_amplitudes_round_trip() {
+        let alpha = Complex64::new(0.6, 0.0);
+        let beta = Complex64::new(0.0, 0.8);
+        let q = Qubit::from_amplitudes(alpha, beta);
+
+        assert!(q.is_normalized());
+        assert_eq!(q.amplitudes(), (alpha, beta));
+    }
+
+    #[test]
+    fn test_bloch_vector_of_basis_states() {
+        let close = |a: (f64, f64, f64), b: (f64, f64, f64)| {
+            (a.0 - b.0).abs() < 1e-10 && (a.1 - b.1).abs() < 1e-10 && (a.2 - b.2).abs() < 1e-10
+        };
+
+        assert!(close(Qubit::new_zero().bloch_vector(), (0.0, 0.0, 1.0)));
+        assert!(close(Qubit::new_one().bloch_vector(), (0.0, 0.0, -1.0)));
+        assert!(close(Qubit::new_plus().bloch_vector(), (1.0, 0.0, 0.0)));
+        assert!(close(Qubit::new_iplus().bloch_vector(), (0.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_from_bloch_round_trip() {
+        let (theta, phi) = (1.1, 2.3);
+        let (x, y, z) = Qubit::from_bloch(theta, phi).bloch_vector();
+
+        assert!((x - theta.sin() * phi.cos()).abs() < 1e-10);
+        assert!((y - theta.sin() * phi.sin()).abs() < 1e-10);
+        assert!((z - theta.cos()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_product_of_plus_and_zero_becomes_bell_pair() {
+        let mut state = TwoQubitState::from_qubits(&Qubit::new_plus(), &Qubit::new_zero());
+        assert!(state.is_normalized());
+        assert!(state.is_product_state());
+        // |+0⟩ = (|00⟩ + |10⟩)/√2
+        assert!((state.state[2].re - 1.0 / 2.0_f64.sqrt()).abs() < 1e-12);
+        assert!(state.state[1].norm() < 1e-12);
+
+        crate::quantum::gates::cnot(&mut state);
+        assert!(state.approx_eq(&TwoQubitState::new_bell_phi_plus(), 1e-12));
+        assert!(!state.is_product_state());
+    }
+
+    #[test]
+    fn test_product_state_detection() {
+        let a = Qubit::from_bloch_angles(0.7, 1.9);
+        let b = Qubit::from_bloch_angles(2.2, -0.4);
+        assert!(TwoQubitState::from_qubits(&a, &b).is_product_state());
+        assert!(TwoQubitState::new_zero_zero().is_product_state());
+        for bell in BellState::ALL {
+            assert!(!TwoQubitState::from_bell(bell).is_product_state());
+        }
+    }
+
+    #[test]
+    fn test_approx_eq_ignores_global_phase() {
+        let plus = Qubit::new_plus();
+        let phase = Complex64::from_polar(1.0, 0.9);
+        let shifted = Qubit::from_amplitudes(phase * plus.state[0], phase * plus.state[1]);
+        assert!(plus.approx_eq(&shifted, 1e-10));
+        assert!(!plus.approx_eq(&Qubit::new_minus(), 1e-10));
+        assert!((plus.distance(&Qubit::new_minus()) - 1.0).abs() < 1e-10);
+        assert!((plus.distance(&Qubit::new_zero()) - 0.5).abs() < 1e-10);
+
+        let phi_plus = TwoQubitState::new_bell_phi_plus();
+        let negated = TwoQubitState {
+            state: phi_plus.state.mapv(|amplitude| -amplitude),
+        };
+        assert!(phi_plus.approx_eq(&negated, 1e-10));
+        assert!(!phi_plus.approx_eq(&TwoQubitState::new_bell_phi_minus(), 1e-10));
+        assert!(phi_plus.distance(&negated).abs() < 1e-10);
+        // |⟨00|Φ+⟩|² = 1/2
+        assert!(!phi_plus.approx_eq(&TwoQubitState::new_zero_zero(), 0.4));
+        assert!(phi_plus.approx_eq(&TwoQubitState::new_zero_zero(), 0.6));
+    }
+
+    #[test]
+    fn test_expectation_values() {
+        let plus = Qubit::new_plus();
+        assert!((plus.expectation_x() - 1.0).abs() < 1e-10);
+        assert!(plus.expectation_y().abs() < 1e-10);
+        assert!(plus.expectation_z().abs() < 1e-10);
+        let iminus = Qubit::new_iminus();
+        assert!((iminus.expectation_y() + 1.0).abs() < 1e-10);
+        assert!((Qubit::new_one().expectation_z() + 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_bloch_angles_round_trip() {
+        for theta in [0.3, 1.0, std::f64::consts::FRAC_PI_2, 2.5, 3.0] {
+            for phi in [-3.0, -1.2, 0.0, 0.4, 2.9] {
+                let qubit = Qubit::from_bloch_angles(theta, phi);
+                assert!(qubit.is_normalized());
+                let (x, y, z) = qubit.bloch_vector();
+                assert!((x * x + y * y + z * z - 1.0).abs() < 1e-10);
+                let (theta_back, phi_back) = qubit.bloch_angles();
+                assert!((theta_back - theta).abs() < 1e-10);
+                assert!((phi_back - phi).abs() < 1e-10);
+            }
+        }
+        assert_eq!(Qubit::new_zero().bloch_angles(), (0.0, 0.0));
+    }
+
     #[test]
     fn test_random_qubit() {
         let q = Qubit::new_random();
@@ -203,4 +1038,319 @@ mod tests {
         assert!(q.prob_zero() >= 0.0 && q.prob_zero() <= 1.0);
         assert!(q.prob_one() >= 0.0 && q.prob_one() <= 1.0);
     }
+
+    #[test]
+    fn test_random_qubits_are_haar_distributed() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(755);
+        let samples = 10_000;
+        let mut bins = [0usize; 10];
+        let (mut sum, mut sum_sq, mut sum_x) = (0.0, 0.0, 0.0);
+        for _ in 0..samples {
+            let q = Qubit::new_random_with_rng(&mut rng);
+            assert!(q.is_normalized());
+            let p = q.prob_zero();
+            sum += p;
+            sum_sq += p * p;
+            sum_x += q.bloch_vector().0;
+            bins[((p * 10.0) as usize).min(9)] += 1;
+        }
+        let mean = sum / samples as f64;
+        let variance = sum_sq / samples as f64 - mean * mean;
+        // P(0) uniform on [0, 1]: mean 1/2, variance 1/12
+        assert!((mean - 0.5).abs() < 0.01, "mean {}", mean);
+        assert!(
+            (variance - 1.0 / 12.0).abs() < 0.005,
+            "variance {}",
+            variance
+        );
+        assert!((sum_x / samples as f64).abs() < 0.02);
+        for count in bins {
+            assert!((850..1150).contains(&count), "{:?}", bins);
+        }
+
+        // Same seed, same states
+        let a = Qubit::new_random_with_rng(&mut rand::rngs::StdRng::seed_from_u64(1));
+        let b = Qubit::new_random_with_rng(&mut rand::rngs::StdRng::seed_from_u64(1));
+        assert_eq!(a.state, b.state);
+    }
+
+    #[test]
+    fn test_ghz_and_w_states() {
+        let ghz = ThreeQubitState::new_ghz();
+        let w = ThreeQubitState::new_w();
+        assert!(ghz.is_normalized() && w.is_normalized());
+        assert!(ghz.fidelity(&w).abs() < 1e-12);
+        assert!((ghz.fidelity(&ThreeQubitState::new_zero_zero_zero()) - 0.5).abs() < 1e-12);
+        assert!(w.approx_eq(&w, 1e-12));
+    }
+
+    #[test]
+    fn test_register_prepares_bell_and_ghz() {
+        use crate::quantum::gates::{get_hadamard_matrix, get_pauli_x_matrix};
+        let hadamard = get_hadamard_matrix();
+        let x = get_pauli_x_matrix();
+
+        let mut bell = QuantumRegister::new_zeros(2);
+        bell.apply_single_qubit_gate(0, &hadamard);
+        bell.apply_controlled_gate(0, 1, &x);
+        let expected = QuantumRegister {
+            num_qubits: 2,
+            state: TwoQubitState::new_bell_phi_plus().state,
+        };
+        assert!((bell.fidelity(&expected) - 1.0).abs() < 1e-12);
+
+        let mut ghz = QuantumRegister::new_zeros(3);
+        ghz.apply_single_qubit_gate(0, &hadamard);
+        ghz.apply_controlled_gate(0, 1, &x);
+        ghz.apply_controlled_gate(1, 2, &x);
+        let expected = QuantumRegister {
+            num_qubits: 3,
+            state: ThreeQubitState::new_ghz().state,
+        };
+        assert!((ghz.fidelity(&expected) - 1.0).abs() < 1e-12);
+        assert!(ghz.is_normalized());
+    }
+
+    #[test]
+    fn test_register_measurement_collapses_ghz() {
+        use crate::quantum::gates::{get_hadamard_matrix, get_pauli_x_matrix};
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(766);
+        for _ in 0..20 {
+            let mut ghz = QuantumRegister::new_zeros(4);
+            ghz.apply_single_qubit_gate(0, &get_hadamard_matrix());
+            for target in 1..4 {
+                ghz.apply_controlled_gate(0, target, &get_pauli_x_matrix());
+            }
+            let first = ghz.measure_with_rng(2, &mut rng);
+            assert!(ghz.is_normalized());
+            for target in [0, 1, 3] {
+                assert_eq!(ghz.measure_with_rng(target, &mut rng), first);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the 12-qubit limit")]
+    fn test_register_size_limit() {
+        QuantumRegister::new_zeros(MAX_REGISTER_QUBITS + 1);
+    }
+
+    #[test]
+    fn test_concurrence() {
+        for bell in BellState::ALL {
+            let state = TwoQubitState::from_bell(bell);
+            assert!((state.concurrence() - 1.0).abs() < 1e-12);
+            assert!(state.is_entangled(1e-9));
+        }
+        let product = TwoQubitState::from_qubits(&Qubit::new_plus(), &Qubit::new_iminus());
+        assert!(product.concurrence() < 1e-12);
+        assert!(!product.is_entangled(1e-9));
+
+        for theta in [0.0, 0.3, 1.0, 2.5] {
+            let mut state = TwoQubitState::new_zero_zero();
+            state.state[0] = Complex64::new(f64::cos(theta), 0.0);
+            state.state[3] = Complex64::new(f64::sin(theta), 0.0);
+            let expected = (2.0 * theta).sin().abs();
+            assert!((state.concurrence() - expected).abs() < 1e-12);
+            let negativity = DensityMatrix::from_pure(&state).negativity();
+            assert!((negativity - expected / 2.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_reduced_states_and_entropy() {
+        let identity_half = Array2::<Complex64>::eye(2).mapv(|x| x * 0.5);
+        let bell = TwoQubitState::new_bell_phi_plus();
+        for reduced in [bell.reduced_a(), bell.reduced_b()] {
+            assert!((&reduced - &identity_half).iter().all(|x| x.norm() < 1e-12));
+            assert!((von_neumann_entropy(&reduced) - 1.0).abs() < 1e-12);
+        }
+
+        let zero = TwoQubitState::new_zero_zero();
+        let projector = ndarray::array![
+            [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)],
+            [Complex64::new(0.0, 0.0), Complex64::new(0.0, 0.0)]
+        ];
+        assert!((&zero.reduced_a() - &projector)
+            .iter()
+            .all(|x| x.norm() < 1e-12));
+        assert!(von_neumann_entropy(&zero.reduced_b()).abs() < 1e-12);
+
+        // Qubit B of |0⟩|+⟩ is |+⟩⟨+|, whose off-diagonals are 1/2
+        let product = TwoQubitState::from_qubits(&Qubit::new_zero(), &Qubit::new_plus());
+        assert!((product.reduced_b()[[0, 1]].re - 0.5).abs() < 1e-12);
+        assert!(von_neumann_entropy(&product.reduced_b()).abs() < 1e-12);
+
+        // Both halves of a pure state carry the same entropy
+        let mut partial = TwoQubitState::new_zero_zero();
+        partial.state[0] = Complex64::new(0.6, 0.0);
+        partial.state[3] = Complex64::new(0.0, 0.8);
+        let expected = -(0.36_f64 * 0.36_f64.log2() + 0.64 * 0.64_f64.log2());
+        assert!((von_neumann_entropy(&partial.reduced_a()) - expected).abs() < 1e-12);
+        assert!((von_neumann_entropy(&partial.reduced_b()) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_negativity_of_werner_states() {
+        // Werner states are entangled iff p > 1/3: N = max(0, (3p - 1)/4)
+        for p in [0.0, 0.2, 1.0 / 3.0, 0.5, 1.0] {
+            let werner = DensityMatrix::werner(p);
+            let expected = ((3.0 * p - 1.0) / 4.0).max(0.0);
+            assert!((werner.negativity() - expected).abs() < 1e-9, "p = {}", p);
+        }
+        assert!(DensityMatrix::werner(0.4).is_entangled(1e-9));
+        assert!(!DensityMatrix::werner(0.3).is_entangled(1e-9));
+    }
+
+    #[test]
+    fn test_hermitian_eigenvalues() {
+        let i = Complex64::new(0.0, 1.0);
+        // Pauli Y has eigenvalues ±1
+        let y = ndarray::array![
+            [Complex64::new(0.0, 0.0), -i],
+            [i, Complex64::new(0.0, 0.0)]
+        ];
+        let eigenvalues = hermitian_eigenvalues(&y);
+        assert!((eigenvalues[0] + 1.0).abs() < 1e-12 && (eigenvalues[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_density_matrix_purity_and_werner_fidelity() {
+        let phi_plus = TwoQubitState::new_bell_phi_plus();
+        let pure = DensityMatrix::from_pure(&TwoQubitState::from_bell(BellState::PsiMinus));
+        assert!((pure.trace() - 1.0).abs() < 1e-12);
+        assert!((pure.purity() - 1.0).abs() < 1e-12);
+        assert!(pure.fidelity_with_pure(&phi_plus).abs() < 1e-12);
+
+        for p in [0.0, 0.3, 0.8, 1.0] {
+            let werner = DensityMatrix::werner(p);
+            assert!((werner.trace() - 1.0).abs() < 1e-12);
+            let expected = (3.0 * p + 1.0) / 4.0;
+            assert!((werner.fidelity_with_pure(&phi_plus) - expected).abs() < 1e-12);
+        }
+        assert!((DensityMatrix::werner(0.0).purity() - 0.25).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(776);
+        let qubit = Qubit::new_random_with_rng(&mut rng);
+        let json = serde_json::to_string(&qubit).unwrap();
+        let parsed: Qubit = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.state, qubit.state);
+
+        let pair = TwoQubitState::from_qubits(&qubit, &Qubit::new_random_with_rng(&mut rng));
+        let json = serde_json::to_string(&pair).unwrap();
+        assert!(json.starts_with("{\"state\":[["));
+        let parsed: TwoQubitState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.state, pair.state);
+
+        let rho = DensityMatrix::from_pure(&pair);
+        let json = serde_json::to_string(&rho).unwrap();
+        let parsed: DensityMatrix = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.matrix, rho.matrix);
+        assert!(serde_json::from_str::<DensityMatrix>("{\"matrix\":[[[1.0,0.0]],[]]}").is_err());
+    }
+
+    #[test]
+    fn test_dirac_display() {
+        assert_eq!(Qubit::new_plus().to_string(), "0.707|0⟩ + 0.707|1⟩");
+        assert_eq!(Qubit::new_minus().to_string(), "0.707|0⟩ - 0.707|1⟩");
+        let i_one = Qubit::new_custom(Complex64::new(0.0, 0.0), Complex64::new(0.0, 1.0));
+        assert_eq!(i_one.to_string(), "1.000i|1⟩");
+        assert_eq!(
+            TwoQubitState::new_bell_phi_plus().to_string(),
+            "0.707|00⟩ + 0.707|11⟩"
+        );
+
+        let phased = Qubit::new_custom(Complex64::new(-0.6, 0.0), Complex64::new(0.48, -0.64));
+        assert_eq!(phased.to_dirac_string(2), "-0.60|0⟩ + (0.48-0.64i)|1⟩");
+        assert_eq!(format!("{:.1}", Qubit::new_plus()), "0.7|0⟩ + 0.7|1⟩");
+    }
+
+    #[test]
+    fn test_werner_states_by_fidelity() {
+        let phi_plus = TwoQubitState::new_bell_phi_plus();
+        for f in [0.25, 0.5, 0.95, 1.0] {
+            let pure = TwoQubitState::werner_approx(f);
+            assert!(pure.is_normalized());
+            assert!((pure.fidelity(&phi_plus) - f).abs() < 1e-12);
+            let mixed = DensityMatrix::werner_with_fidelity(f);
+            assert!((mixed.fidelity_with_pure(&phi_plus) - f).abs() < 1e-12);
+        }
+        assert!(TwoQubitState::werner_approx(1.0).approx_eq(&phi_plus, 1e-12));
+    }
+
+    #[test]
+    fn test_density_matrix_unitary_matches_pure_evolution() {
+        let mut state = TwoQubitState::from_qubits(&Qubit::new_plus(), &Qubit::new_zero());
+        let mut rho = DensityMatrix::from_pure(&state);
+        let cnot = crate::quantum::gates::get_cnot_matrix();
+        crate::quantum::gates::cnot(&mut state);
+        rho.apply_unitary(&cnot);
+        assert!((rho.fidelity_with_pure(&state) - 1.0).abs() < 1e-12);
+        assert!((rho.purity() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_relative_phase() {
+        assert_eq!(Qubit::new_plus().relative_phase(), 0.0);
+        assert!((Qubit::new_minus().relative_phase() - std::f64::consts::PI).abs() < 1e-12);
+        assert!((Qubit::new_iplus().relative_phase() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        // Unchanged by a global phase
+        let shifted = Qubit::from_amplitudes(
+            Complex64::from_polar(0.6, 2.0),
+            Complex64::from_polar(0.8, 2.5),
+        );
+        assert!((shifted.relative_phase() - 0.5).abs() < 1e-12);
+        assert_eq!(Qubit::new_one().relative_phase(), 0.0);
+    }
+
+    #[test]
+    fn test_global_phase_normalization() {
+        use crate::quantum::gates::{hadamard, pauli_x, pauli_y, pauli_z, phase_s};
+        // HZH|0⟩ = X|0⟩ = |1⟩ and Y|0⟩ = i|1⟩ differ only by global phase
+        let mut hzh = Qubit::new_zero();
+        hadamard(&mut hzh);
+        pauli_z(&mut hzh);
+        hadamard(&mut hzh);
+        let mut x = Qubit::new_zero();
+        pauli_x(&mut x);
+        let mut y = Qubit::new_zero();
+        pauli_y(&mut y);
+        assert!((y.state[1] - Complex64::i()).norm() < 1e-12);
+
+        for qubit in [&mut hzh, &mut x, &mut y] {
+            qubit.normalize_global_phase();
+        }
+        for qubit in [&hzh, &y] {
+            for (a, b) in qubit.state.iter().zip(x.state.iter()) {
+                assert!((a - b).norm() < 1e-12);
+            }
+        }
+
+        // First nonzero amplitude becomes real and positive; relative phase kept
+        let mut qubit = Qubit::from_bloch_angles(1.0, 0.7);
+        phase_s(&mut qubit);
+        qubit
+            .state
+            .mapv_inplace(|a| a * Complex64::from_polar(1.0, -2.2));
+        let phase = qubit.relative_phase();
+        qubit.normalize_global_phase();
+        assert!(qubit.state[0].im.abs() < 1e-12 && qubit.state[0].re > 0.0);
+        assert!((qubit.relative_phase() - phase).abs() < 1e-12);
+
+        let mut pair = TwoQubitState::new_bell_phi_plus();
+        pair.state
+            .mapv_inplace(|a| a * Complex64::from_polar(1.0, 1.3));
+        pair.normalize_global_phase();
+        let bell = TwoQubitState::new_bell_phi_plus();
+        for (a, b) in pair.state.iter().zip(bell.state.iter()) {
+            assert!((a - b).norm() < 1e-12);
+        }
+    }
 }
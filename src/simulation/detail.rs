@@ -0,0 +1,129 @@
+//! How much per-pair state a run tracks, and which metrics that allows
+
+use std::fmt;
+
+/// Modelling detail of a run, from cheapest to most faithful
+///
+/// | Metric                    | Analytic | FidelityScalar | StateVector |
+/// |---------------------------|----------|----------------|-------------|
+/// | `Throughput`              | yes      | yes            | yes         |
+/// | `LinkGeneration`          |          | yes            | yes         |
+/// | `MemoryOccupancy`         |          | yes            | yes         |
+/// | `FidelityHistogram`       |          | yes            | yes         |
+/// | `LatencyHistogram`        |          | yes            | yes         |
+/// | `StateFidelityHistogram`  |          |                | yes         |
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DetailLevel {
+    /// Closed-form expected delivery times; no per-pair objects
+    Analytic,
+    /// Per-pair objects with a scalar (Werner) fidelity
+    #[default]
+    FidelityScalar,
+    /// Per-pair state vectors where the model implements them
+    StateVector,
+}
+
+/// A quantity a run report can provide
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Metric {
+    /// Delivered pairs and simulated time
+    Throughput,
+    /// Per-link attempt and success counts
+    LinkGeneration,
+    /// Per-node peak occupancy and pair ages
+    MemoryOccupancy,
+    /// Scalar fidelity of each delivered pair (histogram "fidelity")
+    FidelityHistogram,
+    /// Latency of each delivered pair (histogram "latency_ms")
+    LatencyHistogram,
+    /// Fidelity of each delivered state vector to |Φ+⟩ (histogram "state_fidelity")
+    StateFidelityHistogram,
+}
+
+impl Metric {
+    /// Least detail level that produces this metric
+    pub fn required_detail(self) -> DetailLevel {
+        match self {
+            Metric::Throughput => DetailLevel::Analytic,
+            Metric::LinkGeneration
+            | Metric::MemoryOccupancy
+            | Metric::FidelityHistogram
+            | Metric::LatencyHistogram => DetailLevel::FidelityScalar,
+            Metric::StateFidelityHistogram => DetailLevel::StateVector,
+        }
+    }
+
+    /// `RunReport` histogram holding this metric's samples, if any
+    pub fn histogram_name(self) -> Option<&'static str> {
+        match self {
+            Metric::FidelityHistogram => Some("fidelity"),
+            Metric::LatencyHistogram => Some("latency_ms"),
+            Metric::StateFidelityHistogram => Some("state_fidelity"),
+            Metric::Throughput | Metric::LinkGeneration | Metric::MemoryOccupancy => None,
+        }
+    }
+}
+
+impl DetailLevel {
+    /// Whether runs at this level produce `metric`
+    pub fn supports(self, metric: Metric) -> bool {
+        self >= metric.required_detail()
+    }
+
+    /// Error if `metric` needs more detail than this level tracks
+    pub fn require(self, metric: Metric) -> Result<(), String> {
+        if self.supports(metric) {
+            Ok(())
+        } else {
+            Err(format!(
+                "{:?} needs detail level {} but the run used {}",
+                metric,
+                metric.required_detail(),
+                self
+            ))
+        }
+    }
+}
+
+impl fmt::Display for DetailLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DetailLevel::Analytic => "analytic",
+            DetailLevel::FidelityScalar => "fidelity-scalar",
+            DetailLevel::StateVector => "state-vector",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_support_nested_metric_sets() {
+        let metrics = [
+            Metric::Throughput,
+            Metric::LinkGeneration,
+            Metric::MemoryOccupancy,
+            Metric::FidelityHistogram,
+            Metric::LatencyHistogram,
+            Metric::StateFidelityHistogram,
+        ];
+        for metric in metrics {
+            assert!(DetailLevel::StateVector.supports(metric));
+            if DetailLevel::Analytic.supports(metric) {
+                assert!(DetailLevel::FidelityScalar.supports(metric));
+            }
+        }
+        assert!(DetailLevel::Analytic.supports(Metric::Throughput));
+        assert!(DetailLevel::Analytic
+            .require(Metric::FidelityHistogram)
+            .is_err());
+        assert!(DetailLevel::FidelityScalar
+            .require(Metric::StateFidelityHistogram)
+            .is_err());
+    }
+}
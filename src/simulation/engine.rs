@@ -0,0 +1,980 @@
+use super::detail::DetailLevel;
+use super::event::{Event, EventSummary};
+use super::overrides::{AppliedOverride, ParamOverride};
+use super::randomness::{RandomnessDecl, RandomnessSource, StrictRandomnessGuard};
+use super::report::{fingerprint_event, ReportSegment, RunReport, FINGERPRINT_SEED};
+use super::scheduler::EventScheduler;
+use super::staleness::{StaleMemoryAlarm, StaleMemoryMonitor};
+use super::strict::IgnoredParameter;
+use crate::network::{NetworkTopology, TopologySnapshot};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Processes events popped by the engine, possibly scheduling new ones
+pub trait EventHandler {
+    fn handle(&mut self, event: &Event, scheduler: &mut EventScheduler);
+
+    /// Invariant check run after every event in debug builds
+    fn check_invariants(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Topology to capture when the engine auto-snapshots
+    fn topology(&self) -> Option<&NetworkTopology> {
+        None
+    }
+
+    /// Add the handler's own stats and config to the report of a finished run
+    fn report(&self, _report: &mut RunReport) {}
+
+    /// Seed the handler's own RNG from the engine seed (`with_seed`)
+    /// Called before every run that does not resume a pause
+    fn set_seed(&mut self, _seed: u64) {}
+
+    /// Switch to `level` before a run; returns the level actually used
+    /// Handlers without reduced or extended modes keep `FidelityScalar`
+    fn set_detail_level(&mut self, _level: DetailLevel) -> DetailLevel {
+        DetailLevel::FidelityScalar
+    }
+
+    /// Change a model parameter while the run is paused at `current_time`
+    fn apply_override(
+        &mut self,
+        change: &ParamOverride,
+        _scheduler: &mut EventScheduler,
+        _current_time: f64,
+    ) -> Result<(), String> {
+        Err(format!("Handler does not support override: {}", change))
+    }
+}
+
+/// Guards that stop a run regardless of the model's own stop conditions
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunLimits {
+    /// Abort once this much real time has elapsed
+    pub wall_clock_timeout: Option<Duration>,
+    /// Abort before processing any event later than this simulation time
+    pub max_sim_time: Option<f64>,
+}
+
+/// Why a run was stopped early
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutReason {
+    WallClock,
+    SimTime,
+}
+
+/// Result of `SimulationEngine::run`
+#[derive(Debug, Clone)]
+pub enum RunOutcome {
+    /// The event queue drained normally
+    Completed(RunReport),
+    /// A run limit was hit; the report holds partial stats
+    TimedOut {
+        reason: TimeoutReason,
+        report: RunReport,
+    },
+    /// Stopped at the pause time set by `pause_at`; `run` again to resume
+    Paused { at: f64, report: RunReport },
+}
+
+impl RunOutcome {
+    pub fn report(&self) -> &RunReport {
+        match self {
+            RunOutcome::Completed(report) => report,
+            RunOutcome::TimedOut { report, .. } => report,
+            RunOutcome::Paused { report, .. } => report,
+        }
+    }
+
+    pub fn into_report(self) -> RunReport {
+        match self {
+            RunOutcome::Completed(report) => report,
+            RunOutcome::TimedOut { report, .. } => report,
+            RunOutcome::Paused { report, .. } => report,
+        }
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        matches!(self, RunOutcome::TimedOut { .. })
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(self, RunOutcome::Paused { .. })
+    }
+}
+
+/// Stretch of simulated time with no events
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdleGap {
+    /// Time of the event before the gap
+    pub start: f64,
+    /// Time of the event after the gap
+    pub end: f64,
+}
+
+impl IdleGap {
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Idle-time diagnostics accumulated over all runs of an engine
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdleGapReport {
+    /// Largest gaps between consecutive events, longest first
+    pub largest_gaps: Vec<IdleGap>,
+    /// Gaps longer than the engine's `idle_gap_threshold`
+    pub idle_gaps: usize,
+    /// Summed length of those gaps
+    pub total_idle_time: f64,
+    /// Time between the first and last handled event
+    pub span: f64,
+}
+
+/// Number of gaps kept in `IdleGapReport::largest_gaps`
+const REPORTED_GAPS: usize = 5;
+
+/// Callback for gaps longer than a threshold
+struct LargeGapHook {
+    threshold: f64,
+    callback: Box<dyn FnMut(&IdleGap) + Send>,
+}
+
+/// Drives an `EventScheduler`, dispatching events to a handler
+///
+/// `Send` but not `Sync`: move an engine into a worker thread, one per replication
+pub struct SimulationEngine {
+    pub scheduler: EventScheduler,
+    pub limits: RunLimits,
+    /// Snapshot the handler's topology every this many time units
+    pub snapshot_interval: Option<f64>,
+    /// Auto-snapshots taken so far as (time, snapshot)
+    /// Each captures the state before events at that time are handled
+    pub snapshots: Vec<(f64, TopologySnapshot)>,
+    next_snapshot_time: f64,
+    /// Animation frames, captured like the snapshots
+    #[cfg(feature = "telemetry")]
+    frame_exporter: Option<crate::telemetry::FrameExporter>,
+    /// Gaps between events longer than this count as idle time
+    pub idle_gap_threshold: f64,
+    first_event_time: Option<f64>,
+    last_event_time: Option<f64>,
+    idle: IdleGapReport,
+    large_gap_hook: Option<LargeGapHook>,
+    stale_memory: Option<StaleMemoryMonitor>,
+    /// Each node's memory as last observed this run: (version, oldest creation time)
+    memory_watch: HashMap<usize, (u64, Option<f64>)>,
+    /// Seed handed to the handler and echoed in run reports
+    pub seed: Option<u64>,
+    randomness: Vec<RandomnessDecl>,
+    /// Flag unseeded draws during runs (debug builds)
+    pub strict_randomness: bool,
+    /// Detail requested from the handler for each run
+    pub detail_level: DetailLevel,
+    pause_time: Option<f64>,
+    paused_at: Option<f64>,
+    overrides: Vec<AppliedOverride>,
+    /// Handler stats at each override, as (time, cumulative report)
+    segment_marks: Vec<(f64, RunReport)>,
+}
+
+impl SimulationEngine {
+    pub fn new() -> Self {
+        Self::with_limits(RunLimits::default())
+    }
+
+    /// Create an engine with run limits
+    pub fn with_limits(limits: RunLimits) -> Self {
+        SimulationEngine {
+            scheduler: EventScheduler::new(),
+            limits,
+            snapshot_interval: None,
+            snapshots: Vec::new(),
+            next_snapshot_time: 0.0,
+            #[cfg(feature = "telemetry")]
+            frame_exporter: None,
+            idle_gap_threshold: 0.0,
+            first_event_time: None,
+            last_event_time: None,
+            idle: IdleGapReport::default(),
+            large_gap_hook: None,
+            stale_memory: None,
+            memory_watch: HashMap::new(),
+            seed: None,
+            randomness: Vec::new(),
+            strict_randomness: false,
+            detail_level: DetailLevel::default(),
+            pause_time: None,
+            paused_at: None,
+            overrides: Vec::new(),
+            segment_marks: Vec::new(),
+        }
+    }
+
+    /// Auto-snapshot the handler's topology every `interval` time units
+    pub fn with_snapshot_interval(mut self, interval: f64) -> Self {
+        self.snapshot_interval = Some(interval);
+        self
+    }
+
+    /// Record animation frames of the handler's topology during runs
+    #[cfg(feature = "telemetry")]
+    pub fn with_frame_exporter(mut self, exporter: crate::telemetry::FrameExporter) -> Self {
+        self.frame_exporter = Some(exporter);
+        self
+    }
+
+    /// Frames recorded so far, if an exporter is attached
+    #[cfg(feature = "telemetry")]
+    pub fn frame_exporter(&self) -> Option<&crate::telemetry::FrameExporter> {
+        self.frame_exporter.as_ref()
+    }
+
+    /// Record `seed` in run reports and seed the handler with it
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Register the decision points `source` draws for
+    pub fn with_randomness(mut self, source: &impl RandomnessSource) -> Self {
+        self.register_randomness(source);
+        self
+    }
+
+    /// Register the decision points `source` draws for
+    pub fn register_randomness(&mut self, source: &impl RandomnessSource) {
+        self.randomness.extend(source.randomness());
+    }
+
+    /// Every registered stochastic decision point, in registration order
+    pub fn randomness_manifest(&self) -> Vec<RandomnessDecl> {
+        self.randomness.clone()
+    }
+
+    /// Panic on draws from an unseeded RNG during runs (debug builds only)
+    pub fn with_strict_randomness(mut self) -> Self {
+        self.strict_randomness = true;
+        self
+    }
+
+    /// Ask the handler to track `level` of detail
+    pub fn with_detail_level(mut self, level: DetailLevel) -> Self {
+        self.detail_level = level;
+        self
+    }
+
+    /// Stop the next run before any event later than `time`
+    pub fn pause_at(&mut self, time: f64) {
+        self.pause_time = Some(time);
+    }
+
+    /// Simulation time the last run paused at, until the next run resumes
+    pub fn paused_at(&self) -> Option<f64> {
+        self.paused_at
+    }
+
+    /// Change a parameter of `handler` while paused
+    ///
+    /// The change is recorded with the pause time in every later report,
+    /// whose `segments` split the handler's stats at each override.
+    pub fn apply_override(
+        &mut self,
+        handler: &mut impl EventHandler,
+        change: ParamOverride,
+    ) -> Result<(), String> {
+        let time = self
+            .paused_at
+            .ok_or("Overrides can only be applied while the run is paused")?;
+        change.validate()?;
+        handler.apply_override(&change, &mut self.scheduler, time)?;
+
+        let mut cumulative = RunReport::default();
+        handler.report(&mut cumulative);
+        self.segment_marks.push((time, cumulative));
+        self.overrides.push(AppliedOverride { time, change });
+        Ok(())
+    }
+
+    /// Engine settings that will have no effect with `handler`
+    pub fn ignored_parameters(&self, handler: &impl EventHandler) -> Vec<IgnoredParameter> {
+        let mut ignored = Vec::new();
+        if handler.topology().is_none() {
+            if self.snapshot_interval.is_some() {
+                ignored.push(IgnoredParameter::new(
+                    "SimulationEngine",
+                    "snapshot_interval",
+                    "the handler exposes no topology to snapshot",
+                ));
+            }
+            #[cfg(feature = "telemetry")]
+            if self.frame_exporter.is_some() {
+                ignored.push(IgnoredParameter::new(
+                    "SimulationEngine",
+                    "frame_exporter",
+                    "the handler exposes no topology to draw",
+                ));
+            }
+            if self.stale_memory.is_some() {
+                ignored.push(IgnoredParameter::new(
+                    "SimulationEngine",
+                    "stale_memory_alarm",
+                    "the handler exposes no topology to monitor",
+                ));
+            }
+        }
+        ignored
+    }
+
+    /// Overrides applied so far, in order
+    pub fn overrides(&self) -> &[AppliedOverride] {
+        &self.overrides
+    }
+
+    /// Handler stats of `report` split at each override time
+    fn segments(&self, report: &RunReport) -> Vec<ReportSegment> {
+        let empty = RunReport::default();
+        let mut segments = Vec::new();
+        let (mut start, mut earlier) = (0.0, &empty);
+        for (time, mark) in &self.segment_marks {
+            // Several overrides at one pause open a single segment
+            if *time > start {
+                segments.push(ReportSegment::between(start, *time, earlier, mark));
+            }
+            (start, earlier) = (*time, mark);
+        }
+        segments.push(ReportSegment::between(
+            start,
+            report.final_time.max(start),
+            earlier,
+            report,
+        ));
+        segments
+    }
+
+    /// Count only gaps longer than `threshold` as idle time
+    pub fn with_idle_gap_threshold(mut self, threshold: f64) -> Self {
+        self.idle_gap_threshold = threshold;
+        self
+    }
+
+    /// Call `callback` for every gap between consecutive events longer than
+    /// `threshold`, e.g. to catch events scheduled in seconds instead of ms
+    pub fn on_large_gap(
+        mut self,
+        threshold: f64,
+        callback: impl FnMut(&IdleGap) + Send + 'static,
+    ) -> Self {
+        self.large_gap_hook = Some(LargeGapHook {
+            threshold,
+            callback: Box::new(callback),
+        });
+        self
+    }
+
+    /// Raise a `StaleMemoryAlarm` when a node of the handler's topology holds
+    /// a pair older than `staleness_ms` for longer than `grace_ms`
+    pub fn with_stale_memory_alarm(mut self, staleness_ms: f64, grace_ms: f64) -> Self {
+        self.stale_memory = Some(StaleMemoryMonitor::new(staleness_ms, grace_ms));
+        self
+    }
+
+    /// Stale-memory alarms raised so far, in order
+    pub fn stale_memory_alarms(&self) -> &[StaleMemoryAlarm] {
+        self.stale_memory
+            .as_ref()
+            .map_or(&[], |monitor| monitor.alarms())
+    }
+
+    /// Largest gaps and total idle time between handled events
+    pub fn idle_gap_report(&self) -> IdleGapReport {
+        let mut report = self.idle.clone();
+        if let (Some(first), Some(last)) = (self.first_event_time, self.last_event_time) {
+            report.span = last - first;
+        }
+        report
+    }
+
+    /// Account for the gap before an event at `time`
+    fn track_gap(&mut self, time: f64) {
+        self.first_event_time.get_or_insert(time);
+        let Some(previous) = self.last_event_time.replace(time) else {
+            return;
+        };
+        let gap = IdleGap {
+            start: previous,
+            end: time,
+        };
+        if gap.duration() <= 0.0 {
+            return;
+        }
+        if gap.duration() > self.idle_gap_threshold {
+            self.idle.idle_gaps += 1;
+            self.idle.total_idle_time += gap.duration();
+        }
+
+        let gaps = &mut self.idle.largest_gaps;
+        let position = gaps
+            .iter()
+            .position(|other| gap.duration() > other.duration())
+            .unwrap_or(gaps.len());
+        if position < REPORTED_GAPS {
+            gaps.insert(position, gap);
+            gaps.truncate(REPORTED_GAPS);
+        }
+
+        if let Some(hook) = &mut self.large_gap_hook {
+            if gap.duration() > hook.threshold {
+                (hook.callback)(&gap);
+            }
+        }
+    }
+
+    /// Take any snapshots due at or before `time`
+    fn take_due_snapshots(&mut self, handler: &impl EventHandler, time: f64) {
+        let (Some(interval), Some(topology)) = (self.snapshot_interval, handler.topology()) else {
+            return;
+        };
+        while self.next_snapshot_time <= time {
+            self.snapshots
+                .push((self.next_snapshot_time, topology.snapshot()));
+            self.next_snapshot_time += interval;
+        }
+    }
+
+    /// Capture any animation frames due at or before `time`
+    #[cfg(feature = "telemetry")]
+    fn take_due_frames(&mut self, handler: &impl EventHandler, time: f64) {
+        let (Some(exporter), Some(topology)) = (&mut self.frame_exporter, handler.topology())
+        else {
+            return;
+        };
+        if exporter.next_frame_time() > time {
+            return;
+        }
+        // Per-link counters for the success rates
+        let mut stats = RunReport::default();
+        handler.report(&mut stats);
+        exporter.capture_due(topology, &stats.links, time);
+    }
+
+    /// Track pair ages in the handler's topology at `time`
+    ///
+    /// Only nodes whose memory changed since the last call are scanned. An
+    /// untouched node's oldest pair is aged when the node next changes or
+    /// the run ends (`settle_memory`), which gives the same maxima as
+    /// scanning every node each time.
+    fn observe_memory(&mut self, handler: &impl EventHandler, time: f64, report: &mut RunReport) {
+        let Some(topology) = handler.topology() else {
+            return;
+        };
+        for node in topology.nodes() {
+            let version = node.memory_version();
+            let previous = self.memory_watch.get(&node.id).copied();
+            if previous.is_some_and(|(seen, _)| seen == version) {
+                continue;
+            }
+            let stats = report.nodes.entry(node.id).or_default();
+            stats.memory_capacity = node.memory_capacity;
+            stats.peak_occupancy = stats.peak_occupancy.max(node.num_stored_pairs());
+            let oldest = node.oldest_pair_creation_time();
+            // Pairs held since the last call, some possibly just consumed
+            let held = previous.and_then(|(_, oldest)| oldest);
+            for creation_time in held.into_iter().chain(oldest) {
+                stats.max_pair_age_ms = stats.max_pair_age_ms.max(time - creation_time);
+            }
+            self.memory_watch.insert(node.id, (version, oldest));
+        }
+        if let Some(monitor) = &mut self.stale_memory {
+            monitor.observe(topology, time);
+        }
+    }
+
+    /// Age the pairs still held at `final_time` and forget this run's observations
+    fn settle_memory(&mut self, final_time: f64, report: &mut RunReport) {
+        for (node_id, (_, oldest)) in self.memory_watch.drain() {
+            if let (Some(stats), Some(creation_time)) = (report.nodes.get_mut(&node_id), oldest) {
+                stats.max_pair_age_ms = stats.max_pair_age_ms.max(final_time - creation_time);
+            }
+        }
+    }
+
+    /// Process events until the queue is empty, a limit is hit or the run pauses
+    pub fn run(&mut self, handler: &mut impl EventHandler) -> RunOutcome {
+        let start = Instant::now();
+        let first_alarm = self.stale_memory_alarms().len();
+        let mut report = RunReport {
+            seed: self.seed,
+            replications: 1,
+            fingerprint: Some(FINGERPRINT_SEED),
+            detail_level: handler.set_detail_level(self.detail_level),
+            ..RunReport::default()
+        };
+        report.ignored_parameters = self.ignored_parameters(handler);
+        report.randomness = self.randomness_manifest();
+        for decl in &self.randomness {
+            report.config.insert(
+                format!("randomness.{}.{}", decl.component, decl.label),
+                decl.distribution_string(),
+            );
+        }
+        let _strict = self.strict_randomness.then(StrictRandomnessGuard::enter);
+
+        if let (Some(seed), None) = (self.seed, self.paused_at) {
+            handler.set_seed(seed);
+        }
+        self.paused_at = None;
+        let mut paused = None;
+        let timeout = loop {
+            if let Some(limit) = self.limits.wall_clock_timeout {
+                if start.elapsed() >= limit {
+                    break Some(TimeoutReason::WallClock);
+                }
+            }
+
+            let next_time = match self.scheduler.peek_next() {
+                Some(event) => event.time,
+                None => break None,
+            };
+            if let Some(pause_time) = self.pause_time {
+                if next_time > pause_time {
+                    paused = Some(pause_time);
+                    break None;
+                }
+            }
+            if let Some(max_time) = self.limits.max_sim_time {
+                if next_time > max_time {
+                    break Some(TimeoutReason::SimTime);
+                }
+            }
+
+            self.take_due_snapshots(handler, next_time);
+            #[cfg(feature = "telemetry")]
+            self.take_due_frames(handler, next_time);
+            let event = self.scheduler.next_event().unwrap();
+            self.track_gap(event.time);
+            // Before handling to catch pairs consumed by this event at their oldest
+            self.observe_memory(handler, event.time, &mut report);
+            handler.handle(&event, &mut self.scheduler);
+            self.observe_memory(handler, event.time, &mut report);
+            #[cfg(debug_assertions)]
+            if let Err(message) = handler.check_invariants() {
+                panic!("Invariant violated after {:?}: {}", event, message);
+            }
+
+            report.events_processed += 1;
+            report.final_time = event.time;
+            report.last_event = Some(EventSummary::from(&event));
+            report.fingerprint = report
+                .fingerprint
+                .map(|hash| fingerprint_event(hash, &event));
+        };
+
+        self.settle_memory(report.final_time, &mut report);
+        report.wall_clock = start.elapsed();
+        report.pending_by_type = self.scheduler.count_by_type();
+        report.stale_memory_alarms = self.stale_memory_alarms()[first_alarm..].to_vec();
+        handler.report(&mut report);
+        if !self.overrides.is_empty() {
+            report.overrides = self.overrides.clone();
+            report.segments = self.segments(&report);
+        }
+        self.pause_time = None;
+
+        match (timeout, paused) {
+            (Some(reason), _) => RunOutcome::TimedOut { reason, report },
+            (None, Some(at)) => {
+                self.paused_at = Some(at);
+                RunOutcome::Paused { at, report }
+            }
+            (None, None) => RunOutcome::Completed(report),
+        }
+    }
+}
+
+impl Default for SimulationEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::EventType;
+
+    /// Reschedules itself forever, `delay` after each event
+    struct SelfPerpetuating {
+        delay: f64,
+    }
+
+    impl EventHandler for SelfPerpetuating {
+        fn handle(&mut self, event: &Event, scheduler: &mut EventScheduler) {
+            scheduler.schedule(Event::new(
+                event.time + self.delay,
+                event.event_type,
+                event.node_id,
+            ));
+        }
+    }
+
+    /// Handles events without scheduling new ones
+    struct Sink;
+
+    impl EventHandler for Sink {
+        fn handle(&mut self, _event: &Event, _scheduler: &mut EventScheduler) {}
+    }
+
+    #[test]
+    fn test_completed_run() {
+        let mut engine = SimulationEngine::new();
+        engine
+            .scheduler
+            .schedule(Event::new(1.0, EventType::Measurement, 0));
+        engine
+            .scheduler
+            .schedule(Event::new(2.0, EventType::Measurement, 0));
+
+        let outcome = engine.run(&mut Sink);
+        assert!(!outcome.is_timed_out());
+        assert_eq!(outcome.report().events_processed, 2);
+        assert_eq!(outcome.report().final_time, 2.0);
+    }
+
+    #[test]
+    fn test_fingerprint_identifies_event_sequence() {
+        let run = |times: &[f64]| {
+            let mut engine = SimulationEngine::new();
+            for &time in times {
+                engine
+                    .scheduler
+                    .schedule(Event::new(time, EventType::Measurement, 0));
+            }
+            engine.run(&mut Sink).report().fingerprint
+        };
+        assert_eq!(run(&[1.0, 2.0]), run(&[1.0, 2.0]));
+        assert_ne!(run(&[1.0, 2.0]), run(&[1.0, 3.0]));
+        assert_ne!(run(&[1.0, 2.0]), run(&[]));
+    }
+
+    #[test]
+    fn test_wall_clock_timeout() {
+        let mut engine = SimulationEngine::with_limits(RunLimits {
+            wall_clock_timeout: Some(Duration::from_millis(20)),
+            max_sim_time: None,
+        });
+        engine
+            .scheduler
+            .schedule(Event::new(0.0, EventType::EntanglementGeneration, 3));
+
+        // Zero delay: simulation time never advances
+        let outcome = engine.run(&mut SelfPerpetuating { delay: 0.0 });
+
+        match &outcome {
+            RunOutcome::TimedOut { reason, report } => {
+                assert_eq!(*reason, TimeoutReason::WallClock);
+                assert!(report.events_processed > 0);
+                assert_eq!(
+                    report
+                        .pending_by_type
+                        .get(&EventType::EntanglementGeneration),
+                    Some(&1)
+                );
+                assert_eq!(report.last_event.unwrap().node_id, 3);
+            }
+            _ => panic!("Run should have timed out"),
+        }
+    }
+
+    #[test]
+    fn test_max_sim_time() {
+        let mut engine = SimulationEngine::with_limits(RunLimits {
+            wall_clock_timeout: None,
+            max_sim_time: Some(10.0),
+        });
+        engine
+            .scheduler
+            .schedule(Event::new(0.0, EventType::EntanglementGeneration, 0));
+
+        let outcome = engine.run(&mut SelfPerpetuating { delay: 1.0 });
+
+        match outcome {
+            RunOutcome::TimedOut { reason, report } => {
+                assert_eq!(reason, TimeoutReason::SimTime);
+                // Events at t = 0, 1, ..., 10
+                assert_eq!(report.events_processed, 11);
+                assert_eq!(report.final_time, 10.0);
+            }
+            _ => panic!("Run should have timed out"),
+        }
+    }
+
+    #[test]
+    fn test_idle_gap_report_flags_unit_bug() {
+        let flagged = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = flagged.clone();
+        let mut engine = SimulationEngine::new()
+            .with_idle_gap_threshold(10.0)
+            .on_large_gap(1000.0, move |gap| sink.lock().unwrap().push(*gap));
+
+        // Regular 1 ms attempts, plus one event scheduled 1e6 units out
+        for time in [0.0, 1.0, 2.0, 3.0, 1e6 + 3.0, 1e6 + 4.0] {
+            engine
+                .scheduler
+                .schedule(Event::new(time, EventType::EntanglementGeneration, 0));
+        }
+        engine.run(&mut Sink);
+
+        let report = engine.idle_gap_report();
+        assert_eq!(
+            report.largest_gaps[0],
+            IdleGap {
+                start: 3.0,
+                end: 1e6 + 3.0
+            }
+        );
+        assert_eq!(report.largest_gaps.len(), 5);
+        assert_eq!(report.idle_gaps, 1);
+        assert_eq!(report.total_idle_time, 1e6);
+        assert_eq!(report.span, 1e6 + 4.0);
+        assert_eq!(*flagged.lock().unwrap(), vec![report.largest_gaps[0]]);
+    }
+
+    /// Stores one pair at node 0 per event
+    struct Filler {
+        topology: NetworkTopology,
+    }
+
+    impl EventHandler for Filler {
+        fn handle(&mut self, event: &Event, _scheduler: &mut EventScheduler) {
+            let pair = crate::network::StoredPair::new(
+                1,
+                crate::quantum::TwoQubitState::new_bell_phi_plus(),
+                event.time,
+                100.0,
+            );
+            let _ = self.topology.get_node_mut(0).unwrap().store_pair(pair);
+        }
+
+        fn topology(&self) -> Option<&NetworkTopology> {
+            Some(&self.topology)
+        }
+    }
+
+    /// Keeps one 0-1 pair stored; consumes it every event except while stalled
+    struct StallingConsumer {
+        topology: NetworkTopology,
+        stalled: std::ops::Range<f64>,
+    }
+
+    impl EventHandler for StallingConsumer {
+        fn handle(&mut self, event: &Event, _scheduler: &mut EventScheduler) {
+            if !self.stalled.contains(&event.time) {
+                self.topology.discard_link_pair(0, 1, event.time);
+            }
+            if self.topology.get_node(0).unwrap().num_stored_pairs() == 0 {
+                let (node_a, node_b) = self.topology.get_node_pair_mut(0, 1).unwrap();
+                let bell_state = crate::quantum::TwoQubitState::new_bell_phi_plus();
+                let pair = crate::network::StoredPair::new(1, bell_state, event.time, 100.0);
+                node_a.store_pair(pair.clone()).unwrap();
+                node_b
+                    .store_pair(crate::network::StoredPair {
+                        partner_node_id: 0,
+                        ..pair
+                    })
+                    .unwrap();
+            }
+        }
+
+        fn topology(&self) -> Option<&NetworkTopology> {
+            Some(&self.topology)
+        }
+    }
+
+    #[test]
+    fn test_stale_memory_alarm_raised_once_and_cleared() {
+        let mut engine = SimulationEngine::new().with_stale_memory_alarm(5.0, 3.0);
+        for time in 0..=40 {
+            engine
+                .scheduler
+                .schedule(Event::new(time as f64, EventType::Measurement, 0));
+        }
+        // The pair stored at t=10 is held until consumption resumes at t=30
+        let mut handler = StallingConsumer {
+            topology: NetworkTopology::new_linear(2, 1, 10.0, 0.2),
+            stalled: 11.0..30.0,
+        };
+        let outcome = engine.run(&mut handler);
+
+        // Stale from t=15, alarm after the 3 ms grace period
+        let alarms = engine.stale_memory_alarms();
+        assert_eq!(alarms.len(), 2);
+        for (alarm, node_id) in alarms.iter().zip([0, 1]) {
+            assert_eq!(alarm.node_id, node_id);
+            assert_eq!(alarm.raised_at, 18.0);
+            assert_eq!(alarm.oldest_pair_age, 8.0);
+            assert_eq!(alarm.cleared_at, Some(30.0));
+        }
+        assert!(alarms.iter().all(|alarm| !alarm.is_active()));
+
+        let report = outcome.report();
+        assert_eq!(report.stale_memory_alarms, alarms);
+        assert_eq!(report.nodes[&0].max_pair_age_ms, 20.0);
+        assert_eq!(report.nodes[&1].max_pair_age_ms, 20.0);
+        assert_eq!(report.nodes[&0].peak_occupancy, 1);
+    }
+
+    /// Scans every node around each event, as a reference for the engine
+    struct FullScan {
+        inner: StallingConsumer,
+        report: RunReport,
+    }
+
+    impl EventHandler for FullScan {
+        fn handle(&mut self, event: &Event, scheduler: &mut EventScheduler) {
+            self.report.observe_memory(&self.inner.topology, event.time);
+            self.inner.handle(event, scheduler);
+            self.report.observe_memory(&self.inner.topology, event.time);
+        }
+
+        fn topology(&self) -> Option<&NetworkTopology> {
+            self.inner.topology()
+        }
+    }
+
+    #[test]
+    fn test_memory_stats_match_full_scan() {
+        let mut engine = SimulationEngine::new();
+        for time in 0..=40 {
+            engine
+                .scheduler
+                .schedule(Event::new(time as f64, EventType::Measurement, 0));
+        }
+        let mut topology = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        // Held at node 2 for the whole run, untouched by any event
+        let bell_state = crate::quantum::TwoQubitState::new_bell_phi_plus();
+        let pair = crate::network::StoredPair::new(1, bell_state, 0.0, 100.0);
+        topology.get_node_mut(2).unwrap().store_pair(pair).unwrap();
+        let mut handler = FullScan {
+            inner: StallingConsumer {
+                topology,
+                stalled: 11.0..30.0,
+            },
+            report: RunReport::default(),
+        };
+        let outcome = engine.run(&mut handler);
+
+        let report = outcome.report();
+        assert_eq!(report.nodes, handler.report.nodes);
+        assert_eq!(report.nodes[&2].max_pair_age_ms, 40.0);
+        assert_eq!(report.nodes[&0].max_pair_age_ms, 20.0);
+    }
+
+    #[cfg(feature = "telemetry")]
+    #[test]
+    fn test_frame_exporter_follows_run() {
+        let mut engine = SimulationEngine::new()
+            .with_frame_exporter(crate::telemetry::FrameExporter::new(4.0, 100));
+        for time in [5.0, 15.0, 25.0] {
+            engine
+                .scheduler
+                .schedule(Event::new(time, EventType::EntanglementGeneration, 0));
+        }
+        let mut handler = Filler {
+            topology: NetworkTopology::new_linear(2, 5, 10.0, 0.2),
+        };
+        engine.run(&mut handler);
+
+        // Frames at 0, 4, ..., 24: every frame time up to the last event
+        let exporter = engine.frame_exporter().unwrap();
+        assert_eq!(exporter.frames_captured(), 7);
+        let occupancy: Vec<usize> = exporter
+            .frames()
+            .iter()
+            .map(|frame| frame.nodes[0].occupancy)
+            .collect();
+        assert_eq!(occupancy, vec![0, 0, 1, 1, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_auto_snapshots() {
+        let mut engine = SimulationEngine::new().with_snapshot_interval(10.0);
+        for time in [5.0, 15.0, 25.0] {
+            engine
+                .scheduler
+                .schedule(Event::new(time, EventType::EntanglementGeneration, 0));
+        }
+        let mut handler = Filler {
+            topology: NetworkTopology::new_linear(2, 5, 10.0, 0.2),
+        };
+        engine.run(&mut handler);
+
+        let times: Vec<f64> = engine.snapshots.iter().map(|(time, _)| *time).collect();
+        assert_eq!(times, vec![0.0, 10.0, 20.0]);
+
+        let diff = engine.snapshots[0].1.diff(&engine.snapshots[2].1);
+        assert_eq!(
+            diff.changes,
+            vec![crate::network::TopologyChange::OccupancyChanged {
+                node_id: 0,
+                old: 0,
+                new: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_pause_resumes_where_it_stopped() {
+        let mut engine = SimulationEngine::new();
+        for t in 0..10 {
+            engine
+                .scheduler
+                .schedule(Event::new(t as f64, EventType::EntanglementGeneration, 0));
+        }
+
+        engine.pause_at(4.5);
+        let outcome = engine.run(&mut Sink);
+        assert!(outcome.is_paused());
+        assert_eq!(outcome.report().final_time, 4.0);
+        assert_eq!(engine.paused_at(), Some(4.5));
+
+        // Sink has no parameters to override
+        assert!(engine
+            .apply_override(&mut Sink, ParamOverride::AttemptInterval(2.0))
+            .is_err());
+        assert!(engine.overrides().is_empty());
+
+        let outcome = engine.run(&mut Sink);
+        assert!(!outcome.is_paused());
+        assert_eq!(engine.paused_at(), None);
+        assert_eq!(outcome.report().events_processed, 5);
+        assert!(engine
+            .apply_override(&mut Sink, ParamOverride::AttemptInterval(2.0))
+            .unwrap_err()
+            .contains("paused"));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "offending_entries: [AuditEntry { sequence: 2")]
+    fn test_audited_run_panics_on_double_remove() {
+        use crate::experiment::{RequestService, ServiceConfig};
+        use crate::network::{AuditOp, StoredPair};
+        use crate::quantum::TwoQubitState;
+
+        let mut topology = NetworkTopology::new_linear(2, 2, 1.0, 0.2);
+        let node = topology.get_node_mut(0).unwrap();
+        node.enable_audit(None);
+        let pair = StoredPair::new(1, TwoQubitState::new_bell_phi_plus(), 0.0, 100.0);
+        node.store_pair(pair).unwrap();
+        let pair_id = node.remove_pair_with(1, 0.0).unwrap().pair_id;
+        // Second removal of the same pair, as a buggy protocol might log it
+        node.inject_audit_entry(AuditOp::Remove, Some(pair_id), 0.0);
+
+        let mut service = RequestService::new(topology, ServiceConfig::default());
+        let mut engine = SimulationEngine::new();
+        service.submit(0, 1, 1, &mut engine.scheduler, 0.0).unwrap();
+        engine.run(&mut service);
+    }
+}
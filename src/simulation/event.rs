@@ -1,7 +1,8 @@
 use std::cmp::Ordering;
 
 /// Types of events that can occur in the simulation
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventType {
     /// Attempt to generate entanglement on a channel
     EntanglementGeneration,
@@ -42,6 +43,31 @@ impl Event {
     }
 }
 
+/// Lightweight view of a pending event, used for scheduler introspection
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventSummary {
+    /// Time when the event is scheduled
+    pub time: f64,
+    /// Type of event
+    pub event_type: EventType,
+    /// ID of the node where the event occurs
+    pub node_id: usize,
+    /// Optional: ID of another node involved
+    pub target_node_id: Option<usize>,
+}
+
+impl From<&Event> for EventSummary {
+    fn from(event: &Event) -> Self {
+        EventSummary {
+            time: event.time,
+            event_type: event.event_type,
+            node_id: event.node_id,
+            target_node_id: event.target_node_id,
+        }
+    }
+}
+
 // Make events orderable by time (needed for priority queue)
 impl PartialEq for Event {
     fn eq(&self, other: &Self) -> bool {
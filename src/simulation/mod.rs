@@ -1,5 +1,21 @@
+pub mod detail;
+pub mod engine;
 pub mod event;
+pub mod overrides;
+pub mod randomness;
+pub mod report;
 pub mod scheduler;
+pub mod staleness;
+pub mod strict;
 
-pub use event::{Event, EventType};
-pub use scheduler::EventScheduler;
+pub use detail::{DetailLevel, Metric};
+pub use engine::{
+    EventHandler, IdleGap, IdleGapReport, RunLimits, RunOutcome, SimulationEngine, TimeoutReason,
+};
+pub use event::{Event, EventSummary, EventType};
+pub use overrides::{AppliedOverride, ParamOverride};
+pub use randomness::{unseeded_rng, HandlerRng, RandomnessDecl, RandomnessSource};
+pub use report::{LinkStats, NodeStats, ReportSegment, RequestStats, RunReport};
+pub use scheduler::{Clock, EventScheduler, PastEventPolicy};
+pub use staleness::{StaleMemoryAlarm, StaleMemoryMonitor};
+pub use strict::{IgnoredParameter, ParameterUsage, StrictConfig};
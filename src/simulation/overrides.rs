@@ -0,0 +1,217 @@
+//! Parameter changes applied to a paused run
+
+use crate::network::{ChannelId, NetworkTopology, RetryPolicy};
+use crate::protocols::banking::BankingPolicy;
+use std::fmt;
+
+/// A parameter change for `SimulationEngine::apply_override`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParamOverride {
+    ChannelAttenuation {
+        channel_id: ChannelId,
+        db_per_km: f64,
+    },
+    /// Takes effect from the link's next attempt; its failure streak carries over
+    ChannelRetryPolicy {
+        channel_id: ChannelId,
+        policy: RetryPolicy,
+    },
+    DetectorEfficiency(f64),
+    CollectionEfficiency(f64),
+    BsmEfficiency(f64),
+    /// Delivered pairs older than this are dropped (None: never)
+    DeliveryCutoff(Option<f64>),
+    AttemptInterval(f64),
+    SwapSuccessProbability(f64),
+    /// Replace the banking policy `node_id` holds for `policy.partner`
+    BankingPolicy {
+        node_id: usize,
+        policy: BankingPolicy,
+    },
+}
+
+impl ParamOverride {
+    /// Check the new value is physically meaningful
+    pub fn validate(&self) -> Result<(), String> {
+        let probability = |name: &str, value: f64| {
+            if (0.0..=1.0).contains(&value) {
+                Ok(())
+            } else {
+                Err(format!("{} must be in [0, 1], got {}", name, value))
+            }
+        };
+        match *self {
+            ParamOverride::ChannelAttenuation { db_per_km, .. } => {
+                if db_per_km.is_finite() && db_per_km >= 0.0 {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Attenuation must be finite and non-negative, got {}",
+                        db_per_km
+                    ))
+                }
+            }
+            ParamOverride::ChannelRetryPolicy { policy, .. } => policy.validate(),
+            ParamOverride::DetectorEfficiency(value) => probability("Detector efficiency", value),
+            ParamOverride::CollectionEfficiency(value) => {
+                probability("Collection efficiency", value)
+            }
+            ParamOverride::BsmEfficiency(value) => probability("BSM efficiency", value),
+            ParamOverride::DeliveryCutoff(Some(cutoff_ms)) if cutoff_ms < 0.0 => Err(format!(
+                "Delivery cutoff must be non-negative, got {}",
+                cutoff_ms
+            )),
+            ParamOverride::DeliveryCutoff(_) => Ok(()),
+            ParamOverride::AttemptInterval(interval_ms) => {
+                if interval_ms > 0.0 {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Attempt interval must be positive, got {}",
+                        interval_ms
+                    ))
+                }
+            }
+            ParamOverride::SwapSuccessProbability(value) => {
+                probability("Swap success probability", value)
+            }
+            ParamOverride::BankingPolicy { policy, .. } => {
+                probability("Banking minimum fidelity", policy.min_fidelity)
+            }
+        }
+    }
+
+    /// Apply a channel override to `topology`
+    /// Returns Ok(false) for overrides that do not target a channel
+    pub fn apply_to_topology(&self, topology: &mut NetworkTopology) -> Result<bool, String> {
+        let channel_id = match *self {
+            ParamOverride::ChannelAttenuation { channel_id, .. }
+            | ParamOverride::ChannelRetryPolicy { channel_id, .. } => channel_id,
+            _ => return Ok(false),
+        };
+        let channel = topology
+            .channel_mut(channel_id)
+            .ok_or_else(|| format!("No channel with id {}", channel_id))?;
+        match *self {
+            ParamOverride::ChannelAttenuation { db_per_km, .. } => {
+                channel.attenuation_db_per_km = db_per_km;
+            }
+            ParamOverride::ChannelRetryPolicy { policy, .. } => channel.retry_policy = policy,
+            _ => unreachable!(),
+        }
+        Ok(true)
+    }
+}
+
+impl fmt::Display for ParamOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamOverride::ChannelAttenuation {
+                channel_id,
+                db_per_km,
+            } => write!(
+                f,
+                "channel {} attenuation = {} dB/km",
+                channel_id, db_per_km
+            ),
+            ParamOverride::ChannelRetryPolicy { channel_id, policy } => {
+                write!(f, "channel {} retry policy = {:?}", channel_id, policy)
+            }
+            ParamOverride::DetectorEfficiency(value) => {
+                write!(f, "detector efficiency = {}", value)
+            }
+            ParamOverride::CollectionEfficiency(value) => {
+                write!(f, "collection efficiency = {}", value)
+            }
+            ParamOverride::BsmEfficiency(value) => write!(f, "BSM efficiency = {}", value),
+            ParamOverride::DeliveryCutoff(cutoff_ms) => {
+                write!(f, "delivery cutoff = {:?} ms", cutoff_ms)
+            }
+            ParamOverride::AttemptInterval(interval_ms) => {
+                write!(f, "attempt interval = {} ms", interval_ms)
+            }
+            ParamOverride::SwapSuccessProbability(value) => {
+                write!(f, "swap success probability = {}", value)
+            }
+            ParamOverride::BankingPolicy { node_id, policy } => write!(
+                f,
+                "node {} banking with {} = {} pairs >= {}",
+                node_id, policy.partner, policy.target_count, policy.min_fidelity
+            ),
+        }
+    }
+}
+
+/// An override with the simulation time it took effect
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AppliedOverride {
+    /// Pause time: events up to here ran with the old value
+    pub time: f64,
+    pub change: ParamOverride,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rejects_unphysical_values() {
+        assert!(ParamOverride::DetectorEfficiency(1.2).validate().is_err());
+        assert!(ParamOverride::AttemptInterval(0.0).validate().is_err());
+        assert!(ParamOverride::DeliveryCutoff(Some(-1.0))
+            .validate()
+            .is_err());
+        assert!(ParamOverride::ChannelAttenuation {
+            channel_id: ChannelId(0),
+            db_per_km: f64::NAN,
+        }
+        .validate()
+        .is_err());
+        assert!(ParamOverride::ChannelRetryPolicy {
+            channel_id: ChannelId(0),
+            policy: RetryPolicy {
+                max_consecutive_failures: Some(3),
+                backoff: crate::network::Backoff::Fixed(-1.0),
+            },
+        }
+        .validate()
+        .is_err());
+        assert!(ParamOverride::DeliveryCutoff(None).validate().is_ok());
+    }
+
+    #[test]
+    fn test_channel_override_targets_channel_by_id() {
+        let mut topology = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        let change = ParamOverride::ChannelAttenuation {
+            channel_id: ChannelId(1),
+            db_per_km: 0.5,
+        };
+        assert_eq!(change.apply_to_topology(&mut topology), Ok(true));
+        assert_eq!(
+            topology
+                .channel(ChannelId(0))
+                .unwrap()
+                .attenuation_db_per_km,
+            0.2
+        );
+        assert_eq!(
+            topology
+                .channel(ChannelId(1))
+                .unwrap()
+                .attenuation_db_per_km,
+            0.5
+        );
+
+        let missing = ParamOverride::ChannelAttenuation {
+            channel_id: ChannelId(9),
+            db_per_km: 0.5,
+        };
+        assert!(missing.apply_to_topology(&mut topology).is_err());
+        assert_eq!(
+            ParamOverride::BsmEfficiency(0.5).apply_to_topology(&mut topology),
+            Ok(false)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Manifest of the stochastic decision points in a simulation
+//!
+//! Components that draw random numbers describe each draw as a
+//! `RandomnessDecl`; the engine collects them at build time and echoes the
+//! list in every run report. Draws from an unseeded RNG go through
+//! `unseeded_rng`, which debug builds reject while a strict-randomness run
+//! is in progress.
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::cell::Cell;
+use std::fmt;
+
+/// One point where a component draws a random number
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RandomnessDecl {
+    /// Type drawing the number, e.g. "BarrettKokProtocol[0-1]"
+    pub component: String,
+    /// What the draw decides, e.g. "transmission"
+    pub label: String,
+    /// Distribution name, e.g. "Bernoulli"
+    pub distribution: String,
+    /// Distribution parameters as (name, value)
+    pub parameters: Vec<(String, f64)>,
+}
+
+impl RandomnessDecl {
+    pub fn new(
+        component: &str,
+        label: &str,
+        distribution: &str,
+        parameters: &[(&str, f64)],
+    ) -> Self {
+        RandomnessDecl {
+            component: component.to_string(),
+            label: label.to_string(),
+            distribution: distribution.to_string(),
+            parameters: parameters
+                .iter()
+                .map(|&(name, value)| (name.to_string(), value))
+                .collect(),
+        }
+    }
+
+    /// Yes/no draw that succeeds with probability `p`
+    pub fn bernoulli(component: &str, label: &str, p: f64) -> Self {
+        Self::new(component, label, "Bernoulli", &[("p", p)])
+    }
+
+    /// Draw of one of `outcomes` equally likely values
+    pub fn uniform(component: &str, label: &str, outcomes: usize) -> Self {
+        Self::new(
+            component,
+            label,
+            "Uniform",
+            &[("outcomes", outcomes as f64)],
+        )
+    }
+
+    /// Distribution with its parameters, e.g. "Bernoulli(p=0.5)"
+    pub fn distribution_string(&self) -> String {
+        let parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        format!("{}({})", self.distribution, parameters.join(", "))
+    }
+}
+
+impl fmt::Display for RandomnessDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}: {}",
+            self.component,
+            self.label,
+            self.distribution_string()
+        )
+    }
+}
+
+/// Something that draws random numbers during a run
+pub trait RandomnessSource {
+    /// Every decision point this component may draw for
+    fn randomness(&self) -> Vec<RandomnessDecl>;
+}
+
+thread_local! {
+    static STRICT_RANDOMNESS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Thread RNG for components without a seeded `*_with_rng` path
+///
+/// Such draws cannot be reproduced from the run's seed, so debug builds
+/// panic if one happens during a strict-randomness run.
+pub fn unseeded_rng(component: &str) -> rand::rngs::ThreadRng {
+    debug_assert!(
+        !STRICT_RANDOMNESS.with(Cell::get),
+        "{} drew from an unseeded RNG during a strict-randomness run",
+        component
+    );
+    rand::rng()
+}
+
+/// RNG of an event handler, seeded from the engine seed
+/// (`EventHandler::set_seed`)
+///
+/// Until seeded, every draw goes through `unseeded_rng` under the handler's
+/// component name.
+#[derive(Debug, Clone)]
+pub struct HandlerRng {
+    component: &'static str,
+    seeded: Option<StdRng>,
+}
+
+impl HandlerRng {
+    pub fn unseeded(component: &'static str) -> Self {
+        HandlerRng {
+            component,
+            seeded: None,
+        }
+    }
+
+    /// Restart the stream from `seed`
+    pub fn seed(&mut self, seed: u64) {
+        self.seeded = Some(StdRng::seed_from_u64(seed));
+    }
+
+    pub fn is_seeded(&self) -> bool {
+        self.seeded.is_some()
+    }
+}
+
+impl RngCore for HandlerRng {
+    fn next_u32(&mut self) -> u32 {
+        match &mut self.seeded {
+            Some(rng) => rng.next_u32(),
+            None => unseeded_rng(self.component).next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match &mut self.seeded {
+            Some(rng) => rng.next_u64(),
+            None => unseeded_rng(self.component).next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        match &mut self.seeded {
+            Some(rng) => rng.fill_bytes(dst),
+            None => unseeded_rng(self.component).fill_bytes(dst),
+        }
+    }
+}
+
+/// Marks the current thread as in a strict-randomness run until dropped
+pub(crate) struct StrictRandomnessGuard {
+    previous: bool,
+}
+
+impl StrictRandomnessGuard {
+    pub(crate) fn enter() -> Self {
+        StrictRandomnessGuard {
+            previous: STRICT_RANDOMNESS.with(|strict| strict.replace(true)),
+        }
+    }
+}
+
+impl Drop for StrictRandomnessGuard {
+    fn drop(&mut self) {
+        STRICT_RANDOMNESS.with(|strict| strict.set(self.previous));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_names_component_label_and_parameters() {
+        let decl = RandomnessDecl::bernoulli("QuantumChannel[0-1]", "transmission", 0.25);
+        assert_eq!(
+            decl.to_string(),
+            "QuantumChannel[0-1].transmission: Bernoulli(p=0.25)"
+        );
+        assert_eq!(
+            RandomnessDecl::uniform("Swap", "bsm_outcome", 4).distribution_string(),
+            "Uniform(outcomes=4)"
+        );
+    }
+
+    #[test]
+    fn test_guard_restores_previous_mode() {
+        let outer = StrictRandomnessGuard::enter();
+        {
+            let _inner = StrictRandomnessGuard::enter();
+        }
+        assert!(STRICT_RANDOMNESS.with(Cell::get));
+        drop(outer);
+        let _ = unseeded_rng("test");
+    }
+}
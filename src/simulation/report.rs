@@ -0,0 +1,577 @@
+use super::detail::{DetailLevel, Metric};
+use super::event::{Event, EventSummary, EventType};
+use super::overrides::AppliedOverride;
+use super::randomness::RandomnessDecl;
+use super::staleness::StaleMemoryAlarm;
+use super::strict::IgnoredParameter;
+use crate::format::{format_probability, format_rate, format_time};
+use crate::network::{ChannelId, GenerationStats, NetworkTopology};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// FNV-1a offset basis, the fingerprint of an empty run
+pub(crate) const FINGERPRINT_SEED: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Fold one handled event into a run fingerprint
+pub(crate) fn fingerprint_event(hash: u64, event: &Event) -> u64 {
+    let hash = fnv1a(hash, &event.time.to_bits().to_le_bytes());
+    let hash = fnv1a(hash, &[event.event_type as u8]);
+    fnv1a(hash, &(event.node_id as u64).to_le_bytes())
+}
+
+/// Generation statistics of one link
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkStats {
+    pub channel_id: ChannelId,
+    pub from: usize,
+    pub to: usize,
+    pub generation: GenerationStats,
+}
+
+/// Counters of end-to-end pair requests
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestStats {
+    pub submitted: usize,
+    pub delivered: usize,
+    /// Swap rounds that failed and discarded their link pairs
+    pub failed_swaps: usize,
+}
+
+impl RequestStats {
+    /// Counters accumulated after `earlier`
+    pub fn since(&self, earlier: &RequestStats) -> RequestStats {
+        RequestStats {
+            submitted: self.submitted.saturating_sub(earlier.submitted),
+            delivered: self.delivered.saturating_sub(earlier.delivered),
+            failed_swaps: self.failed_swaps.saturating_sub(earlier.failed_swaps),
+        }
+    }
+}
+
+/// Handler stats between two override times
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportSegment {
+    pub start: f64,
+    pub end: f64,
+    pub generation: GenerationStats,
+    /// Per-link generation stats, same order as `RunReport::links`
+    pub links: Vec<LinkStats>,
+    pub requests: RequestStats,
+    /// Samples recorded during the segment
+    pub histograms: BTreeMap<String, Vec<f64>>,
+}
+
+impl ReportSegment {
+    /// Difference of two cumulative reports of the same run
+    pub fn between(start: f64, end: f64, earlier: &RunReport, later: &RunReport) -> Self {
+        let links = later
+            .links
+            .iter()
+            .map(|link| LinkStats {
+                generation: match earlier.link(link.channel_id, link.from, link.to) {
+                    Some(before) => link.generation.since(before),
+                    None => link.generation.clone(),
+                },
+                ..link.clone()
+            })
+            .collect();
+        let histograms = later
+            .histograms
+            .iter()
+            .map(|(name, samples)| {
+                let skip = earlier.histogram(name).len().min(samples.len());
+                (name.clone(), samples[skip..].to_vec())
+            })
+            .collect();
+        ReportSegment {
+            start,
+            end,
+            generation: later.generation.since(&earlier.generation),
+            links,
+            requests: later.requests.since(&earlier.requests),
+            histograms,
+        }
+    }
+}
+
+/// Memory usage of one node over a run
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeStats {
+    pub memory_capacity: usize,
+    /// Most pairs stored at once
+    pub peak_occupancy: usize,
+    /// Oldest pair age seen (ms)
+    pub max_pair_age_ms: f64,
+}
+
+/// Everything known about a run, or about several merged replications
+///
+/// The engine fills in timings, the fingerprint and memory observations;
+/// the handler adds its own stats through `EventHandler::report`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunReport {
+    /// Parameters of the run as name -> value
+    pub config: BTreeMap<String, String>,
+    /// Seed the run's RNG was created from, if any
+    pub seed: Option<u64>,
+    /// Runs merged into this report
+    pub replications: usize,
+    /// Detail the handler tracked; the least detailed when merged
+    pub detail_level: DetailLevel,
+    /// Events handled
+    pub events_processed: usize,
+    /// Simulation time of the last handled event (summed when merged)
+    pub final_time: f64,
+    /// Real time spent in the run
+    pub wall_clock: Duration,
+    /// Events still queued when the run ended, by type
+    pub pending_by_type: HashMap<EventType, usize>,
+    /// The last event handled
+    pub last_event: Option<EventSummary>,
+    /// Hash of the handled event sequence; identical runs share it
+    /// None for reports not produced by the engine
+    pub fingerprint: Option<u64>,
+    /// Generation attempts over all links
+    pub generation: GenerationStats,
+    /// Per-link generation stats, sorted by channel id, then endpoints
+    pub links: Vec<LinkStats>,
+    pub requests: RequestStats,
+    /// Memory usage by node id; empty if the handler has no topology
+    pub nodes: BTreeMap<usize, NodeStats>,
+    pub stale_memory_alarms: Vec<StaleMemoryAlarm>,
+    /// Named samples, e.g. the fidelity of each delivered pair
+    pub histograms: BTreeMap<String, Vec<f64>>,
+    /// Parameter overrides applied while paused, in order
+    pub overrides: Vec<AppliedOverride>,
+    /// Handler stats split at override times; empty without overrides
+    pub segments: Vec<ReportSegment>,
+    /// Configured parameters the run's models did not consume
+    pub ignored_parameters: Vec<IgnoredParameter>,
+    /// Stochastic decision points registered with the engine
+    pub randomness: Vec<RandomnessDecl>,
+}
+
+impl RunReport {
+    /// Stats of channel `channel_id` used from `from` to `to`
+    pub fn link(&self, channel_id: ChannelId, from: usize, to: usize) -> Option<&GenerationStats> {
+        self.links
+            .iter()
+            .find(|link| (link.channel_id, link.from, link.to) == (channel_id, from, to))
+            .map(|link| &link.generation)
+    }
+
+    /// Stats of channel `channel_id` used from `from` to `to`, added if missing
+    pub fn link_mut(
+        &mut self,
+        channel_id: ChannelId,
+        from: usize,
+        to: usize,
+    ) -> &mut GenerationStats {
+        let key = (channel_id, from, to);
+        let index = match self
+            .links
+            .binary_search_by_key(&key, |link| (link.channel_id, link.from, link.to))
+        {
+            Ok(index) => index,
+            Err(index) => {
+                self.links.insert(
+                    index,
+                    LinkStats {
+                        channel_id,
+                        from,
+                        to,
+                        generation: GenerationStats::default(),
+                    },
+                );
+                index
+            }
+        };
+        &mut self.links[index].generation
+    }
+
+    /// Error if the run was too coarse to produce `metric`
+    pub fn require(&self, metric: Metric) -> Result<(), String> {
+        self.detail_level.require(metric)
+    }
+
+    /// Samples of a histogram metric, or an error if the run could not produce it
+    pub fn metric_samples(&self, metric: Metric) -> Result<&[f64], String> {
+        self.require(metric)?;
+        let name = metric
+            .histogram_name()
+            .ok_or_else(|| format!("{:?} is not a histogram metric", metric))?;
+        Ok(self.histogram(name))
+    }
+
+    /// Delivered pairs per second of simulated time (0 for an empty run)
+    pub fn throughput_hz(&self) -> f64 {
+        if self.final_time > 0.0 {
+            self.requests.delivered as f64 / self.final_time * 1000.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Samples recorded under `name` (empty if none)
+    pub fn histogram(&self, name: &str) -> &[f64] {
+        self.histograms.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Append samples to the histogram `name`
+    pub fn record_samples(&mut self, name: &str, samples: &[f64]) {
+        self.histograms
+            .entry(name.to_string())
+            .or_default()
+            .extend_from_slice(samples);
+    }
+
+    /// Track occupancy and pair ages of every node of `topology` at `current_time`
+    pub fn observe_memory(&mut self, topology: &NetworkTopology, current_time: f64) {
+        for node in topology.nodes() {
+            let stats = self.nodes.entry(node.id).or_default();
+            stats.memory_capacity = node.memory_capacity;
+            stats.peak_occupancy = stats.peak_occupancy.max(node.num_stored_pairs());
+            if let Some(age) = node.oldest_pair_age(current_time) {
+                stats.max_pair_age_ms = stats.max_pair_age_ms.max(age);
+            }
+        }
+    }
+
+    /// Fold another replication into this report
+    ///
+    /// Counters and timings are summed, histograms and alarms concatenated,
+    /// node peaks maximized. Config, seed, overrides, segments, ignored
+    /// parameters and the randomness manifest are kept from the first report.
+    pub fn merge(&mut self, other: &RunReport) {
+        if self.replications == 0 {
+            self.config = other.config.clone();
+            self.seed = other.seed;
+            self.fingerprint = other.fingerprint;
+            self.detail_level = other.detail_level;
+            self.overrides = other.overrides.clone();
+            self.segments = other.segments.clone();
+            self.ignored_parameters = other.ignored_parameters.clone();
+            self.randomness = other.randomness.clone();
+        } else if let (Some(hash), Some(other_hash)) = (self.fingerprint, other.fingerprint) {
+            self.fingerprint = Some(fnv1a(hash, &other_hash.to_le_bytes()));
+        } else {
+            self.fingerprint = None;
+        }
+        self.detail_level = self.detail_level.min(other.detail_level);
+        self.replications += other.replications;
+        self.events_processed += other.events_processed;
+        self.final_time += other.final_time;
+        self.wall_clock += other.wall_clock;
+        for (&event_type, &count) in &other.pending_by_type {
+            *self.pending_by_type.entry(event_type).or_insert(0) += count;
+        }
+        if other.last_event.is_some() {
+            self.last_event = other.last_event;
+        }
+
+        self.generation.merge(&other.generation);
+        for link in &other.links {
+            self.link_mut(link.channel_id, link.from, link.to)
+                .merge(&link.generation);
+        }
+        self.requests.submitted += other.requests.submitted;
+        self.requests.delivered += other.requests.delivered;
+        self.requests.failed_swaps += other.requests.failed_swaps;
+        for (&node_id, node) in &other.nodes {
+            let stats = self.nodes.entry(node_id).or_default();
+            stats.memory_capacity = stats.memory_capacity.max(node.memory_capacity);
+            stats.peak_occupancy = stats.peak_occupancy.max(node.peak_occupancy);
+            stats.max_pair_age_ms = stats.max_pair_age_ms.max(node.max_pair_age_ms);
+        }
+        self.stale_memory_alarms
+            .extend_from_slice(&other.stale_memory_alarms);
+        for (name, samples) in &other.histograms {
+            self.record_samples(name, samples);
+        }
+    }
+
+    /// Merge several reports, e.g. one per replication
+    pub fn merge_all<'a>(reports: impl IntoIterator<Item = &'a RunReport>) -> RunReport {
+        let mut merged = RunReport::default();
+        for report in reports {
+            merged.merge(report);
+        }
+        merged
+    }
+
+    /// Human-readable summary; sections without data are left out
+    pub fn summary(&self) -> String {
+        let mut text = String::from("=== Run Report ===\n");
+        text.push_str(&format!("Replications:       {}\n", self.replications));
+        match self.seed {
+            Some(seed) => text.push_str(&format!("Seed:               {}\n", seed)),
+            None => text.push_str("Seed:               none\n"),
+        }
+        text.push_str(&format!("Detail level:       {}\n", self.detail_level));
+        text.push_str(&format!("Events processed:   {}\n", self.events_processed));
+        text.push_str(&format!(
+            "Simulated time:     {}\n",
+            format_time(self.final_time)
+        ));
+        text.push_str(&format!(
+            "Wall clock:         {}\n",
+            format_time(self.wall_clock.as_secs_f64() * 1000.0)
+        ));
+        match self.fingerprint {
+            Some(hash) => text.push_str(&format!("Fingerprint:        {:016x}\n", hash)),
+            None => text.push_str("Fingerprint:        none\n"),
+        }
+
+        if !self.config.is_empty() {
+            text.push_str("--- Config ---\n");
+            for (name, value) in &self.config {
+                text.push_str(&format!("  {} = {}\n", name, value));
+            }
+        }
+
+        if !self.ignored_parameters.is_empty() {
+            text.push_str("--- Ignored parameters ---\n");
+            for ignored in &self.ignored_parameters {
+                text.push_str(&format!("  {}\n", ignored));
+            }
+        }
+
+        if !self.randomness.is_empty() {
+            text.push_str("--- Randomness ---\n");
+            for decl in &self.randomness {
+                text.push_str(&format!("  {}\n", decl));
+            }
+        }
+
+        if self.generation.attempts > 0 {
+            let generation = &self.generation;
+            text.push_str("--- Generation ---\n");
+            text.push_str(&format!("Attempts:           {}\n", generation.attempts));
+            text.push_str(&format!(
+                "Successful:         {} ({})\n",
+                generation.successes,
+                format_probability(generation.success_rate())
+            ));
+            text.push_str(&format!(
+                "Channel failures:   {}\n",
+                generation.channel_failures
+            ));
+            text.push_str(&format!(
+                "Memory full:        {}\n",
+                generation.memory_full_errors
+            ));
+            for link in &self.links {
+                text.push_str(&format!(
+                    "  channel {} ({}-{}): {} attempts, {} successes ({})\n",
+                    link.channel_id,
+                    link.from,
+                    link.to,
+                    link.generation.attempts,
+                    link.generation.successes,
+                    format_probability(link.generation.success_rate())
+                ));
+            }
+        }
+
+        if self.requests.submitted > 0 {
+            text.push_str("--- Requests ---\n");
+            text.push_str(&format!(
+                "Submitted:          {}\n",
+                self.requests.submitted
+            ));
+            text.push_str(&format!(
+                "Delivered:          {}\n",
+                self.requests.delivered
+            ));
+            text.push_str(&format!(
+                "Failed swaps:       {}\n",
+                self.requests.failed_swaps
+            ));
+            text.push_str(&format!(
+                "Throughput:         {}\n",
+                format_rate(self.throughput_hz())
+            ));
+        }
+
+        if !self.nodes.is_empty() {
+            text.push_str("--- Nodes ---\n");
+            for (node_id, node) in &self.nodes {
+                text.push_str(&format!(
+                    "  node {}: peak occupancy {}/{}, max pair age {}\n",
+                    node_id,
+                    node.peak_occupancy,
+                    node.memory_capacity,
+                    format_time(node.max_pair_age_ms)
+                ));
+            }
+        }
+        if !self.stale_memory_alarms.is_empty() {
+            text.push_str(&format!(
+                "Stale memory alarms: {}\n",
+                self.stale_memory_alarms.len()
+            ));
+        }
+
+        if !self.overrides.is_empty() {
+            text.push_str("--- Overrides ---\n");
+            for applied in &self.overrides {
+                text.push_str(&format!(
+                    "  t={}: {}\n",
+                    format_time(applied.time),
+                    applied.change
+                ));
+            }
+            for segment in &self.segments {
+                text.push_str(&format!(
+                    "  [{}, {}]: {} attempts ({} success), {} delivered\n",
+                    format_time(segment.start),
+                    format_time(segment.end),
+                    segment.generation.attempts,
+                    format_probability(segment.generation.success_rate()),
+                    segment.requests.delivered
+                ));
+            }
+        }
+
+        if !self.histograms.is_empty() {
+            text.push_str("--- Histograms (samples, mean, min, max) ---\n");
+            for (name, samples) in &self.histograms {
+                if samples.is_empty() {
+                    text.push_str(&format!("  {}: 0\n", name));
+                    continue;
+                }
+                let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+                let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                text.push_str(&format!(
+                    "  {}: {}, {:.4}, {:.4}, {:.4}\n",
+                    name,
+                    samples.len(),
+                    mean,
+                    min,
+                    max
+                ));
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replication(attempts: usize, fidelities: &[f64]) -> RunReport {
+        let mut report = RunReport {
+            replications: 1,
+            events_processed: attempts,
+            final_time: attempts as f64,
+            fingerprint: Some(FINGERPRINT_SEED),
+            ..RunReport::default()
+        };
+        report.generation.attempts = attempts;
+        report.generation.successes = fidelities.len();
+        let link = report.link_mut(ChannelId(0), 0, 1);
+        link.attempts = attempts;
+        link.successes = fidelities.len();
+        report.requests.delivered = fidelities.len();
+        report.record_samples("fidelity", fidelities);
+        report.nodes.insert(
+            0,
+            NodeStats {
+                memory_capacity: 2,
+                peak_occupancy: fidelities.len(),
+                max_pair_age_ms: attempts as f64,
+            },
+        );
+        report
+    }
+
+    #[test]
+    fn test_merge_sums_counters_and_concatenates_histograms() {
+        let first = replication(10, &[0.9, 0.8]);
+        let second = replication(5, &[0.7]);
+        let merged = RunReport::merge_all([&first, &second]);
+
+        assert_eq!(merged.replications, 2);
+        assert_eq!(merged.events_processed, 15);
+        assert_eq!(merged.final_time, 15.0);
+        assert_eq!(merged.generation.attempts, 15);
+        assert_eq!(merged.link(ChannelId(0), 0, 1).unwrap().attempts, 15);
+        assert_eq!(merged.link(ChannelId(0), 0, 1).unwrap().successes, 3);
+        assert_eq!(merged.requests.delivered, 3);
+        assert_eq!(merged.histogram("fidelity"), &[0.9, 0.8, 0.7]);
+        assert_eq!(merged.nodes[&0].peak_occupancy, 2);
+        assert_eq!(merged.nodes[&0].max_pair_age_ms, 10.0);
+        assert_ne!(merged.fingerprint, first.fingerprint);
+
+        let summary = merged.summary();
+        assert!(summary.contains("Replications:       2"));
+        assert!(summary.contains("channel 0 (0-1): 15 attempts, 3 successes"));
+        assert!(summary.contains("fidelity: 3, 0.8000, 0.7000, 0.9000"));
+    }
+
+    #[test]
+    fn test_summary_uses_engineering_notation() {
+        let mut report = replication(10, &[0.9, 0.8]);
+        report.requests.submitted = 2;
+        report.wall_clock = Duration::from_micros(1250);
+        let summary = report.summary();
+        assert!(
+            summary.contains("Simulated time:     10.0 ms"),
+            "{}",
+            summary
+        );
+        assert!(summary.contains("Wall clock:         1.25 ms"));
+        assert!(summary.contains("Successful:         2 (20.0%)"));
+        // 2 pairs in 10 ms
+        assert!(summary.contains("Throughput:         200 pair/s"));
+        assert!(summary.contains("max pair age 10.0 ms"));
+    }
+
+    #[test]
+    fn test_links_sorted_by_channel_id() {
+        let mut report = RunReport::default();
+        report.link_mut(ChannelId(2), 0, 1).attempts += 1;
+        report.link_mut(ChannelId(0), 2, 3).attempts += 1;
+        report.link_mut(ChannelId(2), 0, 1).attempts += 1;
+        let links: Vec<(ChannelId, usize, usize)> = report
+            .links
+            .iter()
+            .map(|link| (link.channel_id, link.from, link.generation.attempts))
+            .collect();
+        assert_eq!(links, vec![(ChannelId(0), 2, 1), (ChannelId(2), 0, 2)]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut report = replication(4, &[0.95]);
+        report.seed = Some(7);
+        report
+            .config
+            .insert("num_nodes".to_string(), "3".to_string());
+        report
+            .pending_by_type
+            .insert(EventType::EntanglementGeneration, 2);
+        report.wall_clock = Duration::from_micros(1500);
+
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: RunReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
@@ -1,5 +1,18 @@
-use super::event::{Event, EventType};
-use std::collections::BinaryHeap;
+use super::event::{Event, EventSummary, EventType};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// What to do with an event scheduled before the current simulation time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PastEventPolicy {
+    /// Refuse the event (`schedule` panics, `try_schedule` returns Err)
+    #[default]
+    Reject,
+    /// Move the event to the current time, warn and count it
+    ClampWithWarning,
+    /// Accept it unchanged (offline queue construction)
+    Allow,
+}
 
 /// Discrete-event scheduler for quantum network simulation
 pub struct EventScheduler {
@@ -7,6 +20,10 @@ pub struct EventScheduler {
     event_queue: BinaryHeap<Event>,
     /// Current simulation time
     current_time: f64,
+    /// Handling of events scheduled in the past
+    pub past_event_policy: PastEventPolicy,
+    /// Number of events moved forward by `ClampWithWarning`
+    clamped_events: usize,
 }
 
 impl EventScheduler {
@@ -14,12 +31,63 @@ impl EventScheduler {
         EventScheduler {
             event_queue: BinaryHeap::new(),
             current_time: 0.0,
+            past_event_policy: PastEventPolicy::default(),
+            clamped_events: 0,
+        }
+    }
+
+    /// Create a scheduler with a non-default past-event policy
+    pub fn with_past_event_policy(policy: PastEventPolicy) -> Self {
+        EventScheduler {
+            past_event_policy: policy,
+            ..Self::new()
         }
     }
 
     /// Schedule a new event
+    /// Panics if the event time is not finite, or lies in the past under
+    /// `PastEventPolicy::Reject`
     pub fn schedule(&mut self, event: Event) {
+        if let Err(message) = self.try_schedule(event) {
+            panic!("{}", message);
+        }
+    }
+
+    /// Schedule a new event, applying the past-event policy
+    /// Events at NaN or infinite times are always refused
+    pub fn try_schedule(&mut self, mut event: Event) -> Result<(), String> {
+        if !event.time.is_finite() {
+            return Err(format!(
+                "Event {:?} scheduled at non-finite time {}",
+                event.event_type, event.time
+            ));
+        }
+        if event.time < self.current_time {
+            match self.past_event_policy {
+                PastEventPolicy::Reject => {
+                    return Err(format!(
+                        "Event {:?} at t={} scheduled before current time {}",
+                        event.event_type, event.time, self.current_time
+                    ));
+                }
+                PastEventPolicy::ClampWithWarning => {
+                    eprintln!(
+                        "Warning: event {:?} at t={} clamped to current time {}",
+                        event.event_type, event.time, self.current_time
+                    );
+                    event.time = self.current_time;
+                    self.clamped_events += 1;
+                }
+                PastEventPolicy::Allow => {}
+            }
+        }
         self.event_queue.push(event);
+        Ok(())
+    }
+
+    /// Number of events clamped to the current time so far
+    pub fn clamped_events(&self) -> usize {
+        self.clamped_events
     }
 
     /// Get the next event (removes it from queue)
@@ -51,6 +119,94 @@ impl EventScheduler {
     pub fn pending_events(&self) -> usize {
         self.event_queue.len()
     }
+
+    /// Remove every pending event matching the predicate
+    /// Returns the number of cancelled events
+    pub fn cancel_where<F>(&mut self, mut predicate: F) -> usize
+    where
+        F: FnMut(&Event) -> bool,
+    {
+        let before = self.event_queue.len();
+        self.event_queue.retain(|event| !predicate(event));
+        before - self.event_queue.len()
+    }
+
+    /// Summaries of all pending events, sorted by time
+    /// The queue itself is left untouched
+    pub fn pending_snapshot(&self) -> Vec<EventSummary> {
+        // Heap iteration order is arbitrary, so drain a heap of summaries instead
+        let index: BinaryHeap<IndexEntry> = self
+            .event_queue
+            .iter()
+            .map(|event| IndexEntry(EventSummary::from(event)))
+            .collect();
+        index
+            .into_sorted_vec()
+            .into_iter()
+            .rev()
+            .map(|entry| entry.0)
+            .collect()
+    }
+
+    /// Count pending events grouped by type
+    pub fn count_by_type(&self) -> HashMap<EventType, usize> {
+        let mut counts = HashMap::new();
+        for event in self.event_queue.iter() {
+            *counts.entry(event.event_type).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Time of the earliest pending event of the given type
+    pub fn next_event_of_type(&self, event_type: EventType) -> Option<f64> {
+        self.event_queue
+            .iter()
+            .filter(|event| event.event_type == event_type)
+            .map(|event| event.time)
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+    }
+}
+
+/// Source of the current simulation time
+///
+/// Implemented by `EventScheduler` and by `testing::FakeClock`
+pub trait Clock {
+    fn now(&self) -> f64;
+}
+
+impl Clock for EventScheduler {
+    fn now(&self) -> f64 {
+        self.current_time
+    }
+}
+
+/// Heap entry for `pending_snapshot`, ordered like `Event` (min-heap by time)
+struct IndexEntry(EventSummary);
+
+impl PartialEq for IndexEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.time == other.0.time
+    }
+}
+
+impl Eq for IndexEntry {}
+
+impl PartialOrd for IndexEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IndexEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.time.partial_cmp(&self.0.time).unwrap()
+    }
+}
+
+impl Default for EventScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -81,4 +237,122 @@ mod tests {
         scheduler.next_event();
         assert_eq!(scheduler.current_time(), 5.0);
     }
+
+    #[test]
+    fn test_pending_snapshot_matches_pop_order() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule(Event::new(4.0, EventType::Purification, 2));
+        scheduler.schedule(Event::new(1.0, EventType::EntanglementGeneration, 0));
+        scheduler.schedule(Event::new(3.0, EventType::Measurement, 1));
+        scheduler.schedule(Event::new(2.0, EventType::EntanglementSwapping, 1));
+
+        let snapshot = scheduler.pending_snapshot();
+
+        // Snapshot must not consume the queue
+        assert_eq!(scheduler.pending_events(), 4);
+
+        for summary in snapshot {
+            let event = scheduler.next_event().unwrap();
+            assert_eq!(summary.time, event.time);
+            assert_eq!(summary.event_type, event.event_type);
+            assert_eq!(summary.node_id, event.node_id);
+        }
+        assert!(!scheduler.has_events());
+    }
+
+    #[test]
+    fn test_count_by_type_after_cancel() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule(Event::new(1.0, EventType::EntanglementGeneration, 0));
+        scheduler.schedule(Event::new(2.0, EventType::EntanglementGeneration, 1));
+        scheduler.schedule(Event::new(3.0, EventType::Measurement, 1));
+
+        let counts = scheduler.count_by_type();
+        assert_eq!(counts.get(&EventType::EntanglementGeneration), Some(&2));
+        assert_eq!(counts.get(&EventType::Measurement), Some(&1));
+
+        // Cancel everything scheduled at node 1
+        let cancelled = scheduler.cancel_where(|event| event.node_id == 1);
+        assert_eq!(cancelled, 2);
+
+        let counts = scheduler.count_by_type();
+        assert_eq!(counts.get(&EventType::EntanglementGeneration), Some(&1));
+        assert_eq!(counts.get(&EventType::Measurement), None);
+        assert_eq!(scheduler.pending_snapshot().len(), 1);
+    }
+
+    #[test]
+    fn test_next_event_of_type() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule(Event::new(5.0, EventType::Measurement, 0));
+        scheduler.schedule(Event::new(1.0, EventType::EntanglementGeneration, 0));
+        scheduler.schedule(Event::new(2.0, EventType::Measurement, 0));
+
+        assert_eq!(
+            scheduler.next_event_of_type(EventType::Measurement),
+            Some(2.0)
+        );
+        assert_eq!(scheduler.next_event_of_type(EventType::Decoherence), None);
+    }
+
+    /// Scheduler whose clock has advanced to t = 10
+    fn scheduler_at_ten(policy: PastEventPolicy) -> EventScheduler {
+        let mut scheduler = EventScheduler::with_past_event_policy(policy);
+        scheduler.schedule(Event::new(10.0, EventType::Measurement, 0));
+        scheduler.next_event();
+        scheduler
+    }
+
+    #[test]
+    fn test_reject_past_event() {
+        let mut scheduler = scheduler_at_ten(PastEventPolicy::Reject);
+        let result = scheduler.try_schedule(Event::new(4.0, EventType::Measurement, 0));
+        assert!(result.is_err());
+        assert!(!scheduler.has_events());
+
+        // Same-time events are fine
+        assert!(scheduler
+            .try_schedule(Event::new(10.0, EventType::Measurement, 0))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_reject_non_finite_time() {
+        for policy in [
+            PastEventPolicy::Reject,
+            PastEventPolicy::ClampWithWarning,
+            PastEventPolicy::Allow,
+        ] {
+            let mut scheduler = scheduler_at_ten(policy);
+            for time in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+                let result = scheduler.try_schedule(Event::new(time, EventType::Measurement, 0));
+                assert!(result.unwrap_err().contains("non-finite"));
+            }
+            assert!(!scheduler.has_events());
+            assert_eq!(scheduler.clamped_events(), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "scheduled before current time")]
+    fn test_schedule_panics_on_past_event() {
+        let mut scheduler = scheduler_at_ten(PastEventPolicy::Reject);
+        scheduler.schedule(Event::new(4.0, EventType::Measurement, 0));
+    }
+
+    #[test]
+    fn test_clamp_past_event() {
+        let mut scheduler = scheduler_at_ten(PastEventPolicy::ClampWithWarning);
+        scheduler.schedule(Event::new(4.0, EventType::Measurement, 0));
+        assert_eq!(scheduler.clamped_events(), 1);
+        assert_eq!(scheduler.next_event().unwrap().time, 10.0);
+    }
+
+    #[test]
+    fn test_allow_past_event() {
+        let mut scheduler = scheduler_at_ten(PastEventPolicy::Allow);
+        scheduler.schedule(Event::new(4.0, EventType::Measurement, 0));
+        assert_eq!(scheduler.clamped_events(), 0);
+        assert_eq!(scheduler.next_event().unwrap().time, 4.0);
+    }
 }
@@ -0,0 +1,81 @@
+use crate::network::NetworkTopology;
+use std::collections::HashMap;
+
+/// A node held a pair longer than the staleness threshold for a whole grace period
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaleMemoryAlarm {
+    pub node_id: usize,
+    /// Time the alarm was raised
+    pub raised_at: f64,
+    /// Age of the node's oldest pair when the alarm was raised
+    pub oldest_pair_age: f64,
+    /// Time the node was first seen without stale pairs again
+    pub cleared_at: Option<f64>,
+}
+
+impl StaleMemoryAlarm {
+    pub fn is_active(&self) -> bool {
+        self.cleared_at.is_none()
+    }
+}
+
+/// Raises one `StaleMemoryAlarm` per episode of stale memory at a node
+///
+/// A node is stale while its oldest pair is older than `staleness_ms`; the
+/// alarm fires once it has stayed stale for `grace_ms` and clears when the
+/// node next holds no stale pair.
+#[derive(Debug, Clone)]
+pub struct StaleMemoryMonitor {
+    pub staleness_ms: f64,
+    pub grace_ms: f64,
+    /// Every alarm raised so far, in order
+    alarms: Vec<StaleMemoryAlarm>,
+    /// Per stale node: when it became stale and its active alarm, if raised
+    stale: HashMap<usize, (f64, Option<usize>)>,
+}
+
+impl StaleMemoryMonitor {
+    pub fn new(staleness_ms: f64, grace_ms: f64) -> Self {
+        StaleMemoryMonitor {
+            staleness_ms,
+            grace_ms,
+            alarms: Vec::new(),
+            stale: HashMap::new(),
+        }
+    }
+
+    pub fn alarms(&self) -> &[StaleMemoryAlarm] {
+        &self.alarms
+    }
+
+    /// Check every node of `topology` at `current_time`
+    pub fn observe(&mut self, topology: &NetworkTopology, current_time: f64) {
+        for node in topology.nodes() {
+            let age = node
+                .oldest_pair_age(current_time)
+                .filter(|&age| age > self.staleness_ms);
+            let Some(age) = age else {
+                if let Some((_, Some(alarm))) = self.stale.remove(&node.id) {
+                    self.alarms[alarm].cleared_at = Some(current_time);
+                }
+                continue;
+            };
+
+            // The oldest pair crossed the threshold `age - staleness_ms` ago
+            let (stale_since, alarm) = self
+                .stale
+                .entry(node.id)
+                .or_insert((current_time - (age - self.staleness_ms), None));
+            if alarm.is_none() && current_time - *stale_since >= self.grace_ms {
+                *alarm = Some(self.alarms.len());
+                self.alarms.push(StaleMemoryAlarm {
+                    node_id: node.id,
+                    raised_at: current_time,
+                    oldest_pair_age: age,
+                    cleared_at: None,
+                });
+            }
+        }
+    }
+}
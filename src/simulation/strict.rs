@@ -0,0 +1,101 @@
+//! Detection of parameters that are set but never read by the selected models
+
+use std::fmt;
+
+/// A configured parameter the selected models will not consume
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IgnoredParameter {
+    /// Type or config the parameter belongs to, e.g. "BarrettKokProtocol"
+    pub component: String,
+    /// Field name as written in code and config files
+    pub field: String,
+    /// Why the value has no effect
+    pub reason: String,
+}
+
+impl IgnoredParameter {
+    pub fn new(component: &str, field: &str, reason: &str) -> Self {
+        IgnoredParameter {
+            component: component.to_string(),
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for IgnoredParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}: {}", self.component, self.field, self.reason)
+    }
+}
+
+/// Something whose configured parameters may go unused
+pub trait ParameterUsage {
+    /// Parameters set to a non-default value that will have no effect
+    fn ignored_parameters(&self) -> Vec<IgnoredParameter>;
+}
+
+/// Whether ignored parameters are an error or only reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StrictConfig {
+    /// Accept ignored parameters instead of failing
+    pub allow_ignored: bool,
+}
+
+impl StrictConfig {
+    /// Fail on any ignored parameter (the default)
+    pub fn strict() -> Self {
+        StrictConfig {
+            allow_ignored: false,
+        }
+    }
+
+    /// Accept ignored parameters; callers should still surface them
+    pub fn allowing_ignored() -> Self {
+        StrictConfig {
+            allow_ignored: true,
+        }
+    }
+
+    /// Ignored parameters of `subject`, or an error listing them in strict mode
+    pub fn check(&self, subject: &impl ParameterUsage) -> Result<Vec<IgnoredParameter>, String> {
+        let ignored = subject.ignored_parameters();
+        if ignored.is_empty() || self.allow_ignored {
+            return Ok(ignored);
+        }
+        let list: Vec<String> = ignored.iter().map(ToString::to_string).collect();
+        Err(format!(
+            "Configured parameters would be ignored (allow with allow_ignored): {}",
+            list.join("; ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Unused(bool);
+
+    impl ParameterUsage for Unused {
+        fn ignored_parameters(&self) -> Vec<IgnoredParameter> {
+            if self.0 {
+                vec![IgnoredParameter::new("Unused", "knob", "never read")]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_only_ignored_parameters() {
+        assert_eq!(StrictConfig::strict().check(&Unused(false)), Ok(Vec::new()));
+        let error = StrictConfig::strict().check(&Unused(true)).unwrap_err();
+        assert!(error.contains("Unused.knob: never read"));
+        let warnings = StrictConfig::allowing_ignored()
+            .check(&Unused(true))
+            .unwrap();
+        assert_eq!(warnings[0].field, "knob");
+    }
+}
@@ -0,0 +1,94 @@
+use crate::quantum::{Circuit, Qubit};
+use std::fs::File;
+use std::io::Write;
+
+/// One recorded point of a Bloch trajectory
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlochPoint {
+    /// Index of the step (0 = initial state)
+    pub step: usize,
+    /// Gate applied to reach this point ("init" for the initial state)
+    pub gate: String,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Records the Bloch vector of a qubit as gates are applied
+#[derive(Debug, Clone, Default)]
+pub struct BlochTrajectory {
+    pub points: Vec<BlochPoint>,
+}
+
+impl BlochTrajectory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append the current Bloch vector of `qubit`
+    pub fn record(&mut self, gate: &str, qubit: &Qubit) {
+        let (x, y, z) = qubit.bloch_vector();
+        self.points.push(BlochPoint {
+            step: self.points.len(),
+            gate: gate.to_string(),
+            x,
+            y,
+            z,
+        });
+    }
+
+    /// Run a circuit on `qubit`, recording the initial state and the state after each gate
+    pub fn record_circuit(circuit: &Circuit, qubit: &mut Qubit) -> Self {
+        let mut trajectory = BlochTrajectory::new();
+        trajectory.record("init", qubit);
+        circuit.apply_with(qubit, |gate, state| trajectory.record(gate, state));
+        trajectory
+    }
+
+    /// Render as CSV: step,gate,x,y,z
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("step,gate,x,y,z\n");
+        for point in &self.points {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                point.step, point.gate, point.x, point.y, point.z
+            ));
+        }
+        csv
+    }
+
+    /// Write the CSV rendering to a file
+    pub fn write_csv(&self, path: &str) -> std::io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(self.to_csv().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hzh_trajectory() {
+        let circuit = Circuit::new().h().z().h();
+        let mut qubit = Qubit::new_zero();
+
+        let trajectory = BlochTrajectory::record_circuit(&circuit, &mut qubit);
+
+        // Initial point plus one per gate: |0⟩ → |+⟩ → |−⟩ → |1⟩
+        let expected = [
+            (0.0, 0.0, 1.0),
+            (1.0, 0.0, 0.0),
+            (-1.0, 0.0, 0.0),
+            (0.0, 0.0, -1.0),
+        ];
+        assert_eq!(trajectory.points.len(), expected.len());
+        for (point, (x, y, z)) in trajectory.points.iter().zip(expected) {
+            assert!((point.x - x).abs() < 1e-10);
+            assert!((point.y - y).abs() < 1e-10);
+            assert!((point.z - z).abs() < 1e-10);
+        }
+        assert_eq!(trajectory.points[2].gate, "Z");
+        assert_eq!(trajectory.to_csv().lines().count(), 5);
+    }
+}
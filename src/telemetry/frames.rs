@@ -0,0 +1,453 @@
+use crate::network::{ChannelId, EntanglementEdge, NetworkTopology};
+use crate::simulation::LinkStats;
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::Write;
+
+/// One node of a frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameNode {
+    pub id: usize,
+    pub x: f64,
+    pub y: f64,
+    /// Number of stored pairs
+    pub occupancy: usize,
+    pub capacity: usize,
+}
+
+/// One channel of a frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameLink {
+    pub channel_id: ChannelId,
+    pub node_a: usize,
+    pub node_b: usize,
+    /// Successes per attempt since the previous frame (None: no attempts)
+    pub success_rate: Option<f64>,
+}
+
+/// An entangled pair held at both ends when the frame was taken
+#[derive(Debug, Clone, PartialEq)]
+pub struct FramePair {
+    /// Lower node id
+    pub node_a: usize,
+    pub node_b: usize,
+    /// Fidelity at the frame time
+    pub fidelity: f64,
+}
+
+/// Network state at one simulated time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// Index of the frame time: `time = index * interval_ms`
+    pub index: usize,
+    pub time: f64,
+    pub nodes: Vec<FrameNode>,
+    pub links: Vec<FrameLink>,
+    pub pairs: Vec<FramePair>,
+    /// Entanglement graph edges at the frame time
+    pub entanglement: Vec<EntanglementEdge>,
+}
+
+impl Frame {
+    /// JSON object:
+    /// `{"index","time","nodes":[{"id","x","y","occupancy","capacity"}],
+    /// "links":[{"channel_id","node_a","node_b","success_rate"}],
+    /// "pairs":[{"node_a","node_b","fidelity"}],
+    /// "entanglement":[{"node_a","node_b","count","mean_fidelity"}]}`
+    ///
+    /// `success_rate` is null for links without attempts since the previous frame
+    pub fn to_json(&self) -> String {
+        let nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                format!(
+                    "{{\"id\":{},\"x\":{},\"y\":{},\"occupancy\":{},\"capacity\":{}}}",
+                    node.id,
+                    json_number(node.x),
+                    json_number(node.y),
+                    node.occupancy,
+                    node.capacity
+                )
+            })
+            .collect();
+        let links: Vec<String> = self
+            .links
+            .iter()
+            .map(|link| {
+                format!(
+                    "{{\"channel_id\":{},\"node_a\":{},\"node_b\":{},\"success_rate\":{}}}",
+                    link.channel_id.0,
+                    link.node_a,
+                    link.node_b,
+                    link.success_rate.map_or("null".to_string(), json_number)
+                )
+            })
+            .collect();
+        let pairs: Vec<String> = self
+            .pairs
+            .iter()
+            .map(|pair| {
+                format!(
+                    "{{\"node_a\":{},\"node_b\":{},\"fidelity\":{}}}",
+                    pair.node_a,
+                    pair.node_b,
+                    json_number(pair.fidelity)
+                )
+            })
+            .collect();
+        let entanglement: Vec<String> = self
+            .entanglement
+            .iter()
+            .map(|edge| {
+                format!(
+                    "{{\"node_a\":{},\"node_b\":{},\"count\":{},\"mean_fidelity\":{}}}",
+                    edge.node_a,
+                    edge.node_b,
+                    edge.count,
+                    json_number(edge.mean_fidelity)
+                )
+            })
+            .collect();
+        format!(
+            "{{\"index\":{},\"time\":{},\"nodes\":[{}],\"links\":[{}],\"pairs\":[{}],\"entanglement\":[{}]}}",
+            self.index,
+            json_number(self.time),
+            nodes.join(","),
+            links.join(","),
+            pairs.join(","),
+            entanglement.join(",")
+        )
+    }
+}
+
+/// Records a frame of the network state every `interval_ms` of simulated time
+///
+/// Attach to a run with `SimulationEngine::with_frame_exporter`. At most
+/// `max_frames` frames are kept: when the cap is reached every other frame
+/// is dropped and the capture stride doubles, so the kept frames stay evenly
+/// spaced. The first frame and the most recent one are always kept.
+/// `examples/frame_viewer.html` plays back the output of `write_json`.
+#[derive(Debug, Clone)]
+pub struct FrameExporter {
+    pub interval_ms: f64,
+    pub max_frames: usize,
+    /// Layout positions by node id; other nodes are placed on a unit circle
+    pub positions: BTreeMap<usize, (f64, f64)>,
+    /// Kept frames, every `stride`-th frame index
+    frames: Vec<Frame>,
+    /// Most recent frame if it is off the stride
+    latest: Option<Frame>,
+    stride: usize,
+    next_index: usize,
+    /// (attempts, successes) by channel at the previous frame
+    previous_counts: BTreeMap<ChannelId, (usize, usize)>,
+}
+
+impl FrameExporter {
+    /// Frame every `interval_ms`, keeping at most `max_frames` (at least 2)
+    pub fn new(interval_ms: f64, max_frames: usize) -> Self {
+        assert!(interval_ms > 0.0, "Frame interval must be positive");
+        FrameExporter {
+            interval_ms,
+            max_frames: max_frames.max(2),
+            positions: BTreeMap::new(),
+            frames: Vec::new(),
+            latest: None,
+            stride: 1,
+            next_index: 0,
+            previous_counts: BTreeMap::new(),
+        }
+    }
+
+    /// Draw `node_id` at (x, y) instead of its default circle position
+    pub fn with_position(mut self, node_id: usize, x: f64, y: f64) -> Self {
+        self.positions.insert(node_id, (x, y));
+        self
+    }
+
+    /// Simulated time of the next frame
+    pub fn next_frame_time(&self) -> f64 {
+        self.next_index as f64 * self.interval_ms
+    }
+
+    /// Number of frame times at which the state was captured
+    pub fn frames_captured(&self) -> usize {
+        self.next_index
+    }
+
+    /// Kept frames are every `stride`-th captured frame (plus the latest)
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Kept frames in time order
+    pub fn frames(&self) -> Vec<&Frame> {
+        self.frames.iter().chain(self.latest.as_ref()).collect()
+    }
+
+    /// Capture every frame due at or before `time` from `topology`
+    /// `links` supplies cumulative per-link attempt counts for the success rates
+    pub fn capture_due(&mut self, topology: &NetworkTopology, links: &[LinkStats], time: f64) {
+        while self.next_frame_time() <= time {
+            let frame = self.capture(topology, links, self.next_frame_time());
+            self.keep(frame);
+            self.next_index += 1;
+        }
+    }
+
+    fn capture(&mut self, topology: &NetworkTopology, links: &[LinkStats], time: f64) -> Frame {
+        let node_count = topology.nodes().len().max(1) as f64;
+        let nodes = topology
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(position, node)| {
+                let (x, y) = self.positions.get(&node.id).copied().unwrap_or_else(|| {
+                    let angle = 2.0 * PI * position as f64 / node_count;
+                    (angle.cos(), angle.sin())
+                });
+                FrameNode {
+                    id: node.id,
+                    x,
+                    y,
+                    occupancy: node.stored_pairs().len(),
+                    capacity: node.memory_capacity,
+                }
+            })
+            .collect();
+
+        let mut counts: BTreeMap<ChannelId, (usize, usize)> = BTreeMap::new();
+        for link in links {
+            let entry = counts.entry(link.channel_id).or_insert((0, 0));
+            entry.0 += link.generation.attempts;
+            entry.1 += link.generation.successes;
+        }
+        let links = topology
+            .iter_channels()
+            .map(|(channel_id, channel)| {
+                let (attempts, successes) = counts.get(&channel_id).copied().unwrap_or((0, 0));
+                let (previous_attempts, previous_successes) = self
+                    .previous_counts
+                    .get(&channel_id)
+                    .copied()
+                    .unwrap_or((0, 0));
+                let new_attempts = attempts.saturating_sub(previous_attempts);
+                let new_successes = successes.saturating_sub(previous_successes);
+                FrameLink {
+                    channel_id,
+                    node_a: channel.node_a,
+                    node_b: channel.node_b,
+                    success_rate: (new_attempts > 0)
+                        .then(|| new_successes as f64 / new_attempts as f64),
+                }
+            })
+            .collect();
+        self.previous_counts = counts;
+
+        // Each pair is stored at both ends; list it once from the lower id
+        let pairs = topology
+            .iter_all_pairs(time)
+            .filter(|pair| pair.node_id < pair.partner_node_id)
+            .map(|pair| FramePair {
+                node_a: pair.node_id,
+                node_b: pair.partner_node_id,
+                fidelity: pair.fidelity,
+            })
+            .collect();
+
+        Frame {
+            index: self.next_index,
+            time,
+            nodes,
+            links,
+            pairs,
+            entanglement: topology.entanglement_graph(time).edges,
+        }
+    }
+
+    fn keep(&mut self, frame: Frame) {
+        if !frame.index.is_multiple_of(self.stride) {
+            self.latest = Some(frame);
+            return;
+        }
+        self.latest = None;
+        self.frames.push(frame);
+        // Leave room for an off-stride latest frame
+        if self.frames.len() >= self.max_frames {
+            let last = self.frames.pop().unwrap();
+            let mut position = 0;
+            self.frames.retain(|_| {
+                position += 1;
+                position % 2 == 1
+            });
+            self.stride *= 2;
+            if last.index.is_multiple_of(self.stride) {
+                self.frames.push(last);
+            } else {
+                self.latest = Some(last);
+            }
+        }
+    }
+
+    /// JSON object: `{"interval_ms","stride","frames_captured","frames":[...]}`
+    /// with frames as documented on `Frame::to_json`
+    pub fn to_json(&self) -> String {
+        let frames: Vec<String> = self.frames().iter().map(|frame| frame.to_json()).collect();
+        format!(
+            "{{\"interval_ms\":{},\"stride\":{},\"frames_captured\":{},\"frames\":[{}]}}",
+            json_number(self.interval_ms),
+            self.stride,
+            self.next_index,
+            frames.join(",")
+        )
+    }
+
+    /// Write the JSON rendering to a file
+    pub fn write_json(&self, path: &str) -> std::io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(self.to_json().as_bytes())
+    }
+}
+
+/// JSON has no representation for infinities or NaN
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{GenerationStats, StoredPair};
+    use crate::quantum::TwoQubitState;
+
+    fn link_stats(attempts: usize, successes: usize) -> Vec<LinkStats> {
+        vec![LinkStats {
+            channel_id: ChannelId(0),
+            from: 0,
+            to: 1,
+            generation: GenerationStats {
+                attempts,
+                successes,
+                ..GenerationStats::default()
+            },
+        }]
+    }
+
+    #[test]
+    fn test_frame_count_matches_interval_and_run_length() {
+        let topology = NetworkTopology::new_linear(3, 2, 10.0, 0.2);
+        let mut exporter = FrameExporter::new(2.5, 100);
+        exporter.capture_due(&topology, &[], 4.0);
+        exporter.capture_due(&topology, &[], 20.0);
+
+        // Frames at 0, 2.5, ..., 20
+        assert_eq!(exporter.frames_captured(), 9);
+        let times: Vec<f64> = exporter.frames().iter().map(|frame| frame.time).collect();
+        assert_eq!(times.len(), 9);
+        assert_eq!(times[1], 2.5);
+        assert_eq!(*times.last().unwrap(), 20.0);
+        assert_eq!(exporter.next_frame_time(), 22.5);
+    }
+
+    #[test]
+    fn test_frame_contents() {
+        let mut topology = NetworkTopology::new_linear(2, 2, 10.0, 0.2);
+        let pair =
+            |partner| StoredPair::new(partner, TwoQubitState::new_bell_phi_plus(), 0.0, 1000.0);
+        topology
+            .get_node_mut(0)
+            .unwrap()
+            .store_pair(pair(1))
+            .unwrap();
+        topology
+            .get_node_mut(1)
+            .unwrap()
+            .store_pair(pair(0))
+            .unwrap();
+
+        let mut exporter = FrameExporter::new(1.0, 10).with_position(1, 5.0, 0.0);
+        exporter.capture_due(&topology, &link_stats(4, 1), 0.0);
+        exporter.capture_due(&topology, &link_stats(4, 1), 1.0);
+        exporter.capture_due(&topology, &link_stats(8, 4), 2.0);
+        let frames = exporter.frames();
+
+        assert_eq!(frames[0].nodes[0].occupancy, 1);
+        assert_eq!(frames[0].nodes[0].capacity, 2);
+        assert_eq!((frames[0].nodes[0].x, frames[0].nodes[0].y), (1.0, 0.0));
+        assert_eq!((frames[0].nodes[1].x, frames[0].nodes[1].y), (5.0, 0.0));
+        assert_eq!(frames[0].links[0].success_rate, Some(0.25));
+        assert_eq!(frames[1].links[0].success_rate, None);
+        assert_eq!(frames[2].links[0].success_rate, Some(0.75));
+        assert_eq!(frames[0].pairs.len(), 1);
+        assert_eq!(
+            (frames[0].pairs[0].node_a, frames[0].pairs[0].node_b),
+            (0, 1)
+        );
+        assert!(frames[2].pairs[0].fidelity <= frames[0].pairs[0].fidelity);
+        assert_eq!(frames[0].entanglement.len(), 1);
+        assert_eq!(frames[0].entanglement[0].count, 1);
+        assert_eq!(
+            frames[2].entanglement[0].mean_fidelity,
+            frames[2].pairs[0].fidelity
+        );
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let topology = NetworkTopology::new_linear(2, 2, 10.0, 0.2);
+        let mut exporter = FrameExporter::new(1.0, 10);
+        exporter.capture_due(&topology, &link_stats(0, 0), 1.0);
+        let json = exporter.to_json();
+        assert!(json.starts_with("{\"interval_ms\":1,\"stride\":1,\"frames_captured\":2,"));
+        assert!(json.contains("\"success_rate\":null"));
+
+        #[cfg(feature = "serde")]
+        {
+            let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+            let frames = value["frames"].as_array().unwrap();
+            assert_eq!(frames.len(), 2);
+            for frame in frames {
+                assert!(frame["time"].is_number());
+                for node in frame["nodes"].as_array().unwrap() {
+                    for key in ["id", "x", "y", "occupancy", "capacity"] {
+                        assert!(node[key].is_number(), "node {}", key);
+                    }
+                }
+                for link in frame["links"].as_array().unwrap() {
+                    for key in ["channel_id", "node_a", "node_b"] {
+                        assert!(link[key].is_number(), "link {}", key);
+                    }
+                    assert!(link["success_rate"].is_null());
+                }
+                assert!(frame["pairs"].is_array());
+                assert!(frame["entanglement"].is_array());
+            }
+        }
+    }
+
+    #[test]
+    fn test_downsampling_keeps_first_and_last_frames() {
+        let topology = NetworkTopology::new_linear(2, 2, 10.0, 0.2);
+        let mut exporter = FrameExporter::new(1.0, 8);
+        for end in [10.0, 37.0, 101.0] {
+            exporter.capture_due(&topology, &[], end);
+            let frames = exporter.frames();
+            assert!(frames.len() <= 8, "{} frames", frames.len());
+            assert_eq!(frames[0].time, 0.0);
+            assert_eq!(frames.last().unwrap().time, end);
+            // Kept frames other than the latest are evenly spaced
+            let kept = &frames[..frames.len() - 1];
+            for frame in kept {
+                assert!(frame.index.is_multiple_of(exporter.stride()));
+            }
+        }
+        assert_eq!(exporter.frames_captured(), 102);
+        assert!(exporter.stride() > 1);
+    }
+}
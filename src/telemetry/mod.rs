@@ -0,0 +1,5 @@
+pub mod bloch;
+pub mod frames;
+
+pub use bloch::{BlochPoint, BlochTrajectory};
+pub use frames::{Frame, FrameExporter, FrameLink, FrameNode, FramePair};
@@ -0,0 +1,194 @@
+use crate::network::node::StoredPair;
+use crate::network::{LinkChannel, QuantumChannel, QuantumNode};
+use crate::protocols::generation::{AttemptOutcome, GenerationProtocol};
+use crate::quantum::TwoQubitState;
+use crate::simulation::Clock;
+use rand::Rng;
+use std::cell::Cell;
+
+/// Channel whose attempts follow a fixed script (repeated when exhausted)
+/// The script cursor is a `Cell`, so fakes are `Send` but not `Sync`
+#[derive(Debug, Clone)]
+pub struct FakeChannel {
+    script: Vec<bool>,
+    next: Cell<usize>,
+}
+
+impl FakeChannel {
+    /// Channel replaying `script` in order, wrapping around at the end
+    pub fn scripted(script: Vec<bool>) -> Self {
+        assert!(
+            !script.is_empty(),
+            "Script must contain at least one outcome"
+        );
+        FakeChannel {
+            script,
+            next: Cell::new(0),
+        }
+    }
+
+    /// Channel that always succeeds (or always fails)
+    pub fn always(success: bool) -> Self {
+        Self::scripted(vec![success])
+    }
+
+    /// Number of attempts sampled so far
+    pub fn attempts(&self) -> usize {
+        self.next.get()
+    }
+}
+
+impl LinkChannel for FakeChannel {
+    /// Fraction of successes in the script
+    fn success_probability(&self) -> f64 {
+        self.script.iter().filter(|&&s| s).count() as f64 / self.script.len() as f64
+    }
+
+    fn attempt_generation(&self) -> bool {
+        let index = self.next.get();
+        self.next.set(index + 1);
+        self.script[index % self.script.len()]
+    }
+}
+
+/// Generation protocol with scripted outcomes and a fixed stored fidelity
+#[derive(Debug, Clone)]
+pub struct FakeGenerationProtocol {
+    script: Vec<AttemptOutcome>,
+    next: Cell<usize>,
+    /// Fidelity assigned to every stored pair
+    pub fidelity: f64,
+}
+
+impl FakeGenerationProtocol {
+    /// Protocol replaying `script` in order, wrapping around at the end
+    pub fn scripted(script: Vec<AttemptOutcome>, fidelity: f64) -> Self {
+        assert!(
+            !script.is_empty(),
+            "Script must contain at least one outcome"
+        );
+        FakeGenerationProtocol {
+            script,
+            next: Cell::new(0),
+            fidelity,
+        }
+    }
+
+    /// Number of attempts made so far (memory errors excluded)
+    pub fn attempts(&self) -> usize {
+        self.next.get()
+    }
+}
+
+impl GenerationProtocol for FakeGenerationProtocol {
+    fn attempt(
+        &self,
+        node_a: &mut QuantumNode,
+        node_b: &mut QuantumNode,
+        _channel: &QuantumChannel,
+        current_time: f64,
+        coherence_time_ms: f64,
+        _rng: &mut impl Rng,
+    ) -> Result<AttemptOutcome, String> {
+        if !node_a.has_memory_available() {
+            return Err(format!("Node {} memory full", node_a.id));
+        }
+        if !node_b.has_memory_available() {
+            return Err(format!("Node {} memory full", node_b.id));
+        }
+
+        let index = self.next.get();
+        self.next.set(index + 1);
+        let outcome = self.script[index % self.script.len()];
+
+        if outcome == AttemptOutcome::Success {
+            let bell_state = TwoQubitState::new_bell_phi_plus();
+            let mut pair_a = StoredPair::new(
+                node_b.id,
+                bell_state.clone(),
+                current_time,
+                coherence_time_ms,
+            );
+            let mut pair_b =
+                StoredPair::new(node_a.id, bell_state, current_time, coherence_time_ms)
+                    .with_pair_id(pair_a.pair_id);
+            pair_a.fidelity = self.fidelity;
+            pair_b.fidelity = self.fidelity;
+
+            node_a.store_pair(pair_a)?;
+            node_b.store_pair(pair_b)?;
+        }
+
+        Ok(outcome)
+    }
+}
+
+/// Manually advanced clock
+#[derive(Debug, Clone, Default)]
+pub struct FakeClock {
+    time: f64,
+}
+
+impl FakeClock {
+    /// Clock starting at `time`
+    pub fn at(time: f64) -> Self {
+        FakeClock { time }
+    }
+
+    /// Move the clock forward by `dt`
+    pub fn advance(&mut self, dt: f64) {
+        self.time += dt;
+    }
+
+    /// Jump to an absolute time
+    pub fn set(&mut self, time: f64) {
+        self.time = time;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> f64 {
+        self.time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fake_channel_cycles_script() {
+        let channel = FakeChannel::scripted(vec![true, false, false]);
+        let outcomes: Vec<bool> = (0..6).map(|_| channel.attempt_generation()).collect();
+
+        assert_eq!(outcomes, vec![true, false, false, true, false, false]);
+        assert!((channel.success_probability() - 1.0 / 3.0).abs() < 1e-10);
+        assert_eq!(channel.attempts(), 6);
+    }
+
+    #[test]
+    fn test_fake_protocol_memory_full() {
+        let protocol = FakeGenerationProtocol::scripted(vec![AttemptOutcome::Success], 0.9);
+        let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+        let mut node_a = QuantumNode::new(0, 1);
+        let mut node_b = QuantumNode::new(1, 1);
+        let mut rng = rand::rng();
+
+        let first = protocol.attempt(&mut node_a, &mut node_b, &channel, 0.0, 100.0, &mut rng);
+        assert_eq!(first, Ok(AttemptOutcome::Success));
+        assert!((node_a.stored_pairs()[0].fidelity - 0.9).abs() < 1e-10);
+
+        let second = protocol.attempt(&mut node_a, &mut node_b, &channel, 1.0, 100.0, &mut rng);
+        assert!(second.is_err());
+        assert_eq!(protocol.attempts(), 1);
+    }
+
+    #[test]
+    fn test_fake_clock() {
+        let mut clock = FakeClock::at(5.0);
+        clock.advance(2.5);
+        assert_eq!(clock.now(), 7.5);
+        clock.set(1.0);
+        assert_eq!(clock.now(), 1.0);
+    }
+}
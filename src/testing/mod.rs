@@ -0,0 +1,38 @@
+//! Deterministic test doubles for channels, protocols and clocks
+//!
+//! Scripted fakes replace probabilistic components so higher-level logic can
+//! be tested with exact assertions instead of statistics.
+//!
+//! ```
+//! use qcomnetsim::network::{attempt_entanglement_generation, QuantumNode};
+//! use qcomnetsim::testing::FakeChannel;
+//!
+//! let mut node_a = QuantumNode::new(0, 10);
+//! let mut node_b = QuantumNode::new(1, 10);
+//! let channel = FakeChannel::scripted(vec![false, true]);
+//!
+//! let first = attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, 0.0, 100.0);
+//! let second = attempt_entanglement_generation(&mut node_a, &mut node_b, &channel, 1.0, 100.0);
+//! assert_eq!(first, Ok(false));
+//! assert_eq!(second, Ok(true));
+//! ```
+//!
+//! ```
+//! use qcomnetsim::network::{QuantumChannel, QuantumNode};
+//! use qcomnetsim::protocols::generation::{AttemptOutcome, GenerationProtocol};
+//! use qcomnetsim::testing::FakeGenerationProtocol;
+//!
+//! let protocol = FakeGenerationProtocol::scripted(vec![AttemptOutcome::Success], 0.92);
+//! let channel = QuantumChannel::new(0, 1, 10.0, 0.2);
+//! let mut node_a = QuantumNode::new(0, 10);
+//! let mut node_b = QuantumNode::new(1, 10);
+//! let mut rng = rand::rng();
+//!
+//! protocol.attempt(&mut node_a, &mut node_b, &channel, 0.0, 100.0, &mut rng).unwrap();
+//! assert_eq!(node_a.stored_pairs()[0].fidelity, 0.92);
+//! ```
+
+pub mod fakes;
+pub mod model_equivalence;
+
+pub use fakes::{FakeChannel, FakeClock, FakeGenerationProtocol};
@@ -0,0 +1,198 @@
+//! How the pure-loss and Barrett-Kok generation models relate
+//!
+//! `attempt_entanglement_generation` sends one photon over the channel and
+//! succeeds with its transmission probability. `BarrettKokProtocol` sends a
+//! photon from each node over the full channel, then adds memory emission,
+//! collection, BSM and detector stages. With `BarrettKokProtocol::ideal()`
+//! those stages are lossless, and a Barrett-Kok attempt over a channel
+//! succeeds exactly when a pure-loss attempt over `equivalent_loss_channel`
+//! (twice the length) does, drawing the same number from the same RNG.
+//!
+//! Stored fidelities agree without depolarization, up to rounding: the
+//! pure-loss model computes its fidelity from the Bell state vector. With depolarization the
+//! models deviate even when ideal: Barrett-Kok depolarizes both photons of
+//! its pair, while the pure-loss model depolarizes one.
+
+use crate::network::{attempt_entanglement_generation_with_rng, QuantumChannel, QuantumNode};
+use crate::protocols::barrett_kok::BarrettKokProtocol;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Pure-loss channel whose transmission equals Barrett-Kok's two-photon
+/// transmission over `channel`
+pub fn equivalent_loss_channel(channel: &QuantumChannel) -> QuantumChannel {
+    QuantumChannel {
+        distance_km: 2.0 * channel.distance_km,
+        ..channel.clone()
+    }
+}
+
+/// Stored fidelity after each of `attempts` pure-loss attempts (None: failed)
+/// Each pair is removed again so memory never fills
+pub fn pure_loss_trace(channel: &QuantumChannel, attempts: usize, seed: u64) -> Vec<Option<f64>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    trace(attempts, |node_a, node_b, time| {
+        attempt_entanglement_generation_with_rng(node_a, node_b, channel, time, 100.0, &mut rng)
+    })
+}
+
+/// Stored fidelity after each of `attempts` Barrett-Kok attempts (None: failed)
+pub fn barrett_kok_trace(
+    protocol: &BarrettKokProtocol,
+    channel: &QuantumChannel,
+    attempts: usize,
+    seed: u64,
+) -> Vec<Option<f64>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    trace(attempts, |node_a, node_b, time| {
+        protocol
+            .attempt_generation_with_rng(node_a, node_b, channel, time, 100.0, &mut rng)
+            .map(|attempt| attempt.success)
+    })
+}
+
+fn trace(
+    attempts: usize,
+    mut attempt: impl FnMut(&mut QuantumNode, &mut QuantumNode, f64) -> Result<bool, String>,
+) -> Vec<Option<f64>> {
+    let mut node_a = QuantumNode::new(0, 1);
+    let mut node_b = QuantumNode::new(1, 1);
+    (0..attempts)
+        .map(|step| {
+            let time = step as f64;
+            if !attempt(&mut node_a, &mut node_b, time).unwrap() {
+                return None;
+            }
+            node_b.remove_pair_with(0, time);
+            node_a.remove_pair_with(1, time).map(|pair| pair.fidelity)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quantum::Detector;
+
+    const ATTEMPTS: usize = 2000;
+    const SEEDS: [u64; 3] = [1, 42, 2024];
+
+    /// (distance km, attenuation dB/km) from lossless to lossy
+    const CHANNELS: [(f64, f64); 4] = [(0.0, 0.2), (5.0, 0.2), (25.0, 0.2), (10.0, 0.5)];
+
+    fn successes(trace: &[Option<f64>]) -> Vec<bool> {
+        trace.iter().map(Option::is_some).collect()
+    }
+
+    /// Same successes, and fidelities equal up to rounding
+    fn assert_traces_match(actual: &[Option<f64>], expected: &[Option<f64>], case: &str) {
+        assert_eq!(successes(actual), successes(expected), "{}", case);
+        for (actual, expected) in actual.iter().flatten().zip(expected.iter().flatten()) {
+            assert!(
+                (actual - expected).abs() < 1e-12,
+                "{}: {} vs {}",
+                case,
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_ideal_barrett_kok_matches_pure_loss_exactly() {
+        let protocol = BarrettKokProtocol::ideal();
+        for (distance_km, attenuation) in CHANNELS {
+            let channel = QuantumChannel::new(0, 1, distance_km, attenuation);
+            let loss_channel = equivalent_loss_channel(&channel);
+            for seed in SEEDS {
+                let expected = pure_loss_trace(&loss_channel, ATTEMPTS, seed);
+                let actual = barrett_kok_trace(&protocol, &channel, ATTEMPTS, seed);
+                let case = format!("{} km, seed {}", distance_km, seed);
+                assert_traces_match(&actual, &expected, &case);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rates_agree_in_closed_form() {
+        let protocol = BarrettKokProtocol::ideal();
+        for (distance_km, attenuation) in CHANNELS {
+            let channel = QuantumChannel::new(0, 1, distance_km, attenuation);
+            let loss = equivalent_loss_channel(&channel).success_probability();
+            assert!((protocol.herald_probability(&channel) - loss).abs() < 1e-12);
+            assert_eq!(protocol.heralded_fidelity(&channel), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_lossy_stages_only_remove_successes() {
+        let channel = QuantumChannel::new(0, 1, 5.0, 0.2);
+        let loss_channel = equivalent_loss_channel(&channel);
+        let stages = [
+            BarrettKokProtocol {
+                bsm_efficiency: 0.5,
+                ..BarrettKokProtocol::ideal()
+            },
+            BarrettKokProtocol {
+                detector_a: Detector::with_efficiency(0.9),
+                detector_b: Detector::with_efficiency(0.9),
+                ..BarrettKokProtocol::ideal()
+            },
+            BarrettKokProtocol {
+                collection_efficiency: 0.8,
+                ..BarrettKokProtocol::ideal()
+            },
+            BarrettKokProtocol {
+                memory_efficiency: 0.9,
+                ..BarrettKokProtocol::ideal()
+            },
+        ];
+        for protocol in stages {
+            for seed in SEEDS {
+                let ideal = successes(&pure_loss_trace(&loss_channel, ATTEMPTS, seed));
+                let lossy = successes(&barrett_kok_trace(&protocol, &channel, ATTEMPTS, seed));
+                assert_ne!(lossy, ideal, "{:?} matched the ideal model", protocol);
+                for (step, (&lossy, &ideal)) in lossy.iter().zip(&ideal).enumerate() {
+                    assert!(!lossy || ideal, "extra success at attempt {}", step);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_initial_fidelity_changes_fidelity_not_successes() {
+        let channel = QuantumChannel::new(0, 1, 5.0, 0.2);
+        let protocol = BarrettKokProtocol {
+            initial_fidelity: 0.95,
+            ..BarrettKokProtocol::ideal()
+        };
+        let expected = pure_loss_trace(&equivalent_loss_channel(&channel), ATTEMPTS, 7);
+        let actual = barrett_kok_trace(&protocol, &channel, ATTEMPTS, 7);
+        assert_eq!(successes(&actual), successes(&expected));
+        assert!(actual.iter().flatten().all(|&fidelity| fidelity == 0.95));
+    }
+
+    #[test]
+    fn test_dark_counts_add_false_heralds() {
+        let channel = QuantumChannel::new(0, 1, 25.0, 0.2);
+        let protocol = BarrettKokProtocol::ideal().with_dark_counts(3e8);
+        let ideal = pure_loss_trace(&equivalent_loss_channel(&channel), ATTEMPTS, 7);
+        let dark = barrett_kok_trace(&protocol, &channel, ATTEMPTS, 7);
+        assert_ne!(dark, ideal);
+        assert!(dark.iter().flatten().any(|&fidelity| fidelity < 0.5));
+    }
+
+    #[test]
+    fn test_depolarization_deviates_on_fidelity_only() {
+        let channel = QuantumChannel::new(0, 1, 5.0, 0.2)
+            .with_depolarization(0.01)
+            .unwrap();
+        let protocol = BarrettKokProtocol::ideal();
+        let expected = pure_loss_trace(&equivalent_loss_channel(&channel), ATTEMPTS, 7);
+        let actual = barrett_kok_trace(&protocol, &channel, ATTEMPTS, 7);
+        assert_eq!(successes(&actual), successes(&expected));
+
+        let fidelity = |trace: &[Option<f64>]| trace.iter().flatten().next().copied().unwrap();
+        assert!((fidelity(&actual) - fidelity(&expected)).abs() > 1e-6);
+    }
+}
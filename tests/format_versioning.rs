@@ -0,0 +1,50 @@
+#![cfg(feature = "serde")]
+
+use qcomnetsim::io::SimulationConfig;
+use qcomnetsim::network::{NetworkTopology, TopologySnapshot};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn fixture(name: &str) -> String {
+    format!("{}/{}", FIXTURES, name)
+}
+
+#[test]
+fn test_v1_config_fixture_is_migrated() {
+    let config = SimulationConfig::load(&fixture("config_v1.json")).unwrap();
+    assert_eq!(config.topology, "star");
+    assert_eq!(config.num_nodes, 4);
+    assert_eq!(config.link_distance_km, 7.5);
+    assert_eq!(config.delivery_cutoff_ms, Some(20.0));
+    assert_eq!(config.swap_processing_ms, 0.0);
+
+    let topology = config.build_topology().unwrap();
+    assert!(topology.channels().iter().all(|c| c.distance_km == 7.5));
+}
+
+#[test]
+fn test_future_config_version_is_rejected() {
+    let path = fixture("config_v99.json");
+    let error = SimulationConfig::load(&path).unwrap_err();
+    assert_eq!(
+        error,
+        format!(
+            "{}: simulation config format version 99 is not supported (supported versions: 1-2)",
+            path
+        )
+    );
+}
+
+#[test]
+fn test_topology_snapshot_round_trip() {
+    let snapshot = TopologySnapshot::load(&fixture("topology_snapshot_v1.json")).unwrap();
+    assert_eq!(snapshot.nodes.len(), 2);
+    assert_eq!(snapshot.nodes[1].gate_error, 0.01);
+
+    let path = std::env::temp_dir().join(format!("qcs_snapshot_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    let current = NetworkTopology::new_linear(3, 2, 10.0, 0.2).snapshot();
+    current.save(path).unwrap();
+    assert_eq!(TopologySnapshot::load(path).unwrap(), current);
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,21 @@
+use qcomnetsim::experiment::{crossover_distance, rate_curve, RateCurveConfig};
+
+#[test]
+#[ignore = "slow: run with cargo test -- --ignored"]
+fn test_crossover_in_physical_range() {
+    let mut config = RateCurveConfig::realistic();
+    config.distances_km = vec![10.0, 20.0, 40.0, 60.0, 80.0, 100.0, 150.0];
+    config.chain.slots_per_run = 20_000;
+    config.chain.replications = 2;
+
+    let points = rate_curve(&config);
+    let crossover = crossover_distance(&points).expect("repeater should overtake direct link");
+
+    // Swap at 50% success costs a factor ~3 at short range; fiber loss wins it back
+    // somewhere around 50 km for 0.2 dB/km
+    assert!(
+        crossover > 20.0 && crossover < 150.0,
+        "crossover at {} km",
+        crossover
+    );
+}
@@ -0,0 +1,34 @@
+use qcomnetsim::experiment::{round_robin_requests, run_star, StarConfig};
+
+#[test]
+fn test_symmetric_star_serves_every_leaf_pair_fairly() {
+    // Saturated queues; hub memory for two concurrent requests, so four leaves contend
+    let config = StarConfig {
+        num_leaves: 4,
+        link_distance_km: 10.0,
+        hub_memory: 4,
+        slots_per_run: 20_000,
+        ..StarConfig::default()
+    };
+    let requests = round_robin_requests(config.num_leaves, 10_000);
+    let stats = run_star(&config, &requests).unwrap();
+
+    for source in 1..=4 {
+        for destination in (1..=4).filter(|&d| d != source) {
+            assert!(
+                stats.was_served(source, destination),
+                "{}-{} never served",
+                source,
+                destination
+            );
+        }
+    }
+    assert!(stats.unserved > 0);
+    assert!(stats.hub_blocked_slots > 0);
+    assert!(
+        stats.fairness() > 0.95,
+        "fairness {} with rates {:?}",
+        stats.fairness(),
+        stats.leaf_rates_hz()
+    );
+}
@@ -0,0 +1,113 @@
+use qcomnetsim::analysis::{BudgetPolicies, FidelityBudget};
+use qcomnetsim::experiment::{
+    ChainConfig, ChainRunStats, ServiceConfig, ServiceStats, StarConfig, StarRunStats,
+};
+use qcomnetsim::network::{
+    GenerationStats, NetworkTopology, QuantumChannel, QuantumNode, RetryPolicy, StoredPair,
+    TopologyView,
+};
+use qcomnetsim::protocols::banking::BankingPolicy;
+use qcomnetsim::protocols::barrett_kok::BarrettKokProtocol;
+use qcomnetsim::quantum::{MeasurementConfig, Qubit, TwoQubitState};
+use qcomnetsim::simulation::{IdleGapReport, RunReport, SimulationEngine};
+use qcomnetsim::testing::{FakeChannel, FakeGenerationProtocol};
+#[cfg(feature = "parallel")]
+use rand::{rngs::StdRng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Fails to compile unless every type implements every trait
+macro_rules! assert_impl_all {
+    ($($ty:ty),+ $(,)?: $($bound:path),+ $(,)?) => {
+        const _: fn() = || {
+            fn assert_impl<T: ?Sized $(+ $bound)+>() {}
+            $(assert_impl::<$ty>();)+
+        };
+    };
+}
+
+// States, network model and views
+assert_impl_all!(
+    Qubit,
+    TwoQubitState,
+    QuantumChannel,
+    QuantumNode,
+    StoredPair,
+    NetworkTopology,
+    TopologyView,
+    : Send, Sync
+);
+
+// Protocol and experiment configs
+assert_impl_all!(
+    BarrettKokProtocol,
+    MeasurementConfig,
+    RetryPolicy,
+    BankingPolicy,
+    BudgetPolicies,
+    ChainConfig,
+    ServiceConfig,
+    StarConfig,
+    : Send, Sync
+);
+
+// Stats and reports
+assert_impl_all!(
+    GenerationStats,
+    ChainRunStats,
+    ServiceStats,
+    StarRunStats,
+    FidelityBudget,
+    RunReport,
+    IdleGapReport,
+    : Send, Sync
+);
+
+// Documented non-goals: one per thread
+assert_impl_all!(SimulationEngine, FakeChannel, FakeGenerationProtocol: Send);
+
+#[cfg(feature = "parallel")]
+/// Attempts until the first heralded pair on link 0-1 of a private fork
+fn attempts_to_first_pair(view: &TopologyView, protocol: &BarrettKokProtocol, seed: u64) -> usize {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut topology = view.fork();
+    let (_, channel) = view.find_channel(0, 1).unwrap();
+    let (node_a, node_b) = topology.get_node_pair_mut(0, 1).unwrap();
+    let mut attempts = 1;
+    while !protocol
+        .attempt_generation_with_rng(node_a, node_b, channel, 0.0, 100.0, &mut rng)
+        .unwrap()
+        .success
+    {
+        attempts += 1;
+    }
+    attempts
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_shared_topology_across_threads() {
+    let view = TopologyView::new(NetworkTopology::new_linear(3, 2, 10.0, 0.2));
+    let protocol = BarrettKokProtocol::sequence_parameters();
+
+    // 8 threads read one topology; each trial forks its own memory
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(8)
+        .build()
+        .unwrap();
+    let parallel: Vec<usize> = pool.install(|| {
+        (0..8u64)
+            .into_par_iter()
+            .map(|seed| attempts_to_first_pair(&view, &protocol, seed))
+            .collect()
+    });
+
+    // The shared topology was never mutated
+    assert!(view.nodes().iter().all(|node| node.num_stored_pairs() == 0));
+
+    // Seeded trials are reproducible regardless of thread scheduling
+    let sequential: Vec<usize> = (0..8u64)
+        .map(|seed| attempts_to_first_pair(&view, &protocol, seed))
+        .collect();
+    assert_eq!(parallel, sequential);
+}